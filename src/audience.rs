@@ -0,0 +1,282 @@
+use crate::resolve::component_name;
+use crate::*;
+use indexmap::IndexMap;
+use std::borrow::Borrow;
+
+/// Selects the operations, schemas and parameters to hide from an audience
+/// by their extensions, for [OpenAPI::filter_audience].
+///
+/// An object is hidden if it has any of the extensions, with the given value
+/// if one is set.
+///
+/// ```
+/// # use openapiv3::AudienceFilter;
+/// let filter = AudienceFilter::new()
+///     .extension_value("x-internal", serde_json::json!(true))
+///     .extension("x-beta");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AudienceFilter {
+    extensions: Vec<(String, Option<serde_json::Value>)>,
+}
+
+impl AudienceFilter {
+    /// Creates a filter that hides nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hides objects that have this extension, with any value.
+    pub fn extension(mut self, name: impl Into<String>) -> Self {
+        self.extensions.push((name.into(), None));
+        self
+    }
+
+    /// Hides objects that have this extension with this value.
+    pub fn extension_value(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.extensions.push((name.into(), Some(value)));
+        self
+    }
+
+    /// Returns `true` if an object with these extensions is hidden.
+    pub fn hides(&self, extensions: &Extensions) -> bool {
+        self.extensions
+            .iter()
+            .any(|(name, value)| match (extensions.get(name), value) {
+                (Some(actual), Some(expected)) => actual == expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+    }
+}
+
+impl OpenAPI {
+    /// Returns a copy of this document without the operations, schemas and
+    /// parameters hidden by `filter`, such as the public view of a document
+    /// whose internal parts are marked with `x-internal: true`.
+    ///
+    /// Hidden component schemas are removed, along with the properties and
+    /// `oneOf`, `anyOf` and `allOf` members that are hidden or refer to a
+    /// hidden component schema. Hidden parameters, and references to hidden
+    /// component parameters, are removed from path items and operations.
+    /// Other references to hidden component schemas are left dangling, as
+    /// the objects using them should be hidden as well. Finally, hidden
+    /// operations are removed and the components and tags nothing uses any
+    /// more are pruned, see [OpenAPI::extract_by].
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": {
+    ///         "/pets": { "get": { "responses": { "200": {
+    ///             "description": "Pets",
+    ///             "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+    ///         } } } },
+    ///         "/admin/audit": { "get": { "x-internal": true, "responses": { "200": {
+    ///             "description": "Audit log",
+    ///             "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Audit" } } }
+    ///         } } } }
+    ///     },
+    ///     "components": { "schemas": {
+    ///         "Pet": { "type": "object", "properties": {
+    ///             "name": { "type": "string" },
+    ///             "cost": { "type": "number", "x-internal": true }
+    ///         } },
+    ///         "Audit": { "type": "object" }
+    ///     } }
+    /// }"##).unwrap();
+    ///
+    /// let filter = AudienceFilter::new().extension_value("x-internal", serde_json::json!(true));
+    /// let public = api.filter_audience(&filter);
+    /// assert_eq!(public.paths.paths.keys().collect::<Vec<_>>(), ["/pets"]);
+    /// let schemas = &public.components.unwrap().schemas;
+    /// assert_eq!(schemas.keys().collect::<Vec<_>>(), ["Pet"]);
+    /// let pet = serde_json::to_value(&schemas["Pet"]).unwrap();
+    /// assert_eq!(pet["properties"], serde_json::json!({ "name": { "type": "string" } }));
+    /// ```
+    pub fn filter_audience(&self, filter: &AudienceFilter) -> OpenAPI {
+        let mut document = self.clone();
+        let mut hidden_schemas = Vec::new();
+        let mut hidden_parameters = Vec::new();
+        if let Some(components) = &mut document.components {
+            components.schemas.retain(|name, schema| {
+                let hidden = schema
+                    .as_item()
+                    .is_some_and(|schema| filter.hides(&schema.schema_data.extensions));
+                if hidden {
+                    hidden_schemas.push(name.clone());
+                }
+                !hidden
+            });
+            components.parameters.retain(|name, parameter| {
+                let hidden = parameter.as_item().is_some_and(|parameter| {
+                    filter.hides(&parameter.parameter_data_ref().extensions)
+                });
+                if hidden {
+                    hidden_parameters.push(name.clone());
+                }
+                !hidden
+            });
+        }
+
+        let visible = |parameter: &ReferenceOr<Parameter>| match parameter {
+            ReferenceOr::Item(parameter) => {
+                !filter.hides(&parameter.parameter_data_ref().extensions)
+            }
+            ReferenceOr::Reference { reference } => {
+                component_name(reference, ComponentKind::Parameters)
+                    .map_or(true, |name| !hidden_parameters.contains(&name))
+            }
+        };
+        for item in document.paths.paths.values_mut() {
+            if let ReferenceOr::Item(item) = item {
+                item.parameters.retain(visible);
+                for (_, operation) in item.iter_mut() {
+                    operation.parameters.retain(visible);
+                }
+            }
+        }
+
+        document.transform(&mut HideSchemas {
+            filter,
+            hidden: &hidden_schemas,
+        });
+
+        document.extract_by(|_, _, operation| !filter.hides(&operation.extensions))
+    }
+}
+
+/// Removes the hidden subschemas of every schema.
+struct HideSchemas<'a> {
+    filter: &'a AudienceFilter,
+    hidden: &'a [String],
+}
+
+impl HideSchemas<'_> {
+    fn visible<T: Borrow<Schema>>(&self, schema: &ReferenceOr<T>) -> bool {
+        match schema {
+            ReferenceOr::Item(schema) => {
+                !self.filter.hides(&schema.borrow().schema_data.extensions)
+            }
+            ReferenceOr::Reference { reference } => {
+                component_name(reference, ComponentKind::Schemas)
+                    .map_or(true, |name| !self.hidden.contains(&name))
+            }
+        }
+    }
+
+    fn properties(
+        &self,
+        properties: &mut IndexMap<String, ReferenceOr<Box<Schema>>>,
+        required: &mut Vec<String>,
+    ) {
+        properties.retain(|name, schema| {
+            let visible = self.visible(schema);
+            if !visible {
+                required.retain(|n| n != name);
+            }
+            visible
+        });
+    }
+}
+
+impl VisitorMut for HideSchemas<'_> {
+    fn visit_schema_mut(&mut self, _: &str, schema: &mut Schema) {
+        match &mut schema.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => {
+                self.properties(&mut object.properties, &mut object.required)
+            }
+            SchemaKind::OneOf { one_of: members }
+            | SchemaKind::AllOf { all_of: members }
+            | SchemaKind::AnyOf { any_of: members } => members.retain(|s| self.visible(s)),
+            SchemaKind::Any(any) => {
+                self.properties(&mut any.properties, &mut any.required);
+                any.one_of.retain(|s| self.visible(s));
+                any.all_of.retain(|s| self.visible(s));
+                any.any_of.retain(|s| self.visible(s));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_filter_audience() {
+        let api: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "tags": [ { "name": "pets" }, { "name": "admin" } ],
+            "paths": {
+                "/pets": {
+                    "parameters": [
+                        { "$ref": "#/components/parameters/Trace" },
+                        { "name": "debug", "in": "query", "x-internal": true, "schema": { "type": "boolean" } }
+                    ],
+                    "get": {
+                        "tags": ["pets"],
+                        "parameters": [ { "name": "limit", "in": "query", "schema": { "type": "integer" } } ],
+                        "responses": { "200": { "description": "", "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Pet" }
+                        } } } }
+                    },
+                    "delete": { "tags": ["admin"], "x-internal": true, "responses": {} }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "Trace": { "name": "trace", "in": "header", "x-internal": true, "schema": { "type": "string" } }
+                },
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [ { "$ref": "#/components/schemas/Dog" }, { "$ref": "#/components/schemas/Robot" } ]
+                    },
+                    "Dog": { "type": "object", "required": ["name", "chip"], "properties": {
+                        "name": { "type": "string" },
+                        "chip": { "$ref": "#/components/schemas/Chip" },
+                        "notes": { "type": "string", "x-internal": false }
+                    } },
+                    "Robot": { "type": "object", "x-internal": true },
+                    "Chip": { "type": "string", "x-internal": true }
+                }
+            }
+        }))
+        .unwrap();
+
+        let filter = AudienceFilter::new().extension_value("x-internal", json!(true));
+        let public = filter_value(&api, &filter);
+        let item = &public["paths"]["/pets"];
+        assert_eq!(item["parameters"], json!(null));
+        assert_eq!(item["get"]["parameters"][0]["name"], "limit");
+        assert_eq!(item["delete"], json!(null));
+        assert_eq!(public["tags"], json!([ { "name": "pets" } ]));
+        assert_eq!(
+            public["components"],
+            json!({ "schemas": {
+                "Pet": { "oneOf": [ { "$ref": "#/components/schemas/Dog" } ] },
+                "Dog": { "type": "object", "required": ["name"], "properties": {
+                    "name": { "type": "string" },
+                    "notes": { "type": "string", "x-internal": false }
+                } }
+            } })
+        );
+
+        let everything = AudienceFilter::new().extension("x-internal");
+        let dog = &filter_value(&api, &everything)["components"]["schemas"]["Dog"];
+        assert_eq!(dog["properties"].as_object().unwrap().len(), 1);
+        assert_eq!(
+            api.filter_audience(&AudienceFilter::new()),
+            api.extract_by(|_, _, _| true)
+        );
+    }
+
+    fn filter_value(api: &OpenAPI, filter: &AudienceFilter) -> serde_json::Value {
+        serde_json::to_value(api.filter_audience(filter)).unwrap()
+    }
+}
@@ -0,0 +1,845 @@
+use std::fmt;
+
+use crate::describe::path_item;
+use crate::*;
+use indexmap::IndexMap;
+
+/// The error returned by the `build` methods of the builders in this module.
+///
+/// Mistakes are recorded as they are made and reported by `build`, so that
+/// calls can be chained. Only the first mistake is reported.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    /// A schema setting does not apply to the kind of schema being built.
+    InvalidSchema { message: String },
+    /// A parameter is missing its schema, or is a path parameter that is
+    /// not required.
+    InvalidParameter { name: String, message: String },
+    /// A media type could not be parsed.
+    InvalidMediaType { media_type: String },
+    /// A status code is outside of `100..=599`.
+    InvalidStatusCode { status: u16 },
+    /// An operation has no responses.
+    NoResponses,
+    /// An operation could not be added to the document.
+    Collect(CollectError),
+    /// The built document does not pass [OpenAPI::validate].
+    Invalid(Vec<Violation>),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidSchema { message } => write!(f, "invalid schema: {}", message),
+            BuildError::InvalidParameter { name, message } => {
+                write!(f, "invalid parameter `{}`: {}", name, message)
+            }
+            BuildError::InvalidMediaType { media_type } => {
+                write!(f, "invalid media type `{}`", media_type)
+            }
+            BuildError::InvalidStatusCode { status } => {
+                write!(f, "invalid status code {}", status)
+            }
+            BuildError::NoResponses => write!(f, "operations must have at least one response"),
+            BuildError::Collect(err) => err.fmt(f),
+            BuildError::Invalid(violations) => {
+                write!(f, "the document is not valid")?;
+                for violation in violations {
+                    write!(f, "\n  {}", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Keeps the first error of a builder.
+fn record<T>(error: &mut Option<BuildError>, result: Result<T, BuildError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            error.get_or_insert(err);
+            None
+        }
+    }
+}
+
+fn check_media_type(media_type: &str) -> Result<(), BuildError> {
+    media_type
+        .parse::<MediaTypeKey>()
+        .map(|_| ())
+        .map_err(|_| BuildError::InvalidMediaType {
+            media_type: media_type.to_owned(),
+        })
+}
+
+fn check_status(status: u16) -> Result<StatusCode, BuildError> {
+    match status {
+        100..=599 => Ok(StatusCode::Code(status)),
+        _ => Err(BuildError::InvalidStatusCode { status }),
+    }
+}
+
+/// Builds an [OpenAPI] document.
+///
+/// ```
+/// # use openapiv3::*;
+/// let api = OpenApiBuilder::new("Pets", "1.0.0")
+///     .server("https://pets.example.com")
+///     .schema("Pet", SchemaBuilder::object().required_property("name", SchemaBuilder::string()))
+///     .operation(
+///         "/pets/{id}",
+///         "get",
+///         OperationBuilder::new()
+///             .operation_id("getPet")
+///             .parameter(ParameterBuilder::path("id").schema(SchemaBuilder::integer()))
+///             .response(
+///                 200,
+///                 ResponseBuilder::new("A pet")
+///                     .content("application/json", SchemaBuilder::reference("#/components/schemas/Pet")),
+///             ),
+///     )
+///     .build()
+///     .unwrap();
+/// assert_eq!(api.operations().count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApiBuilder {
+    document: OpenAPI,
+    error: Option<BuildError>,
+}
+
+impl OpenApiBuilder {
+    /// Starts an OpenAPI 3.0.3 document with the given title and API
+    /// version.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            document: OpenAPI {
+                openapi: "3.0.3".to_owned(),
+                info: Info {
+                    title: title.into(),
+                    version: version.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            error: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.document.info.description = Some(description.into());
+        self
+    }
+
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.document.servers.push(Server {
+            url: url.into(),
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn tag(mut self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.document.tags.push(Tag {
+            name: name.into(),
+            description: Some(description.into()),
+            ..Default::default()
+        });
+        self
+    }
+
+    /// Adds an operation. Adding the same path and method twice is an
+    /// error.
+    pub fn operation(mut self, path: &str, method: &str, operation: OperationBuilder) -> Self {
+        let method = method.to_ascii_lowercase();
+        let result = operation.build().and_then(|operation| {
+            let item = path_item(&mut self.document.paths, path).map_err(BuildError::Collect)?;
+            let slot = item.operation_mut(&method).ok_or_else(|| {
+                BuildError::Collect(CollectError::UnknownMethod {
+                    path: path.to_owned(),
+                    method: method.clone(),
+                })
+            })?;
+            if slot.is_some() {
+                return Err(BuildError::Collect(CollectError::DuplicateOperation {
+                    path: path.to_owned(),
+                    method: method.clone(),
+                }));
+            }
+            *slot = Some(operation);
+            Ok(())
+        });
+        record(&mut self.error, result);
+        self
+    }
+
+    /// Adds a schema to [Components::schemas].
+    pub fn schema(mut self, name: impl Into<String>, schema: SchemaBuilder) -> Self {
+        if let Some(schema) = record(&mut self.error, schema.build_ref()) {
+            self.components().schemas.insert(name.into(), schema);
+        }
+        self
+    }
+
+    /// Adds a scheme to [Components::security_schemes].
+    pub fn security_scheme(mut self, name: impl Into<String>, scheme: SecurityScheme) -> Self {
+        self.components()
+            .security_schemes
+            .insert(name.into(), ReferenceOr::Item(scheme));
+        self
+    }
+
+    /// Adds a security requirement that applies to all operations.
+    pub fn security(mut self, requirement: SecurityRequirement) -> Self {
+        self.document
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+        self
+    }
+
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.document.extensions.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the document, or the first mistake made while building it,
+    /// or the violations found by [OpenAPI::validate].
+    pub fn build(self) -> Result<OpenAPI, BuildError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let violations = self.document.validate();
+        if !violations.is_empty() {
+            return Err(BuildError::Invalid(violations));
+        }
+        Ok(self.document)
+    }
+
+    fn components(&mut self) -> &mut Components {
+        self.document
+            .components
+            .get_or_insert_with(Default::default)
+    }
+}
+
+/// Builds an [Operation] for [OpenApiBuilder::operation].
+#[derive(Debug, Clone, Default)]
+pub struct OperationBuilder {
+    operation: Operation,
+    error: Option<BuildError>,
+}
+
+impl OperationBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn operation_id(mut self, id: impl Into<String>) -> Self {
+        self.operation.operation_id = Some(id.into());
+        self
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.operation.summary = Some(summary.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.operation.description = Some(description.into());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.operation.tags.push(tag.into());
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.operation.deprecated = true;
+        self
+    }
+
+    pub fn parameter(mut self, parameter: ParameterBuilder) -> Self {
+        if let Some(parameter) = record(&mut self.error, parameter.build()) {
+            self.operation.parameters.push(ReferenceOr::Item(parameter));
+        }
+        self
+    }
+
+    /// Adds a media type to the request body, creating it if needed.
+    pub fn request_body(mut self, media_type: &str, schema: SchemaBuilder, required: bool) -> Self {
+        let result = check_media_type(media_type).and_then(|()| schema.build_ref());
+        if let Some(schema) = record(&mut self.error, result) {
+            let body = self
+                .operation
+                .request_body
+                .get_or_insert_with(|| ReferenceOr::Item(RequestBody::default()));
+            if let ReferenceOr::Item(body) = body {
+                body.required |= required;
+                body.content.insert(
+                    media_type.to_owned(),
+                    MediaType {
+                        schema: Some(schema),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+        self
+    }
+
+    pub fn response(mut self, status: u16, response: ResponseBuilder) -> Self {
+        let result = check_status(status).and_then(|status| Ok((status, response.build()?)));
+        if let Some((status, response)) = record(&mut self.error, result) {
+            self.operation
+                .responses
+                .responses
+                .insert(status, ReferenceOr::Item(response));
+        }
+        self
+    }
+
+    pub fn default_response(mut self, response: ResponseBuilder) -> Self {
+        if let Some(response) = record(&mut self.error, response.build()) {
+            self.operation.responses.default = Some(ReferenceOr::Item(response));
+        }
+        self
+    }
+
+    /// Adds a security requirement, overriding the document's ones.
+    pub fn security(mut self, requirement: SecurityRequirement) -> Self {
+        self.operation
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+        self
+    }
+
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.operation.extensions.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the operation, which must have at least one response.
+    pub fn build(self) -> Result<Operation, BuildError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let responses = &self.operation.responses;
+        if responses.default.is_none() && responses.responses.is_empty() {
+            return Err(BuildError::NoResponses);
+        }
+        Ok(self.operation)
+    }
+}
+
+/// Builds a [Response] for [OperationBuilder::response].
+#[derive(Debug, Clone)]
+pub struct ResponseBuilder {
+    response: Response,
+    error: Option<BuildError>,
+}
+
+impl ResponseBuilder {
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            response: Response {
+                description: description.into(),
+                ..Default::default()
+            },
+            error: None,
+        }
+    }
+
+    pub fn content(mut self, media_type: &str, schema: SchemaBuilder) -> Self {
+        let result = check_media_type(media_type).and_then(|()| schema.build_ref());
+        if let Some(schema) = record(&mut self.error, result) {
+            self.response.content.insert(
+                media_type.to_owned(),
+                MediaType {
+                    schema: Some(schema),
+                    ..Default::default()
+                },
+            );
+        }
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, schema: SchemaBuilder) -> Self {
+        if let Some(schema) = record(&mut self.error, schema.build_ref()) {
+            let header = Header {
+                description: None,
+                style: Default::default(),
+                required: false,
+                deprecated: None,
+                format: ParameterSchemaOrContent::Schema(Box::new(schema)),
+                example: None,
+                examples: IndexMap::new(),
+                extensions: Extensions::new(),
+            };
+            self.response
+                .headers
+                .insert(name.into(), ReferenceOr::Item(header));
+        }
+        self
+    }
+
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.response.extensions.insert(name.into(), value);
+        self
+    }
+
+    pub fn build(self) -> Result<Response, BuildError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.response),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ParameterLocation {
+    Query,
+    Header,
+    Path,
+    Cookie,
+}
+
+/// Builds a [Parameter] for [OperationBuilder::parameter].
+///
+/// Path parameters are required by default, and must stay so.
+#[derive(Debug, Clone)]
+pub struct ParameterBuilder {
+    location: ParameterLocation,
+    data: ParameterData,
+    schema: Option<SchemaBuilder>,
+}
+
+impl ParameterBuilder {
+    fn new(location: ParameterLocation, name: String) -> Self {
+        Self {
+            location,
+            data: ParameterData {
+                name,
+                description: None,
+                required: matches!(location, ParameterLocation::Path),
+                deprecated: None,
+                format: ParameterSchemaOrContent::Content(IndexMap::new()),
+                example: None,
+                examples: IndexMap::new(),
+                explode: None,
+                extensions: Extensions::new(),
+            },
+            schema: None,
+        }
+    }
+
+    pub fn query(name: impl Into<String>) -> Self {
+        Self::new(ParameterLocation::Query, name.into())
+    }
+
+    pub fn header(name: impl Into<String>) -> Self {
+        Self::new(ParameterLocation::Header, name.into())
+    }
+
+    pub fn path(name: impl Into<String>) -> Self {
+        Self::new(ParameterLocation::Path, name.into())
+    }
+
+    pub fn cookie(name: impl Into<String>) -> Self {
+        Self::new(ParameterLocation::Cookie, name.into())
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.data.description = Some(description.into());
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.data.required = required;
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.data.deprecated = Some(true);
+        self
+    }
+
+    pub fn schema(mut self, schema: SchemaBuilder) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    pub fn example(mut self, example: serde_json::Value) -> Self {
+        self.data.example = Some(example);
+        self
+    }
+
+    pub fn extension(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.data.extensions.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the parameter, which must have a schema.
+    pub fn build(self) -> Result<Parameter, BuildError> {
+        let mut data = self.data;
+        let invalid = |data: &ParameterData, message: &str| BuildError::InvalidParameter {
+            name: data.name.clone(),
+            message: message.to_owned(),
+        };
+        let schema = match self.schema {
+            Some(schema) => schema.build_ref()?,
+            None => return Err(invalid(&data, "no schema was given")),
+        };
+        data.format = ParameterSchemaOrContent::Schema(Box::new(schema));
+        Ok(match self.location {
+            ParameterLocation::Query => Parameter::Query {
+                parameter_data: data,
+                allow_reserved: false,
+                style: Default::default(),
+                allow_empty_value: None,
+            },
+            ParameterLocation::Header => Parameter::Header {
+                parameter_data: data,
+                style: Default::default(),
+            },
+            ParameterLocation::Path if !data.required => {
+                return Err(invalid(&data, "path parameters must be required"))
+            }
+            ParameterLocation::Path => Parameter::Path {
+                parameter_data: data,
+                style: Default::default(),
+            },
+            ParameterLocation::Cookie => Parameter::Cookie {
+                parameter_data: data,
+                style: Default::default(),
+            },
+        })
+    }
+}
+
+/// Builds a [Schema], or a reference to one.
+///
+/// ```
+/// # use openapiv3::*;
+/// let schema = SchemaBuilder::object()
+///     .required_property("id", SchemaBuilder::integer().format("int64"))
+///     .property("tags", SchemaBuilder::array(SchemaBuilder::string()))
+///     .build()
+///     .unwrap();
+/// match schema.schema_kind {
+///     SchemaKind::Type(Type::Object(object)) => assert_eq!(object.required, ["id"]),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchemaBuilder {
+    schema: ReferenceOr<Schema>,
+    error: Option<BuildError>,
+}
+
+impl SchemaBuilder {
+    fn of_kind(schema_kind: SchemaKind) -> Self {
+        Self {
+            schema: ReferenceOr::Item(Schema {
+                schema_data: Default::default(),
+                schema_kind,
+            }),
+            error: None,
+        }
+    }
+
+    pub fn string() -> Self {
+        Self::of_kind(SchemaKind::Type(Type::String(Default::default())))
+    }
+
+    pub fn number() -> Self {
+        Self::of_kind(SchemaKind::Type(Type::Number(Default::default())))
+    }
+
+    pub fn integer() -> Self {
+        Self::of_kind(SchemaKind::Type(Type::Integer(Default::default())))
+    }
+
+    pub fn boolean() -> Self {
+        Self::of_kind(SchemaKind::Type(Type::Boolean(Default::default())))
+    }
+
+    pub fn object() -> Self {
+        Self::of_kind(SchemaKind::Type(Type::Object(Default::default())))
+    }
+
+    pub fn array(items: SchemaBuilder) -> Self {
+        let mut error = None;
+        let items = record(&mut error, items.build_ref()).map(ReferenceOr::boxed);
+        let mut builder = Self::of_kind(SchemaKind::Type(Type::Array(ArrayType {
+            items,
+            min_items: None,
+            max_items: None,
+            unique_items: false,
+            contains: None,
+            min_contains: None,
+            max_contains: None,
+        })));
+        builder.error = error;
+        builder
+    }
+
+    /// A schema accepting any value.
+    pub fn any() -> Self {
+        Self::of_kind(SchemaKind::Any(Default::default()))
+    }
+
+    /// A `$ref` to a schema, such as `#/components/schemas/Pet`.
+    pub fn reference(reference: impl Into<String>) -> Self {
+        Self {
+            schema: ReferenceOr::Reference {
+                reference: reference.into(),
+            },
+            error: None,
+        }
+    }
+
+    pub fn title(self, title: impl Into<String>) -> Self {
+        let title = title.into();
+        self.update_data(|data| data.title = Some(title))
+    }
+
+    pub fn description(self, description: impl Into<String>) -> Self {
+        let description = description.into();
+        self.update_data(|data| data.description = Some(description))
+    }
+
+    pub fn nullable(self) -> Self {
+        self.update_data(|data| data.nullable = true)
+    }
+
+    pub fn read_only(self) -> Self {
+        self.update_data(|data| data.read_only = true)
+    }
+
+    pub fn write_only(self) -> Self {
+        self.update_data(|data| data.write_only = true)
+    }
+
+    pub fn deprecated(self) -> Self {
+        self.update_data(|data| data.deprecated = true)
+    }
+
+    pub fn example(self, example: serde_json::Value) -> Self {
+        self.update_data(|data| data.example = Some(example))
+    }
+
+    pub fn default_value(self, default: serde_json::Value) -> Self {
+        self.update_data(|data| data.default = Some(default))
+    }
+
+    pub fn extension(self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        let name = name.into();
+        self.update_data(|data| {
+            data.extensions.insert(name, value);
+        })
+    }
+
+    /// Sets the format of a string, number or integer schema.
+    pub fn format(self, format: &str) -> Self {
+        let format = Some(format.to_owned());
+        self.update(|kind| match kind {
+            SchemaKind::Type(Type::String(string)) => {
+                string.format = format.into();
+                Ok(())
+            }
+            SchemaKind::Type(Type::Number(number)) => {
+                number.format = format.into();
+                Ok(())
+            }
+            SchemaKind::Type(Type::Integer(integer)) => {
+                integer.format = format.into();
+                Ok(())
+            }
+            _ => Err("only strings, numbers and integers have a format"),
+        })
+    }
+
+    /// Adds an optional property to an object schema.
+    pub fn property(self, name: impl Into<String>, schema: SchemaBuilder) -> Self {
+        self.add_property(name.into(), schema, false)
+    }
+
+    /// Adds a required property to an object schema.
+    pub fn required_property(self, name: impl Into<String>, schema: SchemaBuilder) -> Self {
+        self.add_property(name.into(), schema, true)
+    }
+
+    /// Returns the schema. Building a [SchemaBuilder::reference] is an
+    /// error, as it is only meant to be passed to other builders.
+    pub fn build(self) -> Result<Schema, BuildError> {
+        match self.build_ref()? {
+            ReferenceOr::Item(schema) => Ok(schema),
+            ReferenceOr::Reference { .. } => Err(BuildError::InvalidSchema {
+                message: "a reference is not a schema".to_owned(),
+            }),
+        }
+    }
+
+    fn build_ref(self) -> Result<ReferenceOr<Schema>, BuildError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.schema),
+        }
+    }
+
+    fn add_property(mut self, name: String, schema: SchemaBuilder, required: bool) -> Self {
+        match record(&mut self.error, schema.build_ref()) {
+            Some(schema) => self.update(|kind| match kind {
+                SchemaKind::Type(Type::Object(object)) => {
+                    if required && !object.required.contains(&name) {
+                        object.required.push(name.clone());
+                    }
+                    object.properties.insert(name, schema.boxed());
+                    Ok(())
+                }
+                _ => Err("only objects have properties"),
+            }),
+            None => self,
+        }
+    }
+
+    fn update<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut SchemaKind) -> Result<(), &'static str>,
+    {
+        let result = match &mut self.schema {
+            ReferenceOr::Item(schema) => f(&mut schema.schema_kind),
+            ReferenceOr::Reference { .. } => Err("a reference cannot be changed"),
+        };
+        let result = result.map_err(|message| BuildError::InvalidSchema {
+            message: message.to_owned(),
+        });
+        record(&mut self.error, result);
+        self
+    }
+
+    fn update_data<F: FnOnce(&mut SchemaData)>(mut self, f: F) -> Self {
+        match &mut self.schema {
+            ReferenceOr::Item(schema) => f(&mut schema.schema_data),
+            ReferenceOr::Reference { .. } => {
+                self.error.get_or_insert(BuildError::InvalidSchema {
+                    message: "a reference cannot be changed".to_owned(),
+                });
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build() {
+        let api = OpenApiBuilder::new("Pets", "1")
+            .schema(
+                "Pet",
+                SchemaBuilder::object()
+                    .required_property("name", SchemaBuilder::string().description("The name"))
+                    .property("born", SchemaBuilder::string().format("date")),
+            )
+            .operation(
+                "/pets",
+                "POST",
+                OperationBuilder::new()
+                    .operation_id("addPet")
+                    .request_body(
+                        "application/json",
+                        SchemaBuilder::reference("#/components/schemas/Pet"),
+                        true,
+                    )
+                    .response(201, ResponseBuilder::new("Created")),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&api).unwrap(),
+            json!({
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": { "/pets": { "post": {
+                    "operationId": "addPet",
+                    "requestBody": {
+                        "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Pet" }
+                        } },
+                        "required": true
+                    },
+                    "responses": { "201": { "description": "Created" } }
+                } } },
+                "components": { "schemas": { "Pet": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string", "description": "The name" },
+                        "born": { "type": "string", "format": "date" }
+                    }
+                } } }
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let ok = || ResponseBuilder::new("OK");
+        assert_eq!(
+            SchemaBuilder::boolean()
+                .format("int32")
+                .property("a", SchemaBuilder::any())
+                .build(),
+            Err(BuildError::InvalidSchema {
+                message: "only strings, numbers and integers have a format".to_owned()
+            })
+        );
+        assert!(matches!(
+            ParameterBuilder::path("id")
+                .required(false)
+                .schema(SchemaBuilder::string())
+                .build(),
+            Err(BuildError::InvalidParameter { .. })
+        ));
+        assert_eq!(
+            OperationBuilder::new().response(42, ok()).build(),
+            Err(BuildError::InvalidStatusCode { status: 42 })
+        );
+        assert_eq!(
+            OperationBuilder::new().build(),
+            Err(BuildError::NoResponses)
+        );
+
+        let operation = || OperationBuilder::new().response(200, ok());
+        assert_eq!(
+            OpenApiBuilder::new("A", "1")
+                .operation("/a", "get", operation())
+                .operation("/a", "get", operation())
+                .build(),
+            Err(BuildError::Collect(CollectError::DuplicateOperation {
+                path: "/a".to_owned(),
+                method: "get".to_owned()
+            }))
+        );
+        match OpenApiBuilder::new("A", "1")
+            .operation("/a/{id}", "get", operation())
+            .build()
+        {
+            Err(BuildError::Invalid(violations)) => {
+                assert_eq!(violations[0].kind, ViolationKind::UndeclaredPathParameter)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
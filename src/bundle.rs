@@ -0,0 +1,369 @@
+use std::fmt;
+
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// Where a bundled component was originally defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// The URI of the document the component was taken from.
+    pub source: String,
+    /// The JSON Pointer of the component within that document, e.g.
+    /// `/components/schemas/Pet`.
+    pub pointer: String,
+}
+
+/// Maps local component references, e.g. `#/components/schemas/Pet`, to the
+/// [Provenance] of the component.
+pub type ProvenanceTable = IndexMap<String, Provenance>;
+
+/// How a [Bundler] records [Provenance].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProvenanceRecording {
+    /// Only return the [ProvenanceTable].
+    #[default]
+    SideTable,
+    /// Additionally add an `x-provenance` extension with `source` and
+    /// `pointer` fields to each bundled component that supports extensions.
+    /// Components that are themselves references and callbacks have no room
+    /// for extensions and are only recorded in the table.
+    Extension,
+}
+
+/// The error returned when bundling fails.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleError {
+    /// Two documents define different components under the same name.
+    Conflict {
+        kind: ComponentKind,
+        name: String,
+        first: Provenance,
+        second: Provenance,
+    },
+    /// The bundled document could not be rebuilt after its references were
+    /// rewritten.
+    Rebuild(String),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Conflict {
+                kind,
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "{} `{}` is defined differently in {}#{} and {}#{}",
+                kind, name, first.source, first.pointer, second.source, second.pointer
+            ),
+            BundleError::Rebuild(message) => {
+                write!(f, "failed to rebuild the bundled document: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+/// Merges the components of several documents into a single document,
+/// recording where each component came from.
+///
+/// References of the form `<source>#/components/<kind>/<name>` to an imported
+/// source are rewritten to local references when the bundle is finished.
+/// Loading the documents is left to the caller.
+///
+/// ```
+/// # use openapiv3::*;
+/// let root: OpenAPI = serde_json::from_str(r##"{
+///     "openapi": "3.0.3",
+///     "info": { "title": "Pets", "version": "1" },
+///     "paths": {},
+///     "components": { "schemas": {
+///         "Pets": { "type": "array", "items": { "$ref": "common.json#/components/schemas/Pet" } }
+///     } }
+/// }"##).unwrap();
+/// let common: Components = serde_json::from_str(r#"{
+///     "schemas": { "Pet": { "type": "object" } }
+/// }"#).unwrap();
+///
+/// let mut bundler = Bundler::new(root, "openapi.json");
+/// bundler.import("common.json", common).unwrap();
+/// let (bundled, provenance) = bundler.finish().unwrap();
+///
+/// assert_eq!(provenance["#/components/schemas/Pet"].source, "common.json");
+/// assert_eq!(provenance["#/components/schemas/Pets"].source, "openapi.json");
+/// let value = serde_json::to_value(&bundled).unwrap();
+/// assert_eq!(
+///     value.pointer("/components/schemas/Pets/items/$ref").unwrap(),
+///     "#/components/schemas/Pet"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bundler {
+    document: OpenAPI,
+    sources: Vec<String>,
+    provenance: ProvenanceTable,
+    recording: ProvenanceRecording,
+}
+
+macro_rules! merge_sections {
+    ($self:ident, $source:ident, $components:ident, $($field:ident => $kind:ident),*) => {{
+        let mut target = $self.document.components.clone().unwrap_or_default();
+        let mut table = $self.provenance.clone();
+        $(
+            for (name, item) in $components.$field {
+                let provenance = Provenance {
+                    source: $source.to_owned(),
+                    pointer: section_pointer(ComponentKind::$kind, &name),
+                };
+                let key = format!("#{}", provenance.pointer);
+                match target.$field.get(&name) {
+                    Some(existing) if *existing == item => {}
+                    Some(_) => {
+                        return Err(BundleError::Conflict {
+                            kind: ComponentKind::$kind,
+                            name,
+                            first: table[&key].clone(),
+                            second: provenance,
+                        })
+                    }
+                    None => {
+                        table.insert(key, provenance);
+                        target.$field.insert(name, item);
+                    }
+                }
+            }
+        )*
+        $self.document.components = Some(target);
+        $self.provenance = table;
+    }};
+}
+
+impl Bundler {
+    /// Starts a bundle from the root document, which was loaded from
+    /// `source`.
+    pub fn new(document: OpenAPI, source: impl Into<String>) -> Self {
+        let source = source.into();
+        let mut provenance = ProvenanceTable::new();
+        if let Some(components) = &document.components {
+            for kind in ComponentKind::ALL {
+                for name in section_names(components, kind) {
+                    let pointer = section_pointer(kind, name);
+                    provenance.insert(
+                        format!("#{}", pointer),
+                        Provenance {
+                            source: source.clone(),
+                            pointer,
+                        },
+                    );
+                }
+            }
+        }
+
+        Self {
+            document,
+            sources: vec![source],
+            provenance,
+            recording: ProvenanceRecording::default(),
+        }
+    }
+
+    /// Sets how provenance is recorded.
+    pub fn recording(mut self, recording: ProvenanceRecording) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// Adds the components of the document loaded from `source`.
+    ///
+    /// Components whose name is already taken are skipped if they are equal
+    /// to the existing one, and are reported as a [BundleError::Conflict]
+    /// otherwise, in which case nothing is imported.
+    pub fn import(&mut self, source: &str, components: Components) -> Result<(), BundleError> {
+        merge_sections!(self, source, components,
+            schemas => Schemas,
+            responses => Responses,
+            parameters => Parameters,
+            examples => Examples,
+            request_bodies => RequestBodies,
+            headers => Headers,
+            security_schemes => SecuritySchemes,
+            links => Links,
+            callbacks => Callbacks
+        );
+        if !self.sources.iter().any(|s| s == source) {
+            self.sources.push(source.to_owned());
+        }
+        Ok(())
+    }
+
+    /// Returns the provenance recorded so far.
+    pub fn provenance(&self) -> &ProvenanceTable {
+        &self.provenance
+    }
+
+    /// Rewrites references to the imported sources and returns the bundled
+    /// document along with the provenance of its components.
+    pub fn finish(self) -> Result<(OpenAPI, ProvenanceTable), BundleError> {
+        let mut value = serde_json::to_value(&self.document)
+            .map_err(|err| BundleError::Rebuild(err.to_string()))?;
+        rewrite_references(&mut value, &self.sources);
+
+        if self.recording == ProvenanceRecording::Extension {
+            for (reference, provenance) in &self.provenance {
+                let section = reference.rsplit_once('/').map(|(s, _)| &s[1..]);
+                if section == Some("/components/callbacks") {
+                    continue;
+                }
+                if let Some(serde_json::Value::Object(object)) = value.pointer_mut(&reference[1..])
+                {
+                    if object.contains_key("$ref") {
+                        continue;
+                    }
+                    object.insert(
+                        "x-provenance".to_owned(),
+                        serde_json::json!({
+                            "source": provenance.source,
+                            "pointer": provenance.pointer,
+                        }),
+                    );
+                }
+            }
+        }
+
+        let document =
+            serde_json::from_value(value).map_err(|err| BundleError::Rebuild(err.to_string()))?;
+        Ok((document, self.provenance))
+    }
+}
+
+fn section_pointer(kind: ComponentKind, name: &str) -> String {
+    format!("/components/{}/{}", kind, escape_pointer_token(name))
+}
+
+pub(crate) fn section_names(components: &Components, kind: ComponentKind) -> Vec<&String> {
+    match kind {
+        ComponentKind::Schemas => components.schemas.keys().collect(),
+        ComponentKind::Responses => components.responses.keys().collect(),
+        ComponentKind::Parameters => components.parameters.keys().collect(),
+        ComponentKind::Examples => components.examples.keys().collect(),
+        ComponentKind::RequestBodies => components.request_bodies.keys().collect(),
+        ComponentKind::Headers => components.headers.keys().collect(),
+        ComponentKind::SecuritySchemes => components.security_schemes.keys().collect(),
+        ComponentKind::Links => components.links.keys().collect(),
+        ComponentKind::Callbacks => components.callbacks.keys().collect(),
+    }
+}
+
+/// Turns `<source>#/components/...` references into local ones.
+fn rewrite_references(value: &mut serde_json::Value, sources: &[String]) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                match child {
+                    serde_json::Value::String(reference) if key == "$ref" => {
+                        if let Some((source, fragment)) = reference.split_once('#') {
+                            if !source.is_empty()
+                                && fragment.starts_with("/components/")
+                                && sources.iter().any(|s| s == source)
+                            {
+                                *reference = format!("#{}", fragment);
+                            }
+                        }
+                    }
+                    child => rewrite_references(child, sources),
+                }
+            }
+        }
+        serde_json::Value::Array(array) => {
+            for child in array {
+                rewrite_references(child, sources);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root() -> OpenAPI {
+        serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {},
+                "components": {
+                    "schemas": { "Id": { "type": "string" } },
+                    "parameters": {
+                        "PetId": {
+                            "name": "id", "in": "path", "required": true,
+                            "schema": { "$ref": "common.json#/components/schemas/Id" }
+                        }
+                    }
+                }
+            }"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_conflict() {
+        let mut bundler = Bundler::new(root(), "openapi.json");
+        let same: Components =
+            serde_json::from_str(r#"{ "schemas": { "Id": { "type": "string" } } }"#).unwrap();
+        bundler.import("common.json", same).unwrap();
+        assert_eq!(
+            bundler.provenance()["#/components/schemas/Id"].source,
+            "openapi.json"
+        );
+
+        let different: Components =
+            serde_json::from_str(r#"{ "schemas": { "Id": { "type": "integer" } } }"#).unwrap();
+        assert_eq!(
+            bundler.import("other.json", different),
+            Err(BundleError::Conflict {
+                kind: ComponentKind::Schemas,
+                name: "Id".to_string(),
+                first: Provenance {
+                    source: "openapi.json".to_string(),
+                    pointer: "/components/schemas/Id".to_string(),
+                },
+                second: Provenance {
+                    source: "other.json".to_string(),
+                    pointer: "/components/schemas/Id".to_string(),
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_extension_recording() {
+        let common: Components =
+            serde_json::from_str(r#"{ "examples": { "Pet": { "value": { "name": "Rex" } } } }"#)
+                .unwrap();
+        let mut bundler =
+            Bundler::new(root(), "openapi.json").recording(ProvenanceRecording::Extension);
+        bundler.import("common.json", common).unwrap();
+        let (bundled, provenance) = bundler.finish().unwrap();
+        assert_eq!(provenance.len(), 3);
+
+        let components = bundled.components.unwrap();
+        let example = components.examples["Pet"].as_item().unwrap();
+        assert_eq!(
+            example.extensions["x-provenance"],
+            serde_json::json!({ "source": "common.json", "pointer": "/components/examples/Pet" })
+        );
+        let parameter = components.parameters["PetId"].as_item().unwrap();
+        assert_eq!(
+            parameter.parameter_data_ref().format,
+            ParameterSchemaOrContent::Schema(Box::new(ReferenceOr::ref_(
+                "#/components/schemas/Id"
+            )))
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::*;
+use indexmap::IndexMap;
+
+/// Whether a [DocumentCache] entry holds the document as parsed or with its
+/// schemas dereferenced by [OpenAPI::dereference_schemas].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheMode {
+    Parsed,
+    Dereferenced,
+}
+
+/// A document stored in a [DocumentCache].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedDocument {
+    pub document: OpenAPI,
+    /// The sites inlined by [OpenAPI::dereference_schemas] when loaded with
+    /// [CacheMode::Dereferenced].
+    pub inlined: Option<InlinedSchemas>,
+}
+
+/// The error returned by [DocumentCache::load].
+#[derive(Debug)]
+pub enum CacheError<E> {
+    /// The input could not be parsed.
+    Parse(E),
+    /// The parsed document could not be dereferenced.
+    Resolve(ResolveError),
+}
+
+impl<E: fmt::Display> fmt::Display for CacheError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheError::Parse(err) => err.fmt(f),
+            CacheError::Resolve(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for CacheError<E> {}
+
+type Entries = IndexMap<(ContentHash, CacheMode), Arc<CachedDocument>>;
+
+/// Caches parsed documents keyed by the [ContentHash] of their raw input, for
+/// tools that load the same large documents repeatedly.
+///
+/// The cache is safe to share between threads. When it holds more than its
+/// capacity the oldest entries are evicted first.
+///
+/// ```
+/// # use openapiv3::{CacheMode, DocumentCache};
+/// let cache = DocumentCache::new();
+/// let input = br#"{"openapi": "3.0.3", "info": {"title": "A", "version": "1"}, "paths": {}}"#;
+/// let first = cache.load_json(input, CacheMode::Parsed).unwrap();
+/// let second = cache.load_json(input, CacheMode::Parsed).unwrap();
+/// assert!(std::sync::Arc::ptr_eq(&first, &second));
+/// ```
+#[derive(Debug)]
+pub struct DocumentCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl Default for DocumentCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocumentCache {
+    /// Creates a cache holding up to 16 documents.
+    pub fn new() -> Self {
+        Self::with_capacity(16)
+    }
+
+    /// Creates a cache holding up to `capacity` documents.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    /// Returns the cached document for `input`, parsing it with `parse` and
+    /// caching the result on a miss.
+    ///
+    /// Errors are not cached.
+    pub fn load<E, F>(
+        &self,
+        input: &[u8],
+        mode: CacheMode,
+        parse: F,
+    ) -> Result<Arc<CachedDocument>, CacheError<E>>
+    where
+        F: FnOnce(&[u8]) -> Result<OpenAPI, E>,
+    {
+        let key = (ContentHash::of_bytes(input), mode);
+        if let Some(cached) = self.lock().get(&key) {
+            return Ok(cached.clone());
+        }
+
+        // Parse without holding the lock so that other documents can be
+        // loaded meanwhile.
+        let mut document = parse(input).map_err(CacheError::Parse)?;
+        let inlined = match mode {
+            CacheMode::Parsed => None,
+            CacheMode::Dereferenced => Some(
+                document
+                    .dereference_schemas()
+                    .map_err(CacheError::Resolve)?,
+            ),
+        };
+        let cached = Arc::new(CachedDocument { document, inlined });
+
+        let mut entries = self.lock();
+        let cached = entries.entry(key).or_insert(cached).clone();
+        while entries.len() > self.capacity {
+            entries.shift_remove_index(0);
+        }
+        Ok(cached)
+    }
+
+    /// Like [DocumentCache::load], parsing the input as JSON.
+    pub fn load_json(
+        &self,
+        input: &[u8],
+        mode: CacheMode,
+    ) -> Result<Arc<CachedDocument>, CacheError<serde_json::Error>> {
+        self.load(input, mode, |input| serde_json::from_slice(input))
+    }
+
+    /// Returns the number of cached documents.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached documents.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        // A panic while holding the lock cannot leave the map inconsistent.
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &[u8] = br##"{
+        "openapi": "3.0.3",
+        "info": { "title": "A", "version": "1" },
+        "paths": {},
+        "components": { "schemas": {
+            "A": { "type": "array", "items": { "$ref": "#/components/schemas/B" } },
+            "B": { "type": "string" }
+        } }
+    }"##;
+
+    #[test]
+    fn test_load() {
+        let cache = DocumentCache::with_capacity(2);
+        let parsed = cache.load_json(INPUT, CacheMode::Parsed).unwrap();
+        assert!(parsed.inlined.is_none());
+
+        let mut calls = 0;
+        let dereferenced = cache
+            .load(INPUT, CacheMode::Dereferenced, |input| {
+                calls += 1;
+                serde_json::from_slice::<OpenAPI>(input)
+            })
+            .unwrap();
+        assert_eq!(calls, 1);
+        assert_eq!(
+            dereferenced.inlined.as_ref().unwrap()["/components/schemas/A/items"],
+            "B"
+        );
+
+        let again = cache
+            .load(INPUT, CacheMode::Dereferenced, |_| -> Result<OpenAPI, ()> {
+                panic!("should be cached")
+            })
+            .unwrap();
+        assert!(Arc::ptr_eq(&dereferenced, &again));
+        assert_eq!(cache.len(), 2);
+
+        assert!(matches!(
+            cache.load_json(b"{}", CacheMode::Parsed),
+            Err(CacheError::Parse(_))
+        ));
+        cache
+            .load_json(
+                br#"{"openapi": "3.0.3", "info": {"title": "B", "version": "1"}, "paths": {}}"#,
+                CacheMode::Parsed,
+            )
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // The oldest entry was evicted.
+        let mut calls = 0;
+        cache
+            .load(INPUT, CacheMode::Parsed, |input| {
+                calls += 1;
+                serde_json::from_slice::<OpenAPI>(input)
+            })
+            .unwrap();
+        assert_eq!(calls, 1);
+    }
+}
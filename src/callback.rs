@@ -1,3 +1,6 @@
+use std::fmt;
+use std::str::FromStr;
+
 use crate::*;
 use indexmap::IndexMap;
 
@@ -7,3 +10,244 @@ use indexmap::IndexMap;
 /// The key value used to identify the callback object is an expression,
 /// evaluated at runtime, that identifies a URL to use for the callback operation.
 pub type Callback = IndexMap<String, PathItem>;
+
+/// A parsed key of a [Callback], i.e. a URL in which each `{...}` part is a
+/// [RuntimeExpression], such as
+/// `https://example.com/notify?id={$request.body#/id}`. A key that is a
+/// single expression without braces, such as `$request.query.url`, is also
+/// accepted.
+///
+/// ```
+/// # use openapiv3::*;
+/// let url: CallbackUrl = "{$request.body#/callbackUrl}/events".parse().unwrap();
+/// assert_eq!(
+///     url.parts(),
+///     [
+///         CallbackUrlPart::Expression(RuntimeExpression::Request(ExpressionSource::Body(
+///             Some("/callbackUrl".to_owned())
+///         ))),
+///         CallbackUrlPart::Literal("/events".to_owned()),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackUrl {
+    parts: Vec<CallbackUrlPart>,
+}
+
+/// A piece of a [CallbackUrl].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallbackUrlPart {
+    /// Text that is copied verbatim.
+    Literal(String),
+    /// A runtime expression, written `{expression}` in the key.
+    Expression(RuntimeExpression),
+}
+
+/// The error returned when a [Callback] key is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallbackUrlError {
+    /// A `{` at the given byte offset has no matching `}`.
+    UnclosedBrace { position: usize },
+    /// A `}` at the given byte offset has no matching `{`.
+    UnexpectedClosingBrace { position: usize },
+    /// The expression at the given byte offset is not valid.
+    InvalidExpression {
+        position: usize,
+        error: RuntimeExpressionError,
+    },
+}
+
+impl fmt::Display for CallbackUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnclosedBrace { position } => write!(f, "unclosed `{{` at offset {}", position),
+            Self::UnexpectedClosingBrace { position } => {
+                write!(f, "unexpected `}}` at offset {}", position)
+            }
+            Self::InvalidExpression { position, error } => {
+                write!(f, "{} at offset {}", error, position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CallbackUrlError {}
+
+impl CallbackUrl {
+    /// The literal and expression pieces of this URL, in order.
+    pub fn parts(&self) -> &[CallbackUrlPart] {
+        &self.parts
+    }
+
+    /// Iterates over the runtime expressions in order of appearance.
+    pub fn expressions(&self) -> impl Iterator<Item = &RuntimeExpression> {
+        self.parts.iter().filter_map(|part| match part {
+            CallbackUrlPart::Expression(expression) => Some(expression),
+            CallbackUrlPart::Literal(_) => None,
+        })
+    }
+}
+
+impl FromStr for CallbackUrl {
+    type Err = CallbackUrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = |text: &str, position| {
+            text.parse()
+                .map(CallbackUrlPart::Expression)
+                .map_err(|error| CallbackUrlError::InvalidExpression { position, error })
+        };
+        if s.starts_with('$') {
+            return Ok(CallbackUrl {
+                parts: vec![expression(s, 0)?],
+            });
+        }
+
+        let mut parts = Vec::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            let offset = s.len() - rest.len();
+            match rest.find(['{', '}']) {
+                Some(i) if rest.as_bytes()[i] == b'}' => {
+                    return Err(CallbackUrlError::UnexpectedClosingBrace {
+                        position: offset + i,
+                    })
+                }
+                Some(i) => {
+                    if i > 0 {
+                        parts.push(CallbackUrlPart::Literal(rest[..i].to_owned()));
+                    }
+                    let end = rest[i..].find('}').ok_or(CallbackUrlError::UnclosedBrace {
+                        position: offset + i,
+                    })?;
+                    parts.push(expression(&rest[i + 1..i + end], offset + i + 1)?);
+                    rest = &rest[i + end + 1..];
+                }
+                None => {
+                    parts.push(CallbackUrlPart::Literal(rest.to_owned()));
+                    rest = "";
+                }
+            }
+        }
+        Ok(CallbackUrl { parts })
+    }
+}
+
+impl fmt::Display for CallbackUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let [CallbackUrlPart::Expression(expression)] = self.parts.as_slice() {
+            return write!(f, "{{{}}}", expression);
+        }
+        for part in &self.parts {
+            match part {
+                CallbackUrlPart::Literal(text) => f.write_str(text)?,
+                CallbackUrlPart::Expression(expression) => write!(f, "{{{}}}", expression)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An operation of a callback, as iterated by [OpenAPI::callback_operations].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CallbackOperation<'a> {
+    /// The path of the operation declaring the callback.
+    pub path: &'a str,
+    /// The method of the operation declaring the callback.
+    pub method: &'a str,
+    /// The name of the callback in [Operation::callbacks].
+    pub name: &'a str,
+    /// The key of the [Callback], see [CallbackUrl].
+    pub expression: &'a str,
+    /// The method of the callback operation.
+    pub callback_method: &'a str,
+    pub operation: &'a Operation,
+}
+
+impl OpenAPI {
+    /// Iterates through the operations of the callbacks of every operation
+    /// in this API, like [OpenAPI::operations] does for paths.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Hooks", "version": "1" },
+    ///     "paths": { "/subscribe": { "post": {
+    ///         "responses": {},
+    ///         "callbacks": { "onEvent": { "{$request.body#/url}": {
+    ///             "post": { "responses": { "200": { "description": "" } } }
+    ///         } } }
+    ///     } } }
+    /// }"#).unwrap();
+    /// let callback = api.callback_operations().next().unwrap();
+    /// assert_eq!(
+    ///     (callback.path, callback.name, callback.expression, callback.callback_method),
+    ///     ("/subscribe", "onEvent", "{$request.body#/url}", "post")
+    /// );
+    /// ```
+    pub fn callback_operations(&self) -> impl Iterator<Item = CallbackOperation<'_>> {
+        self.operations().flat_map(|(path, method, operation)| {
+            operation
+                .callbacks
+                .iter()
+                .flat_map(move |(name, callback)| {
+                    callback.iter().flat_map(move |(expression, item)| {
+                        item.iter()
+                            .map(move |(callback_method, operation)| CallbackOperation {
+                                path,
+                                method,
+                                name,
+                                expression,
+                                callback_method,
+                                operation,
+                            })
+                    })
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_url() {
+        let url: CallbackUrl =
+            "http://notify.example.com?id={$request.body#/id}&email={$request.header.X-Email}"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            url.expressions().collect::<Vec<_>>(),
+            [
+                &RuntimeExpression::Request(ExpressionSource::Body(Some("/id".to_owned()))),
+                &RuntimeExpression::Request(ExpressionSource::Header("X-Email".to_owned())),
+            ]
+        );
+        assert_eq!(
+            url.to_string(),
+            "http://notify.example.com?id={$request.body#/id}&email={$request.header.X-Email}"
+        );
+
+        let bare: CallbackUrl = "$request.query.queryUrl".parse().unwrap();
+        assert_eq!(bare.to_string(), "{$request.query.queryUrl}");
+        assert_eq!(
+            "https://{$statusCode}}".parse::<CallbackUrl>(),
+            Err(CallbackUrlError::UnexpectedClosingBrace { position: 21 })
+        );
+        assert_eq!(
+            "{$url".parse::<CallbackUrl>(),
+            Err(CallbackUrlError::UnclosedBrace { position: 0 })
+        );
+        assert!(matches!(
+            "{$request.cookie.id}".parse::<CallbackUrl>(),
+            Err(CallbackUrlError::InvalidExpression { position: 1, .. })
+        ));
+        assert!(matches!(
+            "$response.status".parse::<CallbackUrl>(),
+            Err(CallbackUrlError::InvalidExpression { position: 0, .. })
+        ));
+    }
+}
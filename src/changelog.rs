@@ -0,0 +1,277 @@
+use std::fmt;
+
+use crate::util::{unescape_pointer_token, METHODS};
+use crate::*;
+
+/// How a [ChangelogEntry] affects clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Clients written against the old document may break.
+    Breaking,
+    /// Something was added without breaking clients.
+    Added,
+    /// Something else changed without breaking clients.
+    Changed,
+}
+
+impl Severity {
+    fn of(change: &Change) -> Self {
+        use ChangeKind::*;
+        match change.kind {
+            _ if change.breaking => Severity::Breaking,
+            PathAdded | OperationAdded | ParameterAdded | RequestBodyAdded | MediaTypeAdded
+            | ResponseAdded | PropertyAdded | EnumValueAdded => Severity::Added,
+            _ => Severity::Changed,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Breaking => "breaking",
+            Severity::Added => "added",
+            Severity::Changed => "changed",
+        })
+    }
+}
+
+/// A [Change] in a [Changelog].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogEntry {
+    pub severity: Severity,
+    pub kind: ChangeKind,
+    /// The JSON Pointer of the changed value relative to the path item or
+    /// operation of its section, e.g. `/responses/200`, or the empty string
+    /// for the path item or operation itself.
+    pub location: String,
+    pub message: String,
+}
+
+/// The changes to one path item or operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogSection {
+    /// The path of the path item, or the empty string for changes outside
+    /// of the paths.
+    pub path: String,
+    /// The method of the operation, or `None` for changes to the path item
+    /// itself, such as its parameters.
+    pub method: Option<String>,
+    /// The changes, breaking ones first.
+    pub entries: Vec<ChangelogEntry>,
+}
+
+impl ChangelogSection {
+    /// The title of this section, e.g. `GET /pets`.
+    pub fn title(&self) -> String {
+        match (&self.method, self.path.as_str()) {
+            (_, "") => "General".to_owned(),
+            (Some(method), path) => format!("{} {}", method.to_ascii_uppercase(), path),
+            (None, path) => path.to_owned(),
+        }
+    }
+}
+
+/// The changes of a [SpecDiff] grouped by path item and operation, to be
+/// published as release notes, see [SpecDiff::changelog].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changelog {
+    /// The sections, in the order of their first change.
+    pub sections: Vec<ChangelogSection>,
+}
+
+impl Changelog {
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Renders this changelog as Markdown, with a heading per section and an
+    /// item per change labelled with its severity.
+    pub fn to_markdown(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Changelog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, section) in self.sections.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "### `{}`", section.title())?;
+            writeln!(f)?;
+            for entry in &section.entries {
+                write!(f, "- **{}**: {}", entry.severity, entry.message)?;
+                if !entry.location.is_empty() {
+                    write!(f, " (`{}`)", entry.location)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SpecDiff {
+    /// Groups the changes by the path item or operation they were found in,
+    /// and labels them with their [Severity].
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let old: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": {
+    ///         "get": { "responses": { "200": { "description": "Pets" } } },
+    ///         "delete": { "responses": {} }
+    ///     } }
+    /// }"#).unwrap();
+    /// let new: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "2" },
+    ///     "paths": { "/pets": {
+    ///         "get": { "responses": { "200": { "description": "Pets" }, "404": { "description": "None" } } }
+    ///     } }
+    /// }"#).unwrap();
+    ///
+    /// let changelog = diff(&old, &new).changelog();
+    /// let markdown = changelog.to_markdown();
+    /// let sections = markdown.split("\n\n### ").collect::<Vec<_>>();
+    /// assert_eq!(sections, [
+    ///     "### `GET /pets`\n\n- **added**: response `404` was added (`/responses/404`)",
+    ///     "`DELETE /pets`\n\n- **breaking**: operation `delete` was removed\n",
+    /// ]);
+    /// ```
+    pub fn changelog(&self) -> Changelog {
+        let mut sections: Vec<ChangelogSection> = Vec::new();
+        for change in &self.changes {
+            let (path, method, location) = split_pointer(&change.pointer);
+            let entry = ChangelogEntry {
+                severity: Severity::of(change),
+                kind: change.kind,
+                location,
+                message: change.message.clone(),
+            };
+            match sections
+                .iter_mut()
+                .find(|s| s.path == path && s.method == method)
+            {
+                Some(section) => section.entries.push(entry),
+                None => sections.push(ChangelogSection {
+                    path,
+                    method,
+                    entries: vec![entry],
+                }),
+            }
+        }
+        for section in &mut sections {
+            section.entries.sort_by_key(|entry| entry.severity);
+        }
+        Changelog { sections }
+    }
+}
+
+/// Splits the pointer of a change into its path, method and the rest.
+fn split_pointer(pointer: &str) -> (String, Option<String>, String) {
+    let Some(rest) = pointer.strip_prefix("/paths/") else {
+        return (String::new(), None, pointer.to_owned());
+    };
+    let (path, rest) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let path = unescape_pointer_token(path);
+    let method = rest[1.min(rest.len())..]
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    match METHODS.contains(&method) {
+        true => (
+            path,
+            Some(method.to_owned()),
+            rest[1 + method.len()..].to_owned(),
+        ),
+        false => (path, None, rest.to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_changelog() {
+        let old: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [ { "name": "verbose", "in": "query", "schema": { "type": "boolean" } } ],
+                    "get": { "responses": { "200": { "description": "", "content": { "application/json": {
+                        "schema": { "type": "object", "properties": { "name": { "type": "string", "enum": ["Rex", "Max"] } } }
+                    } } } } }
+                },
+                "/owners": { "get": { "responses": {} } }
+            }
+        }))
+        .unwrap();
+        let new: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "2" },
+            "paths": {
+                "/pets/{id}": {
+                    "get": { "responses": { "200": { "description": "", "content": { "application/json": {
+                        "schema": { "type": "object", "properties": {
+                            "name": { "type": "string", "enum": ["Rex"] },
+                            "age": { "type": "integer" }
+                        } }
+                    } } } } }
+                },
+                "/stores": { "get": { "responses": {} } }
+            }
+        }))
+        .unwrap();
+
+        let changelog = diff(&old, &new).changelog();
+        let sections = changelog
+            .sections
+            .iter()
+            .map(|s| {
+                let entries = s.entries.iter().map(|e| (e.severity, e.location.as_str()));
+                (s.title(), entries.collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        let schema = "/responses/200/content/application~1json/schema";
+        assert_eq!(
+            sections,
+            [
+                ("/owners".to_owned(), vec![(Severity::Breaking, "")]),
+                (
+                    "/pets/{id}".to_owned(),
+                    vec![(Severity::Breaking, "/parameters/0")]
+                ),
+                (
+                    "GET /pets/{id}".to_owned(),
+                    vec![
+                        (
+                            Severity::Added,
+                            format!("{}/properties/age", schema).as_str()
+                        ),
+                        (
+                            Severity::Changed,
+                            format!("{}/properties/name/enum", schema).as_str()
+                        ),
+                    ]
+                ),
+                ("/stores".to_owned(), vec![(Severity::Added, "")]),
+            ]
+        );
+        assert!(changelog
+            .to_markdown()
+            .contains("### `/owners`\n\n- **breaking**: path `/owners` was removed\n"));
+
+        assert_eq!(
+            split_pointer("/security/0"),
+            (String::new(), None, "/security/0".to_owned())
+        );
+        assert!(diff(&old, &old).changelog().is_empty());
+    }
+}
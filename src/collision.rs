@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+
+use crate::util::escape_pointer_token;
+use crate::*;
+
+/// A key that appears more than once in the same JSON object.
+///
+/// Because extensions are flattened into the objects that hold them, a
+/// duplicated extension silently keeps only its last value when parsed, and
+/// an extension whose name clashes with a field of the object is written out
+/// twice when serialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCollision {
+    /// The JSON Pointer of the object containing the key.
+    pub pointer: String,
+    pub key: String,
+}
+
+impl KeyCollision {
+    /// Whether the key is a specification extension, i.e. starts with `x-`.
+    pub fn is_extension(&self) -> bool {
+        self.key.starts_with("x-")
+    }
+}
+
+/// Finds keys repeated within an object of the JSON text `input`, in the
+/// order they are repeated.
+///
+/// Use this before parsing to catch collisions that deserialization would
+/// silently resolve. Scanning stops at the first syntax error, and objects
+/// nested more than 128 levels deep are not inspected.
+///
+/// ```
+/// # use openapiv3::find_key_collisions;
+/// let collisions = find_key_collisions(r#"{"info": {"x-logo": "a.png", "x-logo": "b.png"}}"#);
+/// assert_eq!(collisions[0].pointer, "/info");
+/// assert_eq!(collisions[0].key, "x-logo");
+/// ```
+pub fn find_key_collisions(input: &str) -> Vec<KeyCollision> {
+    let mut scanner = Scanner {
+        input: input.as_bytes(),
+        position: 0,
+        collisions: Vec::new(),
+    };
+    scanner.value(&mut String::new(), 0);
+    scanner.collisions
+}
+
+impl OpenAPI {
+    /// Finds keys that would be written more than once when serializing this
+    /// document, typically because an extension map holds a key that is also
+    /// the name of a field.
+    ///
+    /// ```
+    /// # use openapiv3::OpenAPI;
+    /// let mut api: OpenAPI = serde_json::from_str(
+    ///     r#"{"openapi": "3.0.3", "info": {"title": "A", "version": "1"}, "paths": {}}"#,
+    /// ).unwrap();
+    /// api.info.extensions.insert("title".to_string(), "B".into());
+    /// assert_eq!(api.key_collisions()[0].key, "title");
+    /// ```
+    pub fn key_collisions(&self) -> Vec<KeyCollision> {
+        match serde_json::to_string(self) {
+            Ok(json) => find_key_collisions(&json),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+const MAX_DEPTH: usize = 128;
+
+/// A minimal JSON scanner that only tracks object keys.
+struct Scanner<'a> {
+    input: &'a [u8],
+    position: usize,
+    collisions: Vec<KeyCollision>,
+}
+
+impl Scanner<'_> {
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.position) {
+            self.position += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.input.get(self.position) == Some(&byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Scans a value, returning false on a syntax error.
+    fn value(&mut self, pointer: &mut String, depth: usize) -> bool {
+        if depth > MAX_DEPTH {
+            return false;
+        }
+        self.skip_whitespace();
+        match self.input.get(self.position) {
+            Some(b'{') => self.object(pointer, depth),
+            Some(b'[') => self.array(pointer, depth),
+            Some(b'"') => self.string().is_some(),
+            Some(_) => {
+                let start = self.position;
+                while let Some(byte) = self.input.get(self.position) {
+                    if matches!(byte, b',' | b']' | b'}') || byte.is_ascii_whitespace() {
+                        break;
+                    }
+                    self.position += 1;
+                }
+                self.position > start
+            }
+            None => false,
+        }
+    }
+
+    fn object(&mut self, pointer: &mut String, depth: usize) -> bool {
+        self.position += 1;
+        if self.eat(b'}') {
+            return true;
+        }
+        let mut keys = HashSet::new();
+        loop {
+            self.skip_whitespace();
+            let key = match self.string() {
+                Some(key) => key,
+                None => return false,
+            };
+            if !self.eat(b':') {
+                return false;
+            }
+            if !keys.insert(key.clone()) {
+                self.collisions.push(KeyCollision {
+                    pointer: pointer.clone(),
+                    key: key.clone(),
+                });
+            }
+
+            let length = pointer.len();
+            pointer.push('/');
+            pointer.push_str(&escape_pointer_token(&key));
+            let valid = self.value(pointer, depth + 1);
+            pointer.truncate(length);
+            if !valid {
+                return false;
+            }
+
+            if self.eat(b'}') {
+                return true;
+            }
+            if !self.eat(b',') {
+                return false;
+            }
+        }
+    }
+
+    fn array(&mut self, pointer: &mut String, depth: usize) -> bool {
+        self.position += 1;
+        if self.eat(b']') {
+            return true;
+        }
+        for i in 0.. {
+            let length = pointer.len();
+            pointer.push('/');
+            pointer.push_str(&i.to_string());
+            let valid = self.value(pointer, depth + 1);
+            pointer.truncate(length);
+            if !valid {
+                return false;
+            }
+
+            if self.eat(b']') {
+                return true;
+            }
+            if !self.eat(b',') {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Scans a string, returning its unescaped contents.
+    fn string(&mut self) -> Option<String> {
+        let start = self.position;
+        if self.input.get(start) != Some(&b'"') {
+            return None;
+        }
+        self.position += 1;
+        loop {
+            match self.input.get(self.position)? {
+                b'\\' => self.position += 2,
+                b'"' => break,
+                _ => self.position += 1,
+            }
+        }
+        self.position += 1;
+        let raw = std::str::from_utf8(self.input.get(start..self.position)?).ok()?;
+        serde_json::from_str(raw).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_key_collisions() {
+        let input = r#"{
+            "paths": {
+                "/a\/b": { "get": { "x-id": 1, "summary": "a", "x-id": 2 } }
+            },
+            "tags": [ { "name": "a", "name": "b" }, { "x-a": 1, "x-a": 2 } ],
+            "x-ok": { "x-ok": "not a collision" }
+        }"#;
+        assert_eq!(
+            find_key_collisions(input),
+            [
+                KeyCollision {
+                    pointer: "/paths/~1a~1b/get".to_string(),
+                    key: "x-id".to_string(),
+                },
+                KeyCollision {
+                    pointer: "/tags/0".to_string(),
+                    key: "name".to_string(),
+                },
+                KeyCollision {
+                    pointer: "/tags/1".to_string(),
+                    key: "x-a".to_string(),
+                },
+            ]
+        );
+
+        assert!(find_key_collisions(r#"{"a": 1, "a": "#).len() == 1);
+        assert!(find_key_collisions(&"[".repeat(1000)).is_empty());
+    }
+
+    #[test]
+    fn test_key_collisions() {
+        let mut api: OpenAPI = serde_json::from_str(
+            r#"{
+                "openapi": "3.0.3",
+                "info": { "title": "A", "version": "1" },
+                "paths": {},
+                "x-a": 1
+            }"#,
+        )
+        .unwrap();
+        assert!(api.key_collisions().is_empty());
+
+        api.extensions.insert("paths".to_string(), 1.into());
+        let collisions = api.key_collisions();
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].pointer, "");
+        assert!(!collisions[0].is_extension());
+    }
+}
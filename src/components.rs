@@ -38,5 +38,192 @@ pub struct Components {
     pub callbacks: IndexMap<String, ReferenceOr<Callback>>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
+}
+
+/// The sections of the [Components] object, as they appear in
+/// `#/components/<kind>/<name>` references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ComponentKind {
+    Schemas,
+    Responses,
+    Parameters,
+    Examples,
+    RequestBodies,
+    Headers,
+    SecuritySchemes,
+    Links,
+    Callbacks,
+}
+
+impl ComponentKind {
+    /// All component kinds, in the order they are declared in [Components].
+    pub const ALL: [ComponentKind; 9] = [
+        ComponentKind::Schemas,
+        ComponentKind::Responses,
+        ComponentKind::Parameters,
+        ComponentKind::Examples,
+        ComponentKind::RequestBodies,
+        ComponentKind::Headers,
+        ComponentKind::SecuritySchemes,
+        ComponentKind::Links,
+        ComponentKind::Callbacks,
+    ];
+
+    /// The name of this section as used in the document, e.g.
+    /// `requestBodies`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComponentKind::Schemas => "schemas",
+            ComponentKind::Responses => "responses",
+            ComponentKind::Parameters => "parameters",
+            ComponentKind::Examples => "examples",
+            ComponentKind::RequestBodies => "requestBodies",
+            ComponentKind::Headers => "headers",
+            ComponentKind::SecuritySchemes => "securitySchemes",
+            ComponentKind::Links => "links",
+            ComponentKind::Callbacks => "callbacks",
+        }
+    }
+}
+
+impl std::fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ComponentKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ComponentKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or(())
+    }
+}
+
+impl Components {
+    /// Adds `schema` under `name` and returns a reference to it.
+    ///
+    /// If an identical schema is already present, a reference to it is
+    /// returned instead. If `name` is taken by a different schema, a numeric
+    /// suffix is appended to it, starting at `2`.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut components = Components::default();
+    /// let schema = Schema {
+    ///     schema_data: Default::default(),
+    ///     schema_kind: SchemaKind::Type(Type::Boolean(Default::default())),
+    /// };
+    /// let first = components.intern_schema("Flag", schema.clone());
+    /// let second = components.intern_schema("Other", schema);
+    /// assert_eq!(first, ReferenceOr::ref_("#/components/schemas/Flag"));
+    /// assert_eq!(first, second);
+    /// assert_eq!(components.schemas.len(), 1);
+    /// ```
+    pub fn intern_schema(&mut self, name: &str, schema: Schema) -> ReferenceOr<Schema> {
+        self.intern(name, schema)
+    }
+
+    /// Like [Components::intern_schema], for responses.
+    pub fn intern_response(&mut self, name: &str, response: Response) -> ReferenceOr<Response> {
+        self.intern(name, response)
+    }
+
+    /// Like [Components::intern_schema], for parameters.
+    pub fn intern_parameter(&mut self, name: &str, parameter: Parameter) -> ReferenceOr<Parameter> {
+        self.intern(name, parameter)
+    }
+
+    /// Like [Components::intern_schema], for request bodies.
+    pub fn intern_request_body(
+        &mut self,
+        name: &str,
+        request_body: RequestBody,
+    ) -> ReferenceOr<RequestBody> {
+        self.intern(name, request_body)
+    }
+
+    /// Like [Components::intern_schema], for headers.
+    pub fn intern_header(&mut self, name: &str, header: Header) -> ReferenceOr<Header> {
+        self.intern(name, header)
+    }
+
+    /// Like [Components::intern_schema], for examples.
+    pub fn intern_example(&mut self, name: &str, example: Example) -> ReferenceOr<Example> {
+        self.intern(name, example)
+    }
+
+    fn intern<T: crate::resolve::Component + PartialEq>(
+        &mut self,
+        name: &str,
+        item: T,
+    ) -> ReferenceOr<T> {
+        let section = T::section_mut(self);
+        let existing = section
+            .iter()
+            .find_map(|(existing_name, existing)| match existing {
+                ReferenceOr::Item(existing) if *existing == item => Some(existing_name.clone()),
+                _ => None,
+            });
+        let name = match existing {
+            Some(existing_name) => existing_name,
+            None => {
+                let mut candidate = name.to_owned();
+                let mut n = 2;
+                while section.contains_key(&candidate) {
+                    candidate = format!("{}{}", name, n);
+                    n += 1;
+                }
+                section.insert(candidate.clone(), ReferenceOr::Item(item));
+                candidate
+            }
+        };
+        ReferenceOr::Reference {
+            reference: format!(
+                "#/components/{}/{}",
+                T::KIND,
+                crate::util::escape_pointer_token(&name)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_intern_response() {
+        let mut components = Components::default();
+        let ok = Response {
+            description: "OK".to_string(),
+            ..Default::default()
+        };
+        let not_found = Response {
+            description: "Not Found".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            components.intern_response("Result", ok.clone()),
+            ReferenceOr::ref_("#/components/responses/Result")
+        );
+        assert_eq!(
+            components.intern_response("Result", not_found.clone()),
+            ReferenceOr::ref_("#/components/responses/Result2")
+        );
+        assert_eq!(
+            components.intern_response("Whatever", not_found),
+            ReferenceOr::ref_("#/components/responses/Result2")
+        );
+        assert_eq!(
+            components.intern_response("a/b", ok),
+            ReferenceOr::ref_("#/components/responses/Result")
+        );
+        assert_eq!(components.responses.len(), 2);
+    }
 }
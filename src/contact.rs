@@ -1,4 +1,4 @@
-use indexmap::IndexMap;
+use crate::Extensions;
 use serde::{Deserialize, Serialize};
 
 /// Contact information for the exposed API.
@@ -17,5 +17,5 @@ pub struct Contact {
     pub email: Option<String>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
@@ -0,0 +1,100 @@
+use std::fmt;
+
+use crate::util::write_canonical;
+use crate::*;
+use sha2::{Digest, Sha256};
+
+/// A SHA-256 digest of the canonical form of a document, as returned by
+/// [OpenAPI::content_hash].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ContentHash(pub [u8; 32]);
+
+impl ContentHash {
+    /// Hashes raw bytes, e.g. the unparsed text of a document.
+    pub fn of_bytes(data: &[u8]) -> Self {
+        ContentHash(Sha256::digest(data).into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ContentHash {
+    /// Formats the digest as lowercase hex.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl OpenAPI {
+    /// Computes a digest of this document that does not depend on the order
+    /// of object keys or on how the source was formatted, so that two
+    /// documents with the same content hash are semantically identical.
+    ///
+    /// The document is serialized to JSON with object keys sorted and no
+    /// insignificant whitespace, and the result is hashed with SHA-256.
+    ///
+    /// ```
+    /// # use openapiv3::OpenAPI;
+    /// let a: OpenAPI = serde_json::from_str(
+    ///     r#"{"openapi": "3.0.3", "info": {"title": "A", "version": "1"}, "paths": {}}"#,
+    /// ).unwrap();
+    /// let b: OpenAPI = serde_yaml::from_str(
+    ///     "paths: {}\ninfo:\n  version: '1'\n  title: A\nopenapi: 3.0.3\n",
+    /// ).unwrap();
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> ContentHash {
+        let value = serde_json::to_value(self).expect("documents serialize to JSON");
+        let mut canonical = String::new();
+        write_canonical(&value, &mut canonical);
+        ContentHash::of_bytes(canonical.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_of_bytes() {
+        let hex = |data: &[u8]| ContentHash::of_bytes(data).to_string();
+        assert_eq!(
+            hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let a: OpenAPI = serde_json::from_str(
+            r#"{
+                "openapi": "3.0.3",
+                "info": { "title": "A", "version": "1" },
+                "paths": { "/b": {}, "/a": {} }
+            }"#,
+        )
+        .unwrap();
+        let b: OpenAPI = serde_json::from_str(
+            r#"{"paths":{"/a":{},"/b":{}},"info":{"version":"1","title":"A"},"openapi":"3.0.3"}"#,
+        )
+        .unwrap();
+        assert_eq!(a.content_hash(), b.content_hash());
+
+        let mut c = a.clone();
+        c.info.title = "C".to_string();
+        assert_ne!(a.content_hash(), c.content_hash());
+    }
+}
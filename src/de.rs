@@ -0,0 +1,624 @@
+use std::cell::RefCell;
+
+use crate::util::{escape_pointer_token, METHODS};
+use crate::*;
+use serde::de::{self, Deserialize, DeserializeSeed, IntoDeserializer, Visitor};
+use serde_json::Value;
+
+/// Parses a JSON document, reporting where in the document deserialization
+/// failed.
+///
+/// Syntax errors carry the [Error::line] and [Error::column] of the input,
+/// and errors in the structure of the document carry the [Error::pointer] of
+/// the innermost value that could not be deserialized, along with the line
+/// and column at which that value starts. Values that can be of
+/// several shapes, such as schemas and parameters, are reported as a whole.
+/// Documents for a version of OpenAPI other than 3.0 are rejected with an
+/// error of kind [ErrorKind::Version].
+///
+/// ```
+/// let input = r#"{
+///     "openapi": "3.0.3",
+///     "info": { "title": "Pets", "version": "1" },
+///     "paths": { "/pets": { "get": { "responses": {
+///         "200": { "description": "Pets", "content": { "application/json": { "example": {}, "encoding": [] } } }
+///     } } } }
+/// }"#;
+/// let err = openapiv3::from_str(input).unwrap_err();
+/// assert_eq!(
+///     err.pointer(),
+///     Some("/paths/~1pets/get/responses/200/content/application~1json/encoding")
+/// );
+/// assert_eq!((err.line(), err.column()), (Some(5), Some(103)));
+/// ```
+pub fn from_str(input: &str) -> Result<OpenAPI, Error> {
+    from_value(serde_json::from_str(input)?).map_err(|err| err.locate_in(input))
+}
+
+/// Like [from_str], for bytes.
+pub fn from_slice(input: &[u8]) -> Result<OpenAPI, Error> {
+    let value = serde_json::from_slice(input)?;
+    from_value(value).map_err(|err| match std::str::from_utf8(input) {
+        Ok(input) => err.locate_in(input),
+        Err(_) => err,
+    })
+}
+
+/// Like [from_str], for a reader.
+pub fn from_reader<R: std::io::Read>(mut reader: R) -> Result<OpenAPI, Error> {
+    let mut input = Vec::new();
+    reader.read_to_end(&mut input)?;
+    from_slice(&input)
+}
+
+impl OpenAPI {
+    /// Parses a JSON or YAML document, like [from_str].
+    ///
+    /// The input is taken to be JSON if it starts with `{`, ignoring
+    /// whitespace and a byte order mark, and YAML otherwise. Parsing YAML
+    /// requires the `yaml` feature; syntax errors in YAML carry its
+    /// [Error::line] and [Error::column], and errors in the structure of the
+    /// document its [Error::pointer], as for JSON.
+    ///
+    /// ```
+    /// # use openapiv3::OpenAPI;
+    /// let api = OpenAPI::from_str_auto(r#"{
+    ///     "openapi": "3.0.3", "info": { "title": "Pets", "version": "1" }, "paths": {}
+    /// }"#).unwrap();
+    /// assert_eq!(api.info.title, "Pets");
+    /// ```
+    pub fn from_str_auto(input: &str) -> Result<OpenAPI, Error> {
+        let input = input.trim_start_matches('\u{feff}');
+        if input.trim_start().starts_with('{') {
+            return from_str(input);
+        }
+        from_yaml(input)
+    }
+
+    /// Like [OpenAPI::from_str_auto], for a reader.
+    pub fn from_reader<R: std::io::Read>(mut reader: R) -> Result<OpenAPI, Error> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        OpenAPI::from_str_auto(&input)
+    }
+
+    /// Like [OpenAPI::from_str_auto], for the file at `path`.
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Result<OpenAPI, Error> {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path).map_err(|err| {
+            Error::custom(
+                ErrorKind::Io,
+                format!("failed to read {}: {}", path.display(), err),
+            )
+        })?;
+        OpenAPI::from_str_auto(&input)
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn from_yaml(input: &str) -> Result<OpenAPI, Error> {
+    from_value(from_yaml_str(input)?)
+}
+
+#[cfg(not(feature = "yaml"))]
+fn from_yaml(_: &str) -> Result<OpenAPI, Error> {
+    Err(Error::custom(
+        ErrorKind::Syntax,
+        "the document is not JSON, and parsing YAML requires the `yaml` feature".to_owned(),
+    ))
+}
+
+pub(crate) fn from_value(value: Value) -> Result<OpenAPI, Error> {
+    if let Some(version) = value.get("openapi").and_then(Value::as_str) {
+        check_version(version)?;
+    }
+    OpenAPI::deserialize(&value).map_err(|err| {
+        let (pointer, err) =
+            locate(Kind::OpenAPI, &value, String::new()).unwrap_or((String::new(), err));
+        Error::at(err, pointer)
+    })
+}
+
+/// Rejects documents for versions of OpenAPI other than 3.0, which
+/// deserialize into the model of this crate only by chance.
+pub(crate) fn check_version(version: &str) -> Result<(), Error> {
+    if version == "3.0" || version.starts_with("3.0.") {
+        return Ok(());
+    }
+    Err(Error::custom(
+        ErrorKind::Version,
+        format!("unsupported OpenAPI version `{}`, expected 3.0.x", version),
+    )
+    .with_pointer("/openapi"))
+}
+
+/// Finds the innermost value that fails to deserialize as the part of the
+/// document it is at.
+///
+/// Objects that serde buffers before deserializing, such as those that may
+/// be references, lose track of where their contents failed, so the
+/// document is first descended by the kinds of its objects, and the
+/// innermost failing object is then deserialized with a [Tracked] one.
+fn locate(kind: Kind, value: &Value, pointer: String) -> Option<(String, serde_json::Error)> {
+    let err = kind.check(value).err()?;
+    for (suffix, child, kind) in children(kind, value) {
+        if let Some(found) = locate(kind, child, format!("{}{}", pointer, suffix)) {
+            return Some(found);
+        }
+    }
+    Some(kind.track(value, pointer, err))
+}
+
+macro_rules! kinds {
+    ($($kind:ident => $ty:ty),* $(,)?) => {
+        /// The parts of a document that [locate] descends into.
+        #[derive(Clone, Copy)]
+        enum Kind {
+            $($kind),*
+        }
+
+        impl Kind {
+            fn check(self, value: &Value) -> Result<(), serde_json::Error> {
+                match self {
+                    $(Kind::$kind => <$ty>::deserialize(value).map(drop)),*
+                }
+            }
+
+            fn track(
+                self,
+                value: &Value,
+                pointer: String,
+                err: serde_json::Error,
+            ) -> (String, serde_json::Error) {
+                let failed = RefCell::new(None);
+                let tracked = Tracked {
+                    value,
+                    pointer: &Pointer::Root,
+                    failed: &failed,
+                };
+                let result = match self {
+                    $(Kind::$kind => <$ty>::deserialize(tracked).map(drop)),*
+                };
+                match result {
+                    Err(err) => (pointer + &failed.into_inner().unwrap_or_default(), err),
+                    Ok(()) => (pointer, err),
+                }
+            }
+        }
+    };
+}
+
+kinds! {
+    OpenAPI => OpenAPI,
+    Info => Info,
+    Server => Server,
+    Tag => Tag,
+    Paths => Paths,
+    PathItem => PathItem,
+    Operation => Operation,
+    Callback => Callback,
+    Parameter => Parameter,
+    RequestBody => RequestBody,
+    Responses => Responses,
+    Response => Response,
+    Header => Header,
+    MediaType => MediaType,
+    Encoding => Encoding,
+    Example => Example,
+    Link => Link,
+    Schema => Schema,
+    Components => Components,
+    SecurityScheme => SecurityScheme,
+}
+
+/// How the values of a [Kind] are nested in the object holding them.
+#[derive(Clone, Copy)]
+enum Shape {
+    /// The value of a field.
+    Field,
+    /// The value of a field, if it is an object.
+    Object,
+    /// The values of a field holding a map.
+    Map,
+    /// The items of a field holding an array.
+    List,
+    /// The values of the object itself, other than its extensions.
+    Entries,
+}
+
+/// Returns the values nested in an object of `kind` that are themselves
+/// [Kind]s, with their pointers relative to the object. Values that are
+/// references are left out, as they hold nothing to descend into.
+fn children(kind: Kind, value: &Value) -> Vec<(String, &Value, Kind)> {
+    use Shape::*;
+    let Value::Object(object) = value else {
+        return Vec::new();
+    };
+    let mut specs = match kind {
+        Kind::OpenAPI => vec![
+            (Field, "info", Kind::Info),
+            (List, "servers", Kind::Server),
+            (Field, "paths", Kind::Paths),
+            (Field, "components", Kind::Components),
+            (List, "tags", Kind::Tag),
+        ],
+        Kind::Paths => vec![(Entries, "", Kind::PathItem)],
+        Kind::PathItem => vec![
+            (List, "servers", Kind::Server),
+            (List, "parameters", Kind::Parameter),
+        ],
+        Kind::Operation => vec![
+            (List, "parameters", Kind::Parameter),
+            (Field, "requestBody", Kind::RequestBody),
+            (Field, "responses", Kind::Responses),
+            (Map, "callbacks", Kind::Callback),
+            (List, "servers", Kind::Server),
+        ],
+        Kind::Callback => vec![(Entries, "", Kind::PathItem)],
+        Kind::Parameter | Kind::Header => vec![
+            (Field, "schema", Kind::Schema),
+            (Map, "content", Kind::MediaType),
+            (Map, "examples", Kind::Example),
+        ],
+        Kind::RequestBody => vec![(Map, "content", Kind::MediaType)],
+        Kind::Responses => vec![(Entries, "", Kind::Response)],
+        Kind::Response => vec![
+            (Map, "headers", Kind::Header),
+            (Map, "content", Kind::MediaType),
+            (Map, "links", Kind::Link),
+        ],
+        Kind::MediaType => vec![
+            (Field, "schema", Kind::Schema),
+            (Map, "examples", Kind::Example),
+            (Map, "encoding", Kind::Encoding),
+        ],
+        Kind::Encoding => vec![(Map, "headers", Kind::Header)],
+        Kind::Schema => vec![
+            (Map, "properties", Kind::Schema),
+            (Field, "items", Kind::Schema),
+            (Object, "additionalProperties", Kind::Schema),
+            (Field, "not", Kind::Schema),
+            (List, "allOf", Kind::Schema),
+            (List, "oneOf", Kind::Schema),
+            (List, "anyOf", Kind::Schema),
+        ],
+        Kind::Components => vec![
+            (Map, "schemas", Kind::Schema),
+            (Map, "responses", Kind::Response),
+            (Map, "parameters", Kind::Parameter),
+            (Map, "examples", Kind::Example),
+            (Map, "requestBodies", Kind::RequestBody),
+            (Map, "headers", Kind::Header),
+            (Map, "securitySchemes", Kind::SecurityScheme),
+            (Map, "links", Kind::Link),
+            (Map, "callbacks", Kind::Callback),
+        ],
+        Kind::Info
+        | Kind::Server
+        | Kind::Tag
+        | Kind::Example
+        | Kind::Link
+        | Kind::SecurityScheme => Vec::new(),
+    };
+    if let Kind::PathItem = kind {
+        specs.extend(METHODS.map(|method| (Field, method, Kind::Operation)));
+    }
+
+    let token = |key: &str| format!("/{}", escape_pointer_token(key));
+    let mut children = Vec::new();
+    for (shape, key, kind) in specs {
+        match (shape, object.get(key)) {
+            (Field, Some(child)) => children.push((token(key), child, kind)),
+            (Object, Some(child)) if child.is_object() => children.push((token(key), child, kind)),
+            (Map, Some(Value::Object(map))) => children.extend(
+                map.iter()
+                    .map(|(name, child)| (token(key) + &token(name), child, kind)),
+            ),
+            (List, Some(Value::Array(items))) => children.extend(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, child)| (format!("{}/{}", token(key), i), child, kind)),
+            ),
+            (Entries, _) => children.extend(
+                object
+                    .iter()
+                    .filter(|(name, _)| !name.starts_with("x-"))
+                    .map(|(name, child)| (token(name), child, kind)),
+            ),
+            _ => {}
+        }
+    }
+    children.retain(|(_, child, _)| child.get("$ref").is_none());
+    children
+}
+
+/// The location of a value, as a linked list of its ancestors.
+enum Pointer<'a> {
+    Root,
+    Key(&'a Pointer<'a>, &'a str),
+    Index(&'a Pointer<'a>, usize),
+}
+
+impl Pointer<'_> {
+    fn render(&self) -> String {
+        match self {
+            Pointer::Root => String::new(),
+            Pointer::Key(parent, key) => {
+                format!("{}/{}", parent.render(), escape_pointer_token(key))
+            }
+            Pointer::Index(parent, index) => format!("{}/{}", parent.render(), index),
+        }
+    }
+}
+
+/// A deserializer for a [Value] that records the pointer of the innermost
+/// value that fails to deserialize.
+#[derive(Clone, Copy)]
+struct Tracked<'a, 'de> {
+    value: &'de Value,
+    pointer: &'a Pointer<'a>,
+    failed: &'a RefCell<Option<String>>,
+}
+
+impl Tracked<'_, '_> {
+    fn track<T>(&self, result: Result<T, serde_json::Error>) -> Result<T, serde_json::Error> {
+        if result.is_err() {
+            let mut failed = self.failed.borrow_mut();
+            if failed.is_none() {
+                *failed = Some(self.pointer.render());
+            }
+        }
+        result
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Tracked<'_, 'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let result = match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+                (Some(n), _, _) => visitor.visit_u64(n),
+                (_, Some(n), _) => visitor.visit_i64(n),
+                (_, _, Some(n)) => visitor.visit_f64(n),
+                _ => n.deserialize_any(visitor),
+            },
+            Value::String(s) => visitor.visit_borrowed_str(s),
+            Value::Array(array) => visitor.visit_seq(Seq {
+                tracked: self,
+                items: array.iter().enumerate(),
+            }),
+            Value::Object(object) => visitor.visit_map(Map {
+                tracked: self,
+                entries: object.iter(),
+                value: None,
+            }),
+        };
+        self.track(result)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => self.track(visitor.visit_none()),
+            _ => self.track(visitor.visit_some(self)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.track(visitor.visit_newtype_struct(self))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let result = match self.value {
+            Value::String(s) => visitor.visit_enum(s.as_str().into_deserializer()),
+            value => value.deserialize_enum(name, variants, visitor),
+        };
+        self.track(result)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+struct Seq<'a, 'de, I> {
+    tracked: Tracked<'a, 'de>,
+    items: I,
+}
+
+impl<'de, I> de::SeqAccess<'de> for Seq<'_, 'de, I>
+where
+    I: Iterator<Item = (usize, &'de Value)>,
+{
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some((index, value)) = self.items.next() else {
+            return Ok(None);
+        };
+        let pointer = Pointer::Index(self.tracked.pointer, index);
+        let tracked = Tracked {
+            value,
+            pointer: &pointer,
+            failed: self.tracked.failed,
+        };
+        seed.deserialize(tracked).map(Some)
+    }
+}
+
+struct Map<'a, 'de, I> {
+    tracked: Tracked<'a, 'de>,
+    entries: I,
+    value: Option<(&'de str, &'de Value)>,
+}
+
+impl<'de, I> de::MapAccess<'de> for Map<'_, 'de, I>
+where
+    I: Iterator<Item = (&'de String, &'de Value)>,
+{
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some((key, value));
+        seed.deserialize(de::value::BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        let pointer = Pointer::Key(self.tracked.pointer, key);
+        let tracked = Tracked {
+            value,
+            pointer: &pointer,
+            failed: self.tracked.failed,
+        };
+        seed.deserialize(tracked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_pointers() {
+        let document = |paths: &str| {
+            format!(
+                r#"{{ "openapi": "3.0.3", "info": {{ "title": "Pets", "version": "1" }}, "paths": {} }}"#,
+                paths
+            )
+        };
+
+        for entry in std::fs::read_dir("fixtures").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|e| e == "json") {
+                let input = std::fs::read_to_string(&path).unwrap();
+                let expected: OpenAPI = serde_json::from_str(&input).unwrap();
+                assert_eq!(from_str(&input).unwrap(), expected, "{}", path.display());
+            }
+        }
+
+        for (paths, pointer) in [
+            (r#"{ "/pets": [] }"#, "/paths/~1pets"),
+            (
+                r#"{ "/pets": { "get": { "tags": "pets", "responses": {} } } }"#,
+                "/paths/~1pets/get/tags",
+            ),
+            (
+                r#"{ "/pets": { "get": { "responses": { "default": { "description": "", "headers": { "X-Rate": { "deprecated": "yes" } } } } } } }"#,
+                "/paths/~1pets/get/responses/default/headers/X-Rate/deprecated",
+            ),
+            (
+                r#"{ "/pets": { "get": { "responses": { "200": { "description": "", "content": { "application/json": { "schema": { "type": "array", "items": { "type": "array", "minItems": -1 } } } } } } } } }"#,
+                "/paths/~1pets/get/responses/200/content/application~1json/schema/items",
+            ),
+            (
+                r#"{ "/pets": { "servers": [ { "url": "/" }, { "url": 1 } ] } }"#,
+                "/paths/~1pets/servers/1/url",
+            ),
+        ] {
+            let err = from_str(&document(paths)).unwrap_err();
+            assert_eq!(err.pointer(), Some(pointer), "{}", paths);
+            assert_eq!(err.line(), Some(1));
+        }
+
+        let input = "{\n  \"openapi\": \"3.0.3\",\n  \"info\": { \"title\": \"Pets\", \"version\": 1 },\n  \"paths\": {}\n}";
+        for err in [
+            from_str(input).unwrap_err(),
+            from_slice(input.as_bytes()).unwrap_err(),
+            from_reader(input.as_bytes()).unwrap_err(),
+        ] {
+            assert_eq!(err.pointer(), Some("/info/version"));
+            assert_eq!((err.line(), err.column()), (Some(3), Some(41)));
+        }
+
+        let err = from_slice(b"{ \"openapi\": ").unwrap_err();
+        assert_eq!((err.line(), err.pointer()), (Some(1), None));
+        let err = from_reader(document("{}").as_bytes()).unwrap();
+        assert!(err.paths.paths.is_empty());
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let kind = |input: &str| from_str(input).unwrap_err().kind();
+        assert_eq!(kind(r#"{ "openapi": "#), ErrorKind::Syntax);
+        assert_eq!(kind(r#"{ "openapi": "3.0.3" }"#), ErrorKind::Data);
+        assert_eq!(kind(r#"{ "openapi": 3 }"#), ErrorKind::Data);
+
+        let err = from_str(r#"{ "openapi": "3.1.0", "webhooks": {} }"#).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Version);
+        assert_eq!(err.pointer(), Some("/openapi"));
+        assert_eq!(kind(r#"{ "openapi": "3.01" }"#), ErrorKind::Version);
+
+        let err = OpenAPI::from_path("fixtures/missing.json").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+        let err: Error = ReferenceOr::<Schema>::ref_("#/components/schemas/A")
+            .resolve(&Components::default())
+            .unwrap_err()
+            .into();
+        assert_eq!(err.kind(), ErrorKind::Reference);
+    }
+
+    #[test]
+    fn test_from_path() {
+        let json = OpenAPI::from_path("fixtures/xkcd.json").unwrap();
+        assert_eq!(
+            Ok(&json),
+            from_slice(include_bytes!("../fixtures/xkcd.json")).as_ref()
+        );
+        assert!(OpenAPI::from_path("fixtures/missing.json").is_err());
+
+        let yaml = OpenAPI::from_path("fixtures/petstore.yaml");
+        #[cfg(feature = "yaml")]
+        assert_eq!(yaml.unwrap().info.title, "Swagger Petstore");
+        #[cfg(not(feature = "yaml"))]
+        assert!(yaml.unwrap_err().message().contains("`yaml` feature"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_from_str_auto_yaml() {
+        let err = OpenAPI::from_str_auto("openapi: 3.0.3\ninfo: [\n").unwrap_err();
+        assert!(err.line().is_some());
+
+        let input = "openapi: 3.0.3\ninfo: { title: A, version: '1' }\npaths:\n  /a:\n    get:\n      responses:\n        200: { description: [] }\n";
+        let err = OpenAPI::from_str_auto(input).unwrap_err();
+        assert_eq!(
+            err.pointer(),
+            Some("/paths/~1a/get/responses/200/description")
+        );
+    }
+}
@@ -0,0 +1,582 @@
+use crate::resolve::{component_name, resolve_reference, Component};
+use crate::walk::schema_slots_mut;
+use crate::*;
+use indexmap::IndexMap;
+
+/// Maps the JSON Pointer of each site where a schema was inlined by
+/// [OpenAPI::dereference_schemas] to the name of the component it came from.
+pub type InlinedSchemas = IndexMap<String, String>;
+
+impl OpenAPI {
+    /// Replaces schema references throughout the document with copies of the
+    /// referenced schemas, so that schemas can be traversed without consulting
+    /// [Components].
+    ///
+    /// The returned map records, for every site that held a reference, the
+    /// name of the component that was inlined there, which lets code
+    /// generators emit named types rather than anonymous duplicates. Where a
+    /// reference points at another reference the first component name in the
+    /// chain is recorded. References that would lead back into a schema that
+    /// is already being inlined are left in place, so recursive schemas stay
+    /// finite.
+    ///
+    /// `components/schemas` itself is dereferenced too. Schemas in
+    /// `components/schemas` are looked up as they were before this call.
+    pub fn dereference_schemas(&mut self) -> Result<InlinedSchemas, ResolveError> {
+        let source = self.components.clone().unwrap_or_default();
+        let mut inlined = InlinedSchemas::new();
+        let mut stack = Vec::new();
+
+        for (pointer, slot) in schema_slots_mut(self) {
+            // A component is not inlined into itself.
+            stack.extend(
+                pointer
+                    .strip_prefix("/components/schemas/")
+                    .map(crate::util::unescape_pointer_token),
+            );
+            inline(&source, slot, pointer, &mut stack, &mut inlined)?;
+            stack.clear();
+        }
+
+        Ok(inlined)
+    }
+}
+
+impl OpenAPI {
+    /// Replaces every local reference to a parameter, request body, response,
+    /// header or schema with a copy of the referenced item, so that the
+    /// document can be used without consulting [Components].
+    ///
+    /// Schemas are inlined as by [OpenAPI::dereference_schemas], so references
+    /// that would make a recursive schema infinite are left in place. Path
+    /// items that are references are left alone, as are examples, links and
+    /// callbacks defined in [Components]. On error the document is left
+    /// unchanged.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "get": {
+    ///         "responses": { "404": { "$ref": "#/components/responses/NotFound" } }
+    ///     } } },
+    ///     "components": { "responses": { "NotFound": { "description": "Not found" } } }
+    /// }"##).unwrap();
+    /// api.inline_all_refs().unwrap();
+    /// let (_, _, get) = api.operations().next().unwrap();
+    /// assert_eq!(get.responses.responses[&StatusCode::Code(404)].as_item().unwrap().description, "Not found");
+    /// ```
+    pub fn inline_all_refs(&mut self) -> Result<(), ResolveError> {
+        let mut document = self.clone();
+        let source = document.components.clone().unwrap_or_default();
+        let inliner = Inliner { source: &source };
+
+        for item in document.paths.paths.values_mut() {
+            if let ReferenceOr::Item(item) = item {
+                inliner.path_item(item)?;
+            }
+        }
+        if let Some(components) = &mut document.components {
+            for response in components.responses.values_mut() {
+                inliner.response(response)?;
+            }
+            for parameter in components.parameters.values_mut() {
+                inliner.parameter(parameter)?;
+            }
+            for body in components.request_bodies.values_mut() {
+                inliner.request_body(body)?;
+            }
+            for header in components.headers.values_mut() {
+                inliner.header(header)?;
+            }
+            for callback in components.callbacks.values_mut() {
+                if let ReferenceOr::Item(callback) = callback {
+                    inliner.callback(callback)?;
+                }
+            }
+        }
+
+        document.dereference_schemas()?;
+        *self = document;
+        Ok(())
+    }
+}
+
+impl OpenAPI {
+    /// Replaces references to trivial component schemas, those of type
+    /// string, number, integer or boolean, enums included, with copies of
+    /// them, for renderers that show references poorly. Returns the names of
+    /// the components that were inlined somewhere, in declaration order.
+    ///
+    /// If `remove_unused` is set, the inlined components that are no longer
+    /// referenced, by a `$ref` or a discriminator mapping, are removed.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": {},
+    ///     "components": { "schemas": {
+    ///         "Id": { "type": "string", "format": "uuid" },
+    ///         "Pet": { "type": "object", "properties": {
+    ///             "id": { "$ref": "#/components/schemas/Id" },
+    ///             "owner": { "$ref": "#/components/schemas/Owner" }
+    ///         } },
+    ///         "Owner": { "type": "object" }
+    ///     } }
+    /// }"##).unwrap();
+    /// assert_eq!(api.inline_trivial_schemas(true), ["Id"]);
+    ///
+    /// let schemas = &api.components.as_ref().unwrap().schemas;
+    /// assert_eq!(schemas.keys().collect::<Vec<_>>(), ["Pet", "Owner"]);
+    /// let pet = serde_json::to_value(&schemas["Pet"]).unwrap();
+    /// assert_eq!(pet["properties"]["id"]["format"], "uuid");
+    /// ```
+    pub fn inline_trivial_schemas(&mut self, remove_unused: bool) -> Vec<String> {
+        let trivial: IndexMap<String, Schema> = match &self.components {
+            Some(components) => components
+                .schemas
+                .iter()
+                .filter_map(|(name, schema)| match schema {
+                    ReferenceOr::Item(schema) if is_trivial(schema) => {
+                        Some((name.clone(), schema.clone()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            None => return Vec::new(),
+        };
+
+        let mut inlined = Vec::new();
+        for (_, slot) in schema_slots_mut(self) {
+            inline_trivial(&trivial, slot, &mut inlined);
+        }
+        let inlined: Vec<String> = trivial
+            .into_keys()
+            .filter(|name| inlined.contains(name))
+            .collect();
+
+        if remove_unused && !inlined.is_empty() {
+            let mut used = UsedSchemas::default();
+            self.transform(&mut used);
+            if let Some(components) = &mut self.components {
+                for name in &inlined {
+                    if !used.0.contains(name) {
+                        components.schemas.shift_remove(name);
+                    }
+                }
+            }
+        }
+        inlined
+    }
+}
+
+/// Whether a schema is simple enough to be repeated wherever it is used.
+fn is_trivial(schema: &Schema) -> bool {
+    matches!(
+        schema.schema_kind,
+        SchemaKind::Type(Type::String(_) | Type::Number(_) | Type::Integer(_) | Type::Boolean(_))
+    )
+}
+
+fn inline_trivial(
+    trivial: &IndexMap<String, Schema>,
+    mut slot: SchemaSlot<'_>,
+    inlined: &mut Vec<String>,
+) {
+    let name = slot
+        .reference()
+        .and_then(|reference| component_name(reference, ComponentKind::Schemas).ok());
+    if let Some((name, schema)) = name.and_then(|name| trivial.get_key_value(&name)) {
+        slot.replace_with_item(schema.clone());
+        if !inlined.contains(name) {
+            inlined.push(name.clone());
+        }
+    }
+    if let Some(schema) = slot.as_item_mut() {
+        for (_, child) in schema.subschemas_mut() {
+            inline_trivial(trivial, child, inlined);
+        }
+    }
+}
+
+/// Collects the names of the local schema components referenced in a
+/// document.
+#[derive(Default)]
+struct UsedSchemas(Vec<String>);
+
+impl VisitorMut for UsedSchemas {
+    fn visit_schema_mut(&mut self, _: &str, schema: &mut Schema) {
+        if let Some(discriminator) = &schema.schema_data.discriminator {
+            for target in discriminator.mapping.values() {
+                let name = component_name(target, ComponentKind::Schemas)
+                    .unwrap_or_else(|_| target.clone());
+                self.0.push(name);
+            }
+        }
+    }
+
+    fn visit_reference_mut(&mut self, _: &str, reference: &mut String) {
+        if let Ok(name) = component_name(reference, ComponentKind::Schemas) {
+            self.0.push(name);
+        }
+    }
+}
+
+/// Inlines references to components other than schemas.
+struct Inliner<'a> {
+    source: &'a Components,
+}
+
+impl Inliner<'_> {
+    fn item<T: Component + Clone>(&self, item: &mut ReferenceOr<T>) -> Result<(), ResolveError> {
+        if let ReferenceOr::Reference { reference } = item {
+            *item = ReferenceOr::Item(resolve_reference::<T>(self.source, reference)?.clone());
+        }
+        Ok(())
+    }
+
+    fn path_item(&self, item: &mut PathItem) -> Result<(), ResolveError> {
+        for parameter in &mut item.parameters {
+            self.parameter(parameter)?;
+        }
+        for (_, operation) in item.iter_mut() {
+            for parameter in &mut operation.parameters {
+                self.parameter(parameter)?;
+            }
+            if let Some(body) = &mut operation.request_body {
+                self.request_body(body)?;
+            }
+            let responses = &mut operation.responses;
+            for response in responses
+                .default
+                .iter_mut()
+                .chain(responses.responses.values_mut())
+            {
+                self.response(response)?;
+            }
+            for callback in operation.callbacks.values_mut() {
+                self.callback(callback)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn callback(&self, callback: &mut Callback) -> Result<(), ResolveError> {
+        for item in callback.values_mut() {
+            self.path_item(item)?;
+        }
+        Ok(())
+    }
+
+    fn parameter(&self, parameter: &mut ReferenceOr<Parameter>) -> Result<(), ResolveError> {
+        self.item(parameter)?;
+        if let ReferenceOr::Item(parameter) = parameter {
+            if let ParameterSchemaOrContent::Content(content) =
+                &mut parameter.parameter_data_mut().format
+            {
+                self.content(content)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn request_body(&self, body: &mut ReferenceOr<RequestBody>) -> Result<(), ResolveError> {
+        self.item(body)?;
+        if let ReferenceOr::Item(body) = body {
+            self.content(&mut body.content)?;
+        }
+        Ok(())
+    }
+
+    fn response(&self, response: &mut ReferenceOr<Response>) -> Result<(), ResolveError> {
+        self.item(response)?;
+        if let ReferenceOr::Item(response) = response {
+            for header in response.headers.values_mut() {
+                self.header(header)?;
+            }
+            self.content(&mut response.content)?;
+        }
+        Ok(())
+    }
+
+    fn header(&self, header: &mut ReferenceOr<Header>) -> Result<(), ResolveError> {
+        self.item(header)?;
+        if let ReferenceOr::Item(header) = header {
+            if let ParameterSchemaOrContent::Content(content) = &mut header.format {
+                self.content(content)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn content(&self, content: &mut IndexMap<String, MediaType>) -> Result<(), ResolveError> {
+        for media_type in content.values_mut() {
+            for encoding in media_type.encoding.values_mut() {
+                for header in encoding.headers.values_mut() {
+                    self.header(header)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn inline(
+    source: &Components,
+    mut slot: SchemaSlot<'_>,
+    pointer: String,
+    stack: &mut Vec<String>,
+    inlined: &mut InlinedSchemas,
+) -> Result<(), ResolveError> {
+    let mut pushed = 0;
+    if let Some(reference) = slot.reference() {
+        let schema = resolve_reference::<Schema>(source, reference)?.clone();
+        let chain = reference_chain(source, reference)?;
+        if chain.iter().any(|name| stack.contains(name)) {
+            return Ok(());
+        }
+        inlined.insert(pointer.clone(), chain[0].clone());
+        pushed = chain.len();
+        stack.extend(chain);
+        slot.replace_with_item(schema);
+    }
+
+    if let Some(schema) = slot.as_item_mut() {
+        for (key, child) in schema.subschemas_mut() {
+            let child_pointer = format!("{}{}", pointer, key.pointer());
+            inline(source, child, child_pointer, stack, inlined)?;
+        }
+    }
+    stack.truncate(stack.len() - pushed);
+    Ok(())
+}
+
+/// Returns the names of the schema components visited when following
+/// `reference` to an item.
+fn reference_chain(source: &Components, reference: &str) -> Result<Vec<String>, ResolveError> {
+    let mut chain = Vec::new();
+    let mut current = reference;
+    loop {
+        let name = component_name(current, ComponentKind::Schemas)?;
+        let next = source.schemas.get(&name);
+        chain.push(name);
+        match next {
+            Some(ReferenceOr::Reference { reference }) => current = reference,
+            _ => return Ok(chain),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dereference_schemas() {
+        let mut openapi: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {
+                    "/pets": {
+                        "get": {
+                            "responses": {
+                                "200": {
+                                    "description": "",
+                                    "content": { "application/json": { "schema": {
+                                        "type": "array",
+                                        "items": { "$ref": "#/components/schemas/PetAlias" }
+                                    } } }
+                                }
+                            }
+                        }
+                    }
+                },
+                "components": { "schemas": {
+                    "PetAlias": { "$ref": "#/components/schemas/Pet" },
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "owner": { "$ref": "#/components/schemas/Person" }
+                        }
+                    },
+                    "Person": {
+                        "type": "object",
+                        "properties": {
+                            "pets": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/Pet" }
+                            }
+                        }
+                    }
+                } }
+            }"##,
+        )
+        .unwrap();
+
+        let inlined = openapi.dereference_schemas().unwrap();
+        let schema = "/paths/~1pets/get/responses/200/content/application~1json/schema";
+        assert_eq!(
+            inlined
+                .get(&format!("{}/items", schema))
+                .map(String::as_str),
+            Some("PetAlias")
+        );
+        assert_eq!(
+            inlined
+                .get(&format!("{}/items/properties/owner", schema))
+                .map(String::as_str),
+            Some("Person")
+        );
+        assert_eq!(
+            inlined
+                .get("/components/schemas/PetAlias")
+                .map(String::as_str),
+            Some("Pet")
+        );
+        assert_eq!(
+            inlined
+                .get("/components/schemas/Pet/properties/owner")
+                .map(String::as_str),
+            Some("Person")
+        );
+
+        // The reference back to `Pet` from within `Person` is kept.
+        let value = serde_json::to_value(&openapi).unwrap();
+        let pointer = format!("{}/items/properties/owner/properties/pets/items", schema);
+        assert_eq!(
+            value.pointer(&pointer),
+            Some(&serde_json::json!({ "$ref": "#/components/schemas/Pet" }))
+        );
+        assert!(!inlined.contains_key(&pointer));
+
+        let mut broken = openapi.clone();
+        broken.components.as_mut().unwrap().schemas.insert(
+            "Broken".to_string(),
+            ReferenceOr::ref_("#/components/schemas/Nope"),
+        );
+        assert_eq!(
+            broken.dereference_schemas(),
+            Err(ResolveError::Missing {
+                reference: "#/components/schemas/Nope".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_inline_all_refs() {
+        let mut openapi: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {
+                    "/pets/{id}": {
+                        "parameters": [ { "$ref": "#/components/parameters/Id" } ],
+                        "put": {
+                            "requestBody": { "$ref": "#/components/requestBodies/Pet" },
+                            "responses": { "default": { "$ref": "#/components/responses/Error" } }
+                        }
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "Id": { "type": "string" },
+                        "Node": { "type": "object", "properties": { "next": { "$ref": "#/components/schemas/Node" } } }
+                    },
+                    "parameters": {
+                        "Id": { "name": "id", "in": "path", "required": true, "schema": { "$ref": "#/components/schemas/Id" } }
+                    },
+                    "requestBodies": {
+                        "Pet": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Node" } } } }
+                    },
+                    "responses": {
+                        "Error": {
+                            "description": "Error",
+                            "headers": { "X-Trace": { "$ref": "#/components/headers/Trace" } }
+                        }
+                    },
+                    "headers": { "Trace": { "schema": { "$ref": "#/components/schemas/Id" } } }
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let mut broken = openapi.clone();
+        broken.components.as_mut().unwrap().headers.clear();
+        let before = broken.clone();
+        assert_eq!(
+            broken.inline_all_refs(),
+            Err(ResolveError::Missing {
+                reference: "#/components/headers/Trace".to_string()
+            })
+        );
+        assert_eq!(broken, before);
+
+        openapi.inline_all_refs().unwrap();
+        let value = serde_json::to_value(&openapi).unwrap();
+        let item = &value["paths"]["/pets/{id}"];
+        assert_eq!(
+            item["parameters"][0]["schema"],
+            serde_json::json!({ "type": "string" })
+        );
+        assert_eq!(
+            item["put"]["responses"]["default"]["headers"]["X-Trace"]["schema"],
+            serde_json::json!({ "type": "string" })
+        );
+        let node = &item["put"]["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(
+            node["properties"]["next"],
+            serde_json::json!({ "$ref": "#/components/schemas/Node" })
+        );
+    }
+
+    #[test]
+    fn test_inline_trivial_schemas() {
+        let document = || -> OpenAPI {
+            serde_json::from_value(serde_json::json!({
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": { "/pets": { "get": {
+                    "parameters": [ { "name": "kind", "in": "query", "schema": { "$ref": "#/components/schemas/Kind" } } ],
+                    "responses": {}
+                } } },
+                "components": { "schemas": {
+                    "Kind": { "type": "string", "enum": ["cat", "dog"] },
+                    "Flag": { "type": "boolean" },
+                    "Pets": {
+                        "type": "array",
+                        "items": { "oneOf": [ { "$ref": "#/components/schemas/Flag" } ] }
+                    },
+                    "Tagged": {
+                        "oneOf": [ { "$ref": "#/components/schemas/Flag" } ],
+                        "discriminator": { "propertyName": "kind", "mapping": { "flag": "Flag" } }
+                    }
+                } }
+            }))
+            .unwrap()
+        };
+
+        let mut api = document();
+        assert_eq!(api.inline_trivial_schemas(false), ["Flag", "Kind"]);
+        let value = serde_json::to_value(&api).unwrap();
+        assert_eq!(
+            value["paths"]["/pets"]["get"]["parameters"][0]["schema"]["enum"],
+            serde_json::json!(["cat", "dog"])
+        );
+        let schemas = &value["components"]["schemas"];
+        assert_eq!(schemas["Pets"]["items"]["oneOf"][0]["type"], "boolean");
+        assert_eq!(schemas.as_object().unwrap().len(), 4);
+
+        // `Flag` is still named by the discriminator mapping.
+        let mut api = document();
+        api.inline_trivial_schemas(true);
+        let schemas = &api.components.unwrap().schemas;
+        assert_eq!(
+            schemas.keys().collect::<Vec<_>>(),
+            ["Flag", "Pets", "Tagged"]
+        );
+    }
+}
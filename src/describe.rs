@@ -0,0 +1,300 @@
+use std::fmt;
+
+use crate::*;
+
+/// Implemented by request handlers, or by types generated for them by
+/// framework macros, to contribute a single [Operation] to a document built
+/// with [DocumentCollector].
+pub trait DescribeOperation {
+    /// The path template the operation is served on, e.g. `/pets/{petId}`.
+    fn path(&self) -> &str;
+
+    /// The lowercase HTTP method, e.g. `get`.
+    fn method(&self) -> &str;
+
+    /// Describes the operation. Reusable definitions such as schemas may be
+    /// added to `components` and referenced from the returned [Operation].
+    fn describe(&self, components: &mut Components) -> Operation;
+}
+
+/// Implemented by types that describe a whole [PathItem], e.g. a router
+/// mounting several handlers on the same path.
+pub trait DescribePath {
+    /// The path template, e.g. `/pets/{petId}`.
+    fn path(&self) -> &str;
+
+    /// Describes the path item. Reusable definitions such as schemas may be
+    /// added to `components` and referenced from the returned [PathItem].
+    fn describe(&self, components: &mut Components) -> PathItem;
+}
+
+/// The error returned by [DocumentCollector] when contributions conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectError {
+    /// An operation for this path and method was already added.
+    DuplicateOperation { path: String, method: String },
+    /// The method is not one supported by a [PathItem].
+    UnknownMethod { path: String, method: String },
+    /// The path is already present as a `$ref`, so nothing can be added to
+    /// it.
+    ReferencedPath { path: String },
+}
+
+impl fmt::Display for CollectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectError::DuplicateOperation { path, method } => {
+                write!(
+                    f,
+                    "operation {} {} was described more than once",
+                    method, path
+                )
+            }
+            CollectError::UnknownMethod { path, method } => {
+                write!(f, "unsupported method `{}` for {}", method, path)
+            }
+            CollectError::ReferencedPath { path } => {
+                write!(f, "path {} is a reference and cannot be extended", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CollectError {}
+
+/// Assembles an [OpenAPI] document from [DescribeOperation] and
+/// [DescribePath] implementations.
+///
+/// ```
+/// # use openapiv3::*;
+/// struct ListPets;
+///
+/// impl DescribeOperation for ListPets {
+///     fn path(&self) -> &str {
+///         "/pets"
+///     }
+///     fn method(&self) -> &str {
+///         "get"
+///     }
+///     fn describe(&self, _: &mut Components) -> Operation {
+///         Operation {
+///             operation_id: Some("listPets".to_string()),
+///             ..Default::default()
+///         }
+///     }
+/// }
+///
+/// let mut collector = DocumentCollector::new(Info {
+///     title: "Pets".to_string(),
+///     version: "1.0.0".to_string(),
+///     ..Default::default()
+/// });
+/// collector.add_operation(&ListPets).unwrap();
+/// let api = collector.finish();
+/// assert_eq!(api.operations().count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DocumentCollector {
+    document: OpenAPI,
+    components: Components,
+}
+
+impl DocumentCollector {
+    /// Starts a new OpenAPI 3.0.3 document.
+    pub fn new(info: Info) -> Self {
+        Self::from_document(OpenAPI {
+            openapi: "3.0.3".to_owned(),
+            info,
+            ..Default::default()
+        })
+    }
+
+    /// Adds to an existing document, e.g. one holding hand-written servers,
+    /// tags and security schemes.
+    pub fn from_document(mut document: OpenAPI) -> Self {
+        let components = document.components.take().unwrap_or_default();
+        Self {
+            document,
+            components,
+        }
+    }
+
+    /// Adds the operation described by `operation`.
+    pub fn add_operation<D>(&mut self, operation: &D) -> Result<&mut Self, CollectError>
+    where
+        D: DescribeOperation + ?Sized,
+    {
+        let path = operation.path();
+        let method = operation.method().to_ascii_lowercase();
+        let item = path_item(&mut self.document.paths, path)?;
+        let slot = item
+            .operation_mut(&method)
+            .ok_or_else(|| CollectError::UnknownMethod {
+                path: path.to_owned(),
+                method: method.clone(),
+            })?;
+        if slot.is_some() {
+            return Err(CollectError::DuplicateOperation {
+                path: path.to_owned(),
+                method,
+            });
+        }
+        *slot = Some(operation.describe(&mut self.components));
+        Ok(self)
+    }
+
+    /// Adds the path item described by `path`, merging it with any
+    /// operations already added for the same path. Summary and description
+    /// are only taken if not already set, while servers and parameters are
+    /// appended.
+    pub fn add_path<D>(&mut self, path: &D) -> Result<&mut Self, CollectError>
+    where
+        D: DescribePath + ?Sized,
+    {
+        let template = path.path();
+        let described = path.describe(&mut self.components);
+        let item = path_item(&mut self.document.paths, template)?;
+
+        if let Some((method, _)) = described
+            .iter()
+            .find(|(method, _)| item.iter().any(|(existing, _)| existing == *method))
+        {
+            return Err(CollectError::DuplicateOperation {
+                path: template.to_owned(),
+                method: method.to_owned(),
+            });
+        }
+        for (method, operation) in described.clone() {
+            *item.operation_mut(method).expect("PathItem methods") = Some(operation);
+        }
+        if item.summary.is_none() {
+            item.summary = described.summary;
+        }
+        if item.description.is_none() {
+            item.description = described.description;
+        }
+        item.servers.extend(described.servers);
+        item.parameters.extend(described.parameters);
+        item.extensions.extend(described.extensions);
+        Ok(self)
+    }
+
+    /// Returns the finished document. The components section is omitted if
+    /// nothing was added to it.
+    pub fn finish(self) -> OpenAPI {
+        let Self {
+            mut document,
+            components,
+        } = self;
+        if components != Components::default() {
+            document.components = Some(components);
+        }
+        document
+    }
+}
+
+pub(crate) fn path_item<'a>(
+    paths: &'a mut Paths,
+    path: &str,
+) -> Result<&'a mut PathItem, CollectError> {
+    match paths
+        .paths
+        .entry(path.to_owned())
+        .or_insert_with(|| ReferenceOr::Item(PathItem::default()))
+    {
+        ReferenceOr::Item(item) => Ok(item),
+        ReferenceOr::Reference { .. } => Err(CollectError::ReferencedPath {
+            path: path.to_owned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    struct Handler(&'static str, &'static str);
+
+    impl DescribeOperation for Handler {
+        fn path(&self) -> &str {
+            self.0
+        }
+
+        fn method(&self) -> &str {
+            self.1
+        }
+
+        fn describe(&self, components: &mut Components) -> Operation {
+            components.schemas.insert(
+                "Pet".to_string(),
+                ReferenceOr::Item(Schema {
+                    schema_data: Default::default(),
+                    schema_kind: SchemaKind::Type(Type::Object(Default::default())),
+                }),
+            );
+            Operation {
+                summary: Some(format!("{} {}", self.1, self.0)),
+                ..Default::default()
+            }
+        }
+    }
+
+    struct Router;
+
+    impl DescribePath for Router {
+        fn path(&self) -> &str {
+            "/pets"
+        }
+
+        fn describe(&self, _: &mut Components) -> PathItem {
+            PathItem {
+                summary: Some("Pets".to_string()),
+                delete: Some(Operation::default()),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn test_collector() {
+        let mut collector = DocumentCollector::new(Info::default());
+        collector
+            .add_operation(&Handler("/pets", "GET"))
+            .unwrap()
+            .add_operation(&Handler("/pets/{id}", "get"))
+            .unwrap()
+            .add_path(&Router)
+            .unwrap();
+
+        assert_eq!(
+            collector
+                .add_operation(&Handler("/pets", "get"))
+                .unwrap_err(),
+            CollectError::DuplicateOperation {
+                path: "/pets".to_string(),
+                method: "get".to_string()
+            }
+        );
+        assert!(matches!(
+            collector.add_operation(&Handler("/pets", "connect")),
+            Err(CollectError::UnknownMethod { .. })
+        ));
+        assert!(matches!(
+            collector.add_path(&Router),
+            Err(CollectError::DuplicateOperation { .. })
+        ));
+
+        let api = collector.finish();
+        assert_eq!(api.openapi, "3.0.3");
+        assert_eq!(
+            api.operations()
+                .map(|(path, method, _)| format!("{} {}", method, path))
+                .collect::<Vec<_>>(),
+            vec!["get /pets", "delete /pets", "get /pets/{id}"]
+        );
+        assert!(api.components.unwrap().schemas.contains_key("Pet"));
+
+        let empty = DocumentCollector::new(Info::default()).finish();
+        assert!(empty.components.is_none());
+    }
+}
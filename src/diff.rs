@@ -0,0 +1,930 @@
+use std::fmt;
+
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// The kind of a [Change] found by [diff].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    PathAdded,
+    PathRemoved,
+    OperationAdded,
+    OperationRemoved,
+    ParameterAdded,
+    ParameterRemoved,
+    RequestBodyAdded,
+    RequestBodyRemoved,
+    /// A media type was added to a request body or response.
+    MediaTypeAdded,
+    /// A media type was removed from a request body or response.
+    MediaTypeRemoved,
+    ResponseAdded,
+    ResponseRemoved,
+    PropertyAdded,
+    PropertyRemoved,
+    /// A parameter, request body or property became required.
+    BecameRequired,
+    /// A parameter, request body or property became optional.
+    BecameOptional,
+    EnumValueAdded,
+    EnumValueRemoved,
+    /// The `type` of a schema changed.
+    TypeChanged,
+    SecurityRequirementAdded,
+    SecurityRequirementRemoved,
+}
+
+/// A difference between two documents, as returned by [diff].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    /// The JSON Pointer of the changed value, in the new document unless it
+    /// was removed. Schemas are reached through the references leading to
+    /// them, so the pointer is that of where the schema is used.
+    pub pointer: String,
+    pub message: String,
+    /// Whether clients written against the old document may break.
+    pub breaking: bool,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let breaking = if self.breaking { " (breaking)" } else { "" };
+        write!(f, "{}: {}{}", self.pointer, self.message, breaking)
+    }
+}
+
+/// The differences between two documents, as returned by [diff].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecDiff {
+    pub changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns `true` if any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|change| change.breaking)
+    }
+
+    /// Iterates over the breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+}
+
+/// Compares the operations of two versions of a document and classifies
+/// each difference as breaking or not.
+///
+/// Paths, operations, parameters, request bodies, responses and their
+/// media types, the properties, `required`, enumerations and types of
+/// schemas, and the security requirements in effect for each operation are
+/// compared. Schemas are compared after following references, and changes
+/// are breaking depending on whether the schema is sent or received by the
+/// client: e.g. a new required property breaks requests, while a removed
+/// property or a new enumeration value breaks responses. Path items that are
+/// references are only compared by their presence, and references that
+/// cannot be resolved are not followed.
+///
+/// ```
+/// # use openapiv3::*;
+/// let old: OpenAPI = serde_json::from_str(r#"{
+///     "openapi": "3.0.3",
+///     "info": { "title": "Pets", "version": "1" },
+///     "paths": { "/pets": { "get": {
+///         "parameters": [ { "name": "limit", "in": "query", "schema": { "type": "integer" } } ],
+///         "responses": { "200": { "description": "Pets" } }
+///     } } }
+/// }"#).unwrap();
+/// let mut new = old.clone();
+/// new.paths.paths.insert("/owners".to_owned(), new.paths.paths["/pets"].clone());
+/// new.paths.paths.shift_remove("/pets");
+///
+/// let diff = diff(&old, &new);
+/// assert!(diff.is_breaking());
+/// let kinds = diff.changes.iter().map(|c| (c.kind, c.breaking)).collect::<Vec<_>>();
+/// assert_eq!(kinds, [(ChangeKind::PathRemoved, true), (ChangeKind::PathAdded, false)]);
+/// ```
+pub fn diff(old: &OpenAPI, new: &OpenAPI) -> SpecDiff {
+    if old.semantically_equal(new) {
+        return SpecDiff::default();
+    }
+    let mut differ = Differ {
+        old,
+        new,
+        changes: Vec::new(),
+        references: Vec::new(),
+    };
+    differ.paths();
+    SpecDiff {
+        changes: differ.changes,
+    }
+}
+
+/// Whether a schema is sent or received by clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+/// A schema and the reference that led to it, if any.
+type Target<'a> = (Option<&'a str>, Option<&'a Schema>);
+
+struct Differ<'a> {
+    old: &'a OpenAPI,
+    new: &'a OpenAPI,
+    changes: Vec<Change>,
+    /// The pairs of references to schemas being compared, to stop at
+    /// recursive schemas.
+    references: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Differ<'a> {
+    fn report(&mut self, kind: ChangeKind, pointer: String, message: String, breaking: bool) {
+        self.changes.push(Change {
+            kind,
+            pointer,
+            message,
+            breaking,
+        });
+    }
+
+    fn paths(&mut self) {
+        for (path, old) in self.old.paths.iter() {
+            let pointer = format!("/paths/{}", escape_pointer_token(path));
+            match (old, self.new.paths.paths.get(path)) {
+                (_, None) => self.report(
+                    ChangeKind::PathRemoved,
+                    pointer,
+                    format!("path `{}` was removed", path),
+                    true,
+                ),
+                (ReferenceOr::Item(old), Some(ReferenceOr::Item(new))) => {
+                    self.path_item(old, new, &pointer)
+                }
+                _ => {}
+            }
+        }
+        for path in self.new.paths.paths.keys() {
+            if !self.old.paths.paths.contains_key(path) {
+                self.report(
+                    ChangeKind::PathAdded,
+                    format!("/paths/{}", escape_pointer_token(path)),
+                    format!("path `{}` was added", path),
+                    false,
+                );
+            }
+        }
+    }
+
+    fn path_item(&mut self, old: &'a PathItem, new: &'a PathItem, pointer: &str) {
+        for (method, old_operation) in old.iter() {
+            let pointer = format!("{}/{}", pointer, method);
+            match new.iter().find(|(m, _)| *m == method) {
+                Some((_, new_operation)) => {
+                    self.operation((old, old_operation), (new, new_operation), &pointer)
+                }
+                None => self.report(
+                    ChangeKind::OperationRemoved,
+                    pointer,
+                    format!("operation `{}` was removed", method),
+                    true,
+                ),
+            }
+        }
+        for (method, _) in new.iter() {
+            if !old.iter().any(|(m, _)| m == method) {
+                self.report(
+                    ChangeKind::OperationAdded,
+                    format!("{}/{}", pointer, method),
+                    format!("operation `{}` was added", method),
+                    false,
+                );
+            }
+        }
+    }
+
+    fn operation(
+        &mut self,
+        old: (&'a PathItem, &'a Operation),
+        new: (&'a PathItem, &'a Operation),
+        pointer: &str,
+    ) {
+        self.parameters(old, new, pointer);
+        self.request_body(old.1, new.1, pointer);
+        self.responses(&old.1.responses, &new.1.responses, pointer);
+        self.security(old.1, new.1, pointer);
+    }
+
+    fn parameters(
+        &mut self,
+        old: (&'a PathItem, &'a Operation),
+        new: (&'a PathItem, &'a Operation),
+        pointer: &str,
+    ) {
+        let old_parameters = parameters(self.old, old.0, old.1, pointer);
+        let new_parameters = parameters(self.new, new.0, new.1, pointer);
+
+        for ((location, name), (old_pointer, old)) in &old_parameters {
+            let Some((new_pointer, new)) = new_parameters.get(&(*location, *name)) else {
+                self.report(
+                    ChangeKind::ParameterRemoved,
+                    old_pointer.clone(),
+                    format!("{} parameter `{}` was removed", location, name),
+                    true,
+                );
+                continue;
+            };
+            let (old_data, new_data) = (old.parameter_data_ref(), new.parameter_data_ref());
+            self.required(
+                old_data.required,
+                new_data.required,
+                Direction::Request,
+                new_pointer.clone(),
+                format!("{} parameter `{}`", location, name),
+            );
+            if let (
+                ParameterSchemaOrContent::Schema(old_schema),
+                ParameterSchemaOrContent::Schema(new_schema),
+            ) = (&old_data.format, &new_data.format)
+            {
+                self.schema(
+                    unboxed(self.old, old_schema),
+                    unboxed(self.new, new_schema),
+                    format!("{}/schema", new_pointer),
+                    Direction::Request,
+                );
+            }
+        }
+        for ((location, name), (new_pointer, new)) in &new_parameters {
+            if !old_parameters.contains_key(&(*location, *name)) {
+                let required = new.parameter_data_ref().required;
+                self.report(
+                    ChangeKind::ParameterAdded,
+                    new_pointer.clone(),
+                    format!(
+                        "{} {} parameter `{}` was added",
+                        if required { "required" } else { "optional" },
+                        location,
+                        name
+                    ),
+                    required,
+                );
+            }
+        }
+    }
+
+    fn request_body(&mut self, old: &'a Operation, new: &'a Operation, pointer: &str) {
+        let pointer = format!("{}/requestBody", pointer);
+        let old = old
+            .request_body
+            .as_ref()
+            .and_then(|body| resolve(self.old, body));
+        let new = new
+            .request_body
+            .as_ref()
+            .and_then(|body| resolve(self.new, body));
+        match (old, new) {
+            (None, None) => {}
+            (Some(_), None) => self.report(
+                ChangeKind::RequestBodyRemoved,
+                pointer,
+                "the request body was removed".to_owned(),
+                true,
+            ),
+            (None, Some(new)) => self.report(
+                ChangeKind::RequestBodyAdded,
+                pointer,
+                format!(
+                    "{} request body was added",
+                    if new.required {
+                        "a required"
+                    } else {
+                        "an optional"
+                    }
+                ),
+                new.required,
+            ),
+            (Some(old), Some(new)) => {
+                self.required(
+                    old.required,
+                    new.required,
+                    Direction::Request,
+                    pointer.clone(),
+                    "the request body".to_owned(),
+                );
+                self.content(&old.content, &new.content, &pointer, Direction::Request);
+            }
+        }
+    }
+
+    fn responses(&mut self, old: &'a Responses, new: &'a Responses, pointer: &str) {
+        let old_responses = responses(old);
+        let new_responses = responses(new);
+        for (status, old) in &old_responses {
+            let pointer = format!("{}/responses/{}", pointer, status);
+            let Some(new) = new_responses.get(status) else {
+                self.report(
+                    ChangeKind::ResponseRemoved,
+                    pointer,
+                    format!("response `{}` was removed", status),
+                    true,
+                );
+                continue;
+            };
+            if let (Some(old), Some(new)) = (resolve(self.old, old), resolve(self.new, new)) {
+                self.content(&old.content, &new.content, &pointer, Direction::Response);
+            }
+        }
+        for status in new_responses.keys() {
+            if !old_responses.contains_key(status) {
+                self.report(
+                    ChangeKind::ResponseAdded,
+                    format!("{}/responses/{}", pointer, status),
+                    format!("response `{}` was added", status),
+                    false,
+                );
+            }
+        }
+    }
+
+    fn content(
+        &mut self,
+        old: &'a IndexMap<String, MediaType>,
+        new: &'a IndexMap<String, MediaType>,
+        pointer: &str,
+        direction: Direction,
+    ) {
+        for (media_type, old_media_type) in old {
+            let pointer = format!("{}/content/{}", pointer, escape_pointer_token(media_type));
+            match new.get(media_type) {
+                Some(new_media_type) => {
+                    if let (Some(old_schema), Some(new_schema)) =
+                        (&old_media_type.schema, &new_media_type.schema)
+                    {
+                        self.schema(
+                            unboxed(self.old, old_schema),
+                            unboxed(self.new, new_schema),
+                            format!("{}/schema", pointer),
+                            direction,
+                        );
+                    }
+                }
+                None => self.report(
+                    ChangeKind::MediaTypeRemoved,
+                    pointer,
+                    format!("media type `{}` was removed", media_type),
+                    true,
+                ),
+            }
+        }
+        for media_type in new.keys() {
+            if !old.contains_key(media_type) {
+                self.report(
+                    ChangeKind::MediaTypeAdded,
+                    format!("{}/content/{}", pointer, escape_pointer_token(media_type)),
+                    format!("media type `{}` was added", media_type),
+                    false,
+                );
+            }
+        }
+    }
+
+    fn schema(&mut self, old: Target<'a>, new: Target<'a>, pointer: String, direction: Direction) {
+        if let (Some(old_reference), Some(new_reference)) = (old.0, new.0) {
+            if self.references.contains(&(old_reference, new_reference)) {
+                return;
+            }
+            self.references.push((old_reference, new_reference));
+            self.schema_item(old.1, new.1, pointer, direction);
+            self.references.pop();
+        } else {
+            self.schema_item(old.1, new.1, pointer, direction);
+        }
+    }
+
+    fn schema_item(
+        &mut self,
+        old: Option<&'a Schema>,
+        new: Option<&'a Schema>,
+        pointer: String,
+        direction: Direction,
+    ) {
+        let (Some(old), Some(new)) = (old, new) else {
+            return;
+        };
+
+        if let (Some(old_type), Some(new_type)) = (type_name(old), type_name(new)) {
+            if old_type != new_type {
+                self.report(
+                    ChangeKind::TypeChanged,
+                    pointer,
+                    format!("the type changed from `{}` to `{}`", old_type, new_type),
+                    true,
+                );
+                return;
+            }
+        }
+
+        if let (Some(old_values), Some(new_values)) = (old.enum_values(), new.enum_values()) {
+            for value in old_values.iter().filter(|v| !new_values.contains(v)) {
+                self.report(
+                    ChangeKind::EnumValueRemoved,
+                    format!("{}/enum", pointer),
+                    format!("enumeration value {} was removed", value),
+                    direction == Direction::Request,
+                );
+            }
+            for value in new_values.iter().filter(|v| !old_values.contains(v)) {
+                self.report(
+                    ChangeKind::EnumValueAdded,
+                    format!("{}/enum", pointer),
+                    format!("enumeration value {} was added", value),
+                    direction == Direction::Response,
+                );
+            }
+        }
+
+        if let (Some((old_properties, old_required)), Some((new_properties, new_required))) =
+            (properties(old), properties(new))
+        {
+            for (name, old_property) in old_properties {
+                let pointer = format!("{}/properties/{}", pointer, escape_pointer_token(name));
+                let Some(new_property) = new_properties.get(name) else {
+                    self.report(
+                        ChangeKind::PropertyRemoved,
+                        pointer,
+                        format!("property `{}` was removed", name),
+                        direction == Direction::Response,
+                    );
+                    continue;
+                };
+                self.required(
+                    old_required.contains(name),
+                    new_required.contains(name),
+                    direction,
+                    pointer.clone(),
+                    format!("property `{}`", name),
+                );
+                self.schema(
+                    boxed(self.old, old_property),
+                    boxed(self.new, new_property),
+                    pointer,
+                    direction,
+                );
+            }
+            for name in new_properties.keys() {
+                if !old_properties.contains_key(name) {
+                    let required = new_required.contains(name);
+                    self.report(
+                        ChangeKind::PropertyAdded,
+                        format!("{}/properties/{}", pointer, escape_pointer_token(name)),
+                        format!(
+                            "{} property `{}` was added",
+                            if required { "required" } else { "optional" },
+                            name
+                        ),
+                        required && direction == Direction::Request,
+                    );
+                }
+            }
+        }
+
+        if let (Some(old_items), Some(new_items)) = (items(old), items(new)) {
+            self.schema(
+                boxed(self.old, old_items),
+                boxed(self.new, new_items),
+                format!("{}/items", pointer),
+                direction,
+            );
+        }
+
+        let subschemas = match (&old.schema_kind, &new.schema_kind) {
+            (SchemaKind::AllOf { all_of: a }, SchemaKind::AllOf { all_of: b }) => {
+                Some(("allOf", a, b))
+            }
+            (SchemaKind::OneOf { one_of: a }, SchemaKind::OneOf { one_of: b }) => {
+                Some(("oneOf", a, b))
+            }
+            (SchemaKind::AnyOf { any_of: a }, SchemaKind::AnyOf { any_of: b }) => {
+                Some(("anyOf", a, b))
+            }
+            _ => None,
+        };
+        if let Some((keyword, old_schemas, new_schemas)) = subschemas {
+            for (i, (old_schema, new_schema)) in old_schemas.iter().zip(new_schemas).enumerate() {
+                self.schema(
+                    unboxed(self.old, old_schema),
+                    unboxed(self.new, new_schema),
+                    format!("{}/{}/{}", pointer, keyword, i),
+                    direction,
+                );
+            }
+        }
+    }
+
+    /// Reports a change of whether `subject` is required. Becoming required
+    /// breaks requests and becoming optional breaks responses.
+    fn required(
+        &mut self,
+        old: bool,
+        new: bool,
+        direction: Direction,
+        pointer: String,
+        subject: String,
+    ) {
+        match (old, new) {
+            (false, true) => self.report(
+                ChangeKind::BecameRequired,
+                pointer,
+                format!("{} became required", subject),
+                direction == Direction::Request,
+            ),
+            (true, false) => self.report(
+                ChangeKind::BecameOptional,
+                pointer,
+                format!("{} became optional", subject),
+                direction == Direction::Response,
+            ),
+            _ => {}
+        }
+    }
+
+    /// Compares the security requirements in effect. Removing an alternative
+    /// is breaking, as is requiring security where none was.
+    fn security(&mut self, old: &'a Operation, new: &'a Operation, pointer: &str) {
+        let old_security = old
+            .security
+            .as_ref()
+            .or(self.old.security.as_ref())
+            .map_or(&[][..], Vec::as_slice);
+        let new_security = new
+            .security
+            .as_ref()
+            .or(self.new.security.as_ref())
+            .map_or(&[][..], Vec::as_slice);
+        let pointer = format!("{}/security", pointer);
+        for requirement in old_security {
+            if !new_security.contains(requirement) {
+                self.report(
+                    ChangeKind::SecurityRequirementRemoved,
+                    pointer.clone(),
+                    format!("security requirement {} was removed", describe(requirement)),
+                    true,
+                );
+            }
+        }
+        for requirement in new_security {
+            if !old_security.contains(requirement) {
+                self.report(
+                    ChangeKind::SecurityRequirementAdded,
+                    pointer.clone(),
+                    format!("security requirement {} was added", describe(requirement)),
+                    old_security.is_empty(),
+                );
+            }
+        }
+    }
+}
+
+/// The parameters in effect for an operation by location and name, along
+/// with their pointers.
+fn parameters<'a>(
+    document: &'a OpenAPI,
+    item: &'a PathItem,
+    operation: &'a Operation,
+    pointer: &str,
+) -> IndexMap<(&'static str, &'a str), (String, &'a Parameter)> {
+    let item_pointer = pointer.rsplit_once('/').map_or("", |(item, _)| item);
+    let shared = item
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(i, parameter)| (format!("{}/parameters/{}", item_pointer, i), parameter));
+    let own = operation
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(i, parameter)| (format!("{}/parameters/{}", pointer, i), parameter));
+
+    let mut parameters = IndexMap::new();
+    for (pointer, parameter) in shared.chain(own) {
+        if let Some(parameter) = resolve(document, parameter) {
+            let location = match parameter {
+                Parameter::Query { .. } => "query",
+                Parameter::Header { .. } => "header",
+                Parameter::Path { .. } => "path",
+                Parameter::Cookie { .. } => "cookie",
+            };
+            let name = parameter.parameter_data_ref().name.as_str();
+            parameters.insert((location, name), (pointer, parameter));
+        }
+    }
+    parameters
+}
+
+fn responses(responses: &Responses) -> IndexMap<String, &ReferenceOr<Response>> {
+    responses
+        .default
+        .iter()
+        .map(|response| ("default".to_owned(), response))
+        .chain(
+            responses
+                .responses
+                .iter()
+                .map(|(status, response)| (status.to_string(), response)),
+        )
+        .collect()
+}
+
+fn resolve<'a, T: Component>(document: &'a OpenAPI, item: &'a ReferenceOr<T>) -> Option<&'a T> {
+    match (item, &document.components) {
+        (ReferenceOr::Item(item), _) => Some(item),
+        (ReferenceOr::Reference { .. }, Some(components)) => item.resolve(components).ok(),
+        (ReferenceOr::Reference { .. }, None) => None,
+    }
+}
+
+fn unboxed<'a>(document: &'a OpenAPI, schema: &'a ReferenceOr<Schema>) -> Target<'a> {
+    match schema {
+        ReferenceOr::Item(schema) => (None, Some(schema)),
+        ReferenceOr::Reference { reference } => (Some(reference), resolve(document, schema)),
+    }
+}
+
+fn boxed<'a>(document: &'a OpenAPI, schema: &'a ReferenceOr<Box<Schema>>) -> Target<'a> {
+    match schema {
+        ReferenceOr::Item(schema) => (None, Some(schema)),
+        ReferenceOr::Reference { reference } => (
+            Some(reference),
+            document
+                .components
+                .as_ref()
+                .and_then(|components| schema.resolve(components).ok()),
+        ),
+    }
+}
+
+fn type_name(schema: &Schema) -> Option<&str> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(_)) => Some("string"),
+        SchemaKind::Type(Type::Number(_)) => Some("number"),
+        SchemaKind::Type(Type::Integer(_)) => Some("integer"),
+        SchemaKind::Type(Type::Object(_)) => Some("object"),
+        SchemaKind::Type(Type::Array(_)) => Some("array"),
+        SchemaKind::Type(Type::Boolean(_)) => Some("boolean"),
+        SchemaKind::Any(any) => any.typ.as_deref(),
+        _ => None,
+    }
+}
+
+type Properties<'a> = (&'a IndexMap<String, ReferenceOr<Box<Schema>>>, &'a [String]);
+
+fn properties(schema: &Schema) -> Option<Properties<'_>> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => Some((&object.properties, &object.required)),
+        SchemaKind::Any(any) => Some((&any.properties, &any.required)),
+        _ => None,
+    }
+}
+
+fn items(schema: &Schema) -> Option<&ReferenceOr<Box<Schema>>> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Array(array)) => array.items.as_ref(),
+        SchemaKind::Any(any) => any.items.as_ref(),
+        _ => None,
+    }
+}
+
+fn describe(requirement: &SecurityRequirement) -> String {
+    let names = requirement
+        .keys()
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        "{}".to_owned()
+    } else {
+        names.join(" and ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document(value: serde_json::Value) -> OpenAPI {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = document(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "parameters": [ { "name": "limit", "in": "query", "schema": { "type": "integer" } } ],
+                    "get": {
+                        "responses": { "200": { "description": "", "content": { "application/json": {
+                            "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                        } } } }
+                    },
+                    "post": {
+                        "requestBody": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Pet" }
+                        } } },
+                        "responses": { "201": { "description": "" } }
+                    }
+                }
+            },
+            "components": { "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "kind": { "type": "string", "enum": ["dog", "cat"] },
+                        "age": { "type": "integer" },
+                        "parent": { "$ref": "#/components/schemas/Pet" }
+                    }
+                }
+            } }
+        }));
+        let new = document(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "2" },
+            "security": [ { "key": [] } ],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            { "name": "limit", "in": "query", "required": true, "schema": { "type": "integer" } }
+                        ],
+                        "responses": { "200": { "description": "", "content": { "application/json": {
+                            "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                        } } } }
+                    },
+                    "post": {
+                        "security": [],
+                        "requestBody": { "content": { "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Pet" }
+                        } } },
+                        "responses": { "201": { "description": "" }, "400": { "description": "" } }
+                    }
+                }
+            },
+            "components": { "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "required": ["name", "tag"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "kind": { "type": "string", "enum": ["dog", "bird"] },
+                        "age": { "type": "string" },
+                        "parent": { "$ref": "#/components/schemas/Pet" },
+                        "tag": { "type": "string" }
+                    }
+                }
+            } }
+        }));
+
+        let changes = diff(&old, &new)
+            .changes
+            .into_iter()
+            .map(|c| (c.kind, c.pointer, c.breaking))
+            .collect::<Vec<_>>();
+        let get = "/paths/~1pets/get";
+        let items = "/paths/~1pets/get/responses/200/content/application~1json/schema/items";
+        let body = "/paths/~1pets/post/requestBody/content/application~1json/schema";
+        let expected = [
+            (
+                ChangeKind::BecameRequired,
+                format!("{}/parameters/0", get),
+                true,
+            ),
+            (
+                ChangeKind::TypeChanged,
+                format!("{}/properties/age", items),
+                true,
+            ),
+            (
+                ChangeKind::EnumValueRemoved,
+                format!("{}/properties/kind/enum", items),
+                false,
+            ),
+            (
+                ChangeKind::EnumValueAdded,
+                format!("{}/properties/kind/enum", items),
+                true,
+            ),
+            (
+                ChangeKind::PropertyAdded,
+                format!("{}/properties/tag", items),
+                false,
+            ),
+            (
+                ChangeKind::SecurityRequirementAdded,
+                format!("{}/security", get),
+                true,
+            ),
+            (
+                ChangeKind::ParameterRemoved,
+                "/paths/~1pets/parameters/0".to_owned(),
+                true,
+            ),
+            (
+                ChangeKind::TypeChanged,
+                format!("{}/properties/age", body),
+                true,
+            ),
+            (
+                ChangeKind::EnumValueRemoved,
+                format!("{}/properties/kind/enum", body),
+                true,
+            ),
+            (
+                ChangeKind::EnumValueAdded,
+                format!("{}/properties/kind/enum", body),
+                false,
+            ),
+            (
+                ChangeKind::PropertyAdded,
+                format!("{}/properties/tag", body),
+                true,
+            ),
+            (
+                ChangeKind::ResponseAdded,
+                "/paths/~1pets/post/responses/400".to_owned(),
+                false,
+            ),
+        ];
+        assert_eq!(changes, expected);
+    }
+
+    #[test]
+    fn test_diff_removals() {
+        let old = document(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "security": [ { "key": [] }, { "oauth": ["read"] } ],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [ { "name": "X-Trace", "in": "header", "schema": { "type": "string" } } ],
+                        "requestBody": { "required": true, "content": { "text/plain": {} } },
+                        "responses": {
+                            "200": { "description": "", "content": { "application/json": {}, "text/plain": {} } },
+                            "404": { "description": "" }
+                        }
+                    },
+                    "delete": { "responses": {} }
+                }
+            }
+        }));
+        let new = document(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "security": [ { "key": [] } ],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "requestBody": { "content": { "text/plain": {}, "application/json": {} } },
+                        "responses": {
+                            "200": { "description": "", "content": { "application/json": {} } }
+                        }
+                    }
+                }
+            }
+        }));
+
+        let diff = diff(&old, &new);
+        let changes = diff
+            .changes
+            .iter()
+            .map(|c| (c.kind, c.breaking))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                (ChangeKind::ParameterRemoved, true),
+                (ChangeKind::BecameOptional, false),
+                (ChangeKind::MediaTypeAdded, false),
+                (ChangeKind::MediaTypeRemoved, true),
+                (ChangeKind::ResponseRemoved, true),
+                (ChangeKind::SecurityRequirementRemoved, true),
+                (ChangeKind::OperationRemoved, true),
+            ]
+        );
+        assert_eq!(
+            diff.changes[5].to_string(),
+            "/paths/~1pets/get/security: security requirement `oauth` was removed (breaking)"
+        );
+        assert_eq!(diff.breaking().count(), 5);
+    }
+}
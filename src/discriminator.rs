@@ -1,5 +1,10 @@
+use std::fmt;
+
+use crate::resolve::{component_name, resolve_reference};
+use crate::*;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// When request bodies or response payloads may be one of a number of different schemas,
 /// a discriminator object can be used to aid in serialization, deserialization,
@@ -19,5 +24,223 @@ pub struct Discriminator {
     pub mapping: IndexMap<String, String>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
+}
+
+/// The error returned by [Schema::select_variant].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiscriminatorError {
+    /// The schema has no discriminator.
+    NoDiscriminator,
+    /// The payload is not an object with the discriminator property.
+    MissingProperty { property_name: String },
+    /// The discriminator property of the payload is not a string.
+    NotAString { property_name: String },
+    /// The value maps to a schema that is not one of the `oneOf` or `anyOf`
+    /// variants.
+    UnknownValue { value: String },
+    /// The schema the value maps to cannot be resolved.
+    Resolve(ResolveError),
+}
+
+impl fmt::Display for DiscriminatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiscriminatorError::NoDiscriminator => f.write_str("the schema has no discriminator"),
+            DiscriminatorError::MissingProperty { property_name } => {
+                write!(f, "the payload has no `{}` property", property_name)
+            }
+            DiscriminatorError::NotAString { property_name } => {
+                write!(
+                    f,
+                    "the `{}` property of the payload is not a string",
+                    property_name
+                )
+            }
+            DiscriminatorError::UnknownValue { value } => {
+                write!(f, "`{}` does not select any variant", value)
+            }
+            DiscriminatorError::Resolve(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DiscriminatorError {}
+
+impl Schema {
+    /// Selects the variant of a polymorphic schema that describes `payload`,
+    /// from the value of its discriminator property. The value is looked up
+    /// in the mapping of the discriminator, and otherwise taken as the name
+    /// of a schema in `components`.
+    ///
+    /// When the schema lists its variants with `oneOf` or `anyOf`, only
+    /// those referenced there may be selected. Returns the name of the
+    /// selected component along with its schema.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let components: Components = serde_json::from_str(r##"{
+    ///     "schemas": {
+    ///         "Pet": {
+    ///             "oneOf": [ { "$ref": "#/components/schemas/Cat" }, { "$ref": "#/components/schemas/Dog" } ],
+    ///             "discriminator": { "propertyName": "kind", "mapping": { "dog": "Dog" } }
+    ///         },
+    ///         "Cat": { "type": "object" },
+    ///         "Dog": { "type": "object" }
+    ///     }
+    /// }"##).unwrap();
+    /// let pet = components.schemas["Pet"].as_item().unwrap();
+    /// let payload = serde_json::json!({ "kind": "dog", "name": "Rex" });
+    /// let (name, _) = pet.select_variant(&payload, &components).unwrap();
+    /// assert_eq!(name, "Dog");
+    /// ```
+    pub fn select_variant<'a>(
+        &self,
+        payload: &Value,
+        components: &'a Components,
+    ) -> Result<(String, &'a Schema), DiscriminatorError> {
+        let discriminator = self
+            .schema_data
+            .discriminator
+            .as_ref()
+            .ok_or(DiscriminatorError::NoDiscriminator)?;
+        let property_name = &discriminator.property_name;
+        let value = payload
+            .get(property_name)
+            .ok_or_else(|| DiscriminatorError::MissingProperty {
+                property_name: property_name.clone(),
+            })?
+            .as_str()
+            .ok_or_else(|| DiscriminatorError::NotAString {
+                property_name: property_name.clone(),
+            })?;
+
+        let target = discriminator
+            .mapping
+            .get(value)
+            .map_or(value, String::as_str);
+        let name = match target.starts_with('#') {
+            true => component_name(target, ComponentKind::Schemas)
+                .map_err(DiscriminatorError::Resolve)?,
+            false => target.to_owned(),
+        };
+
+        let variants = match &self.schema_kind {
+            SchemaKind::OneOf { one_of: variants } | SchemaKind::AnyOf { any_of: variants } => {
+                Some(variants)
+            }
+            _ => None,
+        };
+        let is_variant = |variant: &ReferenceOr<Schema>| match variant {
+            ReferenceOr::Reference { reference } => {
+                component_name(reference, ComponentKind::Schemas).is_ok_and(|n| n == name)
+            }
+            ReferenceOr::Item(_) => false,
+        };
+        if variants.is_some_and(|variants| !variants.iter().any(is_variant)) {
+            return Err(DiscriminatorError::UnknownValue {
+                value: value.to_owned(),
+            });
+        }
+
+        let reference = Reference::schema(name.clone()).to_string();
+        let schema = resolve_reference(components, &reference).map_err(|error| match error {
+            ResolveError::Missing { .. } if variants.is_none() => {
+                DiscriminatorError::UnknownValue {
+                    value: value.to_owned(),
+                }
+            }
+            error => DiscriminatorError::Resolve(error),
+        })?;
+        Ok((name, schema))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_variant() {
+        let components: Components = serde_json::from_value(json!({
+            "schemas": {
+                "Pet": {
+                    "oneOf": [
+                        { "$ref": "#/components/schemas/Cat" },
+                        { "$ref": "#/components/schemas/Dog" },
+                        { "$ref": "#/components/schemas/Lizard" }
+                    ],
+                    "discriminator": {
+                        "propertyName": "petType",
+                        "mapping": { "dog": "#/components/schemas/Dog", "cow": "Cow" }
+                    }
+                },
+                "Animal": {
+                    "type": "object",
+                    "discriminator": { "propertyName": "petType" }
+                },
+                "Cat": { "type": "object", "description": "cat" },
+                "Dog": { "type": "object", "description": "dog" },
+                "Cow": { "type": "object" }
+            }
+        }))
+        .unwrap();
+        let pet = components.schemas["Pet"].as_item().unwrap();
+        let select = |schema: &Schema, payload| {
+            schema
+                .select_variant(&payload, &components)
+                .map(|(name, _)| name)
+        };
+
+        let (name, schema) = pet
+            .select_variant(&json!({ "petType": "dog" }), &components)
+            .unwrap();
+        assert_eq!(name, "Dog");
+        assert_eq!(schema.schema_data.description.as_deref(), Some("dog"));
+        assert_eq!(
+            select(pet, json!({ "petType": "Cat" })),
+            Ok("Cat".to_owned())
+        );
+        assert_eq!(
+            select(pet, json!({ "petType": "cow" })),
+            Err(DiscriminatorError::UnknownValue {
+                value: "cow".to_owned()
+            })
+        );
+        assert_eq!(
+            select(pet, json!({ "petType": "Lizard" })),
+            Err(DiscriminatorError::Resolve(ResolveError::Missing {
+                reference: "#/components/schemas/Lizard".to_owned()
+            }))
+        );
+        assert_eq!(
+            select(pet, json!({ "name": "Rex" })),
+            Err(DiscriminatorError::MissingProperty {
+                property_name: "petType".to_owned()
+            })
+        );
+        assert_eq!(
+            select(pet, json!({ "petType": 1 })),
+            Err(DiscriminatorError::NotAString {
+                property_name: "petType".to_owned()
+            })
+        );
+
+        let animal = components.schemas["Animal"].as_item().unwrap();
+        assert_eq!(
+            select(animal, json!({ "petType": "Cow" })),
+            Ok("Cow".to_owned())
+        );
+        assert_eq!(
+            select(animal, json!({ "petType": "Horse" })),
+            Err(DiscriminatorError::UnknownValue {
+                value: "Horse".to_owned()
+            })
+        );
+        assert_eq!(
+            select(components.schemas["Cat"].as_item().unwrap(), json!({})),
+            Err(DiscriminatorError::NoDiscriminator)
+        );
+    }
 }
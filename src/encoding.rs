@@ -36,9 +36,10 @@ pub struct Encoding {
     /// SHALL be ignored if the request body media type is
     /// not application/x-www-form-urlencoded.
     ///
-    /// In this Library this value defaults to false always despite the specification.
-    #[serde(default, skip_serializing_if = "is_false")]
-    pub explode: bool,
+    /// See [Encoding::effective] for the value that applies when this is
+    /// not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
     /// Determines whether the parameter value SHOULD allow reserved characters,
     /// as defined by RFC3986 :/?#[]@!$&'()*+,;= to be included without percent-encoding.
     /// The default value is false. This property SHALL be ignored if the request
@@ -47,5 +48,188 @@ pub struct Encoding {
     pub allow_reserved: bool,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
+}
+
+/// The encoding that applies to a property once the defaults from the
+/// specification are taken into account. See [Encoding::effective].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveEncoding {
+    /// The Content-Type for the property.
+    pub content_type: String,
+    /// The serialization style. This is `None` unless the request body media
+    /// type is application/x-www-form-urlencoded, since it is otherwise
+    /// ignored.
+    pub style: Option<QueryStyle>,
+    /// Whether arrays and objects generate separate parameters. Always `false`
+    /// when `style` is `None`.
+    pub explode: bool,
+    /// Whether reserved characters are sent without percent-encoding. Always
+    /// `false` when `style` is `None`.
+    pub allow_reserved: bool,
+}
+
+impl Encoding {
+    /// Computes the encoding that applies to a property of a request body of
+    /// type `media_type`, filling in unspecified fields from the defaults in
+    /// the specification.
+    ///
+    /// The default content type is derived from `property_schema`: string
+    /// with format binary is `application/octet-stream`, other primitive
+    /// types are `text/plain`, objects are `application/json` and arrays use
+    /// the default of their items. Referenced and composed schemas are
+    /// assumed to be objects. Without a schema the content type is
+    /// `application/octet-stream`.
+    ///
+    /// `style`, `explode` and `allowReserved` only apply to
+    /// application/x-www-form-urlencoded bodies, where `style` defaults to
+    /// form and `explode` defaults to `true` for form and `false` otherwise.
+    pub fn effective(
+        &self,
+        media_type: &MediaTypeKey,
+        property_schema: Option<&Schema>,
+    ) -> EffectiveEncoding {
+        let content_type = self
+            .content_type
+            .clone()
+            .unwrap_or_else(|| default_content_type(property_schema).to_owned());
+
+        let form_urlencoded =
+            media_type.typ == "application" && media_type.full_subtype() == "x-www-form-urlencoded";
+        if !form_urlencoded {
+            return EffectiveEncoding {
+                content_type,
+                style: None,
+                explode: false,
+                allow_reserved: false,
+            };
+        }
+
+        let style = self.style.clone().unwrap_or_default();
+        let explode = self.explode.unwrap_or(style == QueryStyle::Form);
+        EffectiveEncoding {
+            content_type,
+            style: Some(style),
+            explode,
+            allow_reserved: self.allow_reserved,
+        }
+    }
+}
+
+fn default_content_type(schema: Option<&Schema>) -> &'static str {
+    const BINARY: &str = "application/octet-stream";
+    const TEXT: &str = "text/plain";
+    const JSON: &str = "application/json";
+
+    let schema = match schema {
+        Some(schema) => schema,
+        None => return BINARY,
+    };
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(StringType {
+            format: VariantOrUnknownOrEmpty::Item(StringFormat::Binary),
+            ..
+        })) => BINARY,
+        SchemaKind::Type(Type::String(_))
+        | SchemaKind::Type(Type::Number(_))
+        | SchemaKind::Type(Type::Integer(_))
+        | SchemaKind::Type(Type::Boolean(_)) => TEXT,
+        SchemaKind::Type(Type::Object(_)) => JSON,
+        SchemaKind::Type(Type::Array(ArrayType { items, .. })) => match items {
+            Some(ReferenceOr::Item(items)) => default_content_type(Some(items)),
+            Some(ReferenceOr::Reference { .. }) => JSON,
+            None => BINARY,
+        },
+        SchemaKind::Any(any) => match any.typ.as_deref() {
+            Some("string") if any.format.as_deref() == Some("binary") => BINARY,
+            Some("string" | "number" | "integer" | "boolean") => TEXT,
+            Some(_) => JSON,
+            None => BINARY,
+        },
+        SchemaKind::OneOf { .. }
+        | SchemaKind::AllOf { .. }
+        | SchemaKind::AnyOf { .. }
+        | SchemaKind::Not { .. } => JSON,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{Encoding, QueryStyle, Schema};
+
+    fn schema(value: serde_json::Value) -> Schema {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_effective_content_type() {
+        let multipart = "multipart/form-data".parse().unwrap();
+        let encoding = Encoding::default();
+        let content_type = |value| {
+            encoding
+                .effective(&multipart, Some(&schema(value)))
+                .content_type
+        };
+
+        assert_eq!(
+            content_type(json!({ "type": "string", "format": "binary" })),
+            "application/octet-stream"
+        );
+        assert_eq!(content_type(json!({ "type": "integer" })), "text/plain");
+        assert_eq!(
+            content_type(json!({ "type": "object" })),
+            "application/json"
+        );
+        assert_eq!(
+            content_type(
+                json!({ "type": "array", "items": { "type": "string", "format": "binary" } })
+            ),
+            "application/octet-stream"
+        );
+        assert_eq!(
+            content_type(json!({ "type": "array", "items": { "type": "boolean" } })),
+            "text/plain"
+        );
+        assert_eq!(
+            encoding.effective(&multipart, None).content_type,
+            "application/octet-stream"
+        );
+
+        let explicit = Encoding {
+            content_type: Some("image/png".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            explicit
+                .effective(&multipart, Some(&schema(json!({ "type": "object" }))))
+                .content_type,
+            "image/png"
+        );
+    }
+
+    #[test]
+    fn test_effective_style_and_explode() {
+        let form = "application/x-www-form-urlencoded".parse().unwrap();
+        let multipart = "multipart/form-data".parse().unwrap();
+
+        let effective = Encoding::default().effective(&form, None);
+        assert_eq!(effective.style, Some(QueryStyle::Form));
+        assert!(effective.explode);
+
+        let effective = Encoding::default().effective(&multipart, None);
+        assert_eq!(effective.style, None);
+        assert!(!effective.explode);
+
+        let pipe: Encoding = serde_json::from_value(json!({ "style": "pipeDelimited" })).unwrap();
+        assert!(!pipe.effective(&form, None).explode);
+
+        let no_explode: Encoding = serde_json::from_value(json!({ "explode": false })).unwrap();
+        assert!(!no_explode.effective(&form, None).explode);
+        assert_eq!(
+            serde_json::to_value(&no_explode).unwrap()["explode"],
+            json!(false)
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use indexmap::IndexMap;
+use crate::Extensions;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -24,5 +24,5 @@ pub struct Example {
     pub external_value: Option<String>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
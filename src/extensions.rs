@@ -0,0 +1,337 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::util::PredicateVisitor;
+
+/// The specification extensions of an object, i.e. its fields whose names
+/// start with `x-`.
+///
+/// This dereferences to the underlying map and serializes exactly like it.
+/// When deserializing, fields that are not extensions are ignored, unless
+/// other keys are let in with [Extensions::with_key_predicate].
+///
+/// ```
+/// # use openapiv3::Extensions;
+/// # use serde_json::json;
+/// let mut extensions = Extensions::new();
+/// extensions.insert_serialize("x-acme-retries", &3).unwrap();
+/// extensions.insert("x-acme-internal".to_owned(), json!(true));
+/// extensions.insert("x-logo".to_owned(), json!("logo.png"));
+///
+/// assert_eq!(extensions.get_as::<u32>("x-acme-retries").unwrap(), Some(3));
+/// assert_eq!(
+///     extensions.namespace("acme").map(|(name, _)| name).collect::<Vec<_>>(),
+///     ["retries", "internal"]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Extensions(IndexMap<String, serde_json::Value>);
+
+type KeyPredicate = Rc<dyn Fn(&str) -> bool>;
+
+thread_local! {
+    static KEY_PREDICATE: RefCell<Option<KeyPredicate>> = const { RefCell::new(None) };
+}
+
+impl Extensions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_inner(self) -> IndexMap<String, serde_json::Value> {
+        self.0
+    }
+
+    /// Deserializes the value of the extension `name`, if present.
+    pub fn get_as<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, serde_json::Error> {
+        self.0
+            .get(name)
+            .map(|value| T::deserialize(value))
+            .transpose()
+    }
+
+    /// Serializes `value` and inserts it as the extension `name`, returning
+    /// the previous value.
+    pub fn insert_serialize<T: Serialize + ?Sized>(
+        &mut self,
+        name: impl Into<String>,
+        value: &T,
+    ) -> Result<Option<serde_json::Value>, serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        Ok(self.0.insert(name.into(), value))
+    }
+
+    /// Iterates over the extensions whose names start with `prefix`.
+    pub fn iter_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a serde_json::Value)> + 'a {
+        self.0
+            .iter()
+            .filter(move |(name, _)| name.starts_with(prefix))
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Iterates over the extensions of a vendor namespace, i.e. those named
+    /// `x-{namespace}-{name}`, yielding `name` and the value.
+    pub fn namespace<'a>(
+        &'a self,
+        namespace: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a serde_json::Value)> + 'a {
+        self.0.iter().filter_map(move |(name, value)| {
+            let name = name
+                .strip_prefix("x-")?
+                .strip_prefix(namespace)?
+                .strip_prefix('-')?;
+            Some((name, value))
+        })
+    }
+
+    /// Runs `parse`, which deserializes a document on the current thread,
+    /// keeping the unknown fields of its objects for which `keep` returns
+    /// `true` as extensions, instead of only those starting with `x-`.
+    ///
+    /// Fields the specification defines are never affected, so `|_| true`
+    /// keeps every unknown field. Calls may be nested, and the previous
+    /// predicate is restored when `parse` returns.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let raw = r#"{ "description": "OK", "name: count": 3, "ext-cache": true, "x-id": 1 }"#;
+    ///
+    /// let response: Response = serde_json::from_str(raw).unwrap();
+    /// assert_eq!(response.extensions.keys().collect::<Vec<_>>(), ["x-id"]);
+    ///
+    /// let response: Response =
+    ///     Extensions::with_key_predicate(|_| true, || serde_json::from_str(raw)).unwrap();
+    /// assert_eq!(response.extensions.len(), 3);
+    ///
+    /// let prefixes = ["x-", "ext-"];
+    /// let response: Response = Extensions::with_key_predicate(
+    ///     move |key| prefixes.iter().any(|prefix| key.starts_with(prefix)),
+    ///     || serde_json::from_str(raw),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(response.extensions.keys().collect::<Vec<_>>(), ["ext-cache", "x-id"]);
+    /// ```
+    pub fn with_key_predicate<T>(
+        keep: impl Fn(&str) -> bool + 'static,
+        parse: impl FnOnce() -> T,
+    ) -> T {
+        struct Restore(Option<KeyPredicate>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                KEY_PREDICATE.with(|predicate| *predicate.borrow_mut() = previous);
+            }
+        }
+
+        let previous = KEY_PREDICATE.with(|predicate| predicate.replace(Some(Rc::new(keep))));
+        let _restore = Restore(previous);
+        parse()
+    }
+
+    /// Removes the extensions of a vendor namespace, as matched by
+    /// [Extensions::namespace], returning them.
+    pub fn remove_namespace(&mut self, namespace: &str) -> Extensions {
+        let prefix = format!("x-{}-", namespace);
+        let (removed, kept) = std::mem::take(&mut self.0)
+            .into_iter()
+            .partition(|(name, _)| name.starts_with(&prefix));
+        self.0 = kept;
+        Extensions(removed)
+    }
+}
+
+impl Deref for Extensions {
+    type Target = IndexMap<String, serde_json::Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Extensions {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<IndexMap<String, serde_json::Value>> for Extensions {
+    fn from(map: IndexMap<String, serde_json::Value>) -> Self {
+        Extensions(map)
+    }
+}
+
+impl FromIterator<(String, serde_json::Value)> for Extensions {
+    fn from_iter<I: IntoIterator<Item = (String, serde_json::Value)>>(iter: I) -> Self {
+        Extensions(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Extensions {
+    type Item = (String, serde_json::Value);
+    type IntoIter = indexmap::map::IntoIter<String, serde_json::Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Extensions {
+    type Item = (&'a String, &'a serde_json::Value);
+    type IntoIter = indexmap::map::Iter<'a, String, serde_json::Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Extensions {
+    type Item = (&'a String, &'a mut serde_json::Value);
+    type IntoIter = indexmap::map::IterMut<'a, String, serde_json::Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+impl<'de> Deserialize<'de> for Extensions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_extensions_except(deserializer, |_| false)
+    }
+}
+
+/// Whether [Extensions::with_key_predicate] is in effect on this thread.
+#[cfg(feature = "parallel")]
+pub(crate) fn has_key_predicate() -> bool {
+    KEY_PREDICATE.with(|predicate| predicate.borrow().is_some())
+}
+
+/// Whether the field `key` is kept as an extension, see
+/// [Extensions::with_key_predicate].
+fn is_extension_key(key: &str) -> bool {
+    match KEY_PREDICATE.with(|predicate| predicate.borrow().clone()) {
+        Some(keep) => keep(key),
+        None => key.starts_with("x-"),
+    }
+}
+
+/// Deserializes the extensions of an object that is flattened along with
+/// other objects, which see its fields too. Fields for which `known` returns
+/// `true` belong to those and are never extensions.
+pub(crate) fn deserialize_extensions_except<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    known: fn(&str) -> bool,
+) -> Result<Extensions, D::Error> {
+    deserializer
+        .deserialize_map(PredicateVisitor(
+            |key: &String| !known(key) && is_extension_key(key),
+            PhantomData,
+        ))
+        .map(Extensions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_extensions() {
+        let mut extensions: Extensions = serde_json::from_value(json!({
+            "x-acme-limits": { "rate": 10 },
+            "x-acme-beta": true,
+            "x-other": 1,
+            "description": "ignored"
+        }))
+        .unwrap();
+        assert_eq!(extensions.len(), 3);
+
+        #[derive(Deserialize)]
+        struct Limits {
+            rate: u32,
+        }
+        let limits: Limits = extensions.get_as("x-acme-limits").unwrap().unwrap();
+        assert_eq!(limits.rate, 10);
+        assert!(extensions.get_as::<Limits>("x-missing").unwrap().is_none());
+        assert!(extensions.get_as::<String>("x-acme-beta").is_err());
+        assert_eq!(
+            extensions.iter_prefix("x-o").collect::<Vec<_>>(),
+            [("x-other", &json!(1))]
+        );
+
+        let acme = extensions.remove_namespace("acme");
+        assert_eq!(
+            acme.keys().collect::<Vec<_>>(),
+            ["x-acme-beta", "x-acme-limits"]
+        );
+        assert_eq!(
+            serde_json::to_value(&extensions).unwrap(),
+            json!({ "x-other": 1 })
+        );
+    }
+
+    #[test]
+    fn test_key_predicate() {
+        let raw = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1", "audience": "internal" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [ {
+                            "name": "tags", "in": "query", "style": "pipeDelimited", "allowReserved": true,
+                            "schema": { "type": "array", "items": { "type": "string" } },
+                            "name: count": 3
+                        } ],
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "links": { "next": { "operationId": "listPets", "kind": "page" } },
+                                "content": { "application/json": { "schema": {
+                                    "type": "object",
+                                    "required": ["id"],
+                                    "properties": { "id": { "type": "integer", "minimum": 1 } },
+                                    "sensitive": true
+                                } } }
+                            },
+                            "4XX": { "description": "Error" },
+                            "default": { "description": "Unexpected" },
+                            "comment": "todo"
+                        }
+                    }
+                },
+                "owner": "pets-team"
+            }
+        });
+
+        let api: crate::OpenAPI =
+            Extensions::with_key_predicate(|_| true, || serde_json::from_value(raw.clone()))
+                .unwrap();
+        assert_eq!(serde_json::to_value(&api).unwrap(), raw);
+        let operation = api.paths.paths["/pets"]
+            .as_item()
+            .unwrap()
+            .get
+            .as_ref()
+            .unwrap();
+        assert_eq!(
+            operation.responses.extensions.keys().collect::<Vec<_>>(),
+            ["comment"]
+        );
+        assert_eq!(api.paths.extensions.keys().collect::<Vec<_>>(), ["owner"]);
+
+        let api: crate::OpenAPI = serde_json::from_value(raw).unwrap();
+        assert!(api.info.extensions.is_empty());
+        assert!(api.paths.extensions.is_empty());
+    }
+}
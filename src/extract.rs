@@ -0,0 +1,300 @@
+use crate::resolve::component_name;
+use crate::*;
+use indexmap::IndexSet;
+
+impl OpenAPI {
+    /// Returns a copy of this document with only the operations matching
+    /// `filter`, see [OpenAPI::extract_by].
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": {
+    ///         "/pets": { "get": { "tags": ["public"], "responses": { "200": {
+    ///             "description": "Pets",
+    ///             "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pets" } } }
+    ///         } } } },
+    ///         "/admin/audit": { "get": { "responses": { "200": {
+    ///             "description": "Audit log",
+    ///             "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Audit" } } }
+    ///         } } } }
+    ///     },
+    ///     "components": { "schemas": {
+    ///         "Pets": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } },
+    ///         "Pet": { "type": "object" },
+    ///         "Audit": { "type": "object" }
+    ///     } }
+    /// }"##).unwrap();
+    ///
+    /// let public = api.extract(&OperationFilter::new().tag("public"));
+    /// assert_eq!(public.paths.paths.keys().collect::<Vec<_>>(), ["/pets"]);
+    /// let schemas = &public.components.unwrap().schemas;
+    /// assert_eq!(schemas.keys().collect::<Vec<_>>(), ["Pets", "Pet"]);
+    /// ```
+    pub fn extract(&self, filter: &OperationFilter) -> OpenAPI {
+        self.extract_by(|path, method, operation| filter.matches(path, method, operation))
+    }
+
+    /// Returns a copy of this document with only the operations for which
+    /// `keep` returns `true`, and only the components they need.
+    ///
+    /// Path items left without operations, and path items that are
+    /// references, are removed, as are tags that no remaining operation uses.
+    /// The components kept are those referenced from outside the components,
+    /// including through discriminator mappings and security requirements,
+    /// followed by those referenced from kept components, so the result
+    /// resolves on its own if the original did. References that cannot be
+    /// resolved are ignored.
+    pub fn extract_by<F>(&self, mut keep: F) -> OpenAPI
+    where
+        F: FnMut(&str, &str, &Operation) -> bool,
+    {
+        let mut subset = self.clone();
+        let components = subset.components.take();
+
+        subset.paths.paths.retain(|path, item| match item {
+            ReferenceOr::Item(item) => {
+                let removed = item
+                    .iter()
+                    .filter(|(method, operation)| !keep(path, method, operation))
+                    .map(|(method, _)| method.to_owned())
+                    .collect::<Vec<_>>();
+                for method in removed {
+                    *item.operation_mut(&method).unwrap() = None;
+                }
+                item.iter().next().is_some()
+            }
+            ReferenceOr::Reference { .. } => false,
+        });
+
+        let tags = subset
+            .operations()
+            .flat_map(|(_, _, operation)| operation.tags.iter().cloned())
+            .collect::<IndexSet<_>>();
+        subset.tags.retain(|tag| tags.contains(&tag.name));
+
+        if let Some(mut components) = components {
+            let used = used_components(&mut subset, &components);
+            macro_rules! retain {
+                ($($field:ident => $kind:ident),*) => {
+                    $(
+                        components
+                            .$field
+                            .retain(|name, _| used.contains(&(ComponentKind::$kind, name.clone())));
+                    )*
+                };
+            }
+            retain!(
+                schemas => Schemas,
+                responses => Responses,
+                parameters => Parameters,
+                examples => Examples,
+                request_bodies => RequestBodies,
+                headers => Headers,
+                security_schemes => SecuritySchemes,
+                links => Links,
+                callbacks => Callbacks
+            );
+            subset.components = Some(components);
+        }
+        subset
+    }
+}
+
+/// Computes the components reachable from `document`, which has no
+/// components of its own.
+fn used_components(
+    document: &mut OpenAPI,
+    components: &Components,
+) -> IndexSet<(ComponentKind, String)> {
+    let mut collector = References::default();
+    document.transform(&mut collector);
+    for requirement in document.security.iter().flatten() {
+        collector.security(requirement);
+    }
+    for (_, _, operation) in document.operations() {
+        collector.operation_security(operation);
+    }
+
+    let mut used = IndexSet::new();
+    while let Some((kind, name)) = collector.found.pop() {
+        if used.contains(&(kind, name.clone())) {
+            continue;
+        }
+        // Components are visited one at a time in a document of their own.
+        let mut single = Components::default();
+        macro_rules! copy {
+            ($($field:ident => $kind:ident),*) => {
+                match kind {
+                    $(
+                        ComponentKind::$kind => match components.$field.get(&name) {
+                            Some(item) => {
+                                single.$field.insert(name.clone(), item.clone());
+                            }
+                            None => continue,
+                        },
+                    )*
+                }
+            };
+        }
+        copy!(
+            schemas => Schemas,
+            responses => Responses,
+            parameters => Parameters,
+            examples => Examples,
+            request_bodies => RequestBodies,
+            headers => Headers,
+            security_schemes => SecuritySchemes,
+            links => Links,
+            callbacks => Callbacks
+        );
+        for callback in single.callbacks.values().filter_map(|c| c.as_item()) {
+            for item in callback.values() {
+                for (_, operation) in item.iter() {
+                    collector.operation_security(operation);
+                }
+            }
+        }
+        let mut scratch = OpenAPI {
+            components: Some(single),
+            ..OpenAPI::default()
+        };
+        scratch.transform(&mut collector);
+        used.insert((kind, name));
+    }
+    used
+}
+
+#[derive(Default)]
+struct References {
+    found: Vec<(ComponentKind, String)>,
+}
+
+impl References {
+    fn security(&mut self, requirement: &SecurityRequirement) {
+        for name in requirement.keys() {
+            self.found
+                .push((ComponentKind::SecuritySchemes, name.clone()));
+        }
+    }
+
+    fn operation_security(&mut self, operation: &Operation) {
+        for requirement in operation.security.iter().flatten() {
+            self.security(requirement);
+        }
+        for callback in operation.callbacks.values() {
+            for item in callback.values() {
+                for (_, operation) in item.iter() {
+                    self.operation_security(operation);
+                }
+            }
+        }
+    }
+}
+
+impl VisitorMut for References {
+    fn visit_schema_mut(&mut self, _: &str, schema: &mut Schema) {
+        if let Some(discriminator) = &schema.schema_data.discriminator {
+            for target in discriminator.mapping.values() {
+                let name = match target.starts_with('#') {
+                    true => component_name(target, ComponentKind::Schemas).ok(),
+                    false => Some(target.clone()),
+                };
+                self.found
+                    .extend(name.map(|name| (ComponentKind::Schemas, name)));
+            }
+        }
+    }
+
+    fn visit_reference_mut(&mut self, _: &str, reference: &mut String) {
+        let found = ComponentKind::ALL
+            .into_iter()
+            .find_map(|kind| Some((kind, component_name(reference, kind).ok()?)));
+        self.found.extend(found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let api: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "tags": [ { "name": "pets" }, { "name": "admin" } ],
+                "paths": {
+                    "/pets/{id}": {
+                        "parameters": [ { "$ref": "#/components/parameters/Id" } ],
+                        "get": {
+                            "tags": ["pets"],
+                            "security": [ { "key": [] } ],
+                            "responses": { "200": { "$ref": "#/components/responses/Pet" } }
+                        },
+                        "delete": { "tags": ["admin"], "responses": { "204": { "description": "" } } }
+                    },
+                    "/admin": { "get": { "tags": ["admin"], "responses": {} } },
+                    "/remote": { "$ref": "other.json#/paths/~1remote" }
+                },
+                "components": {
+                    "parameters": {
+                        "Id": { "name": "id", "in": "path", "required": true, "schema": { "$ref": "#/components/schemas/Id" } }
+                    },
+                    "responses": {
+                        "Pet": {
+                            "description": "",
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } }
+                        }
+                    },
+                    "schemas": {
+                        "Id": { "type": "string" },
+                        "Pet": {
+                            "oneOf": [ { "$ref": "#/components/schemas/Dog" }, { "$ref": "#/components/schemas/Missing" } ],
+                            "discriminator": { "propertyName": "kind", "mapping": { "cat": "Cat" } }
+                        },
+                        "Dog": { "type": "object" },
+                        "Cat": { "type": "object" },
+                        "Audit": { "type": "object" }
+                    },
+                    "securitySchemes": {
+                        "key": { "type": "apiKey", "name": "key", "in": "header" },
+                        "admin": { "type": "http", "scheme": "basic" }
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let subset = api.extract(&OperationFilter::new().path_glob("/pets/*").method("get"));
+        assert_eq!(
+            subset.paths.paths.keys().collect::<Vec<_>>(),
+            ["/pets/{id}"]
+        );
+        let item = subset.paths.paths["/pets/{id}"].as_item().unwrap();
+        assert!(item.get.is_some() && item.delete.is_none());
+        assert_eq!(
+            subset.tags.iter().map(|t| &t.name).collect::<Vec<_>>(),
+            ["pets"]
+        );
+
+        let components = subset.components.unwrap();
+        assert_eq!(
+            components.schemas.keys().collect::<Vec<_>>(),
+            ["Id", "Pet", "Dog", "Cat"]
+        );
+        assert_eq!(components.parameters.len(), 1);
+        assert_eq!(components.responses.len(), 1);
+        assert_eq!(
+            components.security_schemes.keys().collect::<Vec<_>>(),
+            ["key"]
+        );
+
+        let none = api.extract_by(|_, _, _| false);
+        assert!(none.paths.paths.is_empty() && none.tags.is_empty());
+        assert!(none.components.unwrap().schemas.is_empty());
+    }
+}
@@ -0,0 +1,345 @@
+use crate::resolve::{component_name, resolve_reference, Component};
+use crate::*;
+use indexmap::IndexMap;
+
+/// An operation with everything that applies to it gathered in one place and
+/// all references followed, as returned by [OpenAPI::flat_operations].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatOperation<'a> {
+    /// The path template, e.g. `/pets/{petId}`.
+    pub path: &'a str,
+    /// The lowercase HTTP method.
+    pub method: &'a str,
+    pub operation: &'a Operation,
+    /// The parameters of the path item and of the operation, with operation
+    /// parameters overriding path item parameters of the same name and
+    /// location.
+    pub parameters: Vec<&'a Parameter>,
+    /// Whether the request body is required. `false` if there is no request
+    /// body.
+    pub request_body_required: bool,
+    /// The schema of the request body for each media type. Media types
+    /// without a schema map to `None`.
+    pub request_body: IndexMap<&'a str, Option<&'a Schema>>,
+    /// The responses for specific status codes or ranges.
+    pub responses: IndexMap<&'a StatusCode, &'a Response>,
+    /// The response for all other status codes.
+    pub default_response: Option<&'a Response>,
+}
+
+/// The parameters that apply to an operation, as returned by
+/// [PathItem::effective_parameters].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveParameters<'a> {
+    /// The parameters of the path item and of the operation, in order of
+    /// first definition, with operation parameters overriding path item
+    /// parameters of the same name and location.
+    pub parameters: Vec<&'a Parameter>,
+    /// The path item parameters overridden by operation parameters.
+    pub overridden: Vec<&'a Parameter>,
+    /// The parameters defined more than once on the same level, path item
+    /// or operation, which the specification forbids. Each is listed once
+    /// for every repetition, and the last definition is the one used.
+    pub duplicates: Vec<&'a Parameter>,
+}
+
+impl PathItem {
+    /// Computes the parameters that apply to `operation` of this path item,
+    /// following references through `components`.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let item: PathItem = serde_json::from_str(r#"{
+    ///     "parameters": [
+    ///         { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } },
+    ///         { "name": "limit", "in": "query", "schema": { "type": "integer" } }
+    ///     ],
+    ///     "get": {
+    ///         "parameters": [ { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } } ],
+    ///         "responses": {}
+    ///     }
+    /// }"#).unwrap();
+    /// let get = item.get.as_ref().unwrap();
+    /// let effective = item.effective_parameters(get, None).unwrap();
+    /// assert_eq!(effective.parameters.len(), 2);
+    /// assert_eq!(effective.parameters[0], get.parameters[0].as_item().unwrap());
+    /// assert_eq!(effective.overridden[0], item.parameters[0].as_item().unwrap());
+    /// ```
+    pub fn effective_parameters<'a>(
+        &'a self,
+        operation: &'a Operation,
+        components: Option<&'a Components>,
+    ) -> Result<EffectiveParameters<'a>, ResolveError> {
+        let mut duplicates = Vec::new();
+        let mut parameters = level(components, &self.parameters, &mut duplicates)?;
+        let mut overridden = Vec::new();
+        for parameter in level(components, &operation.parameters, &mut duplicates)? {
+            match parameters.iter_mut().find(|p| same_parameter(p, parameter)) {
+                Some(existing) => overridden.push(std::mem::replace(existing, parameter)),
+                None => parameters.push(parameter),
+            }
+        }
+        Ok(EffectiveParameters {
+            parameters,
+            overridden,
+            duplicates,
+        })
+    }
+}
+
+/// Resolves the parameters of one level, path item or operation, keeping the
+/// last definition of repeated ones and adding the repetitions to
+/// `duplicates`.
+fn level<'a>(
+    components: Option<&'a Components>,
+    parameters: &'a [ReferenceOr<Parameter>],
+    duplicates: &mut Vec<&'a Parameter>,
+) -> Result<Vec<&'a Parameter>, ResolveError> {
+    let mut resolved: Vec<&Parameter> = Vec::new();
+    for parameter in parameters {
+        let parameter = resolve(components, parameter)?;
+        match resolved.iter_mut().find(|p| same_parameter(p, parameter)) {
+            Some(existing) => {
+                *existing = parameter;
+                duplicates.push(parameter);
+            }
+            None => resolved.push(parameter),
+        }
+    }
+    Ok(resolved)
+}
+
+impl OpenAPI {
+    /// Returns every operation in this API in its denormalized form, as
+    /// needed to generate clients or render API consoles.
+    ///
+    /// Path items containing `$ref`s are skipped. Only the top-level request
+    /// body schemas are resolved; schemas nested within them may still
+    /// contain references.
+    ///
+    /// ```
+    /// # use openapiv3::OpenAPI;
+    /// let api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets/{id}": {
+    ///         "parameters": [ { "$ref": "#/components/parameters/Id" } ],
+    ///         "get": { "responses": { "200": { "description": "A pet" } } }
+    ///     } },
+    ///     "components": { "parameters": {
+    ///         "Id": { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+    ///     } }
+    /// }"##).unwrap();
+    /// let operations = api.flat_operations().unwrap();
+    /// assert_eq!(operations[0].parameters[0].parameter_data_ref().name, "id");
+    /// ```
+    pub fn flat_operations(&self) -> Result<Vec<FlatOperation<'_>>, ResolveError> {
+        let components = self.components.as_ref();
+        let mut flattened = Vec::new();
+        for (path, item) in self.paths.iter() {
+            let item = match item {
+                ReferenceOr::Item(item) => item,
+                ReferenceOr::Reference { .. } => continue,
+            };
+            for (method, operation) in item.iter() {
+                flattened.push(flatten(components, path, item, method, operation)?);
+            }
+        }
+        Ok(flattened)
+    }
+}
+
+/// Gathers everything that applies to `operation` of `item`, see
+/// [OpenAPI::flat_operations].
+pub(crate) fn flatten<'a>(
+    components: Option<&'a Components>,
+    path: &'a str,
+    item: &'a PathItem,
+    method: &'a str,
+    operation: &'a Operation,
+) -> Result<FlatOperation<'a>, ResolveError> {
+    let parameters = item.effective_parameters(operation, components)?.parameters;
+
+    let mut request_body_required = false;
+    let mut request_body = IndexMap::new();
+    if let Some(body) = &operation.request_body {
+        let body = resolve(components, body)?;
+        request_body_required = body.required;
+        for (media_type, content) in &body.content {
+            let schema = match &content.schema {
+                Some(schema) => Some(resolve(components, schema)?),
+                None => None,
+            };
+            request_body.insert(media_type.as_str(), schema);
+        }
+    }
+
+    let mut responses = IndexMap::new();
+    for (status, response) in &operation.responses.responses {
+        responses.insert(status, resolve(components, response)?);
+    }
+    let default_response = match &operation.responses.default {
+        Some(response) => Some(resolve(components, response)?),
+        None => None,
+    };
+
+    Ok(FlatOperation {
+        path,
+        method,
+        operation,
+        parameters,
+        request_body_required,
+        request_body,
+        responses,
+        default_response,
+    })
+}
+
+fn resolve<'a, T: Component>(
+    components: Option<&'a Components>,
+    item: &'a ReferenceOr<T>,
+) -> Result<&'a T, ResolveError> {
+    match (item, components) {
+        (ReferenceOr::Item(item), _) => Ok(item),
+        (ReferenceOr::Reference { reference }, Some(components)) => {
+            resolve_reference(components, reference)
+        }
+        (ReferenceOr::Reference { reference }, None) => {
+            component_name(reference, T::KIND)?;
+            Err(ResolveError::Missing {
+                reference: reference.clone(),
+            })
+        }
+    }
+}
+
+/// Whether two parameters have the same name and location.
+pub(crate) fn same_parameter(a: &Parameter, b: &Parameter) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+        && a.parameter_data_ref().name == b.parameter_data_ref().name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_operations() {
+        let api: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {
+                    "/pets/{id}": {
+                        "parameters": [
+                            { "name": "id", "in": "path", "required": true, "description": "old", "schema": {} },
+                            { "name": "id", "in": "query", "schema": {} }
+                        ],
+                        "put": {
+                            "parameters": [ { "$ref": "#/components/parameters/Id" } ],
+                            "requestBody": { "$ref": "#/components/requestBodies/Pet" },
+                            "responses": {
+                                "2XX": { "$ref": "#/components/responses/Ok" },
+                                "default": { "description": "Error" }
+                            }
+                        }
+                    },
+                    "/other": { "$ref": "other.json#/paths/other" }
+                },
+                "components": {
+                    "schemas": { "Pet": { "type": "object" } },
+                    "parameters": {
+                        "Id": { "name": "id", "in": "path", "required": true, "description": "new", "schema": {} }
+                    },
+                    "requestBodies": {
+                        "Pet": {
+                            "required": true,
+                            "content": {
+                                "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } },
+                                "text/plain": {}
+                            }
+                        }
+                    },
+                    "responses": { "Ok": { "$ref": "#/components/responses/Done" }, "Done": { "description": "Done" } }
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let operations = api.flat_operations().unwrap();
+        assert_eq!(operations.len(), 1);
+        let put = &operations[0];
+        assert_eq!((put.path, put.method), ("/pets/{id}", "put"));
+
+        let descriptions = put
+            .parameters
+            .iter()
+            .map(|p| p.parameter_data_ref().description.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(descriptions, [Some("new"), None]);
+
+        assert!(put.request_body_required);
+        assert_eq!(
+            put.request_body["application/json"],
+            api.components.as_ref().unwrap().schemas["Pet"].as_item()
+        );
+        assert_eq!(put.request_body["text/plain"], None);
+        assert_eq!(
+            put.responses[&StatusCode::Range(2)].description,
+            "Done".to_string()
+        );
+        assert_eq!(put.default_response.unwrap().description, "Error");
+
+        let mut broken = api.clone();
+        broken.components = None;
+        assert_eq!(
+            broken.flat_operations(),
+            Err(ResolveError::Missing {
+                reference: "#/components/parameters/Id".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_effective_parameters() {
+        let components: Components = serde_json::from_value(serde_json::json!({
+            "parameters": { "Limit": { "name": "limit", "in": "query", "description": "ref", "schema": {} } }
+        }))
+        .unwrap();
+        let item: PathItem = serde_json::from_value(serde_json::json!({
+            "parameters": [
+                { "name": "id", "in": "path", "required": true, "description": "1", "schema": {} },
+                { "name": "id", "in": "path", "required": true, "description": "2", "schema": {} },
+                { "name": "id", "in": "header", "description": "3", "schema": {} }
+            ],
+            "get": {
+                "parameters": [
+                    { "name": "limit", "in": "query", "description": "4", "schema": {} },
+                    { "name": "id", "in": "path", "required": true, "description": "5", "schema": {} },
+                    { "$ref": "#/components/parameters/Limit" }
+                ],
+                "responses": {}
+            }
+        }))
+        .unwrap();
+        let descriptions = |parameters: &[&Parameter]| {
+            parameters
+                .iter()
+                .map(|p| p.parameter_data_ref().description.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let get = item.get.as_ref().unwrap();
+        let effective = item.effective_parameters(get, Some(&components)).unwrap();
+        assert_eq!(descriptions(&effective.parameters), ["5", "3", "ref"]);
+        assert_eq!(descriptions(&effective.overridden), ["2"]);
+        assert_eq!(descriptions(&effective.duplicates), ["2", "ref"]);
+
+        assert_eq!(
+            item.effective_parameters(get, None),
+            Err(ResolveError::Missing {
+                reference: "#/components/parameters/Limit".to_string()
+            })
+        );
+    }
+}
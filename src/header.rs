@@ -36,5 +36,5 @@ pub struct Header {
     pub examples: IndexMap<String, ReferenceOr<Example>>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
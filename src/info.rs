@@ -1,5 +1,4 @@
 use crate::*;
-use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// The object provides metadata about the API.
@@ -28,5 +27,5 @@ pub struct Info {
     pub version: String,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
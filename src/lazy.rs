@@ -0,0 +1,149 @@
+use std::sync::OnceLock;
+
+use crate::*;
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+/// A value kept as raw JSON until it is first accessed.
+#[derive(Debug)]
+pub struct Lazy<T> {
+    raw: Box<RawValue>,
+    parsed: OnceLock<T>,
+}
+
+impl<T: DeserializeOwned> Lazy<T> {
+    pub fn new(raw: Box<RawValue>) -> Self {
+        Lazy {
+            raw,
+            parsed: OnceLock::new(),
+        }
+    }
+
+    /// The JSON the value is parsed from.
+    pub fn raw(&self) -> &str {
+        self.raw.get()
+    }
+
+    /// Parses the value on the first call, and returns the parsed value on
+    /// later ones. Values that fail to parse are parsed again on every call.
+    pub fn get(&self) -> Result<&T, serde_json::Error> {
+        if let Some(parsed) = self.parsed.get() {
+            return Ok(parsed);
+        }
+        let parsed = serde_json::from_str(self.raw.get())?;
+        Ok(self.parsed.get_or_init(|| parsed))
+    }
+
+    pub fn into_inner(self) -> Result<T, serde_json::Error> {
+        match self.parsed.into_inner() {
+            Some(parsed) => Ok(parsed),
+            None => serde_json::from_str(self.raw.get()),
+        }
+    }
+}
+
+/// A document whose component schemas and examples are only parsed when
+/// they are accessed, for tools that need a small part of a large document.
+///
+/// ```
+/// # use openapiv3::*;
+/// let api: LazyOpenAPI = r#"{
+///     "openapi": "3.0.3",
+///     "info": { "title": "Pets", "version": "1" },
+///     "paths": {},
+///     "components": { "schemas": { "Pet": { "type": "object" }, "Broken": { "type": 1 } } }
+/// }"#.parse().unwrap();
+/// assert!(api.document.components.as_ref().unwrap().schemas.is_empty());
+/// assert!(api.schemas["Pet"].get().unwrap().as_item().is_some());
+/// assert!(api.schemas["Broken"].get().is_err());
+/// ```
+#[derive(Debug)]
+pub struct LazyOpenAPI {
+    /// The document, without its component schemas and examples.
+    pub document: OpenAPI,
+    pub schemas: IndexMap<String, Lazy<ReferenceOr<Schema>>>,
+    pub examples: IndexMap<String, Lazy<ReferenceOr<Example>>>,
+}
+
+impl std::str::FromStr for LazyOpenAPI {
+    type Err = serde_json::Error;
+
+    /// Parses a JSON document, leaving its component schemas and examples
+    /// unparsed. Errors in them are only reported when they are accessed.
+    fn from_str(input: &str) -> Result<Self, serde_json::Error> {
+        let mut fields: IndexMap<String, Box<RawValue>> = serde_json::from_str(input)?;
+        let mut schemas = IndexMap::new();
+        let mut examples = IndexMap::new();
+        if let Some(components) = fields.get_mut("components") {
+            let mut sections: IndexMap<String, Box<RawValue>> =
+                serde_json::from_str(components.get())?;
+            if let Some(raw) = sections.shift_remove("schemas") {
+                schemas = entries(&raw)?;
+            }
+            if let Some(raw) = sections.shift_remove("examples") {
+                examples = entries(&raw)?;
+            }
+            *components = serde_json::value::to_raw_value(&sections)?;
+        }
+        let document = serde_json::from_str(&serde_json::to_string(&fields)?)?;
+        Ok(LazyOpenAPI {
+            document,
+            schemas,
+            examples,
+        })
+    }
+}
+
+impl LazyOpenAPI {
+    /// Parses the remaining schemas and examples, returning the whole
+    /// document.
+    pub fn into_openapi(self) -> Result<OpenAPI, serde_json::Error> {
+        let mut document = self.document;
+        if self.schemas.is_empty() && self.examples.is_empty() {
+            return Ok(document);
+        }
+        let components = document.components.get_or_insert_with(Default::default);
+        for (name, schema) in self.schemas {
+            components.schemas.insert(name, schema.into_inner()?);
+        }
+        for (name, example) in self.examples {
+            components.examples.insert(name, example.into_inner()?);
+        }
+        Ok(document)
+    }
+}
+
+fn entries<T: DeserializeOwned>(
+    raw: &RawValue,
+) -> Result<IndexMap<String, Lazy<T>>, serde_json::Error> {
+    let entries: IndexMap<String, Box<RawValue>> = serde_json::from_str(raw.get())?;
+    Ok(entries
+        .into_iter()
+        .map(|(name, raw)| (name, Lazy::new(raw)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy() {
+        let input = std::fs::read_to_string("fixtures/polygon.json").unwrap();
+        let expected: OpenAPI = serde_json::from_str(&input).unwrap();
+
+        let api: LazyOpenAPI = input.parse().unwrap();
+        assert_eq!(api.document.paths, expected.paths);
+        let components = expected.components.as_ref().unwrap();
+        assert_eq!(
+            api.schemas.keys().collect::<Vec<_>>(),
+            components.schemas.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            api.schemas["Company"].get().unwrap(),
+            &components.schemas["Company"]
+        );
+        assert_eq!(api.into_openapi().unwrap(), expected);
+    }
+}
@@ -1,57 +1,151 @@
+mod audience;
+mod builder;
+mod bundle;
+#[cfg(feature = "cache")]
+mod cache;
 mod callback;
+mod changelog;
+mod collision;
 mod components;
 mod contact;
+#[cfg(feature = "hashing")]
+mod content_hash;
+mod de;
+mod dereference;
+mod describe;
+mod diff;
 mod discriminator;
 mod encoding;
 mod example;
+mod extensions;
 mod external_documentation;
+mod extract;
+mod flat_operation;
 mod header;
 mod info;
+#[cfg(feature = "lazy")]
+mod lazy;
 mod license;
 mod link;
+mod loader;
+mod lossy;
 mod media_type;
+mod media_type_key;
+mod merge;
+mod mock;
+mod naming;
+mod nullable;
 mod openapi;
 mod operation;
+mod operation_filter;
+mod operation_id;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod parameter;
+mod parameter_codec;
+mod patch;
+mod path_template;
 mod paths;
 mod reference;
 mod request_body;
+mod request_validation;
+mod resolve;
+mod response_validation;
 mod responses;
+mod runtime_expression;
 mod schema;
+mod schema_graph;
+mod schema_view;
 mod security_requirement;
 mod security_scheme;
+mod semantic_eq;
 mod server;
 mod server_variable;
+mod source_map;
 mod status_code;
+mod strict;
+mod strip;
 mod tag;
+#[cfg(feature = "test_util")]
+pub mod test_util;
+mod transform;
+mod unknown_fields;
 mod util;
+mod validation;
+mod value_validation;
 mod variant_or;
+mod walk;
+mod walker;
+#[cfg(feature = "yaml")]
+mod yaml;
 
+pub use self::audience::*;
+pub use self::builder::*;
+pub use self::bundle::*;
+#[cfg(feature = "cache")]
+pub use self::cache::*;
 pub use self::callback::*;
+pub use self::changelog::*;
+pub use self::collision::*;
 pub use self::components::*;
 pub use self::contact::*;
+#[cfg(feature = "hashing")]
+pub use self::content_hash::*;
+pub use self::de::*;
+pub use self::dereference::*;
+pub use self::describe::*;
+pub use self::diff::*;
 pub use self::discriminator::*;
 pub use self::encoding::*;
 pub use self::example::*;
+pub use self::extensions::*;
 pub use self::external_documentation::*;
+pub use self::flat_operation::*;
 pub use self::header::*;
 pub use self::info::*;
+#[cfg(feature = "lazy")]
+pub use self::lazy::*;
 pub use self::license::*;
 pub use self::link::*;
+pub use self::loader::*;
+pub use self::lossy::*;
 pub use self::media_type::*;
+pub use self::media_type_key::*;
+pub use self::merge::*;
+pub use self::mock::*;
+pub use self::naming::*;
+pub use self::nullable::*;
 pub use self::openapi::*;
 pub use self::operation::*;
+pub use self::operation_filter::*;
+pub use self::operation_id::*;
 pub use self::parameter::*;
+pub use self::parameter_codec::*;
+pub use self::patch::*;
+pub use self::path_template::*;
 pub use self::paths::*;
 pub use self::reference::*;
 pub use self::request_body::*;
+pub use self::request_validation::*;
+pub use self::resolve::*;
 pub use self::responses::*;
+pub use self::runtime_expression::*;
 pub use self::schema::*;
 pub use self::security_requirement::*;
 pub use self::security_scheme::*;
+pub use self::semantic_eq::*;
 pub use self::server::*;
 pub use self::server_variable::*;
+pub use self::source_map::*;
 pub use self::status_code::*;
+pub use self::strict::*;
+pub use self::strip::*;
 pub use self::tag::*;
+pub use self::transform::*;
+pub use self::unknown_fields::*;
 pub use self::util::*;
+pub use self::validation::*;
 pub use self::variant_or::*;
+pub use self::walker::*;
+#[cfg(feature = "yaml")]
+pub use self::yaml::*;
@@ -1,4 +1,4 @@
-use indexmap::IndexMap;
+use crate::Extensions;
 use serde::{Deserialize, Serialize};
 
 /// License information for the exposed API.
@@ -11,5 +11,5 @@ pub struct License {
     pub url: Option<String>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
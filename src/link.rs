@@ -41,7 +41,7 @@ pub struct Link {
     pub server: Option<Server>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
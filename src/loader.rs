@@ -0,0 +1,756 @@
+use std::fmt;
+
+use crate::bundle::section_names;
+use crate::util::{escape_pointer_token, unescape_pointer_token};
+use crate::*;
+use indexmap::{IndexMap, IndexSet};
+use serde_json::Value;
+
+/// The error returned when external references cannot be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// The document could not be read.
+    Io { uri: String, message: String },
+    /// The document could not be parsed.
+    Parse { uri: String, message: String },
+    /// The fragment of the reference does not point at anything in the
+    /// loaded document.
+    Pointer { reference: String },
+    /// Following references leads back to a value that is being inlined.
+    Cycle { reference: String },
+    /// Two documents define different components under the same name.
+    Conflict { kind: String, name: String },
+    /// The resolved document is not a valid OpenAPI document.
+    Invalid { message: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { uri, message } => write!(f, "failed to read {}: {}", uri, message),
+            LoadError::Parse { uri, message } => {
+                write!(f, "failed to parse {}: {}", uri, message)
+            }
+            LoadError::Pointer { reference } => write!(f, "`{}` does not exist", reference),
+            LoadError::Cycle { reference } => {
+                write!(f, "`{}` is part of a reference cycle", reference)
+            }
+            LoadError::Conflict { kind, name } => write!(
+                f,
+                "{} `{}` is defined differently in several documents",
+                kind, name
+            ),
+            LoadError::Invalid { message } => {
+                write!(f, "the resolved document is invalid: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Loads the documents that external references point to.
+pub trait RefLoader {
+    /// Loads the document at `uri`, which is the location of the referencing
+    /// document joined with the document part of the reference.
+    fn load(&mut self, uri: &str) -> Result<Value, LoadError>;
+}
+
+/// A [RefLoader] reading documents from the file system, treating URIs as
+/// paths.
+///
+/// Files ending in `.yaml` or `.yml` are parsed as YAML when the `yaml`
+/// feature is enabled, and everything else as JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileLoader;
+
+impl RefLoader for FileLoader {
+    fn load(&mut self, uri: &str) -> Result<Value, LoadError> {
+        let contents = std::fs::read_to_string(uri).map_err(|err| LoadError::Io {
+            uri: uri.to_owned(),
+            message: err.to_string(),
+        })?;
+        let parse_error = |message: String| LoadError::Parse {
+            uri: uri.to_owned(),
+            message,
+        };
+        #[cfg(feature = "yaml")]
+        if uri.ends_with(".yaml") || uri.ends_with(".yml") {
+            return from_yaml_str(&contents).map_err(|err| parse_error(err.to_string()));
+        }
+        serde_json::from_str(&contents).map_err(|err| parse_error(err.to_string()))
+    }
+}
+
+/// Follows references into other documents, e.g.
+/// `./common.yaml#/components/schemas/Error`, producing a single document.
+///
+/// Referenced components of other documents are copied into the
+/// [Components] of the resolved document under their original names and
+/// referenced locally. Anything else that is referenced, such as a whole
+/// file, is inlined where it is referenced. References within the loaded
+/// documents are followed in turn, relative to those documents.
+///
+/// ```
+/// # use openapiv3::*;
+/// # use serde_json::{json, Value};
+/// struct Files;
+/// impl RefLoader for Files {
+///     fn load(&mut self, uri: &str) -> Result<Value, LoadError> {
+///         assert_eq!(uri, "specs/common.json");
+///         Ok(json!({ "components": { "schemas": { "Error": { "type": "string" } } } }))
+///     }
+/// }
+///
+/// let api: OpenAPI = serde_json::from_value(json!({
+///     "openapi": "3.0.3",
+///     "info": { "title": "Pets", "version": "1" },
+///     "paths": { "/pets": { "get": { "responses": { "default": {
+///         "description": "Error",
+///         "content": { "application/json": {
+///             "schema": { "$ref": "./common.json#/components/schemas/Error" }
+///         } }
+///     } } } } }
+/// })).unwrap();
+///
+/// let resolved = ExternalRefResolver::new(Files).resolve(api, "specs/openapi.json").unwrap();
+/// assert!(resolved.components.unwrap().schemas.contains_key("Error"));
+/// ```
+#[derive(Debug)]
+pub struct ExternalRefResolver<L> {
+    loader: L,
+    documents: IndexMap<String, Value>,
+}
+
+impl<L: RefLoader> ExternalRefResolver<L> {
+    pub fn new(loader: L) -> Self {
+        Self {
+            loader,
+            documents: IndexMap::new(),
+        }
+    }
+
+    /// Resolves the external references of `document`, which was loaded
+    /// from `uri`.
+    ///
+    /// Loaded documents are cached, so the resolver can be reused for
+    /// several documents sharing the same files.
+    pub fn resolve(&mut self, document: OpenAPI, uri: &str) -> Result<OpenAPI, LoadError> {
+        self.run(document, uri, false)
+    }
+
+    /// Like [ExternalRefResolver::resolve], but also copies referenced values
+    /// that are not components of their document into [Components] instead
+    /// of inlining them, so that every external reference becomes a local
+    /// one.
+    ///
+    /// The section such a value goes to is inferred from where it is
+    /// referenced, e.g. a reference in a list of parameters is bundled as a
+    /// parameter, and its name is the last token of the pointer or, for
+    /// whole documents, the file name without its extension. A number is
+    /// appended to names that are already taken. References in places that
+    /// do not correspond to a section are inlined.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// # use serde_json::{json, Value};
+    /// struct Files;
+    /// impl RefLoader for Files {
+    ///     fn load(&mut self, uri: &str) -> Result<Value, LoadError> {
+    ///         assert_eq!(uri, "parameters/limit.json");
+    ///         Ok(json!({ "name": "limit", "in": "query", "schema": { "type": "integer" } }))
+    ///     }
+    /// }
+    ///
+    /// let api: OpenAPI = serde_json::from_value(json!({
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "get": {
+    ///         "parameters": [ { "$ref": "parameters/limit.json" } ],
+    ///         "responses": {}
+    ///     } } }
+    /// })).unwrap();
+    ///
+    /// let bundled = ExternalRefResolver::new(Files).bundle(api, "openapi.json").unwrap();
+    /// let operation = bundled.paths.paths["/pets"].as_item().unwrap().get.as_ref().unwrap();
+    /// assert_eq!(operation.parameters[0], ReferenceOr::ref_("#/components/parameters/limit"));
+    /// assert!(bundled.components.unwrap().parameters.contains_key("limit"));
+    /// ```
+    pub fn bundle(&mut self, document: OpenAPI, uri: &str) -> Result<OpenAPI, LoadError> {
+        self.run(document, uri, true)
+    }
+
+    fn run(&mut self, document: OpenAPI, uri: &str, bundle: bool) -> Result<OpenAPI, LoadError> {
+        let taken = document
+            .components
+            .iter()
+            .flat_map(|components| {
+                ComponentKind::ALL.into_iter().flat_map(move |kind| {
+                    section_names(components, kind)
+                        .into_iter()
+                        .map(move |name| (kind.as_str().to_owned(), name.clone()))
+                })
+            })
+            .collect();
+        let mut value = serde_json::to_value(document).map_err(|err| LoadError::Invalid {
+            message: err.to_string(),
+        })?;
+        let mut run = Run {
+            resolver: self,
+            root: uri.to_owned(),
+            imported: IndexSet::new(),
+            components: IndexMap::new(),
+            inlining: Vec::new(),
+            bundle,
+            bundled: IndexMap::new(),
+            taken,
+        };
+        run.process(&mut value, uri, &mut Vec::new())?;
+
+        let Run {
+            root, components, ..
+        } = run;
+        for ((kind, name), component) in components {
+            let sections = value
+                .as_object_mut()
+                .ok_or_else(|| LoadError::Invalid {
+                    message: format!("{} is not an object", root),
+                })?
+                .entry("components")
+                .or_insert_with(|| Value::Object(Default::default()));
+            let section = sections
+                .as_object_mut()
+                .and_then(|sections| {
+                    sections
+                        .entry(kind.clone())
+                        .or_insert_with(|| Value::Object(Default::default()))
+                        .as_object_mut()
+                })
+                .ok_or_else(|| LoadError::Invalid {
+                    message: format!("components/{} is not an object", kind),
+                })?;
+            match section.get(&name) {
+                Some(existing) if *existing != component => {
+                    return Err(LoadError::Conflict { kind, name })
+                }
+                Some(_) => {}
+                None => {
+                    section.insert(name, component);
+                }
+            }
+        }
+
+        serde_json::from_value(value).map_err(|err| LoadError::Invalid {
+            message: err.to_string(),
+        })
+    }
+
+    /// Returns the value that `fragment` points to in the document at `uri`.
+    fn target(&mut self, uri: &str, fragment: &str) -> Result<Value, LoadError> {
+        if !self.documents.contains_key(uri) {
+            let document = self.loader.load(uri)?;
+            self.documents.insert(uri.to_owned(), document);
+        }
+        let pointer = crate::util::percent_decode(fragment);
+        self.documents[uri]
+            .pointer(&pointer)
+            .cloned()
+            .ok_or_else(|| LoadError::Pointer {
+                reference: format!("{}#{}", uri, fragment),
+            })
+    }
+}
+
+/// The state of a single [ExternalRefResolver::resolve] call.
+struct Run<'r, L> {
+    resolver: &'r mut ExternalRefResolver<L>,
+    root: String,
+    /// The `(uri, kind, name)` of every component imported so far.
+    imported: IndexSet<(String, String, String)>,
+    /// The imported components by `(kind, name)`.
+    components: IndexMap<(String, String), Value>,
+    /// The references currently being inlined.
+    inlining: Vec<String>,
+    /// Whether values that are not components are bundled rather than
+    /// inlined.
+    bundle: bool,
+    /// The `(kind, name)` each bundled reference was given.
+    bundled: IndexMap<String, (String, String)>,
+    /// The `(kind, name)` of the components of the root document.
+    taken: IndexSet<(String, String)>,
+}
+
+impl<L: RefLoader> Run<'_, L> {
+    /// Processes `value`, which is found at `pointer` in the resolved
+    /// document.
+    fn process(
+        &mut self,
+        value: &mut Value,
+        base: &str,
+        pointer: &mut Vec<String>,
+    ) -> Result<(), LoadError> {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(reference)) = object.get("$ref") {
+                    let reference = reference.clone();
+                    return self.reference(value, &reference, base, pointer);
+                }
+                for (key, child) in object.iter_mut() {
+                    pointer.push(key.clone());
+                    self.process(child, base, pointer)?;
+                    pointer.pop();
+                }
+            }
+            Value::Array(array) => {
+                for (i, child) in array.iter_mut().enumerate() {
+                    pointer.push(i.to_string());
+                    self.process(child, base, pointer)?;
+                    pointer.pop();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn reference(
+        &mut self,
+        value: &mut Value,
+        reference: &str,
+        base: &str,
+        pointer: &mut Vec<String>,
+    ) -> Result<(), LoadError> {
+        let (document, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let uri = if document.is_empty() {
+            base.to_owned()
+        } else {
+            join_uri(base, document)
+        };
+        let full = format!("{}#{}", uri, fragment);
+
+        if uri == self.root {
+            value["$ref"] = Value::String(format!("#{}", fragment));
+            return Ok(());
+        }
+
+        let mut tokens = fragment.split('/');
+        if let (Some(""), Some("components"), Some(kind), Some(name), None) = (
+            tokens.next(),
+            tokens.next(),
+            tokens.next(),
+            tokens.next(),
+            tokens.next(),
+        ) {
+            value["$ref"] = Value::String(format!("#/components/{}/{}", kind, name));
+            let (kind, name) = (kind.to_owned(), unescape_pointer_token(name));
+            if self
+                .imported
+                .insert((uri.clone(), kind.clone(), name.clone()))
+            {
+                let mut component = self.resolver.target(&uri, fragment)?;
+                let mut pointer = vec!["components".to_owned(), kind.clone(), name.clone()];
+                self.process(&mut component, &uri, &mut pointer)?;
+                match self.components.get(&(kind.clone(), name.clone())) {
+                    Some(existing) if *existing != component => {
+                        return Err(LoadError::Conflict { kind, name })
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.components.insert((kind, name), component);
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(kind) = self.bundle.then(|| section_at(pointer)).flatten() {
+            let (kind, name) = match self.bundled.get(&full) {
+                Some(bundled) => bundled.clone(),
+                None => {
+                    let name = self.unique_name(kind, bundled_name(document, fragment));
+                    let key = (kind.as_str().to_owned(), name);
+                    self.bundled.insert(full, key.clone());
+                    let mut target = self.resolver.target(&uri, fragment)?;
+                    let mut pointer = vec!["components".to_owned(), key.0.clone(), key.1.clone()];
+                    self.process(&mut target, &uri, &mut pointer)?;
+                    self.components.insert(key.clone(), target);
+                    key
+                }
+            };
+            *value = serde_json::json!({
+                "$ref": format!("#/components/{}/{}", kind, escape_pointer_token(&name))
+            });
+            return Ok(());
+        }
+
+        if self.inlining.contains(&full) {
+            return Err(LoadError::Cycle { reference: full });
+        }
+        let mut target = self.resolver.target(&uri, fragment)?;
+        self.inlining.push(full);
+        self.process(&mut target, &uri, pointer)?;
+        self.inlining.pop();
+        *value = target;
+        Ok(())
+    }
+}
+
+impl<L> Run<'_, L> {
+    /// Returns `name`, or `name` followed by the smallest number from 2 that
+    /// makes it unused in the `kind` section.
+    fn unique_name(&self, kind: ComponentKind, name: String) -> String {
+        let kind = kind.as_str().to_owned();
+        let free = |name: &String| {
+            let key = (kind.clone(), name.clone());
+            !self.taken.contains(&key) && !self.components.contains_key(&key)
+        };
+        if free(&name) {
+            return name;
+        }
+        (2..).map(|i| format!("{}{}", name, i)).find(free).unwrap()
+    }
+}
+
+/// Infers the section of [Components] that a value at `pointer` belongs to.
+fn section_at(pointer: &[String]) -> Option<ComponentKind> {
+    let last = pointer.last()?.as_str();
+    let parent = pointer.len().checked_sub(2).map(|i| pointer[i].as_str());
+    if matches!(last, "schema" | "items" | "additionalProperties" | "not") {
+        return Some(ComponentKind::Schemas);
+    }
+    match parent? {
+        "properties" | "allOf" | "oneOf" | "anyOf" | "schemas" => Some(ComponentKind::Schemas),
+        "parameters" => Some(ComponentKind::Parameters),
+        "responses" => Some(ComponentKind::Responses),
+        "requestBodies" => Some(ComponentKind::RequestBodies),
+        "headers" => Some(ComponentKind::Headers),
+        "examples" => Some(ComponentKind::Examples),
+        "links" => Some(ComponentKind::Links),
+        "callbacks" => Some(ComponentKind::Callbacks),
+        "securitySchemes" => Some(ComponentKind::SecuritySchemes),
+        _ if last == "requestBody" => Some(ComponentKind::RequestBodies),
+        _ => None,
+    }
+}
+
+/// Names a bundled value after the last token of `fragment`, or after the
+/// file `document` if there is none.
+fn bundled_name(document: &str, fragment: &str) -> String {
+    match fragment
+        .rsplit('/')
+        .next()
+        .filter(|token| !token.is_empty())
+    {
+        Some(token) => unescape_pointer_token(&crate::util::percent_decode(token)),
+        None => {
+            let file = document.rsplit('/').next().unwrap_or(document);
+            let stem = file.split_once('.').map_or(file, |(stem, _)| stem);
+            match stem.is_empty() {
+                true => "bundled".to_owned(),
+                false => stem.to_owned(),
+            }
+        }
+    }
+}
+
+/// Resolves `reference` relative to the document at `base`.
+fn join_uri(base: &str, reference: &str) -> String {
+    if reference.contains("://") || reference.starts_with('/') {
+        return reference.to_owned();
+    }
+    let directory = match base.rfind('/') {
+        Some(i) => &base[..=i],
+        None => "",
+    };
+    let (prefix, path) = match directory.find("://") {
+        Some(i) => match directory[i + 3..].find('/') {
+            Some(j) => directory.split_at(i + 3 + j),
+            None => (directory, ""),
+        },
+        None => ("", directory),
+    };
+
+    let mut segments: Vec<&str> = path.split('/').collect();
+    segments.pop();
+    for segment in reference.split('/') {
+        match segment {
+            "." => {}
+            ".." if segments.last().is_some_and(|s| !s.is_empty() && *s != "..") => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    format!("{}{}", prefix, segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Memory(IndexMap<&'static str, Value>, Vec<String>);
+
+    impl RefLoader for Memory {
+        fn load(&mut self, uri: &str) -> Result<Value, LoadError> {
+            self.1.push(uri.to_owned());
+            self.0.get(uri).cloned().ok_or_else(|| LoadError::Io {
+                uri: uri.to_owned(),
+                message: "not found".to_owned(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_join_uri() {
+        assert_eq!(join_uri("openapi.yaml", "./a.yaml"), "a.yaml");
+        assert_eq!(join_uri("specs/openapi.yaml", "a.yaml"), "specs/a.yaml");
+        assert_eq!(
+            join_uri("specs/v1/openapi.yaml", "../a.yaml"),
+            "specs/a.yaml"
+        );
+        assert_eq!(join_uri("../openapi.yaml", "../a.yaml"), "../../a.yaml");
+        assert_eq!(join_uri("/srv/openapi.yaml", "./a.yaml"), "/srv/a.yaml");
+        assert_eq!(
+            join_uri("https://example.com/specs/openapi.yaml", "../a.yaml"),
+            "https://example.com/a.yaml"
+        );
+        assert_eq!(join_uri("specs/openapi.yaml", "/a.yaml"), "/a.yaml");
+    }
+
+    #[test]
+    fn test_resolve() {
+        let api: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [ { "$ref": "parameters/limit.json" } ],
+                        "responses": {
+                            "200": { "$ref": "common/responses.json#/components/responses/Pets" }
+                        }
+                    }
+                }
+            },
+            "components": { "schemas": { "Id": { "type": "string" } } }
+        }))
+        .unwrap();
+
+        let files = IndexMap::from([
+            (
+                "specs/parameters/limit.json",
+                json!({ "name": "limit", "in": "query", "schema": { "$ref": "../common/schemas.json#/components/schemas/Limit" } }),
+            ),
+            (
+                "specs/common/responses.json",
+                json!({ "components": { "responses": { "Pets": {
+                    "description": "Pets",
+                    "content": { "application/json": { "schema": {
+                        "type": "array",
+                        "items": { "$ref": "schemas.json#/components/schemas/Pet" }
+                    } } }
+                } } } }),
+            ),
+            (
+                "specs/common/schemas.json",
+                json!({ "components": { "schemas": {
+                    "Limit": { "type": "integer" },
+                    "Pet": { "type": "object", "properties": {
+                        "id": { "$ref": "../openapi.json#/components/schemas/Id" },
+                        "parent": { "$ref": "#/components/schemas/Pet" }
+                    } }
+                } } }),
+            ),
+        ]);
+
+        let mut resolver = ExternalRefResolver::new(Memory(files, Vec::new()));
+        let resolved = resolver.resolve(api.clone(), "specs/openapi.json").unwrap();
+        let value = serde_json::to_value(&resolved).unwrap();
+        assert_eq!(
+            value["paths"]["/pets"]["get"]["parameters"][0]["schema"],
+            json!({ "$ref": "#/components/schemas/Limit" })
+        );
+        assert_eq!(
+            value["paths"]["/pets"]["get"]["responses"]["200"],
+            json!({ "$ref": "#/components/responses/Pets" })
+        );
+        let schemas = &value["components"]["schemas"];
+        assert_eq!(
+            schemas.as_object().unwrap().keys().collect::<Vec<_>>(),
+            ["Id", "Limit", "Pet"]
+        );
+        assert_eq!(
+            schemas["Pet"]["properties"],
+            json!({
+                "id": { "$ref": "#/components/schemas/Id" },
+                "parent": { "$ref": "#/components/schemas/Pet" }
+            })
+        );
+
+        // Documents are only loaded once.
+        resolver.resolve(api, "specs/openapi.json").unwrap();
+        assert_eq!(resolver.loader.1.len(), 3);
+    }
+
+    #[test]
+    fn test_bundle() {
+        let api: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [ { "$ref": "parameters/limit.json" } ],
+                        "responses": {
+                            "200": { "$ref": "responses.json#/Pets" },
+                            "default": { "$ref": "responses.json#/Pets" }
+                        }
+                    }
+                }
+            },
+            "components": { "schemas": { "Pet": { "type": "string" } } }
+        }))
+        .unwrap();
+        let files = IndexMap::from([
+            (
+                "parameters/limit.json",
+                json!({ "name": "limit", "in": "query", "schema": { "$ref": "../schemas.json#/Pet" } }),
+            ),
+            (
+                "responses.json",
+                json!({ "Pets": {
+                    "description": "Pets",
+                    "content": { "application/json": { "schema": {
+                        "type": "array",
+                        "items": { "$ref": "schemas.json#/Pet" }
+                    } } }
+                } }),
+            ),
+            (
+                "schemas.json",
+                json!({ "Pet": { "type": "object", "properties": {
+                    "children": { "type": "array", "items": { "$ref": "#/Pet" } }
+                } } }),
+            ),
+        ]);
+
+        let mut resolver = ExternalRefResolver::new(Memory(files, Vec::new()));
+        let bundled = resolver.bundle(api, "openapi.json").unwrap();
+        let value = serde_json::to_value(&bundled).unwrap();
+        let operation = &value["paths"]["/pets"]["get"];
+        assert_eq!(
+            operation["parameters"][0],
+            json!({ "$ref": "#/components/parameters/limit" })
+        );
+        assert_eq!(
+            operation["responses"],
+            json!({
+                "200": { "$ref": "#/components/responses/Pets" },
+                "default": { "$ref": "#/components/responses/Pets" }
+            })
+        );
+
+        let components = bundled.components.unwrap();
+        assert_eq!(
+            components.schemas.keys().collect::<Vec<_>>(),
+            ["Pet", "Pet2"]
+        );
+        let value = serde_json::to_value(&components.schemas["Pet2"]).unwrap();
+        assert_eq!(
+            value["properties"]["children"]["items"],
+            json!({ "$ref": "#/components/schemas/Pet2" })
+        );
+        assert_eq!(
+            serde_json::to_value(&components.parameters["limit"]).unwrap()["schema"],
+            json!({ "$ref": "#/components/schemas/Pet2" })
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let api = |reference: &str| -> OpenAPI {
+            serde_json::from_value(json!({
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {},
+                "components": { "schemas": {
+                    "Pet": { "type": "string" },
+                    "A": { "$ref": reference }
+                } }
+            }))
+            .unwrap()
+        };
+        let files = IndexMap::from([
+            ("loop.json", json!({ "$ref": "loop.json" })),
+            (
+                "pets.json",
+                json!({ "components": { "schemas": { "Pet": { "type": "object" } } } }),
+            ),
+        ]);
+        let mut resolver = ExternalRefResolver::new(Memory(files, Vec::new()));
+
+        for (reference, error) in [
+            (
+                "loop.json",
+                LoadError::Cycle {
+                    reference: "loop.json#".to_string(),
+                },
+            ),
+            (
+                "pets.json#/components/schemas/Pet",
+                LoadError::Conflict {
+                    kind: "schemas".to_string(),
+                    name: "Pet".to_string(),
+                },
+            ),
+            (
+                "pets.json#/components/schemas/Cat",
+                LoadError::Pointer {
+                    reference: "pets.json#/components/schemas/Cat".to_string(),
+                },
+            ),
+            (
+                "missing.json",
+                LoadError::Io {
+                    uri: "missing.json".to_string(),
+                    message: "not found".to_string(),
+                },
+            ),
+        ] {
+            assert_eq!(resolver.resolve(api(reference), "openapi.json"), Err(error));
+        }
+    }
+
+    #[test]
+    fn test_file_loader() {
+        let directory =
+            std::env::temp_dir().join(format!("openapiv3-loader-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(
+            directory.join("common.json"),
+            r#"{ "components": { "schemas": { "Error": { "type": "string" } } } }"#,
+        )
+        .unwrap();
+
+        let api: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {},
+            "components": { "schemas": {
+                "Problem": { "$ref": "./common.json#/components/schemas/Error" }
+            } }
+        }))
+        .unwrap();
+        let root = directory.join("openapi.json");
+        let resolved = ExternalRefResolver::new(FileLoader).resolve(api, &root.to_string_lossy());
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let schemas = resolved.unwrap().components.unwrap().schemas;
+        assert_eq!(
+            schemas["Problem"],
+            ReferenceOr::ref_("#/components/schemas/Error")
+        );
+        assert!(schemas["Error"].as_item().is_some());
+    }
+}
@@ -0,0 +1,444 @@
+use std::fmt;
+
+use crate::util::{escape_pointer_token, METHODS};
+use crate::*;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A problem found while parsing a document, with [from_str] and the other
+/// parsing functions of the crate, or with [OpenAPI::from_slice_lossy].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    pointer: Option<String>,
+}
+
+/// The kinds of [Error].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input is not valid JSON or YAML.
+    Syntax,
+    /// The input is valid JSON or YAML, but not a valid document.
+    Data,
+    /// The document is for a version of OpenAPI other than 3.0.
+    Version,
+    /// A reference could not be resolved.
+    Reference,
+    /// The input could not be read.
+    Io,
+    /// A [JsonPatch] could not be applied.
+    Patch,
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The 1-based line of the input at which the error occurred, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The 1-based column of the input at which the error occurred, if
+    /// known.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    /// The JSON Pointer of the part of the document that was replaced by a
+    /// placeholder, for errors reported by [OpenAPI::from_slice_recovering],
+    /// or that could not be deserialized, for errors reported by
+    /// [from_str].
+    pub fn pointer(&self) -> Option<&str> {
+        self.pointer.as_deref()
+    }
+
+    /// An error without a location.
+    pub(crate) fn custom(kind: ErrorKind, message: String) -> Self {
+        Error {
+            kind,
+            message,
+            line: None,
+            column: None,
+            pointer: None,
+        }
+    }
+
+    pub(crate) fn with_pointer(mut self, pointer: &str) -> Self {
+        self.pointer = Some(pointer.to_owned());
+        self
+    }
+
+    /// Sets the line and column of an error that has only a pointer to those
+    /// of the value it points to in the JSON `input`, or of its closest
+    /// ancestor in the input.
+    pub(crate) fn locate_in(mut self, input: &str) -> Self {
+        if self.line.is_some() {
+            return self;
+        }
+        let Some(pointer) = &self.pointer else {
+            return self;
+        };
+        if let Ok(spans) = SourceMap::from_json(input) {
+            if let Some(span) = spans.closest(pointer) {
+                self.line = Some(span.line);
+                self.column = Some(span.column);
+            }
+        }
+        self
+    }
+
+    /// An error about the value at `pointer`, which is not a syntax error.
+    pub(crate) fn at(err: serde_json::Error, pointer: String) -> Self {
+        Error {
+            kind: ErrorKind::Data,
+            message: err.to_string(),
+            line: None,
+            column: None,
+            pointer: Some(pointer),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        // serde_json reports line 0 for errors without a position.
+        let (line, column) = match err.line() {
+            0 => (None, None),
+            line => (Some(line), Some(err.column())),
+        };
+        let kind = match err.classify() {
+            serde_json::error::Category::Io => ErrorKind::Io,
+            serde_json::error::Category::Syntax | serde_json::error::Category::Eof => {
+                ErrorKind::Syntax
+            }
+            serde_json::error::Category::Data => ErrorKind::Data,
+        };
+        Error {
+            kind,
+            message: err.to_string(),
+            line,
+            column,
+            pointer: None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::custom(ErrorKind::Io, err.to_string())
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl From<YamlError> for Error {
+    fn from(err: YamlError) -> Self {
+        Error {
+            kind: ErrorKind::Syntax,
+            message: err.message().to_owned(),
+            line: err.line(),
+            column: err.column(),
+            pointer: None,
+        }
+    }
+}
+
+impl From<ResolveError> for Error {
+    fn from(err: ResolveError) -> Self {
+        Error::custom(ErrorKind::Reference, err.to_string())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl OpenAPI {
+    /// Parses a JSON document, returning the problems found instead of
+    /// failing.
+    ///
+    /// This never panics, whatever the input: malformed JSON, invalid UTF-8,
+    /// deeply nested values and out-of-range numbers are all reported as
+    /// errors, which makes it suitable as a fuzz target. The document is
+    /// `None` if it could not be parsed at all.
+    ///
+    /// ```
+    /// # use openapiv3::OpenAPI;
+    /// let (document, errors) = OpenAPI::from_slice_lossy(br#"{"openapi": "3.0.3""#);
+    /// assert!(document.is_none());
+    /// assert_eq!(errors[0].line(), Some(1));
+    /// ```
+    pub fn from_slice_lossy(input: &[u8]) -> (Option<OpenAPI>, Vec<Error>) {
+        match serde_json::from_slice(input) {
+            Ok(document) => (Some(document), Vec::new()),
+            Err(err) => (None, vec![err.into()]),
+        }
+    }
+
+    /// Like [OpenAPI::from_slice_lossy], but salvages as much of the document
+    /// as possible instead of rejecting it as a whole.
+    ///
+    /// Schemas that cannot be parsed are replaced by an empty
+    /// [AnySchema], after first trying to replace only their invalid
+    /// subschemas, and operations that still cannot be parsed are replaced by
+    /// an empty [Operation]. Each replacement is reported as an [Error] with
+    /// its [Error::pointer]. Problems elsewhere, and invalid JSON, still
+    /// cause the document to be rejected.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let input = br#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "A", "version": "1" },
+    ///     "paths": {},
+    ///     "components": { "schemas": {
+    ///         "Pet": { "type": "object", "properties": { "age": { "minimum": "zero" } } }
+    ///     } }
+    /// }"#;
+    /// let (document, errors) = OpenAPI::from_slice_recovering(input);
+    /// assert_eq!(errors[0].pointer(), Some("/components/schemas/Pet/properties/age"));
+    /// let value = serde_json::to_value(document.unwrap()).unwrap();
+    /// assert_eq!(value["components"]["schemas"]["Pet"]["properties"]["age"], serde_json::json!({}));
+    /// ```
+    pub fn from_slice_recovering(input: &[u8]) -> (Option<OpenAPI>, Vec<Error>) {
+        let mut value: Value = match serde_json::from_slice(input) {
+            Ok(value) => value,
+            Err(err) => return (None, vec![err.into()]),
+        };
+        if let Ok(document) = OpenAPI::deserialize(&value) {
+            return (Some(document), Vec::new());
+        }
+
+        let mut errors = Vec::new();
+        if let Some(schemas) = value
+            .pointer_mut("/components/schemas")
+            .and_then(Value::as_object_mut)
+        {
+            for (name, schema) in schemas.iter_mut() {
+                let pointer = format!("/components/schemas/{}", escape_pointer_token(name));
+                repair_schema(schema, &pointer, &mut errors);
+            }
+        }
+        for section in ["paths", "components"] {
+            if let Some(child) = value.get_mut(section) {
+                repair_nested_schemas(child, &format!("/{}", section), &mut errors);
+            }
+        }
+        if let Some(paths) = value.get_mut("paths").and_then(Value::as_object_mut) {
+            for (path, item) in paths.iter_mut() {
+                let pointer = format!("/paths/{}", escape_pointer_token(path));
+                repair_operations(item, &pointer, &mut errors);
+            }
+        }
+
+        match OpenAPI::deserialize(&value) {
+            Ok(document) => (Some(document), errors),
+            Err(err) => {
+                errors.push(err.into());
+                (None, errors)
+            }
+        }
+    }
+}
+
+/// Replaces `value` by an empty schema if it does not parse, after trying to
+/// repair its subschemas.
+fn repair_schema(value: &mut Value, pointer: &str, errors: &mut Vec<Error>) {
+    let err = match ReferenceOr::<Schema>::deserialize(&*value) {
+        Ok(_) => return,
+        Err(err) => err,
+    };
+
+    if let Value::Object(object) = value {
+        for (key, child) in object.iter_mut() {
+            match (key.as_str(), child) {
+                ("properties", Value::Object(properties)) => {
+                    for (name, property) in properties.iter_mut() {
+                        let pointer =
+                            format!("{}/properties/{}", pointer, escape_pointer_token(name));
+                        repair_schema(property, &pointer, errors);
+                    }
+                }
+                ("oneOf" | "allOf" | "anyOf", Value::Array(variants)) => {
+                    for (i, variant) in variants.iter_mut().enumerate() {
+                        repair_schema(variant, &format!("{}/{}/{}", pointer, key, i), errors);
+                    }
+                }
+                ("additionalProperties" | "items" | "contains" | "not", child)
+                    if child.is_object() =>
+                {
+                    repair_schema(child, &format!("{}/{}", pointer, key), errors);
+                }
+                _ => {}
+            }
+        }
+        if ReferenceOr::<Schema>::deserialize(&*value).is_ok() {
+            return;
+        }
+    }
+
+    *value = Value::Object(Default::default());
+    errors.push(Error::at(err, pointer.to_owned()));
+}
+
+/// Repairs the `schema` of every parameter, header and media type below
+/// `value`, leaving examples and extensions alone.
+fn repair_nested_schemas(value: &mut Value, pointer: &str, errors: &mut Vec<Error>) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                let pointer = format!("{}/{}", pointer, escape_pointer_token(key));
+                match key.as_str() {
+                    "schema" if child.is_object() => repair_schema(child, &pointer, errors),
+                    "schemas" | "example" | "examples" | "default" | "enum" => {}
+                    key if key.starts_with("x-") => {}
+                    _ => repair_nested_schemas(child, &pointer, errors),
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (i, child) in array.iter_mut().enumerate() {
+                repair_nested_schemas(child, &format!("{}/{}", pointer, i), errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces the operations of a path item that do not parse by empty ones.
+fn repair_operations(item: &mut Value, pointer: &str, errors: &mut Vec<Error>) {
+    let item = match item.as_object_mut() {
+        Some(item) => item,
+        None => return,
+    };
+    for method in METHODS {
+        if let Some(operation) = item.get_mut(method) {
+            if let Err(err) = Operation::deserialize(&*operation) {
+                *operation = serde_json::json!({ "responses": {} });
+                errors.push(Error::at(err, format!("{}/{}", pointer, method)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_slice_lossy() {
+        let input = include_bytes!("../fixtures/xkcd.json");
+        let (document, errors) = OpenAPI::from_slice_lossy(input);
+        assert!(document.is_some());
+        assert!(errors.is_empty());
+
+        // Every truncation of a valid document is rejected without panicking.
+        for end in 0..input.len() - 1 {
+            let (document, errors) = OpenAPI::from_slice_lossy(&input[..end]);
+            assert!(document.is_none() && errors.len() == 1);
+        }
+
+        let nested = "[".repeat(10_000);
+        for input in [nested.as_bytes(), b"\xff\xfe", b"{\"openapi\": 1e400}"] {
+            let (document, errors) = OpenAPI::from_slice_lossy(input);
+            assert!(document.is_none() && errors.len() == 1);
+        }
+    }
+
+    #[test]
+    fn test_from_slice_recovering() {
+        let input = br##"{
+            "openapi": "3.0.3",
+            "info": { "title": "A", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            { "name": "n", "in": "query", "schema": { "type": "integer", "maximum": "ten" } }
+                        ],
+                        "responses": {}
+                    },
+                    "post": { "responses": [] }
+                }
+            },
+            "components": { "schemas": {
+                "Pet": { "type": "object", "properties": { "tags": { "type": "array", "items": { "enum": 1 } } } },
+                "Id": { "type": "string" }
+            } }
+        }"##;
+        assert!(OpenAPI::from_slice_lossy(input).0.is_none());
+
+        let (document, errors) = OpenAPI::from_slice_recovering(input);
+        let pointers = errors
+            .iter()
+            .map(|e| e.pointer().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pointers,
+            [
+                "/components/schemas/Pet/properties/tags/items",
+                "/paths/~1pets/get/parameters/0/schema",
+                "/paths/~1pets/post",
+            ]
+        );
+
+        let document = document.unwrap();
+        let (_, _, get) = document.operations().next().unwrap();
+        assert_eq!(
+            get.parameters[0]
+                .as_item()
+                .unwrap()
+                .parameter_data_ref()
+                .format,
+            ParameterSchemaOrContent::Schema(Box::new(ReferenceOr::Item(Schema {
+                schema_data: Default::default(),
+                schema_kind: SchemaKind::Any(Default::default()),
+            })))
+        );
+        let post = document.paths.paths["/pets"]
+            .as_item()
+            .unwrap()
+            .post
+            .as_ref();
+        assert_eq!(post, Some(&Operation::default()));
+    }
+
+    #[test]
+    fn test_weird_numbers() {
+        let schema = |schema: &str| {
+            let input = format!(
+                r#"{{
+                    "openapi": "3.0.3",
+                    "info": {{ "title": "A", "version": "1" }},
+                    "paths": {{}},
+                    "components": {{ "schemas": {{ "A": {} }} }}
+                }}"#,
+                schema
+            );
+            let (document, errors) = OpenAPI::from_slice_lossy(input.as_bytes());
+            assert!(errors.is_empty(), "{:?}", errors);
+            document.unwrap().components.unwrap().schemas["A"].clone()
+        };
+
+        for input in [
+            r#"{ "type": "integer", "minimum": 18446744073709551615 }"#,
+            r#"{ "type": "integer", "enum": [18446744073709551615, null] }"#,
+            r#"{ "type": "number", "maximum": -1.7e308, "multipleOf": 0 }"#,
+            r#"{ "minimum": 1e308, "maximum": -0.0 }"#,
+        ] {
+            schema(input);
+        }
+    }
+}
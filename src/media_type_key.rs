@@ -0,0 +1,441 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// A parsed media type or media type range, such as the keys of the
+/// `content` maps in [RequestBody], [Response] and [Parameter] objects.
+///
+/// The type, subtype, suffix and parameter names are compared
+/// case-insensitively, as is the value of the `charset` parameter. Parameter
+/// order is not significant.
+///
+/// ```
+/// # use openapiv3::MediaTypeKey;
+/// let key: MediaTypeKey = "application/vnd.api+json; charset=UTF-8".parse().unwrap();
+/// assert_eq!(key.typ, "application");
+/// assert_eq!(key.subtype, "vnd.api");
+/// assert_eq!(key.suffix.as_deref(), Some("json"));
+/// assert_eq!(key.parameters.get("charset").map(String::as_str), Some("utf-8"));
+/// assert_eq!(key.to_string(), "application/vnd.api+json; charset=utf-8");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MediaTypeKey {
+    /// The top-level type, e.g. `application`, or `*`.
+    pub typ: String,
+    /// The subtype without any structured syntax suffix, e.g. `vnd.api`, or
+    /// `*`.
+    pub subtype: String,
+    /// The structured syntax suffix, e.g. `json` for `vnd.api+json`.
+    pub suffix: Option<String>,
+    /// The media type parameters, keyed by lowercase name.
+    pub parameters: IndexMap<String, String>,
+}
+
+/// The error returned when a string is not a syntactically valid media type or
+/// media type range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaTypeKeyError {
+    input: String,
+    reason: &'static str,
+}
+
+impl fmt::Display for MediaTypeKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid media type `{}`: {}", self.input, self.reason)
+    }
+}
+
+impl std::error::Error for MediaTypeKeyError {}
+
+impl MediaTypeKey {
+    /// Returns `true` if the type or the subtype is the `*` wildcard.
+    pub fn is_range(&self) -> bool {
+        self.typ == "*" || self.subtype == "*"
+    }
+
+    /// Returns the subtype including its suffix, e.g. `vnd.api+json`.
+    pub fn full_subtype(&self) -> String {
+        match &self.suffix {
+            Some(suffix) => format!("{}+{}", self.subtype, suffix),
+            None => self.subtype.clone(),
+        }
+    }
+
+    /// Returns the type and subtype without parameters, e.g.
+    /// `application/vnd.api+json`.
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.typ, self.full_subtype())
+    }
+
+    /// Returns `true` if `other` falls within the range described by `self`.
+    ///
+    /// Wildcards are only honored on `self`, and every parameter of `self`
+    /// must be present in `other` with the same value. Parameters of `other`
+    /// that `self` does not mention are ignored.
+    ///
+    /// ```
+    /// # use openapiv3::MediaTypeKey;
+    /// let range: MediaTypeKey = "text/*".parse().unwrap();
+    /// assert!(range.matches(&"text/plain; charset=utf-8".parse().unwrap()));
+    /// assert!(!range.matches(&"application/json".parse().unwrap()));
+    /// ```
+    pub fn matches(&self, other: &MediaTypeKey) -> bool {
+        let type_matches = self.typ == "*" || self.typ == other.typ;
+        let subtype_matches =
+            self.subtype == "*" || (self.subtype == other.subtype && self.suffix == other.suffix);
+        type_matches
+            && subtype_matches
+            && self
+                .parameters
+                .iter()
+                .all(|(name, value)| other.parameters.get(name) == Some(value))
+    }
+}
+
+impl MediaTypeKey {
+    /// Orders keys from least to most specific: `*/*`, then `type/*`, then
+    /// concrete types, with more parameters being more specific.
+    fn specificity(&self) -> (u8, usize) {
+        let wildcards = match (self.typ.as_str(), self.subtype.as_str()) {
+            ("*", _) => 0,
+            (_, "*") => 1,
+            _ => 2,
+        };
+        (wildcards, self.parameters.len())
+    }
+}
+
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// Reads a quoted-string following its opening quote, unescaping
+/// quoted-pairs. Returns the value and the length consumed including the
+/// closing quote, or `None` if the string is unterminated.
+fn parse_quoted_string(input: &str) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, i + 1)),
+            '\\' => value.push(chars.next()?.1),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+impl FromStr for MediaTypeKey {
+    type Err = MediaTypeKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |reason| MediaTypeKeyError {
+            input: s.to_owned(),
+            reason,
+        };
+
+        let (essence, mut rest) = s.split_at(s.find(';').unwrap_or(s.len()));
+        let essence = essence.trim();
+        let (typ, full_subtype) = essence
+            .split_once('/')
+            .ok_or_else(|| err("expected `type/subtype`"))?;
+        if !is_token(typ) || !is_token(full_subtype) {
+            return Err(err("type and subtype must be non-empty tokens"));
+        }
+        let typ = typ.to_ascii_lowercase();
+        let full_subtype = full_subtype.to_ascii_lowercase();
+        if typ == "*" && full_subtype != "*" {
+            return Err(err("a wildcard type requires a wildcard subtype"));
+        }
+        let (subtype, suffix) = match full_subtype.rsplit_once('+') {
+            Some((subtype, suffix)) if !subtype.is_empty() && !suffix.is_empty() => {
+                (subtype.to_owned(), Some(suffix.to_owned()))
+            }
+            _ => (full_subtype, None),
+        };
+
+        // Parameters are separated by `;`, which may also appear inside a
+        // quoted-string value (RFC 9110, section 5.6.6).
+        let mut parameters = IndexMap::new();
+        loop {
+            rest = rest.trim_start();
+            match rest.strip_prefix(';') {
+                Some(after) => rest = after.trim_start(),
+                None if rest.is_empty() => break,
+                None => return Err(err("expected `;` between parameters")),
+            }
+            let segment = &rest[..rest.find(';').unwrap_or(rest.len())];
+            if segment.trim().is_empty() {
+                continue;
+            }
+            let eq = segment
+                .find('=')
+                .ok_or_else(|| err("expected parameters of the form `name=value`"))?;
+            let name = rest[..eq].trim().to_ascii_lowercase();
+            let raw = rest[eq + 1..].trim_start();
+            let value = match raw.strip_prefix('"') {
+                Some(quoted) => {
+                    let (value, len) = parse_quoted_string(quoted)
+                        .ok_or_else(|| err("unterminated quoted parameter value"))?;
+                    rest = &quoted[len..];
+                    value
+                }
+                None => {
+                    let len = raw.find(';').unwrap_or(raw.len());
+                    rest = &raw[len..];
+                    raw[..len].trim_end().to_owned()
+                }
+            };
+            if !is_token(&name) {
+                return Err(err("parameter names must be non-empty tokens"));
+            }
+            let value = if name == "charset" {
+                value.to_ascii_lowercase()
+            } else {
+                value
+            };
+            parameters.insert(name, value);
+        }
+
+        Ok(Self {
+            typ,
+            subtype,
+            suffix,
+            parameters,
+        })
+    }
+}
+
+impl fmt::Display for MediaTypeKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.essence())?;
+        for (name, value) in &self.parameters {
+            if is_token(value) {
+                write!(f, "; {}={}", name, value)?;
+            } else {
+                let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+                write!(f, "; {}=\"{}\"", name, escaped)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for MediaTypeKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.typ == other.typ
+            && self.subtype == other.subtype
+            && self.suffix == other.suffix
+            && self.parameters.len() == other.parameters.len()
+            && self
+                .parameters
+                .iter()
+                .all(|(name, value)| other.parameters.get(name) == Some(value))
+    }
+}
+
+impl Eq for MediaTypeKey {}
+
+impl Hash for MediaTypeKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.typ.hash(state);
+        self.subtype.hash(state);
+        self.suffix.hash(state);
+        let mut parameters = self.parameters.iter().collect::<Vec<_>>();
+        parameters.sort();
+        parameters.hash(state);
+    }
+}
+
+impl Serialize for MediaTypeKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaTypeKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Returns a view of a `content` map keyed by parsed [MediaTypeKey]s.
+///
+/// The map itself, and therefore its serialized form, is left untouched.
+/// Should two keys parse to the same [MediaTypeKey] the first one wins.
+pub fn typed_content(
+    content: &IndexMap<String, MediaType>,
+) -> Result<IndexMap<MediaTypeKey, &MediaType>, MediaTypeKeyError> {
+    let mut view = IndexMap::with_capacity(content.len());
+    for (key, media_type) in content {
+        view.entry(key.parse()?).or_insert(media_type);
+    }
+    Ok(view)
+}
+
+/// Returns the entry of a `content` map that applies to `media_type`, i.e.
+/// the most specific key matching it, as in `text/plain` over `text/*` over
+/// `*/*`. Among equally specific keys the first one wins.
+///
+/// Keys that are not valid media types are ignored, and `None` is returned
+/// if `media_type` is not valid itself.
+///
+/// ```
+/// # use indexmap::IndexMap;
+/// # use openapiv3::{content_for, MediaType};
+/// let content = IndexMap::from([
+///     ("*/*".to_owned(), MediaType::default()),
+///     ("text/*".to_owned(), MediaType::default()),
+///     ("text/plain".to_owned(), MediaType::default()),
+/// ]);
+/// let (key, _) = content_for(&content, "text/plain; charset=utf-8").unwrap();
+/// assert_eq!(key, "text/plain");
+/// let (key, _) = content_for(&content, "text/html").unwrap();
+/// assert_eq!(key, "text/*");
+/// ```
+pub fn content_for<'a>(
+    content: &'a IndexMap<String, MediaType>,
+    media_type: &str,
+) -> Option<(&'a str, &'a MediaType)> {
+    let media_type = media_type.parse::<MediaTypeKey>().ok()?;
+    let mut best: Option<(MediaTypeKey, &str, &MediaType)> = None;
+    for (key, value) in content {
+        let Ok(parsed) = key.parse::<MediaTypeKey>() else {
+            continue;
+        };
+        let more_specific = best
+            .as_ref()
+            .is_none_or(|(best, _, _)| parsed.specificity() > best.specificity());
+        if parsed.matches(&media_type) && more_specific {
+            best = Some((parsed, key, value));
+        }
+    }
+    best.map(|(_, key, value)| (key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{content_for, typed_content, MediaTypeKey};
+    use crate::MediaType;
+
+    fn key(s: &str) -> MediaTypeKey {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let k = key("Multipart/Form-Data; boundary=\"a b\"");
+        assert_eq!(k.typ, "multipart");
+        assert_eq!(k.subtype, "form-data");
+        assert_eq!(k.suffix, None);
+        assert_eq!(k.parameters.get("boundary").unwrap(), "a b");
+        assert_eq!(k.to_string(), "multipart/form-data; boundary=\"a b\"");
+
+        let k = key("multipart/form-data; boundary=\"a;b\" ; charset=UTF-8");
+        assert_eq!(k.parameters.get("boundary").unwrap(), "a;b");
+        assert_eq!(k.parameters.get("charset").unwrap(), "utf-8");
+        assert_eq!(
+            k.to_string(),
+            "multipart/form-data; boundary=\"a;b\"; charset=utf-8"
+        );
+        let k = key(r#"text/plain; title="say \"hi\"\\"; a=b"#);
+        assert_eq!(k.parameters.get("title").unwrap(), r#"say "hi"\"#);
+        assert_eq!(key(&k.to_string()), k);
+
+        assert!(key("*/*").is_range());
+        assert!(key("image/*").is_range());
+        assert!(!key("image/png").is_range());
+
+        for bad in [
+            "json",
+            "application/",
+            "*/json",
+            "a/b; c",
+            "a b/c",
+            "a/b; c=\"d",
+            "a/b; c=\"d;e",
+            "a/b; c=\"d\"e",
+        ] {
+            assert!(bad.parse::<MediaTypeKey>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_structural_equality() {
+        assert_eq!(
+            key("application/json; charset=UTF-8; q=1"),
+            key("APPLICATION/JSON;q=1;charset=utf-8")
+        );
+        assert_ne!(
+            key("application/json"),
+            key("application/json; charset=utf-8")
+        );
+        assert_ne!(key("application/vnd.a+json"), key("application/vnd.a+xml"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let json = key("application/json; charset=utf-8");
+        assert!(key("*/*").matches(&json));
+        assert!(key("application/*").matches(&json));
+        assert!(key("application/json").matches(&json));
+        assert!(key("application/json; charset=utf-8").matches(&json));
+        assert!(!key("application/json; charset=latin1").matches(&json));
+        assert!(!key("text/*").matches(&json));
+        assert!(!key("application/problem+json").matches(&json));
+        assert!(!json.matches(&key("application/*")));
+    }
+
+    #[test]
+    fn test_typed_content() {
+        let mut content = IndexMap::new();
+        content.insert("text/plain".to_string(), MediaType::default());
+        content.insert("Application/JSON".to_string(), MediaType::default());
+        let view = typed_content(&content).unwrap();
+        assert_eq!(
+            view.keys().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["text/plain", "application/json"]
+        );
+        assert!(view.contains_key(&key("application/json")));
+
+        content.insert("nonsense".to_string(), MediaType::default());
+        assert!(typed_content(&content).is_err());
+    }
+
+    #[test]
+    fn test_content_for() {
+        let mut content = IndexMap::new();
+        for key in [
+            "nonsense",
+            "application/*",
+            "application/json",
+            "application/json; charset=utf-8",
+        ] {
+            content.insert(key.to_string(), MediaType::default());
+        }
+        let key = |media_type| content_for(&content, media_type).map(|(key, _)| key);
+        assert_eq!(
+            key("application/json; charset=utf-8"),
+            Some("application/json; charset=utf-8")
+        );
+        assert_eq!(key("application/json"), Some("application/json"));
+        assert_eq!(key("application/xml"), Some("application/*"));
+        assert_eq!(key("text/plain"), None);
+        assert_eq!(key("nonsense"), None);
+    }
+}
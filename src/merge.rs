@@ -0,0 +1,857 @@
+use std::fmt;
+
+use crate::flat_operation::same_parameter;
+use crate::util::METHODS;
+use crate::*;
+use indexmap::IndexMap;
+
+/// What [OpenAPI::merge] does when both documents define something
+/// differently under the same name.
+///
+/// Definitions that are equal never collide.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Fail with a [MergeError].
+    #[default]
+    Error,
+    /// Keep the definition of the left document and drop the other one.
+    PreferLeft,
+    /// Keep both, adding the prefix to the name of the right document's
+    /// definition.
+    ///
+    /// Renamed components have the references to them in the right document
+    /// rewritten, including discriminator mappings and the security
+    /// requirements naming a renamed security scheme. For paths, the whole
+    /// path item of the right document is moved to the prefixed path, so
+    /// the prefix should start with `/`.
+    RenameWithPrefix(String),
+}
+
+/// Options for [OpenAPI::merge].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MergeOptions {
+    components: MergeStrategy,
+    paths: MergeStrategy,
+}
+
+impl MergeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how colliding components are handled.
+    pub fn components(mut self, strategy: MergeStrategy) -> Self {
+        self.components = strategy;
+        self
+    }
+
+    /// Sets how colliding operations, and path items that are references,
+    /// are handled.
+    pub fn paths(mut self, strategy: MergeStrategy) -> Self {
+        self.paths = strategy;
+        self
+    }
+}
+
+/// The error returned when [OpenAPI::merge] meets a collision it may not
+/// resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeError {
+    /// Both documents define a component differently, or a renamed
+    /// component would take a name that is already used.
+    Component { kind: ComponentKind, name: String },
+    /// Both documents define an operation differently.
+    Operation { path: String, method: String },
+    /// Both documents define a path differently and at least one of them is
+    /// a reference, or a renamed path item would take a path that is
+    /// already used.
+    Path { path: String },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::Component { kind, name } => {
+                write!(f, "{} `{}` is defined differently", kind, name)
+            }
+            MergeError::Operation { path, method } => {
+                write!(f, "operation {} {} is defined differently", method, path)
+            }
+            MergeError::Path { path } => write!(f, "path `{}` is defined differently", path),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// The error returned by [OpenAPI::rename_component].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    /// There is no component of that kind and name.
+    Missing { kind: ComponentKind, name: String },
+    /// A component of that kind already has the new name.
+    Exists { kind: ComponentKind, name: String },
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenameError::Missing { kind, name } => write!(f, "{} `{}` does not exist", kind, name),
+            RenameError::Exists { kind, name } => write!(f, "{} `{}` already exists", kind, name),
+        }
+    }
+}
+
+impl std::error::Error for RenameError {}
+
+macro_rules! each_section {
+    ($callback:ident) => {
+        $callback!(schemas, Schemas);
+        $callback!(responses, Responses);
+        $callback!(parameters, Parameters);
+        $callback!(examples, Examples);
+        $callback!(request_bodies, RequestBodies);
+        $callback!(headers, Headers);
+        $callback!(security_schemes, SecuritySchemes);
+        $callback!(links, Links);
+        $callback!(callbacks, Callbacks);
+    };
+}
+
+impl OpenAPI {
+    /// Combines this document with `other`.
+    ///
+    /// Paths, components, tags, servers and security requirements of both
+    /// documents are kept. The `openapi` version, `info` and external
+    /// documentation are those of this document; tags and extensions of
+    /// `other` are only added where this document has none of the same
+    /// name.
+    ///
+    /// Operations of the same path are combined. Where the two path items
+    /// differ in their shared parameters or servers, and where the documents
+    /// differ in their top-level security or servers, these are first moved
+    /// into the operations that do not override them so that every
+    /// operation keeps its meaning.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let pets: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Gateway", "version": "1" },
+    ///     "paths": { "/pets": { "get": { "responses": { "200": {
+    ///         "description": "Pets",
+    ///         "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } }
+    ///     } } } } },
+    ///     "components": { "schemas": { "Error": { "type": "string" } } }
+    /// }"##).unwrap();
+    /// let owners: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Owners", "version": "2" },
+    ///     "paths": { "/owners": { "get": { "responses": { "default": {
+    ///         "description": "Failure",
+    ///         "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } }
+    ///     } } } } },
+    ///     "components": { "schemas": { "Error": { "type": "object" } } }
+    /// }"##).unwrap();
+    ///
+    /// let options = MergeOptions::new()
+    ///     .components(MergeStrategy::RenameWithPrefix("Owners".to_owned()));
+    /// let merged = pets.merge(owners, options).unwrap();
+    ///
+    /// assert_eq!(merged.info.title, "Gateway");
+    /// let schemas = &merged.components.as_ref().unwrap().schemas;
+    /// assert_eq!(schemas.keys().collect::<Vec<_>>(), ["Error", "OwnersError"]);
+    /// let value = serde_json::to_value(&merged).unwrap();
+    /// assert_eq!(
+    ///     value.pointer("/paths/~1owners/get/responses/default/content/application~1json/schema/$ref").unwrap(),
+    ///     "#/components/schemas/OwnersError"
+    /// );
+    /// ```
+    pub fn merge(
+        mut self,
+        mut other: OpenAPI,
+        options: MergeOptions,
+    ) -> Result<OpenAPI, MergeError> {
+        if let Some(components) = other.components.take() {
+            let left = self.components.get_or_insert_with(Components::default);
+            let merged = merge_components(left, components, &options.components)?;
+            other.components = Some(merged.incoming);
+            other.rename_components(&merged.renames);
+            absorb_components(left, other.components.take().unwrap_or_default());
+        }
+
+        if self.security != other.security {
+            self.push_down_security();
+            other.push_down_security();
+        }
+        if self.servers != other.servers {
+            self.push_down_servers();
+            other.push_down_servers();
+        }
+
+        merge_paths(&mut self.paths, other.paths, &options.paths)?;
+
+        for tag in other.tags {
+            if !self.tags.iter().any(|t| t.name == tag.name) {
+                self.tags.push(tag);
+            }
+        }
+        for (name, value) in other.extensions {
+            self.extensions.entry(name).or_insert(value);
+        }
+        Ok(self)
+    }
+
+    /// Moves the top-level security requirements into the operations that do
+    /// not declare their own.
+    fn push_down_security(&mut self) {
+        if let Some(security) = self.security.take() {
+            for (_, _, operation) in self.operations_mut() {
+                operation.security.get_or_insert_with(|| security.clone());
+            }
+        }
+    }
+
+    /// Moves the top-level servers into the path items that do not declare
+    /// their own.
+    fn push_down_servers(&mut self) {
+        let servers = std::mem::take(&mut self.servers);
+        if servers.is_empty() {
+            return;
+        }
+        for item in self.paths.paths.values_mut() {
+            if let ReferenceOr::Item(item) = item {
+                if item.servers.is_empty() {
+                    item.servers = servers.clone();
+                }
+            }
+        }
+    }
+
+    /// Renames a component, keeping its position in `components`, and
+    /// rewrites every reference to it, including references into it such as
+    /// `#/components/schemas/Pet/properties/name`, discriminator mappings
+    /// and, for security schemes, the security requirements naming it.
+    ///
+    /// References are compared as a whole after normalization, so renaming
+    /// `Pet` leaves references to `PetList` alone.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": {},
+    ///     "components": { "schemas": {
+    ///         "Pet": { "type": "object" },
+    ///         "PetList": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+    ///     } }
+    /// }"##).unwrap();
+    /// api.rename_component(ComponentKind::Schemas, "Pet", "Animal").unwrap();
+    ///
+    /// let schemas = &api.components.as_ref().unwrap().schemas;
+    /// assert_eq!(schemas.keys().collect::<Vec<_>>(), ["Animal", "PetList"]);
+    /// assert_eq!(
+    ///     serde_json::to_value(&schemas["PetList"]).unwrap()["items"]["$ref"],
+    ///     "#/components/schemas/Animal"
+    /// );
+    /// ```
+    pub fn rename_component(
+        &mut self,
+        kind: ComponentKind,
+        name: &str,
+        new_name: &str,
+    ) -> Result<(), RenameError> {
+        let components = self
+            .components
+            .as_mut()
+            .ok_or_else(|| RenameError::Missing {
+                kind,
+                name: name.to_owned(),
+            })?;
+        macro_rules! rename {
+            ($field:ident, $kind:ident) => {
+                if kind == ComponentKind::$kind {
+                    rename_key(&mut components.$field, kind, name, new_name)?;
+                }
+            };
+        }
+        each_section!(rename);
+        if name != new_name {
+            self.rename_components(&[(kind, name.to_owned(), new_name.to_owned())]);
+        }
+        Ok(())
+    }
+
+    /// Applies the renames of [merge_components] to the references of this
+    /// document.
+    fn rename_components(&mut self, renames: &[(ComponentKind, String, String)]) {
+        if renames.is_empty() {
+            return;
+        }
+        let mut references = RenameReferences::default();
+        let mut schemes = IndexMap::new();
+        for (kind, name, renamed) in renames {
+            references.references.insert(
+                normalize_reference(&Reference::new(*kind, name.clone()).to_string()),
+                Reference::new(*kind, renamed.clone()).to_string(),
+            );
+            match kind {
+                ComponentKind::Schemas => {
+                    references.schemas.insert(name.clone(), renamed.clone());
+                }
+                ComponentKind::SecuritySchemes => {
+                    schemes.insert(name.clone(), renamed.clone());
+                }
+                _ => {}
+            }
+        }
+        self.transform(&mut references);
+
+        if !schemes.is_empty() {
+            rename_security(&mut self.security, &schemes);
+            for (_, _, operation) in self.operations_mut() {
+                rename_operation_security(operation, &schemes);
+            }
+        }
+    }
+}
+
+struct MergedComponents {
+    /// The components of the right document, with colliding ones renamed or
+    /// removed.
+    incoming: Components,
+    /// The kind, original name and new name of each renamed component.
+    renames: Vec<(ComponentKind, String, String)>,
+}
+
+fn merge_components(
+    left: &Components,
+    mut right: Components,
+    strategy: &MergeStrategy,
+) -> Result<MergedComponents, MergeError> {
+    let mut renames = Vec::new();
+    macro_rules! resolve {
+        ($field:ident, $kind:ident) => {
+            right.$field = resolve_section(
+                ComponentKind::$kind,
+                &left.$field,
+                std::mem::take(&mut right.$field),
+                strategy,
+                &mut renames,
+            )?;
+        };
+    }
+    each_section!(resolve);
+    Ok(MergedComponents {
+        incoming: right,
+        renames,
+    })
+}
+
+fn absorb_components(left: &mut Components, right: Components) {
+    macro_rules! absorb {
+        ($field:ident, $kind:ident) => {
+            for (name, item) in right.$field {
+                left.$field.entry(name).or_insert(item);
+            }
+        };
+    }
+    each_section!(absorb);
+    for (name, value) in right.extensions {
+        left.extensions.entry(name).or_insert(value);
+    }
+}
+
+/// Resolves the collisions of one section of the right document's
+/// components, returning the section with colliding components renamed or
+/// removed.
+fn resolve_section<T: PartialEq>(
+    kind: ComponentKind,
+    left: &IndexMap<String, T>,
+    right: IndexMap<String, T>,
+    strategy: &MergeStrategy,
+    renames: &mut Vec<(ComponentKind, String, String)>,
+) -> Result<IndexMap<String, T>, MergeError> {
+    let colliding = right
+        .iter()
+        .filter(|(name, item)| left.get(*name).is_some_and(|existing| existing != *item))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    if colliding.is_empty() {
+        return Ok(right);
+    }
+    match strategy {
+        MergeStrategy::Error => Err(MergeError::Component {
+            kind,
+            name: colliding[0].clone(),
+        }),
+        MergeStrategy::PreferLeft => Ok(right
+            .into_iter()
+            .filter(|(name, _)| !colliding.contains(name))
+            .collect()),
+        MergeStrategy::RenameWithPrefix(prefix) => {
+            let mut section = IndexMap::with_capacity(right.len());
+            for (name, item) in right {
+                if !colliding.contains(&name) {
+                    section.insert(name, item);
+                    continue;
+                }
+                let renamed = format!("{}{}", prefix, name);
+                if left.contains_key(&renamed) || section.contains_key(&renamed) {
+                    return Err(MergeError::Component {
+                        kind,
+                        name: renamed,
+                    });
+                }
+                renames.push((kind, name, renamed.clone()));
+                section.insert(renamed, item);
+            }
+            Ok(section)
+        }
+    }
+}
+
+/// Moves the entry `name` of a section of [Components] to `new_name`, in
+/// place.
+fn rename_key<T>(
+    section: &mut IndexMap<String, T>,
+    kind: ComponentKind,
+    name: &str,
+    new_name: &str,
+) -> Result<(), RenameError> {
+    if !section.contains_key(name) {
+        return Err(RenameError::Missing {
+            kind,
+            name: name.to_owned(),
+        });
+    }
+    if name != new_name && section.contains_key(new_name) {
+        return Err(RenameError::Exists {
+            kind,
+            name: new_name.to_owned(),
+        });
+    }
+    *section = std::mem::take(section)
+        .into_iter()
+        .map(|(key, item)| match key == name {
+            true => (new_name.to_owned(), item),
+            false => (key, item),
+        })
+        .collect();
+    Ok(())
+}
+
+#[derive(Default)]
+struct RenameReferences {
+    /// Old references, normalized, to new ones.
+    references: IndexMap<String, String>,
+    /// Old schema names to new ones, for discriminator mappings by name.
+    schemas: IndexMap<String, String>,
+}
+
+impl RenameReferences {
+    /// The new form of `reference`, if it points at or into a renamed
+    /// component.
+    fn renamed(&self, reference: &str) -> Option<String> {
+        let normalized = normalize_reference(reference);
+        // `#/components/{section}/{name}` and the pointer into the component.
+        let (component, rest) = match normalized.match_indices('/').nth(3) {
+            Some((i, _)) => normalized.split_at(i),
+            None => (normalized.as_str(), ""),
+        };
+        let renamed = self.references.get(component)?;
+        Some(format!("{}{}", renamed, rest))
+    }
+}
+
+impl VisitorMut for RenameReferences {
+    fn visit_schema_mut(&mut self, _: &str, schema: &mut Schema) {
+        if let Some(discriminator) = &mut schema.schema_data.discriminator {
+            for target in discriminator.mapping.values_mut() {
+                if let Some(renamed) = self
+                    .renamed(target)
+                    .or_else(|| self.schemas.get(target.as_str()).cloned())
+                {
+                    *target = renamed;
+                }
+            }
+        }
+    }
+
+    fn visit_reference_mut(&mut self, _: &str, reference: &mut String) {
+        if let Some(renamed) = self.renamed(reference) {
+            *reference = renamed;
+        }
+    }
+}
+
+fn rename_security(
+    security: &mut Option<Vec<SecurityRequirement>>,
+    schemes: &IndexMap<String, String>,
+) {
+    for requirement in security.iter_mut().flatten() {
+        *requirement = std::mem::take(requirement)
+            .into_iter()
+            .map(|(name, scopes)| (schemes.get(&name).cloned().unwrap_or(name), scopes))
+            .collect();
+    }
+}
+
+fn rename_operation_security(operation: &mut Operation, schemes: &IndexMap<String, String>) {
+    rename_security(&mut operation.security, schemes);
+    for callback in operation.callbacks.values_mut() {
+        for item in callback.values_mut() {
+            for (_, operation) in item.iter_mut() {
+                rename_operation_security(operation, schemes);
+            }
+        }
+    }
+}
+
+fn merge_paths(left: &mut Paths, right: Paths, strategy: &MergeStrategy) -> Result<(), MergeError> {
+    for (path, mut item) in right.paths {
+        // The colliding method, or `None` if the whole path item collides.
+        let collision = match (left.paths.get(&path), &item) {
+            (None, _) => None,
+            (Some(existing), _) if *existing == item => continue,
+            (Some(ReferenceOr::Item(existing)), ReferenceOr::Item(incoming)) => {
+                colliding_methods(existing, incoming)
+                    .first()
+                    .map(|m| Some(*m))
+            }
+            (Some(_), _) => Some(None),
+        };
+
+        match (collision, strategy) {
+            (None, _) => {}
+            (Some(Some(method)), MergeStrategy::Error) => {
+                return Err(MergeError::Operation {
+                    path,
+                    method: method.to_owned(),
+                })
+            }
+            (Some(None), MergeStrategy::Error) => return Err(MergeError::Path { path }),
+            (Some(None), MergeStrategy::PreferLeft) => continue,
+            (Some(Some(_)), MergeStrategy::PreferLeft) => {
+                if let (Some(ReferenceOr::Item(existing)), ReferenceOr::Item(incoming)) =
+                    (left.paths.get(&path), &mut item)
+                {
+                    for method in colliding_methods(existing, incoming) {
+                        *incoming.operation_mut(method).unwrap() = None;
+                    }
+                }
+            }
+            (Some(_), MergeStrategy::RenameWithPrefix(prefix)) => {
+                let renamed = format!("{}{}", prefix, path);
+                if left.paths.contains_key(&renamed) {
+                    return Err(MergeError::Path { path: renamed });
+                }
+                left.paths.insert(renamed, item);
+                continue;
+            }
+        }
+
+        match (left.paths.get_mut(&path), item) {
+            (Some(ReferenceOr::Item(existing)), ReferenceOr::Item(incoming)) => {
+                merge_path_item(existing, incoming)
+            }
+            (_, item) => {
+                left.paths.insert(path, item);
+            }
+        }
+    }
+    for (name, value) in right.extensions {
+        left.extensions.entry(name).or_insert(value);
+    }
+    Ok(())
+}
+
+/// The methods for which both path items define different operations.
+fn colliding_methods(left: &PathItem, right: &PathItem) -> Vec<&'static str> {
+    METHODS
+        .into_iter()
+        .filter(|method| {
+            let left = left.iter().find(|(m, _)| m == method);
+            let right = right.iter().find(|(m, _)| m == method);
+            matches!((left, right), (Some((_, a)), Some((_, b))) if a != b)
+        })
+        .collect()
+}
+
+/// Adds the operations of `right` that `left` lacks, the two having no
+/// colliding operations.
+fn merge_path_item(left: &mut PathItem, mut right: PathItem) {
+    if left.parameters != right.parameters {
+        push_down_parameters(left);
+        push_down_parameters(&mut right);
+    }
+    if left.servers != right.servers {
+        push_down_path_servers(left);
+        push_down_path_servers(&mut right);
+    }
+    if left.summary.is_none() {
+        left.summary = right.summary.take();
+    }
+    if left.description.is_none() {
+        left.description = right.description.take();
+    }
+    for method in METHODS {
+        let incoming = right.operation_mut(method).unwrap().take();
+        let slot = left.operation_mut(method).unwrap();
+        if slot.is_none() {
+            *slot = incoming;
+        }
+    }
+    for (name, value) in right.extensions {
+        left.extensions.entry(name).or_insert(value);
+    }
+}
+
+/// Moves the shared parameters of a path item into its operations, unless an
+/// operation overrides them.
+fn push_down_parameters(item: &mut PathItem) {
+    let shared = std::mem::take(&mut item.parameters);
+    for (_, operation) in item.iter_mut() {
+        let inherited = shared
+            .iter()
+            .filter(|parameter| {
+                !operation
+                    .parameters
+                    .iter()
+                    .any(|own| overrides(own, parameter))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        operation.parameters.splice(0..0, inherited);
+    }
+}
+
+fn overrides(own: &ReferenceOr<Parameter>, shared: &ReferenceOr<Parameter>) -> bool {
+    match (own, shared) {
+        (ReferenceOr::Item(a), ReferenceOr::Item(b)) => same_parameter(a, b),
+        (a, b) => a == b,
+    }
+}
+
+fn push_down_path_servers(item: &mut PathItem) {
+    let servers = std::mem::take(&mut item.servers);
+    for (_, operation) in item.iter_mut() {
+        if operation.servers.is_empty() {
+            operation.servers = servers.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(value: serde_json::Value) -> OpenAPI {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn left() -> OpenAPI {
+        document(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "security": [ { "key": [] } ],
+            "tags": [ { "name": "pets" } ],
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [ { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } } ],
+                    "get": { "responses": { "200": { "description": "A pet" } } }
+                }
+            },
+            "components": {
+                "securitySchemes": { "key": { "type": "apiKey", "name": "key", "in": "header" } }
+            }
+        }))
+    }
+
+    fn right() -> OpenAPI {
+        document(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Admin", "version": "1" },
+            "security": [ { "key": [] } ],
+            "tags": [ { "name": "pets", "description": "ignored" }, { "name": "admin" } ],
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [ { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } } ],
+                    "get": { "responses": { "200": { "description": "Another pet" } } },
+                    "delete": { "responses": { "204": { "description": "Deleted" } } }
+                }
+            },
+            "components": {
+                "securitySchemes": { "key": { "type": "http", "scheme": "bearer" } },
+                "schemas": {
+                    "Pet": {
+                        "oneOf": [ { "$ref": "#/components/schemas/Dog" } ],
+                        "discriminator": { "propertyName": "kind", "mapping": { "dog": "Dog" } }
+                    },
+                    "Dog": { "type": "object" }
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_merge_errors() {
+        assert_eq!(
+            left().merge(right(), MergeOptions::new()).unwrap_err(),
+            MergeError::Component {
+                kind: ComponentKind::SecuritySchemes,
+                name: "key".to_owned()
+            }
+        );
+        let options = MergeOptions::new().components(MergeStrategy::PreferLeft);
+        assert_eq!(
+            left().merge(right(), options).unwrap_err(),
+            MergeError::Operation {
+                path: "/pets/{id}".to_owned(),
+                method: "get".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_prefer_left() {
+        let options = MergeOptions::new()
+            .components(MergeStrategy::PreferLeft)
+            .paths(MergeStrategy::PreferLeft);
+        let merged = left().merge(right(), options).unwrap();
+        assert_eq!(
+            merged.tags.iter().map(|t| &t.name).collect::<Vec<_>>(),
+            ["pets", "admin"]
+        );
+        assert_eq!(merged.security.as_ref().unwrap().len(), 1);
+
+        let value = serde_json::to_value(&merged).unwrap();
+        let item = &value["paths"]["/pets/{id}"];
+        assert!(item.get("parameters").is_none());
+        assert_eq!(item["get"]["responses"]["200"]["description"], "A pet");
+        assert_eq!(item["get"]["parameters"][0]["schema"]["type"], "string");
+        assert_eq!(item["delete"]["parameters"][0]["schema"]["type"], "integer");
+        assert_eq!(
+            value["components"]["securitySchemes"]["key"]["type"],
+            "apiKey"
+        );
+    }
+
+    #[test]
+    fn test_merge_rename() {
+        let options = MergeOptions::new()
+            .components(MergeStrategy::RenameWithPrefix("Admin".to_owned()))
+            .paths(MergeStrategy::RenameWithPrefix("/admin".to_owned()));
+        let mut left = left();
+        left.components.as_mut().unwrap().schemas.insert(
+            "Dog".to_owned(),
+            ReferenceOr::Item(Schema {
+                schema_data: Default::default(),
+                schema_kind: SchemaKind::Type(Type::Boolean(Default::default())),
+            }),
+        );
+        let merged = left.merge(right(), options).unwrap();
+
+        let schemes = &merged.components.as_ref().unwrap().security_schemes;
+        assert_eq!(schemes.keys().collect::<Vec<_>>(), ["key", "Adminkey"]);
+        let value = serde_json::to_value(&merged).unwrap();
+        let pet = &value["components"]["schemas"]["Pet"];
+        assert_eq!(pet["oneOf"][0]["$ref"], "#/components/schemas/AdminDog");
+        assert_eq!(pet["discriminator"]["mapping"]["dog"], "AdminDog");
+        assert!(value["paths"]["/pets/{id}"].get("delete").is_none());
+        let moved = &value["paths"]["/admin/pets/{id}"];
+        assert_eq!(
+            moved["delete"]["security"][0],
+            serde_json::json!({ "Adminkey": [] })
+        );
+    }
+
+    #[test]
+    fn test_rename_component() {
+        let mut api = document(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "security": [ { "key": [] } ],
+            "paths": {
+                "/pets": { "get": {
+                    "parameters": [ { "name": "n", "in": "query", "schema": { "$ref": "#/components/schemas/Pet/properties/name" } } ],
+                    "responses": { "200": {
+                        "description": "",
+                        "content": { "application/json": { "schema": { "$ref": "#/components/schemas/PetList" } } },
+                        "links": { "self": { "$ref": "#/components/links/Self" } }
+                    } },
+                    "callbacks": { "onPet": { "{$request.body#/url}": { "post": {
+                        "requestBody": { "content": { "application/json": { "schema": { "$ref": "#/components/%73chemas/Pet" } } } },
+                        "responses": {}
+                    } } } }
+                } }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": { "type": "object", "properties": { "name": { "type": "string" } } },
+                    "PetList": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } },
+                    "Pets": {
+                        "oneOf": [ { "$ref": "#/components/schemas/Pet" } ],
+                        "discriminator": { "propertyName": "kind", "mapping": { "a": "Pet", "b": "#/components/schemas/Pet" } }
+                    }
+                },
+                "links": { "Self": { "operationId": "getPets" } },
+                "securitySchemes": { "key": { "type": "apiKey", "name": "key", "in": "header" } }
+            }
+        }));
+        api.rename_component(ComponentKind::Schemas, "Pet", "Animal")
+            .unwrap();
+        api.rename_component(ComponentKind::Links, "Self", "Me")
+            .unwrap();
+        api.rename_component(ComponentKind::SecuritySchemes, "key", "apiKey")
+            .unwrap();
+
+        let value = serde_json::to_value(&api).unwrap();
+        let get = &value["paths"]["/pets"]["get"];
+        assert_eq!(
+            get["parameters"][0]["schema"]["$ref"],
+            "#/components/schemas/Animal/properties/name"
+        );
+        let ok = &get["responses"]["200"];
+        assert_eq!(
+            ok["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/PetList"
+        );
+        assert_eq!(ok["links"]["self"]["$ref"], "#/components/links/Me");
+        assert_eq!(
+            get["callbacks"]["onPet"]["{$request.body#/url}"]["post"]["requestBody"]["content"]
+                ["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Animal"
+        );
+        let schemas = &value["components"]["schemas"];
+        assert_eq!(
+            schemas.as_object().unwrap().keys().collect::<Vec<_>>(),
+            ["Animal", "PetList", "Pets"]
+        );
+        assert_eq!(
+            schemas["PetList"]["items"]["$ref"],
+            "#/components/schemas/Animal"
+        );
+        assert_eq!(
+            schemas["Pets"]["discriminator"]["mapping"],
+            serde_json::json!({ "a": "Animal", "b": "#/components/schemas/Animal" })
+        );
+        assert_eq!(value["security"][0], serde_json::json!({ "apiKey": [] }));
+
+        assert_eq!(
+            api.rename_component(ComponentKind::Schemas, "Pet", "Dog"),
+            Err(RenameError::Missing {
+                kind: ComponentKind::Schemas,
+                name: "Pet".to_owned()
+            })
+        );
+        assert_eq!(
+            api.rename_component(ComponentKind::Schemas, "Animal", "Pets"),
+            Err(RenameError::Exists {
+                kind: ComponentKind::Schemas,
+                name: "Pets".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,652 @@
+use crate::resolve::component_name;
+use crate::*;
+use indexmap::IndexMap;
+use serde_json::{Map, Number, Value};
+
+/// Generates plausible values that satisfy a schema, e.g. to serve from a
+/// mock server or to show in documentation.
+///
+/// Enums, the `date`, `date-time` and `byte` formats, common unknown formats
+/// such as `uuid` and `email`, length, numeric, item and property bounds are
+/// respected. Required properties are always present and optional ones are
+/// included at random. A `oneOf` picks a variant at random and sets the
+/// discriminator property, if any, to name it. `pattern` and `not` are not
+/// taken into account, and generated values are never `null`.
+///
+/// Values are drawn from a pseudo-random generator seeded by the caller, so
+/// the same seed, schema and sequence of calls always give the same values.
+///
+/// ```
+/// # use openapiv3::*;
+/// let schema: Schema = serde_json::from_str(r#"{
+///     "type": "object",
+///     "required": ["id", "tags"],
+///     "properties": {
+///         "id": { "type": "integer", "minimum": 1, "maximum": 9 },
+///         "tags": { "type": "array", "minItems": 1, "items": { "type": "string", "enum": ["a", "b"] } }
+///     }
+/// }"#).unwrap();
+/// let value = MockGenerator::new(7).generate(&schema);
+/// assert!(schema.validate_value(&value, None).is_empty());
+/// assert_eq!(value, MockGenerator::new(7).generate(&schema));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockGenerator<'a> {
+    components: Option<&'a Components>,
+    state: u64,
+    max_depth: usize,
+    depth: usize,
+}
+
+impl<'a> MockGenerator<'a> {
+    /// Creates a generator whose values are determined by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            components: None,
+            state: seed,
+            max_depth: 4,
+            depth: 0,
+        }
+    }
+
+    /// Sets the components that references are resolved against. Without
+    /// them, references generate `null`.
+    pub fn components(mut self, components: &'a Components) -> Self {
+        self.components = Some(components);
+        self
+    }
+
+    /// Sets how deeply nested objects and arrays get optional properties and
+    /// more items than required, which bounds the size of values generated
+    /// from recursive schemas. Defaults to 4.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Generates a value for `schema`.
+    pub fn generate(&mut self, schema: &Schema) -> Value {
+        if self.depth > self.max_depth + 16 {
+            return Value::Null;
+        }
+        self.depth += 1;
+        let value = self.schema(schema);
+        self.depth -= 1;
+        value
+    }
+
+    /// Generates a value for `schema`, resolving it first if it is a
+    /// reference.
+    pub fn generate_ref(&mut self, schema: &ReferenceOr<Schema>) -> Value {
+        match (schema, self.components) {
+            (ReferenceOr::Item(schema), _) => self.generate(schema),
+            (reference, Some(components)) => match reference.resolve(components) {
+                Ok(schema) => self.generate(schema),
+                Err(_) => Value::Null,
+            },
+            (_, None) => Value::Null,
+        }
+    }
+
+    fn generate_boxed(&mut self, schema: &ReferenceOr<Box<Schema>>) -> Value {
+        match (schema, self.components) {
+            (ReferenceOr::Item(schema), _) => self.generate(schema),
+            (reference, Some(components)) => match reference.resolve(components) {
+                Ok(schema) => self.generate(schema),
+                Err(_) => Value::Null,
+            },
+            (_, None) => Value::Null,
+        }
+    }
+
+    /// The next output of a SplitMix64 generator.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `lo..=hi`, or `lo` if the range is empty.
+    fn between(&mut self, lo: i128, hi: i128) -> i128 {
+        if hi <= lo {
+            return lo;
+        }
+        // The width of the range fits in a u128 even when `hi - lo` does not
+        // fit in an i128.
+        let offset = match (hi.wrapping_sub(lo) as u128).checked_add(1) {
+            Some(len) => self.next() as u128 % len,
+            None => self.next() as u128,
+        };
+        (lo as u128).wrapping_add(offset) as i128
+    }
+
+    /// A length in `min..=max`, preferring short non-empty ones.
+    fn length(&mut self, min: usize, max: Option<usize>, preferred: usize) -> usize {
+        let lo = min.max(1).min(max.unwrap_or(usize::MAX));
+        let hi = max.unwrap_or(usize::MAX).min(lo.max(min) + preferred);
+        self.between(lo as i128, hi as i128) as usize
+    }
+
+    fn chance(&mut self) -> bool {
+        self.next() & 1 == 1
+    }
+
+    fn pick<'v, T>(&mut self, values: &'v [T]) -> Option<&'v T> {
+        match values.is_empty() {
+            true => None,
+            false => values.get(self.between(0, values.len() as i128 - 1) as usize),
+        }
+    }
+
+    fn schema(&mut self, schema: &Schema) -> Value {
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::String(string)) => {
+                let enumeration: Vec<_> = string.enumeration.iter().flatten().collect();
+                if let Some(value) = self.pick(&enumeration) {
+                    return Value::String((*value).clone());
+                }
+                let format = match &string.format {
+                    VariantOrUnknownOrEmpty::Item(StringFormat::Date) => Some("date"),
+                    VariantOrUnknownOrEmpty::Item(StringFormat::DateTime) => Some("date-time"),
+                    VariantOrUnknownOrEmpty::Item(StringFormat::Byte) => Some("byte"),
+                    VariantOrUnknownOrEmpty::Unknown(format) => Some(format.as_str()),
+                    _ => None,
+                };
+                self.string(format, string.min_length, string.max_length)
+            }
+            SchemaKind::Type(Type::Number(number)) => {
+                let enumeration: Vec<_> = number.enumeration.iter().flatten().collect();
+                if let Some(value) = self.pick(&enumeration) {
+                    return Number::from_f64(**value).map_or(Value::Null, Value::Number);
+                }
+                self.number(
+                    number.multiple_of,
+                    number.minimum.map(|m| (m, number.exclusive_minimum)),
+                    number.maximum.map(|m| (m, number.exclusive_maximum)),
+                )
+            }
+            SchemaKind::Type(Type::Integer(integer)) => {
+                let enumeration: Vec<_> = integer.enumeration.iter().flatten().collect();
+                if let Some(value) = self.pick(&enumeration) {
+                    return Value::from(**value);
+                }
+                let int32 = integer.format == VariantOrUnknownOrEmpty::Item(IntegerFormat::Int32);
+                self.integer(
+                    integer.multiple_of,
+                    integer.minimum.map(|m| (m, integer.exclusive_minimum)),
+                    integer.maximum.map(|m| (m, integer.exclusive_maximum)),
+                    int32,
+                )
+            }
+            SchemaKind::Type(Type::Boolean(boolean)) => {
+                let enumeration: Vec<_> = boolean.enumeration.iter().flatten().collect();
+                match self.pick(&enumeration) {
+                    Some(value) => Value::Bool(**value),
+                    None => Value::Bool(self.chance()),
+                }
+            }
+            SchemaKind::Type(Type::Object(object)) => self.object(
+                &object.properties,
+                &object.required,
+                object.additional_properties.as_ref(),
+                (object.min_properties, object.max_properties),
+            ),
+            SchemaKind::Type(Type::Array(array)) => self.array(
+                array.items.as_ref(),
+                (array.min_items, array.max_items),
+                array.unique_items,
+                array.contains.as_ref(),
+                array.min_contains,
+            ),
+            SchemaKind::OneOf { one_of } => self.one_of(schema, one_of),
+            SchemaKind::AnyOf { any_of } => match self.pick(any_of) {
+                Some(variant) => self.generate_ref(variant),
+                None => Value::Null,
+            },
+            SchemaKind::AllOf { all_of } => self.all_of(all_of),
+            SchemaKind::Not { .. } => Value::Null,
+            SchemaKind::Any(any) => self.any(schema, any),
+        }
+    }
+
+    fn any(&mut self, schema: &Schema, any: &AnySchema) -> Value {
+        if let Some(value) = self.pick(&any.enumeration) {
+            return value.clone();
+        }
+        if !any.one_of.is_empty() {
+            return self.one_of(schema, &any.one_of);
+        }
+        if let Some(variant) = self.pick(&any.any_of) {
+            return self.generate_ref(variant);
+        }
+        if !any.all_of.is_empty() {
+            return self.all_of(&any.all_of);
+        }
+
+        let typ = match &any.typ {
+            Some(typ) => typ.as_str(),
+            None if !any.properties.is_empty()
+                || !any.required.is_empty()
+                || any.additional_properties.is_some() =>
+            {
+                "object"
+            }
+            None if any.items.is_some() => "array",
+            None if any.minimum.is_some() || any.maximum.is_some() || any.multiple_of.is_some() => {
+                "number"
+            }
+            None => "string",
+        };
+        let minimum = any
+            .minimum
+            .map(|m| (m, any.exclusive_minimum.unwrap_or_default()));
+        let maximum = any
+            .maximum
+            .map(|m| (m, any.exclusive_maximum.unwrap_or_default()));
+        match typ {
+            "number" => self.number(any.multiple_of, minimum, maximum),
+            "integer" => self.integer(
+                any.multiple_of.map(|m| m as i64),
+                minimum.map(|(m, exclusive)| (m.ceil() as i64, exclusive && m.fract() == 0.0)),
+                maximum.map(|(m, exclusive)| (m.floor() as i64, exclusive && m.fract() == 0.0)),
+                false,
+            ),
+            "boolean" => Value::Bool(self.chance()),
+            "object" => self.object(
+                &any.properties,
+                &any.required,
+                any.additional_properties.as_ref(),
+                (any.min_properties, any.max_properties),
+            ),
+            "array" => self.array(
+                any.items.as_ref(),
+                (any.min_items, any.max_items),
+                any.unique_items.unwrap_or_default(),
+                any.contains.as_ref(),
+                any.min_contains,
+            ),
+            _ => self.string(any.format.as_deref(), any.min_length, any.max_length),
+        }
+    }
+
+    fn string(&mut self, format: Option<&str>, min: Option<usize>, max: Option<usize>) -> Value {
+        let digits = |generator: &mut Self, lo, hi| generator.between(lo, hi);
+        let string = match format {
+            Some("date") => format!(
+                "{:04}-{:02}-{:02}",
+                digits(self, 2000, 2030),
+                digits(self, 1, 12),
+                digits(self, 1, 28)
+            ),
+            Some("date-time") => format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                digits(self, 2000, 2030),
+                digits(self, 1, 12),
+                digits(self, 1, 28),
+                digits(self, 0, 23),
+                digits(self, 0, 59),
+                digits(self, 0, 59)
+            ),
+            Some("byte") => {
+                let len = self.length(min.unwrap_or(0) * 3 / 4, max.map(|max| max * 3 / 4), 12);
+                let bytes: Vec<u8> = (0..len).map(|_| self.next() as u8).collect();
+                base64(&bytes)
+            }
+            Some("uuid") => {
+                let (high, low) = (self.next(), self.next());
+                format!(
+                    "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+                    high >> 32,
+                    (high >> 16) & 0xffff,
+                    high & 0xfff,
+                    0x8000 | (low >> 48) & 0x3fff,
+                    low & 0xffff_ffff_ffff
+                )
+            }
+            Some("email") => format!("{}@example.com", self.word(min, max)),
+            Some("hostname") => format!("{}.example.com", self.word(min, max)),
+            Some("uri") | Some("url") => format!("https://example.com/{}", self.word(min, max)),
+            Some("ipv4") => format!(
+                "{}.{}.{}.{}",
+                digits(self, 1, 254),
+                digits(self, 0, 255),
+                digits(self, 0, 255),
+                digits(self, 1, 254)
+            ),
+            _ => self.word(min, max),
+        };
+        Value::String(string)
+    }
+
+    /// A string of lowercase letters whose length is within bounds.
+    fn word(&mut self, min: Option<usize>, max: Option<usize>) -> String {
+        let len = self.length(min.unwrap_or(0), max, 10);
+        (0..len)
+            .map(|_| (b'a' + self.between(0, 25) as u8) as char)
+            .collect()
+    }
+
+    fn integer(
+        &mut self,
+        multiple_of: Option<i64>,
+        minimum: Option<(i64, bool)>,
+        maximum: Option<(i64, bool)>,
+        int32: bool,
+    ) -> Value {
+        let mut lo = minimum.map(|(m, exclusive)| m as i128 + exclusive as i128);
+        let mut hi = maximum.map(|(m, exclusive)| m as i128 - exclusive as i128);
+        if int32 {
+            lo = Some(lo.unwrap_or(i128::MIN).max(i32::MIN as i128));
+            hi = Some(hi.unwrap_or(i128::MAX).min(i32::MAX as i128));
+        }
+        let (lo, hi) = match (lo, hi) {
+            (Some(lo), Some(hi)) if hi - lo > 1000 && lo <= 0 && hi >= 100 => (0, 100),
+            (Some(lo), Some(hi)) => (lo, hi.min(lo + 1000)),
+            (Some(lo), None) => (lo, lo + 100),
+            (None, Some(hi)) => (hi - 100, hi),
+            (None, None) => (0, 100),
+        };
+        let value = match multiple_of.filter(|m| *m > 0).map(i128::from) {
+            Some(m) => {
+                let first = lo.div_euclid(m) + (lo.rem_euclid(m) != 0) as i128;
+                self.between(first, hi.div_euclid(m)) * m
+            }
+            None => self.between(lo, hi),
+        };
+        Value::from(value as i64)
+    }
+
+    fn number(
+        &mut self,
+        multiple_of: Option<f64>,
+        minimum: Option<(f64, bool)>,
+        maximum: Option<(f64, bool)>,
+    ) -> Value {
+        let (lo, hi) = match (minimum, maximum) {
+            (Some((lo, _)), Some((hi, _))) => (lo, hi),
+            (Some((lo, _)), None) => (lo, lo + 100.0),
+            (None, Some((hi, _))) => (hi - 100.0, hi),
+            (None, None) => (0.0, 100.0),
+        };
+        let within = |n: f64| {
+            minimum.is_none_or(|(m, exclusive)| n > m || (!exclusive && n == m))
+                && maximum.is_none_or(|(m, exclusive)| n < m || (!exclusive && n == m))
+        };
+        let value = match multiple_of.filter(|m| *m > 0.0) {
+            Some(m) => {
+                let first = (lo / m).ceil() as i128;
+                let last = (hi / m).floor() as i128;
+                let candidates = [
+                    self.between(first, last),
+                    first,
+                    first.saturating_add(1),
+                    last,
+                    last.saturating_sub(1),
+                ];
+                candidates
+                    .into_iter()
+                    .map(|k| k as f64 * m)
+                    .find(|n| within(*n))
+                    .unwrap_or(lo)
+            }
+            None => {
+                let unit = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+                let n = ((lo + (hi - lo) * unit) * 100.0).round() / 100.0;
+                match within(n) {
+                    true => n,
+                    false => (lo + hi) / 2.0,
+                }
+            }
+        };
+        Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+
+    fn object(
+        &mut self,
+        properties: &IndexMap<String, ReferenceOr<Box<Schema>>>,
+        required: &[String],
+        additional_properties: Option<&AdditionalProperties>,
+        (min_properties, max_properties): (Option<usize>, Option<usize>),
+    ) -> Value {
+        let mut map = Map::new();
+        for name in required {
+            let value = match properties.get(name) {
+                Some(property) => self.generate_boxed(property),
+                None => self.string(None, None, None),
+            };
+            map.insert(name.clone(), value);
+        }
+
+        let max = max_properties.unwrap_or(usize::MAX);
+        let shallow = self.depth <= self.max_depth;
+        for (name, property) in properties {
+            let wanted = map.len() < min_properties.unwrap_or(0) || (shallow && self.chance());
+            if map.len() >= max || map.contains_key(name) || !wanted {
+                continue;
+            }
+            let value = self.generate_boxed(property);
+            map.insert(name.clone(), value);
+        }
+
+        let mut index = 1;
+        while map.len() < min_properties.unwrap_or(0).min(max) {
+            let value = match additional_properties {
+                Some(AdditionalProperties::Any(false)) => break,
+                Some(AdditionalProperties::Schema(schema)) => self.generate_ref(schema),
+                _ => self.string(None, None, None),
+            };
+            map.insert(format!("property{}", index), value);
+            index += 1;
+        }
+        Value::Object(map)
+    }
+
+    fn array(
+        &mut self,
+        items: Option<&ReferenceOr<Box<Schema>>>,
+        (min_items, max_items): (Option<usize>, Option<usize>),
+        unique_items: bool,
+        contains: Option<&ReferenceOr<Box<Schema>>>,
+        min_contains: Option<usize>,
+    ) -> Value {
+        let mut values = Vec::new();
+        if let Some(contains) = contains {
+            for _ in 0..min_contains.unwrap_or(1) {
+                values.push(self.generate_boxed(contains));
+            }
+        }
+        let len = match self.depth <= self.max_depth {
+            true => self.length(min_items.unwrap_or(0), max_items, 2),
+            false => min_items.unwrap_or(0),
+        };
+        let mut attempts = 0;
+        while values.len() < len && attempts < len * 8 {
+            attempts += 1;
+            let value = match items {
+                Some(items) => self.generate_boxed(items),
+                None => self.string(None, None, None),
+            };
+            if !unique_items || !values.contains(&value) {
+                values.push(value);
+            }
+        }
+        Value::Array(values)
+    }
+
+    fn one_of(&mut self, schema: &Schema, one_of: &[ReferenceOr<Schema>]) -> Value {
+        let variant = match self.pick(one_of) {
+            Some(variant) => variant,
+            None => return Value::Null,
+        };
+        let mut value = self.generate_ref(variant);
+        if let (Some(discriminator), ReferenceOr::Reference { reference }, Value::Object(map)) =
+            (&schema.schema_data.discriminator, variant, &mut value)
+        {
+            if let Ok(name) = component_name(reference, ComponentKind::Schemas) {
+                let names = |target: &String| match target.starts_with('#') {
+                    true => component_name(target, ComponentKind::Schemas).is_ok_and(|n| n == name),
+                    false => *target == name,
+                };
+                let key = discriminator
+                    .mapping
+                    .iter()
+                    .find(|(_, target)| names(target))
+                    .map_or(name.clone(), |(key, _)| key.clone());
+                map.insert(discriminator.property_name.clone(), Value::String(key));
+            }
+        }
+        value
+    }
+
+    fn all_of(&mut self, all_of: &[ReferenceOr<Schema>]) -> Value {
+        let mut merged = Value::Null;
+        for schema in all_of {
+            match (&mut merged, self.generate_ref(schema)) {
+                (Value::Object(merged), Value::Object(map)) => {
+                    for (key, value) in map {
+                        merged.entry(key).or_insert(value);
+                    }
+                }
+                (_, Value::Null) => {}
+                (merged, value) => *merged = value,
+            }
+        }
+        merged
+    }
+}
+
+/// Encodes `bytes` as standard, padded base64.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_generated_values_validate() {
+        let components: Components = serde_json::from_value(json!({
+            "schemas": {
+                "Pet": {
+                    "oneOf": [
+                        { "$ref": "#/components/schemas/Cat" },
+                        { "$ref": "#/components/schemas/Dog" }
+                    ],
+                    "discriminator": { "propertyName": "kind", "mapping": { "dog": "#/components/schemas/Dog" } }
+                },
+                "Cat": {
+                    "type": "object",
+                    "required": ["kind", "lives"],
+                    "properties": {
+                        "kind": { "type": "string" },
+                        "lives": { "type": "integer", "format": "int32", "minimum": 1, "maximum": 9, "exclusiveMaximum": true }
+                    }
+                },
+                "Dog": {
+                    "allOf": [
+                        { "$ref": "#/components/schemas/Named" },
+                        {
+                            "type": "object",
+                            "required": ["kind", "born"],
+                            "properties": {
+                                "kind": { "type": "string" },
+                                "born": { "type": "string", "format": "date" },
+                                "friends": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                            }
+                        }
+                    ]
+                },
+                "Named": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": { "name": { "type": "string", "minLength": 2, "maxLength": 4 } }
+                },
+                "Owner": {
+                    "type": "object",
+                    "required": ["id", "pets", "weight", "photo", "seen"],
+                    "minProperties": 7,
+                    "properties": {
+                        "id": { "type": "string", "format": "uuid" },
+                        "pets": { "type": "array", "minItems": 1, "maxItems": 3, "items": { "$ref": "#/components/schemas/Pet" } },
+                        "weight": { "type": "number", "minimum": 0, "exclusiveMinimum": true, "multipleOf": 0.5 },
+                        "photo": { "type": "string", "format": "byte" },
+                        "seen": { "type": "string", "format": "date-time" },
+                        "codes": { "type": "array", "uniqueItems": true, "minItems": 3, "items": { "type": "integer", "enum": [1, 2, 3] } },
+                        "status": { "type": "string", "enum": ["new", "gone"], "nullable": true }
+                    },
+                    "additionalProperties": { "type": "boolean" }
+                }
+            }
+        }))
+        .unwrap();
+        let owner = components.schemas["Owner"].as_item().unwrap();
+        for seed in 0..50 {
+            let value = MockGenerator::new(seed)
+                .components(&components)
+                .generate(owner);
+            assert_eq!(
+                owner.validate_value(&value, Some(&components)),
+                [],
+                "seed {}: {}",
+                seed,
+                value
+            );
+            assert!(value.as_object().unwrap().len() >= 7);
+        }
+
+        let mut generator = MockGenerator::new(1).components(&components);
+        let pet = ReferenceOr::ref_("#/components/schemas/Pet");
+        let kinds: Vec<_> = (0..10)
+            .map(|_| generator.generate_ref(&pet)["kind"].clone())
+            .collect();
+        assert!(kinds.contains(&json!("dog")));
+        assert!(kinds.contains(&json!("Cat")));
+        assert_eq!(MockGenerator::new(1).generate_ref(&pet), Value::Null);
+    }
+
+    #[test]
+    fn test_extreme_bounds() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "number", "minimum": -1e300, "maximum": 1e300, "multipleOf": 1e-10
+        }))
+        .unwrap();
+        for seed in 0..20 {
+            let value = MockGenerator::new(seed).generate(&schema);
+            let n = value.as_f64().unwrap();
+            assert!((-1e300..=1e300).contains(&n), "seed {}: {}", seed, n);
+        }
+
+        let mut generator = MockGenerator::new(0);
+        for (lo, hi) in [(i128::MIN, i128::MAX), (i128::MIN, 0), (-1, i128::MAX)] {
+            let n = generator.between(lo, hi);
+            assert!(lo <= n && n <= hi);
+        }
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
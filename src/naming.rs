@@ -0,0 +1,524 @@
+use std::collections::HashSet;
+
+use crate::*;
+use indexmap::IndexMap;
+
+/// Assigns deterministic, collision-free names to anonymous schemas.
+///
+/// Names are derived from a list of location parts, e.g. `["get", "pets",
+/// "response", "item"]`, which are converted to PascalCase and joined. If the
+/// result is already taken a numeric suffix is appended, starting at `2`.
+/// Given the same reserved names and the same sequence of requests a namer
+/// always produces the same names.
+///
+/// ```
+/// # use openapiv3::SchemaNamer;
+/// let mut namer = SchemaNamer::new();
+/// namer.reserve("GetPetsResponse");
+/// assert_eq!(namer.name(["get", "pets", "response"]), "GetPetsResponse2");
+/// assert_eq!(namer.name(["get_pets", "response", "item"]), "GetPetsResponseItem");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaNamer {
+    taken: HashSet<String>,
+}
+
+impl SchemaNamer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a namer that will not hand out the names of the schemas
+    /// already present in `components`.
+    pub fn for_components(components: &Components) -> Self {
+        let mut namer = Self::new();
+        for name in components.schemas.keys() {
+            namer.reserve(name.clone());
+        }
+        namer
+    }
+
+    /// Marks `name` as taken. Returns `false` if it already was.
+    pub fn reserve(&mut self, name: impl Into<String>) -> bool {
+        self.taken.insert(name.into())
+    }
+
+    /// Returns `true` if `name` has been reserved or handed out.
+    pub fn is_taken(&self, name: &str) -> bool {
+        self.taken.contains(name)
+    }
+
+    /// Returns a fresh name built from the given location parts and marks it
+    /// as taken.
+    pub fn name<I, S>(&mut self, parts: I) -> String
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut base = parts
+            .into_iter()
+            .map(|part| pascal_case(part.as_ref()))
+            .collect::<String>();
+        if !base.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            base.insert_str(0, "Schema");
+        }
+
+        let mut name = base.clone();
+        let mut n = 2;
+        while self.is_taken(&name) {
+            name = format!("{}{}", base, n);
+            n += 1;
+        }
+        self.taken.insert(name.clone());
+        name
+    }
+}
+
+/// Converts `s` to PascalCase, dropping any characters that are not ASCII
+/// alphanumeric. The case of letters within words is preserved, so `petId`
+/// becomes `PetId`.
+pub fn pascal_case(s: &str) -> String {
+    s.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap_or_default();
+            let rest = chars.as_str();
+            if first.is_ascii_uppercase()
+                && rest
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            {
+                // All-caps words such as `GET` or `ID`.
+                format!("{}{}", first, rest.to_ascii_lowercase())
+            } else {
+                format!("{}{}", first.to_ascii_uppercase(), rest)
+            }
+        })
+        .collect()
+}
+
+/// Options for [OpenAPI::promote_inline_schemas_with].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromoteOptions {
+    min_properties: usize,
+    titles: bool,
+}
+
+impl Default for PromoteOptions {
+    fn default() -> Self {
+        PromoteOptions {
+            min_properties: 1,
+            titles: false,
+        }
+    }
+}
+
+impl PromoteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of properties an object schema needs to be promoted,
+    /// so that small objects stay inline. Defaults to 1.
+    pub fn min_properties(mut self, min_properties: usize) -> Self {
+        self.min_properties = min_properties.max(1);
+        self
+    }
+
+    /// Sets whether schemas with a `title` are named after it instead of
+    /// after where they appear. Defaults to `false`.
+    pub fn titles(mut self, titles: bool) -> Self {
+        self.titles = titles;
+        self
+    }
+}
+
+impl OpenAPI {
+    /// Moves anonymous object, enum and composite schemas into
+    /// `components/schemas`, replacing them with references, and returns the
+    /// names assigned to them in document order.
+    ///
+    /// Names are derived from where a schema appears: the operation (its
+    /// `operationId` or its method and path), followed by e.g. the parameter
+    /// name, `Request`, `Response` (suffixed with the status code for non-2XX
+    /// responses) and, for nested schemas, the property name, `Item`, `Value`
+    /// or `Variant`. Schemas nested in a promoted schema are named after it,
+    /// so the items of an inline array returned by `GET /pets` become
+    /// `GetPetsResponseItem`. Names already used in `components/schemas` or
+    /// reserved in `namer` are avoided.
+    pub fn promote_inline_schemas(&mut self, namer: &mut SchemaNamer) -> Vec<String> {
+        self.promote_inline_schemas_with(namer, &PromoteOptions::default())
+    }
+
+    /// Like [OpenAPI::promote_inline_schemas], leaving small objects inline
+    /// or naming schemas after their titles as set in `options`.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "post": {
+    ///         "operationId": "addPet",
+    ///         "requestBody": { "content": { "application/json": { "schema": {
+    ///             "title": "new pet",
+    ///             "type": "object",
+    ///             "properties": {
+    ///                 "name": { "type": "string" },
+    ///                 "owner": { "type": "object", "properties": { "id": { "type": "integer" } } }
+    ///             }
+    ///         } } } },
+    ///         "responses": {}
+    ///     } } }
+    /// }"#).unwrap();
+    /// let options = PromoteOptions::new().min_properties(2).titles(true);
+    /// let names = api.promote_inline_schemas_with(&mut SchemaNamer::new(), &options);
+    /// assert_eq!(names, ["NewPet"]);
+    /// ```
+    pub fn promote_inline_schemas_with(
+        &mut self,
+        namer: &mut SchemaNamer,
+        options: &PromoteOptions,
+    ) -> Vec<String> {
+        let mut components = self.components.take().unwrap_or_default();
+        for name in components.schemas.keys() {
+            namer.reserve(name.clone());
+        }
+
+        let mut promoter = Promoter {
+            namer,
+            options,
+            promoted: IndexMap::new(),
+        };
+
+        for (path, item) in self.paths.paths.iter_mut() {
+            if let ReferenceOr::Item(item) = item {
+                let parts = path_parts(path);
+                promoter.path_item(item, &parts);
+            }
+        }
+
+        for (name, schema) in components.schemas.iter_mut() {
+            if let ReferenceOr::Item(schema) = schema {
+                promoter.children(schema, std::slice::from_ref(name));
+            }
+        }
+        for (name, response) in components.responses.iter_mut() {
+            if let ReferenceOr::Item(response) = response {
+                promoter.response(response, std::slice::from_ref(name));
+            }
+        }
+        for (name, parameter) in components.parameters.iter_mut() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                promoter.parameter(parameter, std::slice::from_ref(name));
+            }
+        }
+        for (name, body) in components.request_bodies.iter_mut() {
+            if let ReferenceOr::Item(body) = body {
+                promoter.content(&mut body.content, std::slice::from_ref(name));
+            }
+        }
+        for (name, header) in components.headers.iter_mut() {
+            if let ReferenceOr::Item(header) = header {
+                promoter.schema_or_content(&mut header.format, std::slice::from_ref(name));
+            }
+        }
+        for (name, callback) in components.callbacks.iter_mut() {
+            if let ReferenceOr::Item(callback) = callback {
+                promoter.callback(callback, std::slice::from_ref(name));
+            }
+        }
+
+        let names = promoter.promoted.keys().cloned().collect();
+        for (name, schema) in promoter.promoted {
+            if let Some(schema) = schema {
+                components.schemas.insert(name, ReferenceOr::Item(schema));
+            }
+        }
+        if components != Components::default() {
+            self.components = Some(components);
+        }
+        names
+    }
+}
+
+fn path_parts(path: &str) -> Vec<String> {
+    path.split(['/', '{', '}'])
+        .filter(|part| !part.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn with(parts: &[String], part: impl Into<String>) -> Vec<String> {
+    let mut parts = parts.to_vec();
+    parts.push(part.into());
+    parts
+}
+
+/// Whether a schema would need a named type of its own in generated code.
+fn is_promotable(schema: &Schema, options: &PromoteOptions) -> bool {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => object.properties.len() >= options.min_properties,
+        SchemaKind::Type(Type::String(string)) => !string.enumeration.is_empty(),
+        SchemaKind::Type(Type::Integer(integer)) => !integer.enumeration.is_empty(),
+        SchemaKind::Type(Type::Number(number)) => !number.enumeration.is_empty(),
+        SchemaKind::Type(_) | SchemaKind::Not { .. } => false,
+        SchemaKind::OneOf { .. } | SchemaKind::AllOf { .. } | SchemaKind::AnyOf { .. } => true,
+        SchemaKind::Any(any) => {
+            any.properties.len() >= options.min_properties
+                || !any.enumeration.is_empty()
+                || !any.one_of.is_empty()
+                || !any.all_of.is_empty()
+                || !any.any_of.is_empty()
+        }
+    }
+}
+
+struct Promoter<'a> {
+    namer: &'a mut SchemaNamer,
+    options: &'a PromoteOptions,
+    promoted: IndexMap<String, Option<Schema>>,
+}
+
+impl Promoter<'_> {
+    fn path_item(&mut self, item: &mut PathItem, parts: &[String]) {
+        for parameter in item.parameters.iter_mut() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                self.parameter(parameter, parts);
+            }
+        }
+        for (method, operation) in item.iter_mut() {
+            let parts = match &operation.operation_id {
+                Some(id) => vec![id.clone()],
+                None => {
+                    let mut op_parts = vec![method.to_owned()];
+                    op_parts.extend_from_slice(parts);
+                    op_parts
+                }
+            };
+            self.operation(operation, &parts);
+        }
+    }
+
+    fn operation(&mut self, operation: &mut Operation, parts: &[String]) {
+        for parameter in operation.parameters.iter_mut() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                self.parameter(parameter, parts);
+            }
+        }
+        if let Some(ReferenceOr::Item(body)) = &mut operation.request_body {
+            self.content(&mut body.content, &with(parts, "Request"));
+        }
+        if let Some(ReferenceOr::Item(response)) = &mut operation.responses.default {
+            self.response(response, &with(parts, "ResponseDefault"));
+        }
+        for (status, response) in operation.responses.responses.iter_mut() {
+            if let ReferenceOr::Item(response) = response {
+                let part = match status {
+                    StatusCode::Code(200..=299) | StatusCode::Range(2) => "Response".to_owned(),
+                    status => format!("Response{}", status),
+                };
+                self.response(response, &with(parts, part));
+            }
+        }
+        for (name, callback) in operation.callbacks.iter_mut() {
+            self.callback(callback, &with(parts, name.as_str()));
+        }
+    }
+
+    fn callback(&mut self, callback: &mut Callback, parts: &[String]) {
+        for item in callback.values_mut() {
+            for (method, operation) in item.iter_mut() {
+                self.operation(operation, &with(parts, method));
+            }
+        }
+    }
+
+    fn parameter(&mut self, parameter: &mut Parameter, parts: &[String]) {
+        let data = parameter.parameter_data_mut();
+        let parts = with(parts, data.name.as_str());
+        self.schema_or_content(&mut data.format, &parts);
+    }
+
+    fn response(&mut self, response: &mut Response, parts: &[String]) {
+        for (name, header) in response.headers.iter_mut() {
+            if let ReferenceOr::Item(header) = header {
+                self.schema_or_content(&mut header.format, &with(parts, name.as_str()));
+            }
+        }
+        self.content(&mut response.content, parts);
+    }
+
+    fn schema_or_content(&mut self, format: &mut ParameterSchemaOrContent, parts: &[String]) {
+        match format {
+            ParameterSchemaOrContent::Schema(schema) => {
+                self.schema(SchemaSlot::Plain(schema), parts)
+            }
+            ParameterSchemaOrContent::Content(content) => self.content(content, parts),
+        }
+    }
+
+    fn content(&mut self, content: &mut IndexMap<String, MediaType>, parts: &[String]) {
+        for media_type in content.values_mut() {
+            if let Some(schema) = &mut media_type.schema {
+                self.schema(SchemaSlot::Plain(schema), parts);
+            }
+        }
+    }
+
+    fn schema(&mut self, mut slot: SchemaSlot<'_>, parts: &[String]) {
+        let Some(schema) = slot.as_item_mut() else {
+            return;
+        };
+        if !is_promotable(schema, self.options) {
+            self.children(schema, parts);
+            return;
+        }
+
+        let title = match &schema.schema_data.title {
+            Some(title) if self.options.titles && !pascal_case(title).is_empty() => Some(title),
+            _ => None,
+        };
+        let name = match title {
+            Some(title) => self.namer.name([title]),
+            None => self.namer.name(parts),
+        };
+        self.promoted.insert(name.clone(), None);
+        self.children(schema, std::slice::from_ref(&name));
+        let schema = slot.replace_with_reference(Reference::schema(name.clone()).to_string());
+        self.promoted.insert(name, schema);
+    }
+
+    fn children(&mut self, schema: &mut Schema, parts: &[String]) {
+        for (key, slot) in schema.subschemas_mut() {
+            let part = match key {
+                SubschemaKey::Property(name) => name.to_owned(),
+                SubschemaKey::AdditionalProperties => "Value".to_owned(),
+                SubschemaKey::Items => "Item".to_owned(),
+                SubschemaKey::Contains => "Contains".to_owned(),
+                SubschemaKey::OneOf(i) | SubschemaKey::AnyOf(i) | SubschemaKey::AllOf(i) => {
+                    format!("Variant{}", i + 1)
+                }
+                SubschemaKey::Not => "Not".to_owned(),
+            };
+            self.schema(slot, &with(parts, part));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(pascal_case("petId"), "PetId");
+        assert_eq!(pascal_case("pet_id"), "PetId");
+        assert_eq!(pascal_case("GET"), "Get");
+        assert_eq!(pascal_case("x-rate-limit"), "XRateLimit");
+        assert_eq!(pascal_case("application/json"), "ApplicationJson");
+    }
+
+    #[test]
+    fn test_promote_inline_schemas() {
+        let mut openapi: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {
+                    "/pets": {
+                        "get": {
+                            "parameters": [{
+                                "name": "status", "in": "query",
+                                "schema": { "type": "string", "enum": ["a", "b"] }
+                            }],
+                            "responses": {
+                                "200": {
+                                    "description": "",
+                                    "content": { "application/json": { "schema": {
+                                        "type": "array",
+                                        "items": {
+                                            "type": "object",
+                                            "properties": {
+                                                "owner": {
+                                                    "type": "object",
+                                                    "properties": { "name": { "type": "string" } }
+                                                },
+                                                "tag": { "$ref": "#/components/schemas/Tag" }
+                                            }
+                                        }
+                                    } } }
+                                },
+                                "404": {
+                                    "description": "",
+                                    "content": { "application/json": { "schema": {
+                                        "type": "object",
+                                        "properties": { "message": { "type": "string" } }
+                                    } } }
+                                }
+                            }
+                        }
+                    }
+                },
+                "components": { "schemas": {
+                    "Tag": {
+                        "type": "object",
+                        "properties": { "kind": { "oneOf": [ { "type": "string" } ] } }
+                    },
+                    "GetPetsStatus": { "type": "string" }
+                } }
+            }"##,
+        )
+        .unwrap();
+
+        let mut namer = SchemaNamer::new();
+        let names = openapi.promote_inline_schemas(&mut namer);
+        assert_eq!(
+            names,
+            vec![
+                "GetPetsStatus2",
+                "GetPetsResponseItem",
+                "GetPetsResponseItemOwner",
+                "GetPetsResponse404",
+                "TagKind",
+            ]
+        );
+
+        let schemas = &openapi.components.as_ref().unwrap().schemas;
+        assert_eq!(schemas.len(), 7);
+        let (_, _, operation) = openapi.operations().next().unwrap();
+        assert_eq!(
+            operation.parameters[0]
+                .as_item()
+                .unwrap()
+                .parameter_data_ref()
+                .format,
+            ParameterSchemaOrContent::Schema(Box::new(ReferenceOr::ref_(
+                "#/components/schemas/GetPetsStatus2"
+            )))
+        );
+
+        let item = schemas["GetPetsResponseItem"].as_item().unwrap();
+        match &item.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => {
+                assert_eq!(
+                    object.properties["owner"],
+                    ReferenceOr::ref_("#/components/schemas/GetPetsResponseItemOwner")
+                );
+                assert_eq!(
+                    object.properties["tag"],
+                    ReferenceOr::ref_("#/components/schemas/Tag")
+                );
+            }
+            kind => panic!("unexpected {:?}", kind),
+        }
+
+        // A second run finds nothing left to promote.
+        let mut again = openapi.clone();
+        assert!(again
+            .promote_inline_schemas(&mut SchemaNamer::new())
+            .is_empty());
+        assert_eq!(again, openapi);
+    }
+}
@@ -0,0 +1,319 @@
+use crate::*;
+use serde_json::{json, Map, Value};
+
+impl Schema {
+    /// Returns `true` if this schema is marked as accepting `null`: it is
+    /// `nullable`, or one of its `oneOf` or `anyOf` members, or all of its
+    /// `allOf` members, are. References are followed in `components`, if
+    /// given, and are not nullable otherwise.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let schema: Schema = serde_json::from_str(r#"{
+    ///     "oneOf": [ { "type": "string" }, { "type": "integer", "nullable": true } ]
+    /// }"#).unwrap();
+    /// assert!(schema.is_nullable(None));
+    /// ```
+    pub fn is_nullable(&self, components: Option<&Components>) -> bool {
+        is_nullable(self, components, &mut Vec::new())
+    }
+}
+
+/// Implements [Schema::is_nullable], with `followed` the references being
+/// followed: a reference leading back to one of them is not nullable.
+fn is_nullable<'a>(
+    schema: &'a Schema,
+    components: Option<&'a Components>,
+    followed: &mut Vec<&'a str>,
+) -> bool {
+    let member = |schema: &'a ReferenceOr<Schema>| match (schema, components) {
+        (ReferenceOr::Item(schema), _) => is_nullable(schema, components, followed),
+        (ReferenceOr::Reference { reference }, Some(resolved)) => {
+            if followed.contains(&reference.as_str()) {
+                return false;
+            }
+            followed.push(reference);
+            let nullable = schema
+                .resolve(resolved)
+                .is_ok_and(|schema| is_nullable(schema, components, followed));
+            followed.pop();
+            nullable
+        }
+        (ReferenceOr::Reference { .. }, None) => false,
+    };
+
+    if schema.schema_data.nullable {
+        return true;
+    }
+    match &schema.schema_kind {
+        SchemaKind::OneOf { one_of: members } | SchemaKind::AnyOf { any_of: members } => {
+            members.iter().any(member)
+        }
+        SchemaKind::AllOf { all_of } => !all_of.is_empty() && all_of.iter().all(member),
+        _ => false,
+    }
+}
+
+/// Returns `true` if a JSON Schema, of OpenAPI 3.0 or 3.1, accepts `null`
+/// explicitly: it is `nullable`, its `type` is or includes `null`, or one of
+/// its `oneOf` or `anyOf` members does. References are not followed.
+///
+/// ```
+/// # use openapiv3::is_nullable_schema;
+/// # use serde_json::json;
+/// assert!(is_nullable_schema(&json!({ "type": "string", "nullable": true })));
+/// assert!(is_nullable_schema(&json!({ "type": ["string", "null"] })));
+/// assert!(is_nullable_schema(&json!({ "anyOf": [ { "$ref": "#/components/schemas/Pet" }, { "type": "null" } ] })));
+/// assert!(!is_nullable_schema(&json!({ "type": "string" })));
+/// ```
+pub fn is_nullable_schema(schema: &Value) -> bool {
+    let Some(object) = schema.as_object() else {
+        return false;
+    };
+    if object.get("nullable") == Some(&Value::Bool(true)) {
+        return true;
+    }
+    match object.get("type") {
+        Some(Value::String(typ)) if typ == "null" => return true,
+        Some(Value::Array(types)) if types.iter().any(|t| t == "null") => return true,
+        _ => {}
+    }
+    ["oneOf", "anyOf"].iter().any(|keyword| {
+        object
+            .get(*keyword)
+            .and_then(Value::as_array)
+            .is_some_and(|members| members.iter().any(is_nullable_schema))
+    })
+}
+
+/// Rewrites the `nullable` keywords of a JSON Schema and its subschemas in
+/// the style of OpenAPI 3.1.
+///
+/// `null` is added to `type`, and to `enum` if it is present. Schemas
+/// without a `type` get a `{ "type": "null" }` member in their `oneOf` or
+/// `anyOf`, or are wrapped in an `anyOf` with one if they have an `allOf` or
+/// a `$ref`. Schemas without any of these accept `null` already.
+///
+/// ```
+/// # use openapiv3::nullable_to_3_1;
+/// # use serde_json::json;
+/// let mut schema = json!({ "type": "object", "properties": {
+///     "name": { "type": "string", "nullable": true },
+///     "owner": { "allOf": [ { "$ref": "#/components/schemas/Owner" } ], "nullable": true }
+/// } });
+/// nullable_to_3_1(&mut schema);
+/// assert_eq!(schema, json!({ "type": "object", "properties": {
+///     "name": { "type": ["string", "null"] },
+///     "owner": { "anyOf": [
+///         { "allOf": [ { "$ref": "#/components/schemas/Owner" } ] },
+///         { "type": "null" }
+///     ] }
+/// } }));
+/// ```
+pub fn nullable_to_3_1(schema: &mut Value) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    if object.remove("nullable") == Some(Value::Bool(true)) {
+        let alternatives = match object.contains_key("oneOf") {
+            true => "oneOf",
+            false => "anyOf",
+        };
+        if let Some(typ) = object.get_mut("type") {
+            if let Value::String(name) = typ {
+                *typ = json!([name, "null"]);
+            }
+            if let Some(Value::Array(values)) = object.get_mut("enum") {
+                if !values.contains(&Value::Null) {
+                    values.push(Value::Null);
+                }
+            }
+        } else if let Some(Value::Array(members)) = object.get_mut(alternatives) {
+            members.push(json!({ "type": "null" }));
+        } else if object.contains_key("allOf") || object.contains_key("$ref") {
+            let inner = std::mem::take(object);
+            object.insert("anyOf".to_owned(), json!([inner, { "type": "null" }]));
+        }
+    }
+    for_each_subschema(schema, nullable_to_3_1);
+}
+
+/// Rewrites a JSON Schema and its subschemas in the style of OpenAPI 3.1
+/// into `nullable` keywords, reversing [nullable_to_3_1].
+///
+/// `null` is removed from `type`, which becomes a single type if only one
+/// remains, and from `enum`. `{ "type": "null" }` members are removed from
+/// `oneOf` and `anyOf`, and an `anyOf` left with a single member is replaced
+/// by it. A `type` of just `null` becomes `"enum": [null]`.
+///
+/// ```
+/// # use openapiv3::nullable_to_3_0;
+/// # use serde_json::json;
+/// let mut schema = json!({ "type": "array", "items": { "type": ["integer", "null"], "enum": [1, 2, null] } });
+/// nullable_to_3_0(&mut schema);
+/// assert_eq!(
+///     schema,
+///     json!({ "type": "array", "items": { "type": "integer", "enum": [1, 2], "nullable": true } })
+/// );
+/// ```
+pub fn nullable_to_3_0(schema: &mut Value) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    let mut nullable = false;
+    match object.get_mut("type") {
+        Some(Value::Array(types)) if types.contains(&json!("null")) => {
+            types.retain(|t| t != "null");
+            if types.len() == 1 {
+                let typ = types.remove(0);
+                object.insert("type".to_owned(), typ);
+            }
+            nullable = true;
+        }
+        Some(Value::String(typ)) if typ == "null" => {
+            object.remove("type");
+            object.insert("enum".to_owned(), json!([null]));
+            nullable = true;
+        }
+        _ => {}
+    }
+    if nullable {
+        if let Some(Value::Array(values)) = object.get_mut("enum") {
+            if values.len() > 1 {
+                values.retain(|value| !value.is_null());
+            }
+        }
+    }
+    for keyword in ["oneOf", "anyOf"] {
+        if let Some(Value::Array(members)) = object.get_mut(keyword) {
+            let before = members.len();
+            members.retain(|member| member != &json!({ "type": "null" }));
+            nullable |= members.len() < before;
+        }
+    }
+    if nullable {
+        unwrap_single_any_of(object);
+        object.insert("nullable".to_owned(), Value::Bool(true));
+    }
+    for_each_subschema(schema, nullable_to_3_0);
+}
+
+/// Replaces an object whose only keyword is an `anyOf` with a single object
+/// member by that member.
+fn unwrap_single_any_of(object: &mut Map<String, Value>) {
+    if object.len() != 1 {
+        return;
+    }
+    if let Some(Value::Array(members)) = object.get_mut("anyOf") {
+        if let [Value::Object(member)] = members.as_mut_slice() {
+            *object = std::mem::take(member);
+        }
+    }
+}
+
+/// Calls `f` on each immediate subschema of a JSON Schema.
+fn for_each_subschema(schema: &mut Value, f: fn(&mut Value)) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    for (keyword, value) in object.iter_mut() {
+        match (keyword.as_str(), value) {
+            ("properties", Value::Object(properties)) => properties.values_mut().for_each(f),
+            ("allOf" | "anyOf" | "oneOf", Value::Array(members)) => members.iter_mut().for_each(f),
+            ("items" | "additionalProperties" | "not" | "contains", value) => f(value),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullable_round_trip() {
+        let schema = json!({ "type": "object", "properties": {
+            "kind": { "type": "string", "enum": ["dog", "cat"], "nullable": true },
+            "tags": { "type": "array", "items": { "type": "string", "nullable": true } },
+            "pet": { "oneOf": [ { "$ref": "#/components/schemas/Dog" } ], "nullable": true },
+            "owner": { "$ref": "#/components/schemas/Owner", "nullable": true },
+            "anything": { "nullable": true },
+            "count": { "type": "integer", "nullable": false },
+            "example": { "type": "object", "example": { "nullable": true } }
+        } });
+
+        let mut converted = schema.clone();
+        nullable_to_3_1(&mut converted);
+        let properties = &converted["properties"];
+        assert_eq!(
+            properties["kind"],
+            json!({ "type": ["string", "null"], "enum": ["dog", "cat", null] })
+        );
+        assert_eq!(
+            properties["tags"]["items"]["type"],
+            json!(["string", "null"])
+        );
+        assert_eq!(properties["pet"]["oneOf"][1], json!({ "type": "null" }));
+        assert_eq!(
+            properties["owner"],
+            json!({ "anyOf": [ { "$ref": "#/components/schemas/Owner" }, { "type": "null" } ] })
+        );
+        assert_eq!(properties["anything"], json!({}));
+        assert_eq!(properties["count"], json!({ "type": "integer" }));
+        assert_eq!(
+            properties["example"]["example"],
+            json!({ "nullable": true })
+        );
+        for name in ["kind", "tags", "pet", "owner"] {
+            assert_eq!(
+                is_nullable_schema(&properties[name]),
+                name != "tags",
+                "{}",
+                name
+            );
+        }
+
+        nullable_to_3_0(&mut converted);
+        let mut expected = schema;
+        expected["properties"]["anything"] = json!({});
+        expected["properties"]["count"] = json!({ "type": "integer" });
+        assert_eq!(converted, expected);
+
+        let mut null = json!({ "type": "null" });
+        nullable_to_3_0(&mut null);
+        assert_eq!(null, json!({ "enum": [null], "nullable": true }));
+    }
+
+    #[test]
+    fn test_is_nullable() {
+        let components: Components = serde_json::from_value(json!({ "schemas": {
+            "Null": { "type": "string", "nullable": true }
+        } }))
+        .unwrap();
+        let schema: Schema = serde_json::from_value(json!({
+            "allOf": [ { "$ref": "#/components/schemas/Null" }, { "nullable": true } ]
+        }))
+        .unwrap();
+        assert!(schema.is_nullable(Some(&components)));
+        assert!(!schema.is_nullable(None));
+        let any: Schema = serde_json::from_value(json!({})).unwrap();
+        assert!(!any.is_nullable(None));
+    }
+
+    #[test]
+    fn test_is_nullable_self_reference() {
+        let components: Components = serde_json::from_value(json!({ "schemas": {
+            "A": { "anyOf": [ { "$ref": "#/components/schemas/A" } ] },
+            "B": { "oneOf": [
+                { "$ref": "#/components/schemas/C" },
+                { "type": "string", "nullable": true }
+            ] },
+            "C": { "allOf": [ { "$ref": "#/components/schemas/B" } ] }
+        } }))
+        .unwrap();
+        let a = components.schemas["A"].as_item().unwrap();
+        assert!(!a.is_nullable(Some(&components)));
+        let c = components.schemas["C"].as_item().unwrap();
+        assert!(c.is_nullable(Some(&components)));
+    }
+}
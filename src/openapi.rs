@@ -1,5 +1,4 @@
 use crate::*;
-use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// This is the root document object of the OpenAPI document.
@@ -45,7 +44,7 @@ pub struct OpenAPI {
     pub external_docs: Option<ExternalDocumentation>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 
 impl OpenAPI {
@@ -64,4 +63,50 @@ impl OpenAPI {
                     .map(move |(method, op)| (path.as_str(), method, op))
             })
     }
+
+    /// Iterates through mutable references to all [Operation]s in this API.
+    ///
+    /// Path items containing `$ref`s are skipped.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (&str, &str, &mut Operation)> {
+        self.paths
+            .paths
+            .iter_mut()
+            .filter_map(|(path, item)| match item {
+                ReferenceOr::Item(item) => Some((path, item)),
+                ReferenceOr::Reference { .. } => None,
+            })
+            .flat_map(|(path, item)| {
+                item.iter_mut()
+                    .map(move |(method, op)| (path.as_str(), method, op))
+            })
+    }
+
+    /// Applies `editor` to every operation matched by `filter` and returns the
+    /// number of operations edited.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// # let mut openapi: OpenAPI = serde_json::from_str(r#"{
+    /// #     "openapi": "3.0.3", "info": { "title": "", "version": "" },
+    /// #     "paths": { "/pets": { "get": { "responses": {} }, "post": { "responses": {} } } }
+    /// # }"#).unwrap();
+    /// // Add an `X-Request-Id` header parameter to every POST.
+    /// let edited = openapi.edit_operations(&OperationFilter::new().method("post"), |_, _, op| {
+    ///     op.parameters.push(ReferenceOr::ref_("#/components/parameters/RequestId"));
+    /// });
+    /// assert_eq!(edited, 1);
+    /// ```
+    pub fn edit_operations<F>(&mut self, filter: &OperationFilter, mut editor: F) -> usize
+    where
+        F: FnMut(&str, &str, &mut Operation),
+    {
+        let mut edited = 0;
+        for (path, method, operation) in self.operations_mut() {
+            if filter.matches(path, method, operation) {
+                editor(path, method, operation);
+                edited += 1;
+            }
+        }
+        edited
+    }
 }
@@ -70,7 +70,7 @@ pub struct Operation {
     pub servers: Vec<Server>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 
 #[cfg(test)]
@@ -103,14 +103,14 @@ mod tests {
                     default: None,
                     responses: {
                         let mut map = IndexMap::new();
-                        map.insert(StatusCode::Code(666), ReferenceOr::ref_("demo"));
+                        map.insert(StatusCode::Code(599), ReferenceOr::ref_("demo"));
                         map
                     },
                     ..Default::default()
                 },
                 ..Default::default()
             },
-            from_str("{ responses: { \"666\": { $ref: 'demo' } } }").unwrap(),
+            from_str("{ responses: { \"599\": { $ref: 'demo' } } }").unwrap(),
         );
 
         assert_eq!(
@@ -119,7 +119,7 @@ mod tests {
                     default: Some(ReferenceOr::ref_("def")),
                     responses: {
                         let mut map = IndexMap::new();
-                        map.insert(StatusCode::Code(666), ReferenceOr::ref_("demo"));
+                        map.insert(StatusCode::Code(599), ReferenceOr::ref_("demo"));
                         map.insert(StatusCode::Code(418), ReferenceOr::ref_("demo"));
                         map
                     },
@@ -127,7 +127,7 @@ mod tests {
                 },
                 ..Default::default()
             },
-            from_str("{ responses: { default: { $ref: 'def' }, \"666\": { $ref: 'demo' }, 418: { $ref: 'demo' } } }").unwrap(),
+            from_str("{ responses: { default: { $ref: 'def' }, \"599\": { $ref: 'demo' }, 418: { $ref: 'demo' } } }").unwrap(),
         );
     }
 }
@@ -0,0 +1,176 @@
+use crate::*;
+
+/// Selects operations by path, method, tag and extensions, e.g. for
+/// [OpenAPI::edit_operations].
+///
+/// Every criterion that is set must match. Setting the same kind of criterion
+/// more than once matches any of the given values, except for extensions,
+/// which must all be present.
+///
+/// ```
+/// # use openapiv3::OperationFilter;
+/// let filter = OperationFilter::new()
+///     .method("post")
+///     .method("put")
+///     .tag("pets")
+///     .extension("x-internal");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationFilter {
+    paths: Vec<String>,
+    path_prefixes: Vec<String>,
+    path_globs: Vec<String>,
+    methods: Vec<String>,
+    tags: Vec<String>,
+    extensions: Vec<(String, Option<serde_json::Value>)>,
+}
+
+impl OperationFilter {
+    /// Creates a filter that matches every operation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches operations of the path item with exactly this key.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Matches operations whose path starts with `prefix`.
+    pub fn path_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.path_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Matches operations whose path matches the glob `pattern`, in which `*`
+    /// matches any characters but `/` and `**` matches any characters,
+    /// e.g. `/pets/*` or `/admin/**`.
+    pub fn path_glob(mut self, pattern: impl Into<String>) -> Self {
+        self.path_globs.push(pattern.into());
+        self
+    }
+
+    /// Matches operations with this method, compared case-insensitively.
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.methods.push(method.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Matches operations that have this tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Matches operations that have this extension, with any value.
+    pub fn extension(mut self, name: impl Into<String>) -> Self {
+        self.extensions.push((name.into(), None));
+        self
+    }
+
+    /// Matches operations that have this extension with this value.
+    pub fn extension_value(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.extensions.push((name.into(), Some(value)));
+        self
+    }
+
+    /// Returns `true` if the operation at `path` and `method` matches.
+    pub fn matches(&self, path: &str, method: &str, operation: &Operation) -> bool {
+        let path_matches =
+            (self.paths.is_empty() && self.path_prefixes.is_empty() && self.path_globs.is_empty())
+                || self.paths.iter().any(|p| p == path)
+                || self
+                    .path_prefixes
+                    .iter()
+                    .any(|p| path.starts_with(p.as_str()))
+                || self.path_globs.iter().any(|g| glob_matches(g, path));
+        let method_matches =
+            self.methods.is_empty() || self.methods.iter().any(|m| m.eq_ignore_ascii_case(method));
+        let tag_matches =
+            self.tags.is_empty() || self.tags.iter().any(|tag| operation.tags.contains(tag));
+        let extensions_match = self.extensions.iter().all(|(name, value)| {
+            match (operation.extensions.get(name), value) {
+                (Some(actual), Some(expected)) => actual == expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            }
+        });
+        path_matches && method_matches && tag_matches && extensions_match
+    }
+}
+
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    if let Some(rest) = pattern.strip_prefix("**") {
+        return (0..=path.len())
+            .filter(|&i| path.is_char_boundary(i))
+            .any(|i| glob_matches(rest, &path[i..]));
+    }
+    if let Some(rest) = pattern.strip_prefix('*') {
+        let segment = path.find('/').unwrap_or(path.len());
+        return (0..=segment)
+            .filter(|&i| path.is_char_boundary(i))
+            .any(|i| glob_matches(rest, &path[i..]));
+    }
+    match (pattern.chars().next(), path.chars().next()) {
+        (None, None) => true,
+        (Some(p), Some(c)) if p == c => {
+            glob_matches(&pattern[p.len_utf8()..], &path[c.len_utf8()..])
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_edit_operations() {
+        let mut openapi: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "", "version": "" },
+            "paths": {
+                "/pets": {
+                    "get": { "tags": ["pets"], "responses": {} },
+                    "post": { "tags": ["pets"], "x-internal": true, "responses": {} }
+                },
+                "/users": {
+                    "post": { "tags": ["users"], "x-internal": false, "responses": {} }
+                },
+                "/remote": { "$ref": "other.json#/paths/~1remote" }
+            }
+        }))
+        .unwrap();
+
+        let mut seen = Vec::new();
+        let filter = OperationFilter::new().method("POST");
+        let edited = openapi.edit_operations(&filter, |path, method, operation| {
+            seen.push(format!("{} {}", method, path));
+            operation.deprecated = true;
+        });
+        assert_eq!(edited, 2);
+        assert_eq!(seen, vec!["post /pets", "post /users"]);
+
+        let filter = OperationFilter::new().extension_value("x-internal", json!(true));
+        assert_eq!(openapi.edit_operations(&filter, |_, _, _| {}), 1);
+        let filter = OperationFilter::new().extension("x-internal").tag("users");
+        assert_eq!(openapi.edit_operations(&filter, |_, _, _| {}), 1);
+        let filter = OperationFilter::new().path("/pets").path_prefix("/u");
+        assert_eq!(openapi.edit_operations(&filter, |_, _, _| {}), 3);
+
+        let filter = OperationFilter::new().path_glob("/*s");
+        assert_eq!(openapi.edit_operations(&filter, |_, _, _| {}), 3);
+        let filter = OperationFilter::new().path_glob("/p*/**");
+        assert_eq!(openapi.edit_operations(&filter, |_, _, _| {}), 0);
+        assert!(glob_matches("/admin/**", "/admin/users/{id}"));
+        assert!(!glob_matches("/admin/*", "/admin/users/{id}"));
+
+        let deprecated = openapi
+            .operations()
+            .filter(|(_, _, operation)| operation.deprecated)
+            .count();
+        assert_eq!(deprecated, 2);
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// A naming convention for identifiers, made of ASCII letters and digits and
+/// starting with a letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseStyle {
+    /// `listPets`
+    Camel,
+    /// `ListPets`
+    Pascal,
+    /// `list_pets`
+    Snake,
+    /// `list-pets`
+    Kebab,
+}
+
+impl CaseStyle {
+    /// Returns `true` if `s` follows this convention.
+    ///
+    /// ```
+    /// # use openapiv3::CaseStyle;
+    /// assert!(CaseStyle::Camel.matches("getPetById2"));
+    /// assert!(!CaseStyle::Camel.matches("GetPet"));
+    /// assert!(CaseStyle::Snake.matches("get_pet_by_id"));
+    /// assert!(!CaseStyle::Snake.matches("get__pet"));
+    /// ```
+    pub fn matches(&self, s: &str) -> bool {
+        let separated = |separator: char| {
+            s.split(separator).all(|word| {
+                !word.is_empty()
+                    && word
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            })
+        };
+        let starts_with = |first: fn(&char) -> bool| s.chars().next().as_ref().is_some_and(first);
+        match self {
+            CaseStyle::Camel | CaseStyle::Pascal => {
+                let first = match self {
+                    CaseStyle::Camel => char::is_ascii_lowercase,
+                    _ => char::is_ascii_uppercase,
+                };
+                starts_with(first) && s.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            CaseStyle::Snake => starts_with(char::is_ascii_lowercase) && separated('_'),
+            CaseStyle::Kebab => starts_with(char::is_ascii_lowercase) && separated('-'),
+        }
+    }
+}
+
+impl fmt::Display for CaseStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CaseStyle::Camel => "camelCase",
+            CaseStyle::Pascal => "PascalCase",
+            CaseStyle::Snake => "snake_case",
+            CaseStyle::Kebab => "kebab-case",
+        })
+    }
+}
+
+type Pattern = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// The rules checked by [OpenAPI::validate_operation_ids]. By default, every
+/// operation must have an `operationId`, in any style.
+#[derive(Clone)]
+pub struct OperationIdRules {
+    required: bool,
+    case: Option<CaseStyle>,
+    pattern: Option<Pattern>,
+    pattern_name: String,
+}
+
+impl fmt::Debug for OperationIdRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OperationIdRules")
+            .field("required", &self.required)
+            .field("case", &self.case)
+            .field(
+                "pattern",
+                &self.pattern.as_ref().map(|_| &self.pattern_name),
+            )
+            .finish()
+    }
+}
+
+impl Default for OperationIdRules {
+    fn default() -> Self {
+        OperationIdRules {
+            required: true,
+            case: None,
+            pattern: None,
+            pattern_name: String::new(),
+        }
+    }
+}
+
+impl OperationIdRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether operations must have an `operationId`.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Requires `operationId`s to follow this naming convention.
+    pub fn case(mut self, case: CaseStyle) -> Self {
+        self.case = Some(case);
+        self
+    }
+
+    /// Requires `operationId`s to be accepted by `matches`, such as a
+    /// closure testing a compiled regular expression, which is described by
+    /// `name` in violations.
+    pub fn pattern(
+        mut self,
+        name: impl Into<String>,
+        matches: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.pattern = Some(Arc::new(matches));
+        self.pattern_name = name.into();
+        self
+    }
+}
+
+impl OpenAPI {
+    /// Checks the `operationId`s of all operations, including those of
+    /// callbacks: that they are present, if `rules` require it, unique across
+    /// the document and named as `rules` require.
+    ///
+    /// A duplicate is reported at each operation reusing an `operationId`,
+    /// naming the first operation that uses it. Unlike [OpenAPI::validate],
+    /// which reports duplicates as well, missing `operationId`s and naming
+    /// are checked, as many code generators rely on them.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": {
+    ///         "/pets": {
+    ///             "get": { "operationId": "listPets", "responses": {} },
+    ///             "post": { "responses": {} }
+    ///         },
+    ///         "/pets/{id}": { "get": { "operationId": "list_pets", "responses": {} } }
+    ///     }
+    /// }"#).unwrap();
+    ///
+    /// let violations = api.validate_operation_ids(&OperationIdRules::new().case(CaseStyle::Camel));
+    /// let found = violations.iter().map(|v| (v.kind, v.pointer.as_str())).collect::<Vec<_>>();
+    /// assert_eq!(found, [
+    ///     (ViolationKind::MissingOperationId, "/paths/~1pets/post"),
+    ///     (ViolationKind::InvalidOperationId, "/paths/~1pets~1{id}/get/operationId"),
+    /// ]);
+    /// assert_eq!(
+    ///     violations[1].message,
+    ///     "operationId `list_pets` is not camelCase"
+    /// );
+    /// ```
+    pub fn validate_operation_ids(&self, rules: &OperationIdRules) -> Vec<Violation> {
+        fn operations<'a>(
+            item: &'a PathItem,
+            pointer: &str,
+            found: &mut Vec<(String, &'a Operation)>,
+        ) {
+            for (method, operation) in item.iter() {
+                let pointer = format!("{}/{}", pointer, method);
+                found.push((pointer.clone(), operation));
+                for (name, callback) in &operation.callbacks {
+                    for (expression, item) in callback {
+                        let pointer = format!(
+                            "{}/callbacks/{}/{}",
+                            pointer,
+                            escape_pointer_token(name),
+                            escape_pointer_token(expression)
+                        );
+                        operations(item, &pointer, found);
+                    }
+                }
+            }
+        }
+
+        let mut found = Vec::new();
+        for (path, item) in self.paths.iter() {
+            if let ReferenceOr::Item(item) = item {
+                let pointer = format!("/paths/{}", escape_pointer_token(path));
+                operations(item, &pointer, &mut found);
+            }
+        }
+
+        let mut violations = Vec::new();
+        let mut first_uses: IndexMap<&str, &str> = IndexMap::new();
+        for (pointer, operation) in &found {
+            let Some(id) = &operation.operation_id else {
+                if rules.required {
+                    violations.push(Violation {
+                        kind: ViolationKind::MissingOperationId,
+                        pointer: pointer.clone(),
+                        message: "operation has no operationId".to_owned(),
+                    });
+                }
+                continue;
+            };
+            let id_pointer = format!("{}/operationId", pointer);
+            if let Some(first) = first_uses.get(id.as_str()) {
+                violations.push(Violation {
+                    kind: ViolationKind::DuplicateOperationId,
+                    pointer: id_pointer.clone(),
+                    message: format!("operationId `{}` is already used by {}", id, first),
+                });
+            } else {
+                first_uses.insert(id, pointer);
+            }
+            let convention = match (&rules.case, &rules.pattern) {
+                (Some(case), _) if !case.matches(id) => Some(case.to_string()),
+                (_, Some(matches)) if !matches(id) => Some(rules.pattern_name.clone()),
+                _ => None,
+            };
+            if let Some(convention) = convention {
+                violations.push(Violation {
+                    kind: ViolationKind::InvalidOperationId,
+                    pointer: id_pointer,
+                    message: format!("operationId `{}` is not {}", id, convention),
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_operation_ids() {
+        let api: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "get": { "operationId": "list-pets", "responses": {} },
+                    "post": {
+                        "operationId": "add-pet",
+                        "responses": {},
+                        "callbacks": { "onAdded": { "{$request.body#/url}": {
+                            "post": { "operationId": "list-pets", "responses": {} }
+                        } } }
+                    }
+                },
+                "/pets/{id}": { "get": { "operationId": "get-pet-2", "responses": {} } }
+            }
+        }))
+        .unwrap();
+
+        let found = |rules: &OperationIdRules| {
+            api.validate_operation_ids(rules)
+                .into_iter()
+                .map(|v| (v.kind, v.pointer, v.message))
+                .collect::<Vec<_>>()
+        };
+        let callback =
+            "/paths/~1pets/post/callbacks/onAdded/{$request.body#~1url}/post/operationId";
+        assert_eq!(
+            found(&OperationIdRules::new().case(CaseStyle::Kebab)),
+            [(
+                ViolationKind::DuplicateOperationId,
+                callback.to_owned(),
+                "operationId `list-pets` is already used by /paths/~1pets/get".to_owned()
+            )]
+        );
+
+        let verbs = ["list-".to_owned(), "get-".to_owned()];
+        let rules = OperationIdRules::new().pattern("a verb then a noun", move |id| {
+            verbs.iter().any(|verb| id.starts_with(verb.as_str()))
+        });
+        let invalid = found(&rules)
+            .into_iter()
+            .filter(|(kind, _, _)| *kind == ViolationKind::InvalidOperationId)
+            .map(|(_, _, message)| message)
+            .collect::<Vec<_>>();
+        assert_eq!(invalid, ["operationId `add-pet` is not a verb then a noun"]);
+
+        for (case, valid, invalid) in [
+            (CaseStyle::Camel, "listPets2", "2listPets"),
+            (CaseStyle::Pascal, "ListPets", "List_Pets"),
+            (CaseStyle::Snake, "list_pets", "list_pets_"),
+            (CaseStyle::Kebab, "list-pets", "List-pets"),
+        ] {
+            assert!(case.matches(valid), "{}", valid);
+            assert!(!case.matches(invalid), "{}", invalid);
+        }
+        assert!(!CaseStyle::Camel.matches(""));
+    }
+}
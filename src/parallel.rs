@@ -0,0 +1,155 @@
+use std::thread;
+
+use crate::*;
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+impl OpenAPI {
+    /// Parses a JSON document like [from_str], deserializing its path items
+    /// and component schemas on several threads.
+    ///
+    /// The document is first split into the raw JSON of each path item and
+    /// component schema, which are then deserialized in parallel and put
+    /// back in their original order. The result is the same as that of
+    /// [from_str]; if the document does not parse, it is parsed again by
+    /// [from_str] to report the error with its location. Within
+    /// [Extensions::with_key_predicate], which only affects the calling
+    /// thread, the document is parsed on the calling thread alone.
+    ///
+    /// ```
+    /// # use openapiv3::OpenAPI;
+    /// let api = OpenAPI::from_str_parallel(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "get": { "responses": {} } } },
+    ///     "components": { "schemas": { "Pet": { "type": "object" } } }
+    /// }"#).unwrap();
+    /// assert!(api.paths.paths.contains_key("/pets"));
+    ///
+    /// let err = OpenAPI::from_str_parallel(r#"{ "paths": { "/pets": [] } }"#).unwrap_err();
+    /// assert_eq!(err.pointer(), Some("/paths/~1pets"));
+    /// ```
+    pub fn from_str_parallel(input: &str) -> Result<OpenAPI, Error> {
+        if crate::extensions::has_key_predicate() {
+            return from_str(input);
+        }
+        let document = split_parse(input).or_else(|_| from_str(input))?;
+        crate::de::check_version(&document.openapi)?;
+        Ok(document)
+    }
+}
+
+fn split_parse(input: &str) -> Result<OpenAPI, serde_json::Error> {
+    let mut fields: IndexMap<String, Box<RawValue>> = serde_json::from_str(input)?;
+
+    let mut paths = Vec::new();
+    if let Some(raw) = fields.get_mut("paths") {
+        let mut entries: IndexMap<String, Box<RawValue>> = serde_json::from_str(raw.get())?;
+        paths = take_entries(&mut entries, |key| key.starts_with('/'));
+        *raw = serde_json::value::to_raw_value(&entries)?;
+    }
+    let mut schemas = Vec::new();
+    if let Some(raw) = fields.get_mut("components") {
+        let mut sections: IndexMap<String, Box<RawValue>> = serde_json::from_str(raw.get())?;
+        if let Some(raw) = sections.get_mut("schemas") {
+            let mut entries: IndexMap<String, Box<RawValue>> = serde_json::from_str(raw.get())?;
+            schemas = take_entries(&mut entries, |_| true);
+            *raw = serde_json::value::to_raw_value(&entries)?;
+        }
+        *raw = serde_json::value::to_raw_value(&sections)?;
+    }
+
+    let (document, paths, schemas) = thread::scope(|scope| {
+        let document = scope.spawn(|| {
+            serde_json::to_string(&fields).and_then(|json| serde_json::from_str::<OpenAPI>(&json))
+        });
+        let paths = parse_entries::<ReferenceOr<PathItem>>(paths);
+        let schemas = parse_entries::<ReferenceOr<Schema>>(schemas);
+        let document = document.join().expect("parsing does not panic");
+        (document, paths, schemas)
+    });
+    let mut document = document?;
+    document.paths.paths = paths?;
+    let schemas = schemas?;
+    if !schemas.is_empty() {
+        let components = document.components.get_or_insert_with(Default::default);
+        components.schemas = schemas;
+    }
+    Ok(document)
+}
+
+/// Removes the entries whose key matches `take`, keeping their order.
+fn take_entries(
+    entries: &mut IndexMap<String, Box<RawValue>>,
+    take: impl Fn(&str) -> bool,
+) -> Vec<(String, Box<RawValue>)> {
+    let (taken, kept): (IndexMap<_, _>, _) = std::mem::take(entries)
+        .into_iter()
+        .partition(|(key, _)| take(key));
+    *entries = kept;
+    taken.into_iter().collect()
+}
+
+/// Deserializes the entries in chunks, one per available thread.
+fn parse_entries<T: DeserializeOwned + Send>(
+    entries: Vec<(String, Box<RawValue>)>,
+) -> Result<IndexMap<String, T>, serde_json::Error> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = entries.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let chunks: Vec<_> = entries
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(key, raw)| Ok((key.clone(), serde_json::from_str(raw.get())?)))
+                        .collect::<Result<Vec<_>, serde_json::Error>>()
+                })
+            })
+            .collect();
+        let mut parsed = IndexMap::with_capacity(entries.len());
+        for chunk in chunks {
+            parsed.extend(chunk.join().expect("parsing does not panic")?);
+        }
+        Ok(parsed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_parallel() {
+        for entry in std::fs::read_dir("fixtures").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|e| e == "json") {
+                let input = std::fs::read_to_string(&path).unwrap();
+                let expected: OpenAPI = serde_json::from_str(&input).unwrap();
+                let parsed = OpenAPI::from_str_parallel(&input).unwrap();
+                assert_eq!(parsed, expected, "{}", path.display());
+            }
+        }
+
+        let input = r#"{
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": { "x-paths": 1 },
+            "components": { "schemas": { "Pet": { "type": "object", "x-kind": "pet" } } }
+        }"#;
+        let api = Extensions::with_key_predicate(
+            |key| key == "x-kind",
+            || OpenAPI::from_str_parallel(input).unwrap(),
+        );
+        assert!(api.paths.extensions.is_empty());
+        let pet = api.components.unwrap().schemas["Pet"].clone();
+        assert!(pet
+            .into_item()
+            .unwrap()
+            .schema_data
+            .extensions
+            .contains_key("x-kind"));
+    }
+}
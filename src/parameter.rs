@@ -43,15 +43,26 @@ pub struct ParameterData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub explode: Option<bool>,
     /// Inline extensions to this object.
-    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    #[serde(flatten, deserialize_with = "deserialize_parameter_extensions")]
+    pub extensions: Extensions,
+}
+
+/// Skips the fields of the [Parameter] variants, which the extensions of
+/// [ParameterData] see as it is flattened into them.
+fn deserialize_parameter_extensions<'de, D>(deserializer: D) -> Result<Extensions, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::extensions::deserialize_extensions_except(deserializer, |key| {
+        matches!(key, "style" | "allowReserved" | "allowEmptyValue")
+    })
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum ParameterSchemaOrContent {
     /// The schema defining the type used for the parameter.
-    Schema(ReferenceOr<Schema>),
+    Schema(Box<ReferenceOr<Schema>>),
     /// A map containing the representations for the parameter. The key is the
     /// media type and the value describes it. The map MUST only contain one
     /// entry.
@@ -177,6 +188,98 @@ impl Parameter {
             } => parameter_data,
         }
     }
+
+    /// Returns the `parameter_data` field of this [ParameterData] by mutable reference.
+    pub fn parameter_data_mut(&mut self) -> &mut ParameterData {
+        match self {
+            Parameter::Query {
+                parameter_data,
+                allow_reserved: _,
+                style: _,
+                allow_empty_value: _,
+            } => parameter_data,
+            Parameter::Header {
+                parameter_data,
+                style: _,
+            } => parameter_data,
+            Parameter::Path {
+                parameter_data,
+                style: _,
+            } => parameter_data,
+            Parameter::Cookie {
+                parameter_data,
+                style: _,
+            } => parameter_data,
+        }
+    }
+}
+
+/// The serialization style of a [Parameter], whatever its location. See
+/// [Parameter::effective_style].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParameterStyle {
+    /// Path-style parameters defined by RFC6570.
+    Matrix,
+    /// Label style parameters defined by RFC6570.
+    Label,
+    /// Simple style parameters defined by RFC6570.
+    Simple,
+    /// Form style parameters defined by RFC6570.
+    Form,
+    /// Space separated array values.
+    SpaceDelimited,
+    /// Pipe separated array values.
+    PipeDelimited,
+    /// Provides a simple way of rendering nested objects using form parameters.
+    DeepObject,
+}
+
+impl Parameter {
+    /// The style this parameter is serialized with. When `style` is not
+    /// specified, this is the default for the location of the parameter:
+    /// form for query and cookie parameters, and simple for path and header
+    /// parameters.
+    pub fn effective_style(&self) -> ParameterStyle {
+        match self {
+            Parameter::Query { style, .. } => match style {
+                QueryStyle::Form => ParameterStyle::Form,
+                QueryStyle::SpaceDelimited => ParameterStyle::SpaceDelimited,
+                QueryStyle::PipeDelimited => ParameterStyle::PipeDelimited,
+                QueryStyle::DeepObject => ParameterStyle::DeepObject,
+            },
+            Parameter::Header {
+                style: HeaderStyle::Simple,
+                ..
+            } => ParameterStyle::Simple,
+            Parameter::Path { style, .. } => match style {
+                PathStyle::Matrix => ParameterStyle::Matrix,
+                PathStyle::Label => ParameterStyle::Label,
+                PathStyle::Simple => ParameterStyle::Simple,
+            },
+            Parameter::Cookie {
+                style: CookieStyle::Form,
+                ..
+            } => ParameterStyle::Form,
+        }
+    }
+
+    /// Whether array and object values generate separate parameters. When
+    /// `explode` is not specified, this is `true` for the form style and
+    /// `false` for the others.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let parameter: Parameter = serde_json::from_str(
+    ///     r#"{ "name": "tags", "in": "query", "schema": { "type": "array" } }"#,
+    /// ).unwrap();
+    /// assert_eq!(parameter.effective_style(), ParameterStyle::Form);
+    /// assert!(parameter.effective_explode());
+    /// ```
+    pub fn effective_explode(&self) -> bool {
+        self.parameter_data_ref()
+            .explode
+            .unwrap_or(self.effective_style() == ParameterStyle::Form)
+    }
 }
 
 struct SkipSerializeIfDefault;
@@ -191,7 +294,7 @@ impl SkipSerializeIfDefault {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum PathStyle {
     /// Path-style parameters defined by RFC6570.
@@ -199,18 +302,15 @@ pub enum PathStyle {
     /// Label style parameters defined by RFC6570.
     Label,
     /// Simple style parameters defined by RFC6570.
+    #[default]
     Simple,
 }
 
-impl Default for PathStyle {
-    fn default() -> Self {
-        PathStyle::Simple
-    }
-}
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum QueryStyle {
     /// Form style parameters defined by RFC6570.
+    #[default]
     Form,
     /// Space separated array values.
     SpaceDelimited,
@@ -220,32 +320,57 @@ pub enum QueryStyle {
     DeepObject,
 }
 
-impl Default for QueryStyle {
-    fn default() -> Self {
-        QueryStyle::Form
-    }
-}
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum CookieStyle {
     /// Form style parameters defined by RFC6570.
+    #[default]
     Form,
 }
 
-impl Default for CookieStyle {
-    fn default() -> CookieStyle {
-        CookieStyle::Form
-    }
-}
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum HeaderStyle {
     /// Simple style parameters defined by RFC6570.
+    #[default]
     Simple,
 }
 
-impl Default for HeaderStyle {
-    fn default() -> HeaderStyle {
-        HeaderStyle::Simple
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_effective_style_and_explode() {
+        let parameter = |value| serde_json::from_value::<Parameter>(value).unwrap();
+        let cases = [
+            (json!({ "in": "query" }), ParameterStyle::Form, true),
+            (json!({ "in": "cookie" }), ParameterStyle::Form, true),
+            (json!({ "in": "path" }), ParameterStyle::Simple, false),
+            (json!({ "in": "header" }), ParameterStyle::Simple, false),
+            (
+                json!({ "in": "query", "style": "deepObject" }),
+                ParameterStyle::DeepObject,
+                false,
+            ),
+            (
+                json!({ "in": "query", "explode": false }),
+                ParameterStyle::Form,
+                false,
+            ),
+            (
+                json!({ "in": "path", "style": "matrix", "explode": true }),
+                ParameterStyle::Matrix,
+                true,
+            ),
+        ];
+        for (mut value, style, explode) in cases {
+            value["name"] = json!("id");
+            value["schema"] = json!({ "type": "string" });
+            let parameter = parameter(value);
+            assert_eq!(parameter.effective_style(), style);
+            assert_eq!(parameter.effective_explode(), explode);
+        }
     }
 }
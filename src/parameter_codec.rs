@@ -0,0 +1,1078 @@
+use std::fmt;
+
+use crate::util::{percent_decode, percent_encode};
+use crate::*;
+use indexmap::IndexMap;
+
+/// The error returned when a parameter value cannot be serialized or
+/// deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParameterCodecError {
+    /// The parameter's `content` map does not have exactly one entry.
+    InvalidContent { parameter: String },
+    /// The parameter's media type is not one this module can handle.
+    UnsupportedMediaType {
+        parameter: String,
+        media_type: String,
+    },
+    /// The parameter is described by a schema and style, which
+    /// [Parameter::deserialize_value] does not handle; see
+    /// [Parameter::extract].
+    UnsupportedStyle { parameter: String },
+    /// The value does not fit the parameter's media type.
+    InvalidValue { parameter: String, message: String },
+    /// A required parameter is absent from the request.
+    Missing { parameter: String },
+}
+
+impl fmt::Display for ParameterCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterCodecError::InvalidContent { parameter } => write!(
+                f,
+                "the content of parameter `{}` must have exactly one entry",
+                parameter
+            ),
+            ParameterCodecError::UnsupportedMediaType {
+                parameter,
+                media_type,
+            } => write!(
+                f,
+                "parameter `{}` uses unsupported media type `{}`",
+                parameter, media_type
+            ),
+            ParameterCodecError::UnsupportedStyle { parameter } => write!(
+                f,
+                "parameter `{}` is serialized by style, which is not supported",
+                parameter
+            ),
+            ParameterCodecError::InvalidValue { parameter, message } => {
+                write!(
+                    f,
+                    "invalid value for parameter `{}`: {}",
+                    parameter, message
+                )
+            }
+            ParameterCodecError::Missing { parameter } => {
+                write!(f, "required parameter `{}` is missing", parameter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParameterCodecError {}
+
+/// How a parameter's `content` media type represents values.
+enum Representation {
+    Json,
+    Text,
+}
+
+fn representation(data: &ParameterData) -> Result<Option<Representation>, ParameterCodecError> {
+    let content = match &data.format {
+        ParameterSchemaOrContent::Schema(_) => return Ok(None),
+        ParameterSchemaOrContent::Content(content) => content,
+    };
+    let (media_type, _) = match content.first() {
+        Some(entry) if content.len() == 1 => entry,
+        _ => {
+            return Err(ParameterCodecError::InvalidContent {
+                parameter: data.name.clone(),
+            })
+        }
+    };
+    let unsupported = || ParameterCodecError::UnsupportedMediaType {
+        parameter: data.name.clone(),
+        media_type: media_type.clone(),
+    };
+    let key: MediaTypeKey = media_type.parse().map_err(|_| unsupported())?;
+    if key.typ == "application" && (key.subtype == "json" || key.suffix.as_deref() == Some("json"))
+    {
+        Ok(Some(Representation::Json))
+    } else if key.typ == "text" && key.subtype == "plain" {
+        Ok(Some(Representation::Text))
+    } else {
+        Err(unsupported())
+    }
+}
+
+/// The parts of a request that carry parameters, as received by a server.
+#[derive(Debug, Clone, Default)]
+pub struct RawParameters<'a> {
+    /// The query string, without the leading `?`.
+    pub query: &'a str,
+    /// The value matched by each variable of the path template, still
+    /// percent-encoded.
+    pub path: IndexMap<&'a str, &'a str>,
+    /// The request headers. Names are compared case-insensitively.
+    pub headers: Vec<(&'a str, &'a str)>,
+    /// The value of the `Cookie` header.
+    pub cookie: &'a str,
+}
+
+/// The parameters of a request, by location and name, as returned by
+/// [FlatOperation::parse_parameters].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedParameters {
+    pub query: IndexMap<String, serde_json::Value>,
+    pub path: IndexMap<String, serde_json::Value>,
+    pub header: IndexMap<String, serde_json::Value>,
+    pub cookie: IndexMap<String, serde_json::Value>,
+}
+
+/// The shape of value a parameter's schema calls for.
+enum Expected<'a> {
+    Scalar(Option<&'a Schema>),
+    List(Option<&'a Schema>),
+    Object(Option<&'a ObjectType>),
+}
+
+impl<'a> Expected<'a> {
+    fn of(schema: Option<&'a Schema>, components: Option<&'a Components>) -> Self {
+        match schema.map(|schema| &schema.schema_kind) {
+            Some(SchemaKind::Type(Type::Array(array))) => Expected::List(
+                array
+                    .items
+                    .as_ref()
+                    .and_then(|items| resolve_boxed(items, components)),
+            ),
+            Some(SchemaKind::Type(Type::Object(object))) => Expected::Object(Some(object)),
+            Some(SchemaKind::Any(any)) if any.typ.as_deref() == Some("array") => {
+                Expected::List(None)
+            }
+            Some(SchemaKind::Any(any)) if any.typ.as_deref() == Some("object") => {
+                Expected::Object(None)
+            }
+            _ => Expected::Scalar(schema),
+        }
+    }
+}
+
+fn resolve_boxed<'a>(
+    schema: &'a ReferenceOr<Box<Schema>>,
+    components: Option<&'a Components>,
+) -> Option<&'a Schema> {
+    match (schema, components) {
+        (ReferenceOr::Item(schema), _) => Some(schema),
+        (reference, Some(components)) => reference.resolve(components).ok(),
+        (_, None) => None,
+    }
+}
+
+/// Splits `name=value` pairs, decoding the names but not the values.
+fn split_pairs(input: &str, separator: char) -> Vec<(String, &str)> {
+    input
+        .split(separator)
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), value)
+        })
+        .collect()
+}
+
+/// Splits a raw value into the items of an array or object, unless a scalar
+/// is expected.
+fn pieces(body: &str, separator: char, scalar: bool) -> Vec<&str> {
+    match scalar {
+        true => vec![body],
+        false => body.split(separator).collect(),
+    }
+}
+
+/// Pairs up the keys and values of an object, from `key=value` pieces when
+/// `exploded` and from alternating keys and values otherwise.
+#[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
+fn entries(pieces: Vec<&str>, exploded: bool) -> Option<Vec<(&str, &str)>> {
+    if exploded {
+        Some(
+            pieces
+                .into_iter()
+                .map(|piece| piece.split_once('=').unwrap_or((piece, "")))
+                .collect(),
+        )
+    } else if pieces.len() % 2 == 0 {
+        Some(pieces.chunks(2).map(|pair| (pair[0], pair[1])).collect())
+    } else {
+        None
+    }
+}
+
+/// Strips `name=` or a bare `name` from the start of a matrix piece.
+fn strip_name<'a>(piece: &'a str, name: &str) -> Option<&'a str> {
+    let rest = piece.strip_prefix(name)?;
+    match rest.strip_prefix('=') {
+        Some(value) => Some(value),
+        None if rest.is_empty() => Some(rest),
+        None => None,
+    }
+}
+
+/// A value broken down into the shapes that styles know how to serialize,
+/// with every string already encoded.
+enum Shape {
+    Empty,
+    Scalar(String),
+    List(Vec<String>),
+    Pairs(Vec<(String, String)>),
+}
+
+fn scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+impl Parameter {
+    /// Encodes a name or value for the location of this parameter.
+    fn encode(&self, raw: &str) -> String {
+        match self {
+            Parameter::Query { allow_reserved, .. } => percent_encode(raw, *allow_reserved),
+            Parameter::Header { .. } => raw.to_owned(),
+            Parameter::Path { .. } | Parameter::Cookie { .. } => percent_encode(raw, false),
+        }
+    }
+
+    /// Serializes `value` according to the style of this parameter, as laid
+    /// out in the Style Examples of the specification and RFC 6570.
+    fn serialize_styled(&self, value: &serde_json::Value) -> Result<String, ParameterCodecError> {
+        let data = self.parameter_data_ref();
+        let invalid = |message: String| ParameterCodecError::InvalidValue {
+            parameter: data.name.clone(),
+            message,
+        };
+        let nested = || invalid("nested arrays and objects cannot be serialized".to_owned());
+        let shape = match value {
+            serde_json::Value::Null => Shape::Empty,
+            serde_json::Value::Array(items) => Shape::List(
+                items
+                    .iter()
+                    .map(|item| scalar(item).map(|item| self.encode(&item)))
+                    .collect::<Option<_>>()
+                    .ok_or_else(nested)?,
+            ),
+            serde_json::Value::Object(object) => Shape::Pairs(
+                object
+                    .iter()
+                    .filter(|(_, value)| !value.is_null())
+                    .map(|(key, value)| Some((self.encode(key), self.encode(&scalar(value)?))))
+                    .collect::<Option<_>>()
+                    .ok_or_else(nested)?,
+            ),
+            value => Shape::Scalar(self.encode(&scalar(value).unwrap_or_default())),
+        };
+
+        let name = match self {
+            Parameter::Header { .. } => data.name.clone(),
+            _ => percent_encode(&data.name, false),
+        };
+        // Exploded cookies are sent as separate cookies.
+        let entry_separator = match self {
+            Parameter::Cookie { .. } => "; ",
+            _ => "&",
+        };
+        let flat = |pairs: &[(String, String)], separator: &str| {
+            pairs
+                .iter()
+                .map(|(key, value)| format!("{}{}{}", key, separator, value))
+                .collect::<Vec<_>>()
+        };
+        let prefixed = |prefix: &str, values: Vec<String>| {
+            values
+                .into_iter()
+                .map(|value| format!("{}{}", prefix, value))
+                .collect::<String>()
+        };
+
+        let style = self.effective_style();
+        let explode = self.effective_explode();
+        Ok(match (style, shape) {
+            (ParameterStyle::Matrix, Shape::Empty) => format!(";{}", name),
+            (ParameterStyle::Matrix, Shape::Scalar(value)) => format!(";{}={}", name, value),
+            (ParameterStyle::Matrix, Shape::List(items)) if explode => {
+                prefixed(&format!(";{}=", name), items)
+            }
+            (ParameterStyle::Matrix, Shape::List(items)) => {
+                format!(";{}={}", name, items.join(","))
+            }
+            (ParameterStyle::Matrix, Shape::Pairs(pairs)) if explode => {
+                prefixed(";", flat(&pairs, "="))
+            }
+            (ParameterStyle::Matrix, Shape::Pairs(pairs)) => {
+                format!(";{}={}", name, flat(&pairs, ",").join(","))
+            }
+
+            (ParameterStyle::Label, Shape::Empty) => ".".to_owned(),
+            (ParameterStyle::Label, Shape::Scalar(value)) => format!(".{}", value),
+            (ParameterStyle::Label, Shape::List(items)) if explode => prefixed(".", items),
+            (ParameterStyle::Label, Shape::List(items)) => format!(".{}", items.join(",")),
+            (ParameterStyle::Label, Shape::Pairs(pairs)) if explode => {
+                prefixed(".", flat(&pairs, "="))
+            }
+            (ParameterStyle::Label, Shape::Pairs(pairs)) => {
+                format!(".{}", flat(&pairs, ",").join(","))
+            }
+
+            (ParameterStyle::Simple, Shape::Empty) => String::new(),
+            (ParameterStyle::Simple, Shape::Scalar(value)) => value,
+            (ParameterStyle::Simple, Shape::List(items)) => items.join(","),
+            (ParameterStyle::Simple, Shape::Pairs(pairs)) => {
+                flat(&pairs, if explode { "=" } else { "," }).join(",")
+            }
+
+            (ParameterStyle::Form, Shape::Empty) => format!("{}=", name),
+            (ParameterStyle::Form, Shape::Scalar(value)) => format!("{}={}", name, value),
+            (
+                ParameterStyle::Form
+                | ParameterStyle::SpaceDelimited
+                | ParameterStyle::PipeDelimited,
+                Shape::List(items),
+            ) if explode => items
+                .iter()
+                .map(|item| format!("{}={}", name, item))
+                .collect::<Vec<_>>()
+                .join(entry_separator),
+            (ParameterStyle::Form, Shape::List(items)) => format!("{}={}", name, items.join(",")),
+            (ParameterStyle::Form, Shape::Pairs(pairs)) if explode => {
+                flat(&pairs, "=").join(entry_separator)
+            }
+            (ParameterStyle::Form, Shape::Pairs(pairs)) => {
+                format!("{}={}", name, flat(&pairs, ",").join(","))
+            }
+
+            (ParameterStyle::SpaceDelimited | ParameterStyle::PipeDelimited, shape) if !explode => {
+                let separator = match style {
+                    ParameterStyle::SpaceDelimited => "%20",
+                    _ => "|",
+                };
+                let values = match shape {
+                    Shape::List(items) => items,
+                    Shape::Pairs(pairs) => flat(&pairs, separator),
+                    _ => {
+                        return Err(invalid(format!(
+                            "{:?} values must be arrays or objects",
+                            style
+                        )))
+                    }
+                };
+                format!("{}={}", name, values.join(separator))
+            }
+
+            (ParameterStyle::DeepObject, Shape::Pairs(pairs)) => pairs
+                .iter()
+                .map(|(key, value)| format!("{}[{}]={}", name, key, value))
+                .collect::<Vec<_>>()
+                .join("&"),
+
+            (style, _) => {
+                return Err(invalid(format!(
+                    "the {:?} style{} cannot serialize this value",
+                    style,
+                    if explode { " with explode" } else { "" }
+                )))
+            }
+        })
+    }
+
+    /// Finds this parameter in `request` and parses its value, or returns
+    /// `None` if it is absent. This reverses [Parameter::serialize_value].
+    ///
+    /// For parameters described by a schema, strings are converted to
+    /// integers, numbers and booleans where the schema, or the schemas of
+    /// its items and properties, calls for them; references that cannot be
+    /// resolved through `components` are treated as strings. Exploded form
+    /// objects collect the query or cookie entries named after properties of
+    /// the schema, or every entry if it lists no properties.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let parameter: Parameter = serde_json::from_str(r#"{
+    ///     "name": "ids", "in": "query",
+    ///     "schema": { "type": "array", "items": { "type": "integer" } }
+    /// }"#).unwrap();
+    /// let request = RawParameters { query: "ids=1&ids=2&page=3", ..Default::default() };
+    /// assert_eq!(
+    ///     parameter.extract(&request, None).unwrap(),
+    ///     Some(serde_json::json!([1, 2]))
+    /// );
+    /// ```
+    pub fn extract(
+        &self,
+        request: &RawParameters<'_>,
+        components: Option<&Components>,
+    ) -> Result<Option<serde_json::Value>, ParameterCodecError> {
+        let data = self.parameter_data_ref();
+        let name = data.name.as_str();
+        let pairs = match self {
+            Parameter::Query { .. } => split_pairs(request.query, '&'),
+            Parameter::Cookie { .. } => split_pairs(request.cookie, ';'),
+            Parameter::Path { .. } | Parameter::Header { .. } => Vec::new(),
+        };
+        let single = || match self {
+            Parameter::Query { .. } | Parameter::Cookie { .. } => {
+                pairs.iter().find(|(n, _)| n == name).map(|(_, v)| *v)
+            }
+            Parameter::Path { .. } => request.path.get(name).copied(),
+            Parameter::Header { .. } => request
+                .headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| *v),
+        };
+
+        let schema = match &data.format {
+            ParameterSchemaOrContent::Content(_) => {
+                return single().map(|raw| self.deserialize_value(raw)).transpose()
+            }
+            ParameterSchemaOrContent::Schema(schema) => match &**schema {
+                ReferenceOr::Item(schema) => Some(schema),
+                reference => components.and_then(|components| reference.resolve(components).ok()),
+            },
+        };
+        let expected = Expected::of(schema, components);
+        let invalid = |message: &str| ParameterCodecError::InvalidValue {
+            parameter: name.to_owned(),
+            message: message.to_owned(),
+        };
+
+        let style = self.effective_style();
+        let explode = self.effective_explode();
+        let value = |pieces: Vec<&str>, exploded: bool| match &expected {
+            Expected::Scalar(schema) => self.typed(&pieces.join(","), *schema),
+            Expected::List(items) => pieces
+                .into_iter()
+                .map(|piece| self.typed(piece, *items))
+                .collect(),
+            Expected::Object(object) => {
+                let entries = entries(pieces, exploded)
+                    .ok_or_else(|| invalid("objects must have as many keys as values"))?;
+                self.object(entries, *object, components)
+            }
+        };
+        let scalar = matches!(expected, Expected::Scalar(_));
+
+        if matches!(self, Parameter::Path { .. } | Parameter::Header { .. }) {
+            let raw = match single() {
+                Some(raw) => raw,
+                None => return Ok(None),
+            };
+            return match style {
+                ParameterStyle::Label => {
+                    let body = raw
+                        .strip_prefix('.')
+                        .ok_or_else(|| invalid("label values must start with `.`"))?;
+                    let separator = if explode { '.' } else { ',' };
+                    value(pieces(body, separator, scalar), explode).map(Some)
+                }
+                ParameterStyle::Matrix => {
+                    let body = raw
+                        .strip_prefix(';')
+                        .ok_or_else(|| invalid("matrix values must start with `;`"))?;
+                    match (&expected, explode) {
+                        (Expected::List(_), true) => value(
+                            body.split(';')
+                                .filter_map(|piece| strip_name(piece, name))
+                                .collect(),
+                            true,
+                        ),
+                        (Expected::Object(_), true) => value(body.split(';').collect(), true),
+                        _ => {
+                            let body = strip_name(body, name)
+                                .ok_or_else(|| invalid("matrix values must start with the name"))?;
+                            value(pieces(body, ',', scalar), false)
+                        }
+                    }
+                    .map(Some)
+                }
+                _ => value(pieces(raw, ',', scalar), explode).map(Some),
+            };
+        }
+
+        let named = || pairs.iter().filter(|(n, _)| n == name).map(|(_, v)| *v);
+        match (style, &expected) {
+            (ParameterStyle::DeepObject, _) => {
+                let prefix = format!("{}[", name);
+                let found: Vec<_> = pairs
+                    .iter()
+                    .filter_map(|(n, v)| Some((n.strip_prefix(&prefix)?.strip_suffix(']')?, *v)))
+                    .collect();
+                match found.is_empty() {
+                    true => Ok(None),
+                    false => match &expected {
+                        Expected::Object(object) => {
+                            self.object(found, *object, components).map(Some)
+                        }
+                        _ => Err(invalid("deepObject parameters must be objects")),
+                    },
+                }
+            }
+            (_, Expected::List(_)) if explode => {
+                let found: Vec<_> = named().collect();
+                match found.is_empty() {
+                    true => Ok(None),
+                    false => value(found, true).map(Some),
+                }
+            }
+            (ParameterStyle::Form, Expected::Object(object)) if explode => {
+                let properties = object.map(|object| &object.properties);
+                let found: Vec<_> = pairs
+                    .iter()
+                    .filter(|(n, _)| properties.is_none_or(|p| p.is_empty() || p.contains_key(n)))
+                    .map(|(n, v)| (n.as_str(), *v))
+                    .collect();
+                match found.is_empty() {
+                    true => Ok(None),
+                    false => self.object(found, *object, components).map(Some),
+                }
+            }
+            _ => {
+                let raw = match named().next() {
+                    Some(raw) => raw,
+                    None => return Ok(None),
+                };
+                let separator = match style {
+                    ParameterStyle::SpaceDelimited => "%20",
+                    ParameterStyle::PipeDelimited => "|",
+                    _ => ",",
+                };
+                let pieces = match scalar {
+                    true => vec![raw],
+                    false => raw.split(separator).collect(),
+                };
+                value(pieces, false).map(Some)
+            }
+        }
+    }
+
+    /// Decodes a single piece of a raw value and converts it to the type of
+    /// `schema`.
+    fn typed(
+        &self,
+        raw: &str,
+        schema: Option<&Schema>,
+    ) -> Result<serde_json::Value, ParameterCodecError> {
+        let decoded = match self {
+            Parameter::Header { .. } => raw.to_owned(),
+            _ => percent_decode(raw),
+        };
+        let invalid = |expected: &str| ParameterCodecError::InvalidValue {
+            parameter: self.parameter_data_ref().name.clone(),
+            message: format!("`{}` is not {}", decoded, expected),
+        };
+        match schema.map(|schema| &schema.schema_kind) {
+            Some(SchemaKind::Type(Type::Integer(_))) => decoded
+                .parse::<i64>()
+                .map(serde_json::Value::from)
+                .map_err(|_| invalid("an integer")),
+            Some(SchemaKind::Type(Type::Number(_))) => decoded
+                .parse::<i64>()
+                .map(serde_json::Value::from)
+                .ok()
+                .or_else(|| {
+                    let number = serde_json::Number::from_f64(decoded.parse().ok()?)?;
+                    Some(serde_json::Value::Number(number))
+                })
+                .ok_or_else(|| invalid("a number")),
+            Some(SchemaKind::Type(Type::Boolean(_))) => match decoded.as_str() {
+                "true" => Ok(serde_json::Value::Bool(true)),
+                "false" => Ok(serde_json::Value::Bool(false)),
+                _ => Err(invalid("a boolean")),
+            },
+            _ => Ok(serde_json::Value::String(decoded)),
+        }
+    }
+
+    fn object(
+        &self,
+        entries: Vec<(&str, &str)>,
+        object: Option<&ObjectType>,
+        components: Option<&Components>,
+    ) -> Result<serde_json::Value, ParameterCodecError> {
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let key = match self {
+                    Parameter::Header { .. } => key.to_owned(),
+                    _ => percent_decode(key),
+                };
+                let schema = object
+                    .and_then(|object| object.properties.get(&key))
+                    .and_then(|property| resolve_boxed(property, components));
+                Ok((key, self.typed(value, schema)?))
+            })
+            .collect()
+    }
+
+    /// Serializes `value` as this parameter would appear in a request.
+    ///
+    /// The result is `name=value` for query and cookie parameters, and the
+    /// bare value for path and header parameters. Values are percent-encoded
+    /// except in headers.
+    ///
+    /// Parameters described by a schema are serialized according to
+    /// [Parameter::effective_style] and [Parameter::effective_explode]:
+    /// matrix, label and form values carry the name of the parameter, such as
+    /// `;id=5` or `id=1&id=2`, and `allowReserved` leaves reserved characters
+    /// of query values unencoded. Arrays and objects may only hold scalars.
+    ///
+    /// Parameters described by `content` are serialized according to their
+    /// media type, which must be JSON (`application/json` or a `+json`
+    /// suffix) or `text/plain`, regardless of `style` and `explode`.
+    ///
+    /// ```
+    /// # use openapiv3::Parameter;
+    /// let parameter: Parameter = serde_json::from_str(r#"{
+    ///     "name": "filter", "in": "query",
+    ///     "content": { "application/json": {} }
+    /// }"#).unwrap();
+    /// assert_eq!(
+    ///     parameter.serialize_value(&serde_json::json!({ "a": 1 })).unwrap(),
+    ///     "filter=%7B%22a%22%3A1%7D"
+    /// );
+    /// ```
+    pub fn serialize_value(
+        &self,
+        value: &serde_json::Value,
+    ) -> Result<String, ParameterCodecError> {
+        let data = self.parameter_data_ref();
+        let raw = match representation(data)? {
+            Some(Representation::Json) => value.to_string(),
+            Some(Representation::Text) => match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                    return Err(ParameterCodecError::InvalidValue {
+                        parameter: data.name.clone(),
+                        message: "text/plain values must be scalars".to_owned(),
+                    })
+                }
+                scalar => scalar.to_string(),
+            },
+            None => return self.serialize_styled(value),
+        };
+
+        Ok(match self {
+            Parameter::Query { allow_reserved, .. } => format!(
+                "{}={}",
+                percent_encode(&data.name, false),
+                percent_encode(&raw, *allow_reserved)
+            ),
+            Parameter::Cookie { .. } => format!("{}={}", data.name, percent_encode(&raw, false)),
+            Parameter::Path { .. } => percent_encode(&raw, false),
+            Parameter::Header { .. } => raw,
+        })
+    }
+
+    /// Deserializes the raw value of this parameter as found in a request,
+    /// i.e. without the `name=` prefix for query and cookie parameters, and
+    /// still percent-encoded except for headers.
+    ///
+    /// This is the inverse of [Parameter::serialize_value] for parameters
+    /// described by `content`; `text/plain` values are always returned as
+    /// strings. Use [Parameter::extract] for parameters described by a
+    /// schema.
+    pub fn deserialize_value(&self, raw: &str) -> Result<serde_json::Value, ParameterCodecError> {
+        let data = self.parameter_data_ref();
+        let decoded = match self {
+            Parameter::Header { .. } => raw.to_owned(),
+            _ => percent_decode(raw),
+        };
+
+        match representation(data)? {
+            Some(Representation::Json) => {
+                serde_json::from_str(&decoded).map_err(|err| ParameterCodecError::InvalidValue {
+                    parameter: data.name.clone(),
+                    message: err.to_string(),
+                })
+            }
+            Some(Representation::Text) => Ok(serde_json::Value::String(decoded)),
+            None => Err(ParameterCodecError::UnsupportedStyle {
+                parameter: data.name.clone(),
+            }),
+        }
+    }
+}
+
+impl FlatOperation<'_> {
+    /// Parses every parameter of this operation from `request`, see
+    /// [Parameter::extract]. Fails with every parameter that is invalid, or
+    /// required but absent; path parameters are always required.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets/{id}": { "get": {
+    ///         "parameters": [
+    ///             { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } },
+    ///             { "name": "X-Trace", "in": "header", "required": true, "schema": { "type": "string" } }
+    ///         ],
+    ///         "responses": {}
+    ///     } } }
+    /// }"#).unwrap();
+    /// let operation = &api.flat_operations().unwrap()[0];
+    ///
+    /// let mut request = RawParameters::default();
+    /// request.path.insert("id", "7");
+    /// request.headers.push(("x-trace", "abc"));
+    /// let parsed = operation.parse_parameters(&request, api.components.as_ref()).unwrap();
+    /// assert_eq!(parsed.path["id"], 7);
+    ///
+    /// request.headers.clear();
+    /// assert_eq!(
+    ///     operation.parse_parameters(&request, None),
+    ///     Err(vec![ParameterCodecError::Missing { parameter: "X-Trace".to_owned() }])
+    /// );
+    /// ```
+    pub fn parse_parameters(
+        &self,
+        request: &RawParameters<'_>,
+        components: Option<&Components>,
+    ) -> Result<ParsedParameters, Vec<ParameterCodecError>> {
+        let mut parsed = ParsedParameters::default();
+        let mut errors = Vec::new();
+        for parameter in &self.parameters {
+            let data = parameter.parameter_data_ref();
+            let values = match parameter {
+                Parameter::Query { .. } => &mut parsed.query,
+                Parameter::Path { .. } => &mut parsed.path,
+                Parameter::Header { .. } => &mut parsed.header,
+                Parameter::Cookie { .. } => &mut parsed.cookie,
+            };
+            match parameter.extract(request, components) {
+                Ok(Some(value)) => {
+                    values.insert(data.name.clone(), value);
+                }
+                Ok(None) if data.required || matches!(parameter, Parameter::Path { .. }) => errors
+                    .push(ParameterCodecError::Missing {
+                        parameter: data.name.clone(),
+                    }),
+                Ok(None) => {}
+                Err(error) => errors.push(error),
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(parsed),
+            false => Err(errors),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parameter(location: &str, media_type: &str) -> Parameter {
+        serde_json::from_value(json!({
+            "name": "q",
+            "in": location,
+            "required": location == "path",
+            "content": { media_type: {} }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_json_content() {
+        let value = json!({ "name": "a b", "tags": [1, 2] });
+        for (location, expected) in [
+            (
+                "query",
+                "q=%7B%22name%22%3A%22a%20b%22%2C%22tags%22%3A%5B1%2C2%5D%7D",
+            ),
+            (
+                "path",
+                "%7B%22name%22%3A%22a%20b%22%2C%22tags%22%3A%5B1%2C2%5D%7D",
+            ),
+            ("header", r#"{"name":"a b","tags":[1,2]}"#),
+        ] {
+            let parameter = parameter(location, "application/json");
+            let serialized = parameter.serialize_value(&value).unwrap();
+            assert_eq!(serialized, expected);
+            let raw = serialized.strip_prefix("q=").unwrap_or(&serialized);
+            assert_eq!(parameter.deserialize_value(raw).unwrap(), value);
+        }
+
+        let parameter = parameter("query", "application/vnd.api+json");
+        assert_eq!(parameter.serialize_value(&json!(null)).unwrap(), "q=null");
+        assert!(matches!(
+            parameter.deserialize_value("%7B"),
+            Err(ParameterCodecError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_text_content() {
+        let parameter = parameter("cookie", "text/plain");
+        assert_eq!(parameter.serialize_value(&json!("a;b")).unwrap(), "q=a%3Bb");
+        assert_eq!(parameter.serialize_value(&json!(5)).unwrap(), "q=5");
+        assert_eq!(parameter.deserialize_value("5").unwrap(), json!("5"));
+        assert!(parameter.serialize_value(&json!([1])).is_err());
+    }
+
+    #[test]
+    fn test_styles() {
+        let blue = json!("blue");
+        let colors = json!(["blue", "black", "brown"]);
+        // `Value` sorts object keys, so these are listed in that order.
+        let rgb = json!({ "B": 150, "G": 200, "R": 100 });
+        let cases = [
+            ("path", "matrix", false, &blue, ";color=blue"),
+            ("path", "matrix", false, &colors, ";color=blue,black,brown"),
+            (
+                "path",
+                "matrix",
+                true,
+                &colors,
+                ";color=blue;color=black;color=brown",
+            ),
+            ("path", "matrix", false, &rgb, ";color=B,150,G,200,R,100"),
+            ("path", "matrix", true, &rgb, ";B=150;G=200;R=100"),
+            ("path", "label", false, &blue, ".blue"),
+            ("path", "label", false, &colors, ".blue,black,brown"),
+            ("path", "label", true, &colors, ".blue.black.brown"),
+            ("path", "label", true, &rgb, ".B=150.G=200.R=100"),
+            ("path", "simple", false, &colors, "blue,black,brown"),
+            ("path", "simple", false, &rgb, "B,150,G,200,R,100"),
+            ("header", "simple", true, &rgb, "B=150,G=200,R=100"),
+            ("query", "form", false, &colors, "color=blue,black,brown"),
+            (
+                "query",
+                "form",
+                true,
+                &colors,
+                "color=blue&color=black&color=brown",
+            ),
+            ("query", "form", false, &rgb, "color=B,150,G,200,R,100"),
+            ("query", "form", true, &rgb, "B=150&G=200&R=100"),
+            (
+                "query",
+                "spaceDelimited",
+                false,
+                &colors,
+                "color=blue%20black%20brown",
+            ),
+            (
+                "query",
+                "pipeDelimited",
+                false,
+                &rgb,
+                "color=B|150|G|200|R|100",
+            ),
+            (
+                "query",
+                "deepObject",
+                true,
+                &rgb,
+                "color[B]=150&color[G]=200&color[R]=100",
+            ),
+            ("cookie", "form", true, &blue, "color=blue"),
+            (
+                "cookie",
+                "form",
+                true,
+                &colors,
+                "color=blue; color=black; color=brown",
+            ),
+        ];
+        for (location, style, explode, value, expected) in cases {
+            let parameter: Parameter = serde_json::from_value(json!({
+                "name": "color",
+                "in": location,
+                "style": style,
+                "explode": explode,
+                "schema": {}
+            }))
+            .unwrap();
+            assert_eq!(
+                parameter.serialize_value(value).unwrap(),
+                expected,
+                "{} {} {}",
+                location,
+                style,
+                explode
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_round_trip() {
+        let schemas = [
+            (json!({ "type": "integer" }), json!(5)),
+            (json!({ "type": "string" }), json!("a b,c")),
+            (
+                // `.` is not encoded, so exploded labels cannot hold fractions.
+                json!({ "type": "array", "items": { "type": "number" } }),
+                json!([1, -2]),
+            ),
+            (
+                json!({
+                    "type": "object",
+                    "properties": { "B": { "type": "integer" }, "G": { "type": "boolean" } }
+                }),
+                json!({ "B": 150, "G": true }),
+            ),
+        ];
+        let styles = [
+            ("path", "simple"),
+            ("path", "label"),
+            ("path", "matrix"),
+            ("header", "simple"),
+            ("query", "form"),
+            ("cookie", "form"),
+        ];
+        for (schema, value) in &schemas {
+            for (location, style) in styles {
+                for explode in [false, true] {
+                    let parameter: Parameter = serde_json::from_value(json!({
+                        "name": "color",
+                        "in": location,
+                        "style": style,
+                        "explode": explode,
+                        "schema": schema
+                    }))
+                    .unwrap();
+                    let serialized = parameter.serialize_value(value).unwrap();
+                    let mut request = RawParameters::default();
+                    match location {
+                        "path" => {
+                            request.path.insert("color", &serialized);
+                        }
+                        "header" => request.headers.push(("Color", &serialized)),
+                        "query" => request.query = &serialized,
+                        _ => request.cookie = &serialized,
+                    }
+                    assert_eq!(
+                        parameter.extract(&request, None).unwrap().as_ref(),
+                        Some(value),
+                        "{} {} {} {}",
+                        location,
+                        style,
+                        explode,
+                        serialized
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract_query() {
+        let parameter = |value| serde_json::from_value::<Parameter>(value).unwrap();
+        let request = RawParameters {
+            query: "filter[size]=2&filter[name]=a%20b&tags=a|b&page=x&q=%7B%22a%22%3A1%7D",
+            cookie: "session=abc; theme=dark",
+            ..Default::default()
+        };
+        let components: Components = serde_json::from_value(json!({
+            "schemas": { "Size": { "type": "integer" } }
+        }))
+        .unwrap();
+
+        let deep = parameter(json!({
+            "name": "filter", "in": "query", "style": "deepObject",
+            "schema": { "type": "object", "properties": {
+                "size": { "$ref": "#/components/schemas/Size" }
+            } }
+        }));
+        assert_eq!(
+            deep.extract(&request, Some(&components)).unwrap(),
+            Some(json!({ "size": 2, "name": "a b" }))
+        );
+        assert_eq!(
+            deep.extract(&request, None).unwrap(),
+            Some(json!({ "size": "2", "name": "a b" }))
+        );
+
+        let pipe = parameter(json!({
+            "name": "tags", "in": "query", "style": "pipeDelimited",
+            "schema": { "type": "array", "items": { "type": "string" } }
+        }));
+        assert_eq!(
+            pipe.extract(&request, None).unwrap(),
+            Some(json!(["a", "b"]))
+        );
+
+        let page =
+            parameter(json!({ "name": "page", "in": "query", "schema": { "type": "integer" } }));
+        assert_eq!(
+            page.extract(&request, None),
+            Err(ParameterCodecError::InvalidValue {
+                parameter: "page".to_owned(),
+                message: "`x` is not an integer".to_owned()
+            })
+        );
+        let missing = parameter(json!({ "name": "missing", "in": "query", "schema": {} }));
+        assert_eq!(missing.extract(&request, None), Ok(None));
+
+        let content = parameter(json!({
+            "name": "q", "in": "query", "content": { "application/json": {} }
+        }));
+        assert_eq!(
+            content.extract(&request, None).unwrap(),
+            Some(json!({ "a": 1 }))
+        );
+
+        let theme =
+            parameter(json!({ "name": "theme", "in": "cookie", "schema": { "type": "string" } }));
+        assert_eq!(theme.extract(&request, None).unwrap(), Some(json!("dark")));
+    }
+
+    #[test]
+    fn test_style_encoding() {
+        let parameter = |value| serde_json::from_value::<Parameter>(value).unwrap();
+        let query = parameter(json!({ "name": "q", "in": "query", "schema": {} }));
+        assert_eq!(
+            query.serialize_value(&json!("a/b c")).unwrap(),
+            "q=a%2Fb%20c"
+        );
+        assert_eq!(query.serialize_value(&json!(null)).unwrap(), "q=");
+        let reserved = parameter(json!({
+            "name": "q", "in": "query", "allowReserved": true, "schema": {}
+        }));
+        assert_eq!(reserved.serialize_value(&json!("a/b")).unwrap(), "q=a/b");
+        let header = parameter(json!({ "name": "X-Id", "in": "header", "schema": {} }));
+        assert_eq!(header.serialize_value(&json!("a b")).unwrap(), "a b");
+        assert_eq!(header.serialize_value(&json!(true)).unwrap(), "true");
+
+        assert!(matches!(
+            query.serialize_value(&json!([[1]])),
+            Err(ParameterCodecError::InvalidValue { .. })
+        ));
+        let deep = parameter(json!({
+            "name": "q", "in": "query", "style": "deepObject", "schema": {}
+        }));
+        assert!(deep.serialize_value(&json!("a")).is_err());
+        let pipe = parameter(json!({
+            "name": "q", "in": "query", "style": "pipeDelimited", "schema": {}
+        }));
+        assert!(pipe.serialize_value(&json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert_eq!(
+            parameter("query", "application/xml").serialize_value(&json!(1)),
+            Err(ParameterCodecError::UnsupportedMediaType {
+                parameter: "q".to_string(),
+                media_type: "application/xml".to_string(),
+            })
+        );
+
+        let parameter: Parameter = serde_json::from_value(json!({
+            "name": "q", "in": "query", "content": {}
+        }))
+        .unwrap();
+        assert_eq!(
+            parameter.serialize_value(&json!(1)),
+            Err(ParameterCodecError::InvalidContent {
+                parameter: "q".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use crate::util::unescape_pointer_token;
+use crate::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A JSON Patch, as described in RFC 6902: operations applied in order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+/// An operation of a [JsonPatch]. Paths are JSON Pointers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Adds a value to an object or inserts it into an array, or replaces
+    /// the whole document if `path` is empty.
+    Add { path: String, value: Value },
+    /// Removes the value at `path`.
+    Remove { path: String },
+    /// Replaces the value at `path`, which must exist.
+    Replace { path: String, value: Value },
+    /// Removes the value at `from` and adds it at `path`.
+    Move { from: String, path: String },
+    /// Adds a copy of the value at `from` at `path`.
+    Copy { from: String, path: String },
+    /// Checks that the value at `path` is equal to `value`.
+    Test { path: String, value: Value },
+}
+
+impl PatchOperation {
+    /// The target of this operation.
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. }
+            | PatchOperation::Remove { path }
+            | PatchOperation::Replace { path, .. }
+            | PatchOperation::Move { path, .. }
+            | PatchOperation::Copy { path, .. }
+            | PatchOperation::Test { path, .. } => path,
+        }
+    }
+}
+
+impl JsonPatch {
+    /// Applies this patch to `value`. If an operation fails, the error points
+    /// at its `path`, and `value` is left as it was.
+    pub fn apply(&self, value: &mut Value) -> Result<(), Error> {
+        let mut patched = value.clone();
+        for (i, operation) in self.0.iter().enumerate() {
+            apply_operation(&mut patched, operation).map_err(|message| {
+                Error::custom(
+                    ErrorKind::Patch,
+                    format!("operation {} failed: {}", i, message),
+                )
+                .with_pointer(operation.path())
+            })?;
+        }
+        *value = patched;
+        Ok(())
+    }
+}
+
+/// Applies a JSON Merge Patch, as described in RFC 7396, to `value`: the
+/// members of objects in `patch` are merged into `value` recursively, with
+/// `null` removing a member, and anything else replaces `value` outright.
+pub fn merge_patch(value: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *value = patch.clone();
+        return;
+    };
+    if !value.is_object() {
+        *value = Value::Object(Default::default());
+    }
+    let Value::Object(object) = value else {
+        unreachable!()
+    };
+    for (key, patch) in patch {
+        match patch {
+            Value::Null => {
+                object.remove(key);
+            }
+            patch => merge_patch(object.entry(key.clone()).or_insert(Value::Null), patch),
+        }
+    }
+}
+
+impl OpenAPI {
+    /// Applies a [JsonPatch] to this document.
+    ///
+    /// The patch is applied to the JSON form of the document, which is then
+    /// parsed again like [from_str], so that the result is checked to be a
+    /// valid document. On failure, the error points at the path of the
+    /// failing operation, or at the invalid part of the patched document, and
+    /// the document is left unchanged.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "get": { "responses": {} } } }
+    /// }"#).unwrap();
+    ///
+    /// let patch: JsonPatch = serde_json::from_str(r#"[
+    ///     { "op": "replace", "path": "/info/version", "value": "2" },
+    ///     { "op": "move", "from": "/paths/~1pets", "path": "/paths/~1animals" }
+    /// ]"#).unwrap();
+    /// api.apply_json_patch(&patch).unwrap();
+    /// assert_eq!(api.info.version, "2");
+    /// assert!(api.paths.paths.contains_key("/animals"));
+    ///
+    /// let patch: JsonPatch = serde_json::from_str(r#"[
+    ///     { "op": "add", "path": "/paths/~1animals/get/parameters", "value": [ {} ] }
+    /// ]"#).unwrap();
+    /// let err = api.apply_json_patch(&patch).unwrap_err();
+    /// assert_eq!(err.pointer(), Some("/paths/~1animals/get/parameters/0"));
+    /// ```
+    pub fn apply_json_patch(&mut self, patch: &JsonPatch) -> Result<(), Error> {
+        let mut value = serde_json::to_value(&*self)?;
+        patch.apply(&mut value)?;
+        *self = crate::de::from_value(value)?;
+        Ok(())
+    }
+
+    /// Applies a JSON Merge Patch to this document, see [merge_patch], and
+    /// checks the result like [OpenAPI::apply_json_patch].
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1", "description": "Pets" },
+    ///     "paths": {}
+    /// }"#).unwrap();
+    /// api.apply_merge_patch(&serde_json::json!({
+    ///     "info": { "version": "2", "description": null }
+    /// })).unwrap();
+    /// assert_eq!(api.info.version, "2");
+    /// assert_eq!(api.info.description, None);
+    /// ```
+    pub fn apply_merge_patch(&mut self, patch: &Value) -> Result<(), Error> {
+        let mut value = serde_json::to_value(&*self)?;
+        merge_patch(&mut value, patch);
+        *self = crate::de::from_value(value)?;
+        Ok(())
+    }
+}
+
+fn apply_operation(value: &mut Value, operation: &PatchOperation) -> Result<(), String> {
+    match operation {
+        PatchOperation::Add { path, value: new } => add(value, path, new.clone()),
+        PatchOperation::Remove { path } => remove(value, path).map(drop),
+        PatchOperation::Replace { path, value: new } => {
+            *value.pointer_mut(path).ok_or("no value at path")? = new.clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+                return Err("cannot move a value into itself".to_owned());
+            }
+            let moved = remove(value, from)?;
+            add(value, path, moved)
+        }
+        PatchOperation::Copy { from, path } => {
+            let copied = value.pointer(from).ok_or("no value at from")?.clone();
+            add(value, path, copied)
+        }
+        PatchOperation::Test {
+            path,
+            value: expected,
+        } => match value.pointer(path) {
+            Some(actual) if actual == expected => Ok(()),
+            Some(_) => Err("value differs".to_owned()),
+            None => Err("no value at path".to_owned()),
+        },
+    }
+}
+
+/// Splits `path` into the pointer to the parent and the last token.
+fn split(path: &str) -> Result<(&str, String), String> {
+    match path.rfind('/') {
+        Some(i) => Ok((&path[..i], unescape_pointer_token(&path[i + 1..]))),
+        None => Err(format!("invalid pointer `{}`", path)),
+    }
+}
+
+fn array_index(token: &str, len: usize) -> Result<usize, String> {
+    match token.parse::<usize>() {
+        Ok(i) if i <= len && (token == "0" || !token.starts_with('0')) => Ok(i),
+        _ => Err(format!("invalid array index `{}`", token)),
+    }
+}
+
+fn add(value: &mut Value, path: &str, new: Value) -> Result<(), String> {
+    if path.is_empty() {
+        *value = new;
+        return Ok(());
+    }
+    let (parent, token) = split(path)?;
+    match value.pointer_mut(parent).ok_or("no parent at path")? {
+        Value::Object(object) => {
+            object.insert(token, new);
+        }
+        Value::Array(array) => {
+            let i = match token.as_str() {
+                "-" => array.len(),
+                token => array_index(token, array.len())?,
+            };
+            array.insert(i, new);
+        }
+        _ => return Err("parent is neither an object nor an array".to_owned()),
+    }
+    Ok(())
+}
+
+fn remove(value: &mut Value, path: &str) -> Result<Value, String> {
+    if path.is_empty() {
+        return Err("cannot remove the whole document".to_owned());
+    }
+    let (parent, token) = split(path)?;
+    let removed = match value.pointer_mut(parent).ok_or("no parent at path")? {
+        Value::Object(object) => object.remove(&token),
+        Value::Array(array) => match array_index(&token, array.len()) {
+            Ok(i) if i < array.len() => Some(array.remove(i)),
+            _ => None,
+        },
+        _ => None,
+    };
+    removed.ok_or_else(|| "no value at path".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(operations: Value) -> JsonPatch {
+        serde_json::from_value(operations).unwrap()
+    }
+
+    #[test]
+    fn test_json_patch() {
+        let mut value = json!({ "a": [1, 2], "b": { "c~/": true } });
+        patch(json!([
+            { "op": "add", "path": "/a/-", "value": 3 },
+            { "op": "add", "path": "/a/0", "value": 0 },
+            { "op": "remove", "path": "/a/1" },
+            { "op": "copy", "from": "/b/c~0~1", "path": "/d" },
+            { "op": "move", "from": "/b", "path": "/a/0" },
+            { "op": "test", "path": "/d", "value": true },
+            { "op": "replace", "path": "/d", "value": null }
+        ]))
+        .apply(&mut value)
+        .unwrap();
+        assert_eq!(value, json!({ "a": [{ "c~/": true }, 0, 2, 3], "d": null }));
+
+        for (operation, message) in [
+            (json!({ "op": "remove", "path": "/x" }), "no value at path"),
+            (
+                json!({ "op": "add", "path": "/a/01", "value": 1 }),
+                "invalid array index `01`",
+            ),
+            (
+                json!({ "op": "add", "path": "/a/9", "value": 1 }),
+                "invalid array index `9`",
+            ),
+            (
+                json!({ "op": "move", "from": "/a", "path": "/a/0" }),
+                "cannot move a value into itself",
+            ),
+            (
+                json!({ "op": "test", "path": "/d", "value": 1 }),
+                "value differs",
+            ),
+            (json!({ "op": "replace", "path": "", "value": 1 }), ""),
+        ] {
+            let mut patched = value.clone();
+            let result = patch(json!([operation])).apply(&mut patched);
+            if message.is_empty() {
+                assert_eq!(patched, json!(1));
+                continue;
+            }
+            let err = result.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Patch);
+            assert_eq!(err.message(), format!("operation 0 failed: {}", message));
+            assert_eq!(err.pointer(), operation["path"].as_str());
+            assert_eq!(patched, value);
+        }
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        // The example of RFC 7396.
+        let mut value = json!({
+            "title": "Goodbye!",
+            "author": { "givenName": "John", "familyName": "Doe" },
+            "tags": ["example", "sample"],
+            "content": "This will be unchanged"
+        });
+        merge_patch(
+            &mut value,
+            &json!({
+                "title": "Hello!",
+                "phoneNumber": "+01-123-456-7890",
+                "author": { "familyName": null },
+                "tags": ["example"]
+            }),
+        );
+        assert_eq!(
+            value,
+            json!({
+                "title": "Hello!",
+                "author": { "givenName": "John" },
+                "tags": ["example"],
+                "content": "This will be unchanged",
+                "phoneNumber": "+01-123-456-7890"
+            })
+        );
+
+        let mut api: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {}
+        }))
+        .unwrap();
+        let original = api.clone();
+        let err = api
+            .apply_merge_patch(&json!({ "info": { "version": [] } }))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Data);
+        assert_eq!(err.pointer(), Some("/info/version"));
+        assert_eq!(api, original);
+    }
+}
@@ -0,0 +1,602 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::util::percent_decode;
+use indexmap::IndexMap;
+
+/// A parsed path template, i.e. a key of the [Paths](crate::Paths) object
+/// such as `/pets/{petId}/photos/{file}.{ext}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathTemplate {
+    segments: Vec<PathSegment>,
+}
+
+/// A `/`-separated segment of a [PathTemplate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathSegment(pub Vec<TemplatePart>);
+
+/// A piece of a [PathSegment].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePart {
+    /// Text that must match verbatim.
+    Literal(String),
+    /// A `{name}` path parameter.
+    Parameter(String),
+}
+
+/// The error returned when a path template is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathTemplateError {
+    /// Path templates must start with `/`.
+    MissingLeadingSlash,
+    /// A `{` at the given byte offset has no matching `}`.
+    UnclosedBrace { position: usize },
+    /// A `}` at the given byte offset has no matching `{`.
+    UnexpectedClosingBrace { position: usize },
+    /// A parameter at the given byte offset has no name.
+    EmptyParameter { position: usize },
+    /// A parameter name appears more than once.
+    DuplicateParameter { name: String },
+}
+
+impl fmt::Display for PathTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLeadingSlash => f.write_str("path templates must start with `/`"),
+            Self::UnclosedBrace { position } => write!(f, "unclosed `{{` at offset {}", position),
+            Self::UnexpectedClosingBrace { position } => {
+                write!(f, "unexpected `}}` at offset {}", position)
+            }
+            Self::EmptyParameter { position } => {
+                write!(f, "empty parameter name at offset {}", position)
+            }
+            Self::DuplicateParameter { name } => {
+                write!(f, "parameter `{}` appears more than once", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PathTemplateError {}
+
+impl PathTemplate {
+    /// The `/`-separated segments, excluding the leading empty one.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Iterates over the parameter names in order of appearance.
+    pub fn parameters(&self) -> impl Iterator<Item = &str> {
+        self.segments
+            .iter()
+            .flat_map(|segment| segment.0.iter())
+            .filter_map(|part| match part {
+                TemplatePart::Parameter(name) => Some(name.as_str()),
+                TemplatePart::Literal(_) => None,
+            })
+    }
+
+    /// Matches a concrete path such as `/pets/42` against this template,
+    /// returning the percent-decoded value of each parameter.
+    ///
+    /// A parameter matches at least one character and never a `/`. When a
+    /// segment holds several parameters, earlier ones match as much as
+    /// possible.
+    ///
+    /// ```
+    /// # use openapiv3::PathTemplate;
+    /// let template: PathTemplate = "/files/{name}.{ext}".parse().unwrap();
+    /// let values = template.match_path("/files/report.tar.gz").unwrap();
+    /// assert_eq!(values["name"], "report.tar");
+    /// assert_eq!(values["ext"], "gz");
+    /// assert!(template.match_path("/files/report").is_none());
+    /// ```
+    pub fn match_path(&self, path: &str) -> Option<IndexMap<String, String>> {
+        Some(
+            self.match_raw(path)?
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), percent_decode(value)))
+                .collect(),
+        )
+    }
+
+    /// Like [PathTemplate::match_path], without decoding the values.
+    pub(crate) fn match_raw<'p>(&self, path: &'p str) -> Option<Vec<(&str, &'p str)>> {
+        let raw = path.strip_prefix('/')?.split('/').collect::<Vec<_>>();
+        if raw.len() != self.segments.len() {
+            return None;
+        }
+        let mut values = Vec::new();
+        for (segment, raw) in self.segments.iter().zip(raw) {
+            if !match_parts(&segment.0, raw, &mut values) {
+                return None;
+            }
+        }
+        Some(values)
+    }
+
+    /// Orders templates so that, among those matching the same path, the
+    /// greater one is the more specific: segments are compared from left to
+    /// right, and literal segments win over templated ones, then longer
+    /// literal text over shorter.
+    pub(crate) fn specificity(&self) -> Vec<(bool, usize)> {
+        self.segments
+            .iter()
+            .map(|segment| {
+                let mut literal = true;
+                let mut length = 0;
+                for part in &segment.0 {
+                    match part {
+                        TemplatePart::Literal(text) => length += text.len(),
+                        TemplatePart::Parameter(_) => literal = false,
+                    }
+                }
+                (literal, length)
+            })
+            .collect()
+    }
+
+    /// Renders this template in the route syntax of a web framework.
+    ///
+    /// ```
+    /// # use openapiv3::{PathTemplate, RouteSyntax};
+    /// let template: PathTemplate = "/pets/{petId}".parse().unwrap();
+    /// assert_eq!(template.to_route(RouteSyntax::AxumColon).unwrap(), "/pets/:petId");
+    /// assert_eq!(template.to_route(RouteSyntax::Warp).unwrap(), r#""pets" / String"#);
+    /// ```
+    pub fn to_route(&self, syntax: RouteSyntax) -> Result<String, RouteError> {
+        for name in self.parameters() {
+            check_parameter_name(name, syntax)?;
+        }
+
+        if syntax == RouteSyntax::Warp {
+            let mut parts = Vec::new();
+            for segment in &self.segments {
+                match segment.0.as_slice() {
+                    [] => {}
+                    [TemplatePart::Literal(text)] => parts.push(format!("{:?}", text)),
+                    [TemplatePart::Parameter(_)] => parts.push("String".to_owned()),
+                    _ => return Err(RouteError::UnsupportedSegment(segment.to_string())),
+                }
+            }
+            return Ok(parts.join(" / "));
+        }
+
+        let mut route = String::new();
+        for segment in &self.segments {
+            route.push('/');
+            if matches!(syntax, RouteSyntax::Axum | RouteSyntax::AxumColon)
+                && segment.0.len() > 1
+                && segment
+                    .0
+                    .iter()
+                    .any(|part| matches!(part, TemplatePart::Parameter(_)))
+            {
+                return Err(RouteError::UnsupportedSegment(segment.to_string()));
+            }
+            for part in &segment.0 {
+                match (part, syntax) {
+                    (TemplatePart::Literal(text), _) => route.push_str(text),
+                    (TemplatePart::Parameter(name), RouteSyntax::AxumColon) => {
+                        route.push(':');
+                        route.push_str(name);
+                    }
+                    (TemplatePart::Parameter(name), _) => {
+                        route.push('{');
+                        route.push_str(name);
+                        route.push('}');
+                    }
+                }
+            }
+        }
+        if route.is_empty() {
+            route.push('/');
+        }
+        Ok(route)
+    }
+
+    /// Parses a route written in the syntax of a web framework.
+    ///
+    /// Catch-all captures (`{*rest}`, `*rest`, `{tail}*`) become ordinary
+    /// parameters and actix regular expressions (`{id:\d+}`) are dropped,
+    /// since path templates cannot express either. Warp routes carry no
+    /// parameter names, so parameters are named `param1`, `param2` and so on.
+    ///
+    /// ```
+    /// # use openapiv3::{PathTemplate, RouteSyntax};
+    /// let template = PathTemplate::from_route("/pets/{id:\\d+}", RouteSyntax::Actix).unwrap();
+    /// assert_eq!(template.to_string(), "/pets/{id}");
+    /// ```
+    pub fn from_route(route: &str, syntax: RouteSyntax) -> Result<Self, RouteError> {
+        let template = match syntax {
+            RouteSyntax::Axum | RouteSyntax::AxumColon => {
+                let mut template = String::new();
+                for segment in route.trim_start_matches('/').split('/') {
+                    template.push('/');
+                    if let Some(name) = segment
+                        .strip_prefix(':')
+                        .or_else(|| segment.strip_prefix('*'))
+                        .or_else(|| segment.strip_prefix("{*").and_then(|s| s.strip_suffix('}')))
+                    {
+                        check_parameter_name(name, syntax)?;
+                        template.push_str(&format!("{{{}}}", name));
+                    } else {
+                        template.push_str(segment);
+                    }
+                }
+                template
+            }
+            RouteSyntax::Actix => {
+                let mut template = String::new();
+                let mut rest = route;
+                while let Some(open) = rest.find('{') {
+                    template.push_str(&rest[..open]);
+                    let close = rest[open..]
+                        .find('}')
+                        .map(|c| open + c)
+                        .ok_or_else(|| RouteError::Syntax(route.to_owned()))?;
+                    let capture = &rest[open + 1..close];
+                    let name = capture.split(':').next().unwrap_or_default();
+                    check_parameter_name(name, syntax)?;
+                    template.push_str(&format!("{{{}}}", name));
+                    rest = &rest[close + 1..];
+                    if let Some(after_tail) = rest.strip_prefix('*') {
+                        rest = after_tail;
+                    }
+                }
+                template.push_str(rest);
+                template
+            }
+            RouteSyntax::Warp => {
+                let mut template = String::new();
+                let mut count = 0;
+                for part in route.split('/').map(str::trim).filter(|p| !p.is_empty()) {
+                    template.push('/');
+                    match part
+                        .strip_prefix('"')
+                        .and_then(|literal| literal.strip_suffix('"'))
+                    {
+                        Some(literal) => template.push_str(literal),
+                        None => {
+                            count += 1;
+                            template.push_str(&format!("{{param{}}}", count));
+                        }
+                    }
+                }
+                if template.is_empty() {
+                    template.push('/');
+                }
+                template
+            }
+        };
+
+        template.parse().map_err(RouteError::Template)
+    }
+}
+
+/// Matches the parts of a segment against `text`, appending the value of
+/// each parameter to `values`.
+fn match_parts<'t, 'p>(
+    parts: &'t [TemplatePart],
+    text: &'p str,
+    values: &mut Vec<(&'t str, &'p str)>,
+) -> bool {
+    match parts.split_first() {
+        None => text.is_empty(),
+        Some((TemplatePart::Literal(literal), rest)) => match text.strip_prefix(literal.as_str()) {
+            Some(text) => match_parts(rest, text, values),
+            None => false,
+        },
+        Some((TemplatePart::Parameter(name), rest)) => {
+            for end in (1..=text.len()).rev() {
+                if !text.is_char_boundary(end) {
+                    continue;
+                }
+                values.push((name, &text[..end]));
+                if match_parts(rest, &text[end..], values) {
+                    return true;
+                }
+                values.pop();
+            }
+            false
+        }
+    }
+}
+
+impl FromStr for PathTemplate {
+    type Err = PathTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s
+            .strip_prefix('/')
+            .ok_or(PathTemplateError::MissingLeadingSlash)?;
+
+        let mut segments = Vec::new();
+        let mut names: Vec<&str> = Vec::new();
+        let mut offset = 1;
+        for raw in rest.split('/') {
+            let mut parts = Vec::new();
+            let mut text = raw;
+            let mut position = offset;
+            while !text.is_empty() {
+                let open = text.find('{');
+                let close = text.find('}');
+                match (open, close) {
+                    (_, Some(c)) if open.is_none_or(|o| c < o) => {
+                        return Err(PathTemplateError::UnexpectedClosingBrace {
+                            position: position + c,
+                        })
+                    }
+                    (Some(o), Some(c)) => {
+                        let name = &text[o + 1..c];
+                        if name.is_empty() {
+                            return Err(PathTemplateError::EmptyParameter {
+                                position: position + o,
+                            });
+                        }
+                        if name.contains('{') {
+                            return Err(PathTemplateError::UnclosedBrace {
+                                position: position + o,
+                            });
+                        }
+                        if names.contains(&name) {
+                            return Err(PathTemplateError::DuplicateParameter {
+                                name: name.to_owned(),
+                            });
+                        }
+                        names.push(name);
+                        if o > 0 {
+                            parts.push(TemplatePart::Literal(text[..o].to_owned()));
+                        }
+                        parts.push(TemplatePart::Parameter(name.to_owned()));
+                        position += c + 1;
+                        text = &text[c + 1..];
+                    }
+                    (Some(o), None) => {
+                        return Err(PathTemplateError::UnclosedBrace {
+                            position: position + o,
+                        })
+                    }
+                    (None, _) => {
+                        parts.push(TemplatePart::Literal(text.to_owned()));
+                        break;
+                    }
+                }
+            }
+            segments.push(PathSegment(parts));
+            offset += raw.len() + 1;
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &self.0 {
+            match part {
+                TemplatePart::Literal(text) => f.write_str(text)?,
+                TemplatePart::Parameter(name) => write!(f, "{{{}}}", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            write!(f, "/{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Route syntaxes of web frameworks supported by [PathTemplate::to_route]
+/// and [PathTemplate::from_route].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteSyntax {
+    /// axum 0.8 and later: `/pets/{id}`, `/files/{*path}`.
+    Axum,
+    /// axum before 0.8: `/pets/:id`, `/files/*path`.
+    AxumColon,
+    /// actix-web: `/pets/{id}`, `/pets/{id:\d+}`, `/files/{tail}*`.
+    Actix,
+    /// The arguments of warp's `path!` macro: `"pets" / String`.
+    Warp,
+}
+
+/// The error returned when converting between path templates and framework
+/// routes fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    /// The path template is malformed.
+    Template(PathTemplateError),
+    /// The route could not be parsed.
+    Syntax(String),
+    /// The parameter name cannot be expressed in the target syntax.
+    IllegalParameterName(String),
+    /// The segment mixes parameters with other text, which the target syntax
+    /// does not support.
+    UnsupportedSegment(String),
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::Template(err) => err.fmt(f),
+            RouteError::Syntax(route) => write!(f, "malformed route `{}`", route),
+            RouteError::IllegalParameterName(name) => {
+                write!(f, "illegal parameter name `{}`", name)
+            }
+            RouteError::UnsupportedSegment(segment) => {
+                write!(
+                    f,
+                    "segment `{}` cannot be expressed in this syntax",
+                    segment
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+fn check_parameter_name(name: &str, syntax: RouteSyntax) -> Result<(), RouteError> {
+    let forbidden: &[char] = match syntax {
+        RouteSyntax::Axum | RouteSyntax::AxumColon => &['/', '{', '}', ':', '*'],
+        RouteSyntax::Actix => &['/', '{', '}', ':'],
+        RouteSyntax::Warp => &['/', '{', '}'],
+    };
+    if name.is_empty() || name.contains(forbidden) || name.contains(char::is_whitespace) {
+        Err(RouteError::IllegalParameterName(name.to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let template: PathTemplate = "/pets/{petId}/files/{name}.{ext}".parse().unwrap();
+        assert_eq!(
+            template.parameters().collect::<Vec<_>>(),
+            vec!["petId", "name", "ext"]
+        );
+        assert_eq!(template.segments().len(), 4);
+        assert_eq!(
+            template.segments()[3].0,
+            vec![
+                TemplatePart::Parameter("name".to_string()),
+                TemplatePart::Literal(".".to_string()),
+                TemplatePart::Parameter("ext".to_string()),
+            ]
+        );
+        assert_eq!(template.to_string(), "/pets/{petId}/files/{name}.{ext}");
+
+        assert_eq!(
+            "pets".parse::<PathTemplate>(),
+            Err(PathTemplateError::MissingLeadingSlash)
+        );
+        assert_eq!(
+            "/pets/{id".parse::<PathTemplate>(),
+            Err(PathTemplateError::UnclosedBrace { position: 6 })
+        );
+        assert_eq!(
+            "/pets/id}".parse::<PathTemplate>(),
+            Err(PathTemplateError::UnexpectedClosingBrace { position: 8 })
+        );
+        assert_eq!(
+            "/a/{}".parse::<PathTemplate>(),
+            Err(PathTemplateError::EmptyParameter { position: 3 })
+        );
+        assert!(matches!(
+            "/{a}/{a}".parse::<PathTemplate>(),
+            Err(PathTemplateError::DuplicateParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_match_path() {
+        let template: PathTemplate = "/pets/{petId}/files/{name}.{ext}".parse().unwrap();
+        let values = template.match_path("/pets/a%20b/files/café.png").unwrap();
+        assert_eq!(
+            values.into_iter().collect::<Vec<_>>(),
+            [
+                ("petId".to_owned(), "a b".to_owned()),
+                ("name".to_owned(), "café".to_owned()),
+                ("ext".to_owned(), "png".to_owned()),
+            ]
+        );
+        assert!(template.match_path("/pets//files/a.png").is_none());
+        assert!(template.match_path("/pets/1/files/a.png/x").is_none());
+        assert!(template.match_path("pets/1/files/a.png").is_none());
+
+        let root: PathTemplate = "/".parse().unwrap();
+        assert!(root.match_path("/").unwrap().is_empty());
+        assert!(root.match_path("/a").is_none());
+    }
+
+    #[test]
+    fn test_to_route() {
+        let template: PathTemplate = "/pets/{petId}/photos".parse().unwrap();
+        assert_eq!(
+            template.to_route(RouteSyntax::Axum).unwrap(),
+            "/pets/{petId}/photos"
+        );
+        assert_eq!(
+            template.to_route(RouteSyntax::AxumColon).unwrap(),
+            "/pets/:petId/photos"
+        );
+        assert_eq!(
+            template.to_route(RouteSyntax::Actix).unwrap(),
+            "/pets/{petId}/photos"
+        );
+        assert_eq!(
+            template.to_route(RouteSyntax::Warp).unwrap(),
+            r#""pets" / String / "photos""#
+        );
+
+        let partial: PathTemplate = "/files/{name}.{ext}".parse().unwrap();
+        assert_eq!(
+            partial.to_route(RouteSyntax::Actix).unwrap(),
+            "/files/{name}.{ext}"
+        );
+        assert!(matches!(
+            partial.to_route(RouteSyntax::Axum),
+            Err(RouteError::UnsupportedSegment(_))
+        ));
+        assert!(matches!(
+            partial.to_route(RouteSyntax::Warp),
+            Err(RouteError::UnsupportedSegment(_))
+        ));
+
+        let odd: PathTemplate = "/a/{b:c}".parse().unwrap();
+        assert_eq!(
+            odd.to_route(RouteSyntax::Actix),
+            Err(RouteError::IllegalParameterName("b:c".to_string()))
+        );
+        assert_eq!(
+            "/".parse::<PathTemplate>()
+                .unwrap()
+                .to_route(RouteSyntax::Axum)
+                .unwrap(),
+            "/"
+        );
+    }
+
+    #[test]
+    fn test_from_route() {
+        let from = |route, syntax| PathTemplate::from_route(route, syntax).unwrap().to_string();
+        assert_eq!(
+            from("/pets/:id/photos", RouteSyntax::AxumColon),
+            "/pets/{id}/photos"
+        );
+        assert_eq!(
+            from("/files/*path", RouteSyntax::AxumColon),
+            "/files/{path}"
+        );
+        assert_eq!(from("/pets/{id}", RouteSyntax::Axum), "/pets/{id}");
+        assert_eq!(from("/files/{*path}", RouteSyntax::Axum), "/files/{path}");
+        assert_eq!(
+            from("/pets/{id:\\d+}/{tail}*", RouteSyntax::Actix),
+            "/pets/{id}/{tail}"
+        );
+        assert_eq!(
+            from(r#""pets" / u32 / "photos" / String"#, RouteSyntax::Warp),
+            "/pets/{param1}/photos/{param2}"
+        );
+
+        assert_eq!(
+            PathTemplate::from_route("/pets/:", RouteSyntax::AxumColon),
+            Err(RouteError::IllegalParameterName(String::new()))
+        );
+        assert_eq!(
+            PathTemplate::from_route("/pets/{id", RouteSyntax::Actix),
+            Err(RouteError::Syntax("/pets/{id".to_string()))
+        );
+    }
+}
@@ -57,7 +57,7 @@ pub struct PathItem {
     pub parameters: Vec<ReferenceOr<Parameter>>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 
 impl PathItem {
@@ -76,6 +76,39 @@ impl PathItem {
         .into_iter()
         .filter_map(|(method, maybe_op)| maybe_op.as_ref().map(|op| (method, op)))
     }
+
+    /// Returns an iterator of mutable references to the [Operation]s in the
+    /// [PathItem].
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &'_ mut Operation)> {
+        vec![
+            ("get", &mut self.get),
+            ("put", &mut self.put),
+            ("post", &mut self.post),
+            ("delete", &mut self.delete),
+            ("options", &mut self.options),
+            ("head", &mut self.head),
+            ("patch", &mut self.patch),
+            ("trace", &mut self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, maybe_op)| maybe_op.as_mut().map(|op| (method, op)))
+    }
+
+    /// Returns the slot for the [Operation] with the given lowercase method
+    /// name, or `None` if `method` is not one supported by a [PathItem].
+    pub fn operation_mut(&mut self, method: &str) -> Option<&mut Option<Operation>> {
+        match method {
+            "get" => Some(&mut self.get),
+            "put" => Some(&mut self.put),
+            "post" => Some(&mut self.post),
+            "delete" => Some(&mut self.delete),
+            "options" => Some(&mut self.options),
+            "head" => Some(&mut self.head),
+            "patch" => Some(&mut self.patch),
+            "trace" => Some(&mut self.trace),
+            _ => None,
+        }
+    }
 }
 
 impl IntoIterator for PathItem {
@@ -112,15 +145,69 @@ pub struct Paths {
     #[serde(flatten, deserialize_with = "deserialize_paths")]
     pub paths: IndexMap<String, ReferenceOr<PathItem>>,
     /// Inline extensions to this object.
-    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    #[serde(flatten, deserialize_with = "deserialize_paths_extensions")]
+    pub extensions: Extensions,
 }
 
 impl Paths {
     /// Iterate over path items.
-    pub fn iter(&self) -> indexmap::map::Iter<String, ReferenceOr<PathItem>> {
+    pub fn iter(&self) -> indexmap::map::Iter<'_, String, ReferenceOr<PathItem>> {
         self.paths.iter()
     }
+
+    /// Finds the path item serving a request path, such as
+    /// `/pets/123/photos`, returning its key and the percent-decoded values
+    /// of its path parameters.
+    ///
+    /// Any query string or fragment is ignored; the base path of the server
+    /// must already be removed. When several templates match, the most
+    /// specific one wins, e.g. `/pets/mine` over `/pets/{id}`, and otherwise
+    /// the first one. Keys that are not valid path templates are skipped.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let paths: Paths = serde_json::from_str(r#"{
+    ///     "/pets/{id}": {},
+    ///     "/pets/mine": {},
+    ///     "/pets/{id}/photos": {}
+    /// }"#).unwrap();
+    /// let (key, _, values) = paths.match_url("/pets/123/photos?page=2").unwrap();
+    /// assert_eq!(key, "/pets/{id}/photos");
+    /// assert_eq!(values["id"], "123");
+    /// assert_eq!(paths.match_url("/pets/mine").unwrap().0, "/pets/mine");
+    /// assert!(paths.match_url("/owners").is_none());
+    /// ```
+    pub fn match_url(
+        &self,
+        url: &str,
+    ) -> Option<(&str, &ReferenceOr<PathItem>, IndexMap<String, String>)> {
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+        let (key, item, template) = self.find_template(path)?;
+        let values = template.match_path(path)?;
+        Some((key, item, values))
+    }
+
+    /// Finds the most specific template matching `path`, see
+    /// [Paths::match_url].
+    pub(crate) fn find_template(
+        &self,
+        path: &str,
+    ) -> Option<(&str, &ReferenceOr<PathItem>, PathTemplate)> {
+        let mut best: Option<(Vec<_>, &str, _, _)> = None;
+        for (key, item) in &self.paths {
+            let template = match key.parse::<PathTemplate>() {
+                Ok(template) => template,
+                Err(_) => continue,
+            };
+            if template.match_raw(path).is_some() {
+                let specificity = template.specificity();
+                if best.as_ref().is_none_or(|(best, ..)| specificity > *best) {
+                    best = Some((specificity, key.as_str(), item, template));
+                }
+            }
+        }
+        best.map(|(_, key, item, template)| (key, item, template))
+    }
 }
 
 impl IntoIterator for Paths {
@@ -133,6 +220,13 @@ impl IntoIterator for Paths {
     }
 }
 
+fn deserialize_paths_extensions<'de, D>(deserializer: D) -> Result<Extensions, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    crate::extensions::deserialize_extensions_except(deserializer, |key| key.starts_with('/'))
+}
+
 fn deserialize_paths<'de, D>(
     deserializer: D,
 ) -> Result<IndexMap<String, ReferenceOr<PathItem>>, D::Error>
@@ -174,4 +268,22 @@ mod tests {
         ];
         assert_eq!(path_item.into_iter().collect::<Vec<_>>(), expected);
     }
+
+    #[test]
+    fn test_match_url() {
+        let paths: Paths = serde_json::from_value(serde_json::json!({
+            "/{kind}/{id}": {},
+            "/pets/{id}": {},
+            "/pets/{id}.json": {},
+            "/pets/mine": {},
+            "/pets/{id": {}
+        }))
+        .unwrap();
+        let matched = |url| paths.match_url(url).map(|(key, _, _)| key);
+        assert_eq!(matched("/pets/mine"), Some("/pets/mine"));
+        assert_eq!(matched("/pets/1.json"), Some("/pets/{id}.json"));
+        assert_eq!(matched("/pets/1#top"), Some("/pets/{id}"));
+        assert_eq!(matched("/owners/1"), Some("/{kind}/{id}"));
+        assert_eq!(matched("/pets"), None);
+    }
 }
@@ -1,5 +1,18 @@
+use std::fmt;
+use std::str::FromStr;
+
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+use crate::util::{escape_pointer_token, percent_decode, percent_encode, unescape_pointer_token};
+use crate::ComponentKind;
+
+/// Either a `$ref` or an inline item.
+///
+/// As the specification requires, any keys next to `$ref` are ignored when
+/// deserializing, so objects such as `{"nullable": true, "$ref": "..."}`
+/// emitted by some generators parse as plain references. Use
+/// [collect_ref_siblings] on the raw document to recover those keys.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum ReferenceOr<T> {
@@ -63,6 +76,35 @@ impl<T> ReferenceOr<T> {
             ReferenceOr::Item(i) => Some(i),
         }
     }
+
+    /// Returns `true` if this is a [ReferenceOr::Reference] whose target is
+    /// equivalent to `reference` once both are normalized with
+    /// [normalize_reference].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use openapiv3::ReferenceOr;
+    ///
+    /// let r: ReferenceOr<u8> = ReferenceOr::ref_("#/components/schemas/Foo");
+    /// assert!(r.points_to("#/components/schemas~1Foo/"));
+    /// assert!(!ReferenceOr::Item(1).points_to("#/components/schemas/Foo"));
+    /// ```
+    pub fn points_to(&self, reference: &str) -> bool {
+        match self {
+            ReferenceOr::Reference { reference: r } => references_equal(r, reference),
+            ReferenceOr::Item(_) => false,
+        }
+    }
+
+    /// Boxes the item, if this is one. This is the inverse of
+    /// [ReferenceOr::unbox].
+    pub fn boxed(self) -> ReferenceOr<Box<T>> {
+        match self {
+            ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference },
+            ReferenceOr::Item(item) => ReferenceOr::Item(Box::new(item)),
+        }
+    }
 }
 
 impl<T> ReferenceOr<Box<T>> {
@@ -73,3 +115,395 @@ impl<T> ReferenceOr<Box<T>> {
         }
     }
 }
+
+/// A parsed `$ref` target naming a component, such as
+/// `#/components/schemas/Foo` or `common.yaml#/components/schemas/Foo`.
+///
+/// Parsing percent-decodes the fragment and unescapes the name, and
+/// [Display](fmt::Display) writes the canonical, escaped form back, so names
+/// containing `/` or `~` round-trip.
+///
+/// ```
+/// # use openapiv3::*;
+/// let reference: Reference = "#/components/schemas/Foo".parse().unwrap();
+/// assert_eq!(reference, Reference::schema("Foo"));
+/// assert_eq!(Reference::schema("a/b").to_string(), "#/components/schemas/a~1b");
+///
+/// let external: Reference = "common.yaml#/components/responses/NotFound".parse().unwrap();
+/// assert_eq!(external.uri.as_deref(), Some("common.yaml"));
+/// assert_eq!(external.kind, ComponentKind::Responses);
+///
+/// let schema: ReferenceOr<Schema> = Reference::schema("Pet").into();
+/// assert_eq!(schema, ReferenceOr::ref_("#/components/schemas/Pet"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reference {
+    /// The document holding the component, or `None` for the current one.
+    pub uri: Option<String>,
+    pub kind: ComponentKind,
+    /// The name of the component, unescaped.
+    pub name: String,
+}
+
+/// The error returned when a string is not a [Reference] to a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceError {
+    input: String,
+    reason: &'static str,
+}
+
+impl fmt::Display for ReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid component reference `{}`: {}",
+            self.input, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ReferenceError {}
+
+impl Reference {
+    /// A reference to the component `name` in the current document.
+    pub fn new(kind: ComponentKind, name: impl Into<String>) -> Self {
+        Self {
+            uri: None,
+            kind,
+            name: name.into(),
+        }
+    }
+
+    pub fn schema(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::Schemas, name)
+    }
+
+    pub fn response(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::Responses, name)
+    }
+
+    pub fn parameter(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::Parameters, name)
+    }
+
+    pub fn example(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::Examples, name)
+    }
+
+    pub fn request_body(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::RequestBodies, name)
+    }
+
+    pub fn header(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::Headers, name)
+    }
+
+    pub fn security_scheme(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::SecuritySchemes, name)
+    }
+
+    pub fn link(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::Links, name)
+    }
+
+    pub fn callback(name: impl Into<String>) -> Self {
+        Self::new(ComponentKind::Callbacks, name)
+    }
+
+    /// Points this reference at the document `uri` instead.
+    pub fn in_document(mut self, uri: impl Into<String>) -> Self {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// Returns `true` if the component is in the current document.
+    pub fn is_local(&self) -> bool {
+        self.uri.is_none()
+    }
+}
+
+impl FromStr for Reference {
+    type Err = ReferenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |reason| ReferenceError {
+            input: s.to_owned(),
+            reason,
+        };
+        let (uri, fragment) = s
+            .split_once('#')
+            .ok_or_else(|| error("missing `#` fragment"))?;
+        let fragment = percent_decode(fragment);
+        let mut tokens = fragment.split('/');
+        match (tokens.next(), tokens.next(), tokens.next(), tokens.next()) {
+            (Some(""), Some("components"), Some(section), Some(name))
+                if tokens.next().is_none() && !name.is_empty() =>
+            {
+                let kind = section
+                    .parse()
+                    .map_err(|()| error("unknown components section"))?;
+                Ok(Reference {
+                    uri: (!uri.is_empty()).then(|| uri.to_owned()),
+                    kind,
+                    name: unescape_pointer_token(name),
+                })
+            }
+            _ => Err(error("expected `#/components/<section>/<name>`")),
+        }
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}#/components/{}/{}",
+            self.uri.as_deref().unwrap_or_default(),
+            self.kind,
+            percent_encode(&escape_pointer_token(&self.name), false)
+        )
+    }
+}
+
+impl<T> From<Reference> for ReferenceOr<T> {
+    fn from(reference: Reference) -> Self {
+        ReferenceOr::Reference {
+            reference: reference.to_string(),
+        }
+    }
+}
+
+/// Normalizes a `$ref` string so that equivalent spellings of the same target
+/// compare equal.
+///
+/// Both the URI and the fragment are percent-decoded. A JSON Pointer fragment
+/// then has its `~1` and `~0` escapes applied, and empty segments (repeated or
+/// trailing slashes) removed. The result is re-escaped, so
+/// `#/components/schemas/Foo`, `#/components/schemas~1Foo`,
+/// `#/components/schemas/Foo/` and `#/components/%73chemas/Foo` all normalize
+/// to `#/components/schemas/Foo`.
+///
+/// Note that this deliberately treats an escaped `/` the same as a path
+/// separator, so the result is suited to comparing and deduplicating
+/// references, not to resolving them.
+pub fn normalize_reference(reference: &str) -> String {
+    let (uri, fragment) = match reference.split_once('#') {
+        Some((uri, fragment)) => (uri, Some(fragment)),
+        None => (reference, None),
+    };
+
+    let mut normalized = percent_decode(uri);
+    if let Some(fragment) = fragment {
+        let fragment = percent_decode(fragment);
+        normalized.push('#');
+        if fragment.starts_with('/') {
+            let tokens = fragment
+                .split('/')
+                .map(unescape_pointer_token)
+                .collect::<Vec<_>>()
+                .join("/");
+            for token in tokens.split('/').filter(|t| !t.is_empty()) {
+                normalized.push('/');
+                normalized.push_str(&escape_pointer_token(token));
+            }
+        } else {
+            normalized.push_str(&fragment);
+        }
+    }
+    normalized
+}
+
+/// Returns `true` if the two `$ref` strings point at the same target once
+/// normalized with [normalize_reference].
+pub fn references_equal(a: &str, b: &str) -> bool {
+    a == b || normalize_reference(a) == normalize_reference(b)
+}
+
+/// The keys found next to `$ref` in a document, keyed by the JSON Pointer of
+/// the object holding the reference.
+pub type RefSiblings = IndexMap<String, serde_json::Map<String, serde_json::Value>>;
+
+/// Collects the keys next to every `$ref` in a raw document, which are
+/// dropped when it is deserialized.
+///
+/// Example values, defaults, enumerations and extensions are not searched.
+///
+/// ```
+/// # use openapiv3::collect_ref_siblings;
+/// let document = serde_json::json!({
+///     "components": { "schemas": { "Pet": { "properties": {
+///         "owner": { "nullable": true, "$ref": "#/components/schemas/Person" }
+///     } } } }
+/// });
+/// let siblings = collect_ref_siblings(&document);
+/// assert_eq!(
+///     siblings["/components/schemas/Pet/properties/owner"]["nullable"],
+///     true
+/// );
+/// ```
+pub fn collect_ref_siblings(document: &serde_json::Value) -> RefSiblings {
+    fn collect(value: &serde_json::Value, pointer: &mut String, siblings: &mut RefSiblings) {
+        match value {
+            serde_json::Value::Object(object) => {
+                if object.get("$ref").is_some_and(serde_json::Value::is_string) {
+                    let mut others = object.clone();
+                    others.remove("$ref");
+                    if !others.is_empty() {
+                        siblings.insert(pointer.clone(), others);
+                    }
+                    return;
+                }
+                for (key, child) in object {
+                    if matches!(key.as_str(), "example" | "default" | "enum")
+                        || key.starts_with("x-")
+                    {
+                        continue;
+                    }
+                    let length = pointer.len();
+                    pointer.push('/');
+                    pointer.push_str(&escape_pointer_token(key));
+                    collect(child, pointer, siblings);
+                    pointer.truncate(length);
+                }
+            }
+            serde_json::Value::Array(array) => {
+                for (i, child) in array.iter().enumerate() {
+                    let length = pointer.len();
+                    pointer.push('/');
+                    pointer.push_str(&i.to_string());
+                    collect(child, pointer, siblings);
+                    pointer.truncate(length);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut siblings = RefSiblings::new();
+    collect(document, &mut String::new(), &mut siblings);
+    siblings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        collect_ref_siblings, normalize_reference, references_equal, Reference, ReferenceOr,
+    };
+    use crate::{ComponentKind, Parameter, Schema};
+
+    #[test]
+    fn test_normalize_reference() {
+        let canonical = "#/components/schemas/Foo";
+        for r in [
+            "#/components/schemas/Foo",
+            "#/components/schemas~1Foo",
+            "#/components/schemas/Foo/",
+            "#/components//schemas/Foo",
+            "#/components/%73chemas/Foo",
+            "#%2Fcomponents%2Fschemas%2FFoo",
+        ] {
+            assert_eq!(normalize_reference(r), canonical, "{}", r);
+        }
+
+        assert_eq!(normalize_reference("#/a~0b"), "#/a~0b");
+        assert_eq!(normalize_reference("#/a%7E0b"), "#/a~0b");
+        assert_eq!(
+            normalize_reference("common%20types.yaml#/Foo/"),
+            "common types.yaml#/Foo"
+        );
+        assert_eq!(normalize_reference("other.yaml"), "other.yaml");
+        assert_eq!(normalize_reference("#anchor"), "#anchor");
+        assert_eq!(normalize_reference("#/bad%zzescape"), "#/bad%zzescape");
+    }
+
+    #[test]
+    fn test_references_equal() {
+        assert!(references_equal(
+            "#/components/schemas/Foo",
+            "#/components/schemas~1Foo"
+        ));
+        assert!(!references_equal(
+            "#/components/schemas/Foo",
+            "#/components/schemas/Bar"
+        ));
+        assert!(!references_equal(
+            "a.yaml#/components/schemas/Foo",
+            "#/components/schemas/Foo"
+        ));
+    }
+
+    #[test]
+    fn test_ref_siblings() {
+        let schema: ReferenceOr<Schema> = serde_json::from_str(
+            r##"{ "nullable": false, "description": "Owner", "$ref": "#/components/schemas/Person" }"##,
+        )
+        .unwrap();
+        assert_eq!(schema, ReferenceOr::ref_("#/components/schemas/Person"));
+        let parameter: ReferenceOr<Parameter> = serde_json::from_str(
+            r##"{ "$ref": "#/components/parameters/Limit", "x-ms-parameter-location": "method" }"##,
+        )
+        .unwrap();
+        assert_eq!(
+            parameter,
+            ReferenceOr::ref_("#/components/parameters/Limit")
+        );
+
+        let document = serde_json::json!({
+            "paths": { "/pets": { "get": { "parameters": [
+                { "$ref": "#/components/parameters/Limit", "required": true },
+                { "$ref": "#/components/parameters/Offset" }
+            ] } } },
+            "components": { "schemas": { "Pet": {
+                "example": { "$ref": "not a reference", "x": 1 },
+                "properties": { "id": { "$ref": "#/components/schemas/Id", "readOnly": true } }
+            } } }
+        });
+        let siblings = collect_ref_siblings(&document);
+        assert_eq!(
+            siblings.keys().collect::<Vec<_>>(),
+            [
+                "/components/schemas/Pet/properties/id",
+                "/paths/~1pets/get/parameters/0"
+            ]
+        );
+        assert_eq!(
+            siblings["/paths/~1pets/get/parameters/0"],
+            *serde_json::json!({ "required": true }).as_object().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reference() {
+        for (input, expected) in [
+            ("#/components/schemas/Foo", Reference::schema("Foo")),
+            (
+                "#/components/requestBodies/a~1b~0c",
+                Reference::request_body("a/b~c"),
+            ),
+            ("#/components/headers/X%20Rate", Reference::header("X Rate")),
+            (
+                "other.yaml#/components/securitySchemes/Key",
+                Reference::security_scheme("Key").in_document("other.yaml"),
+            ),
+        ] {
+            let reference: Reference = input.parse().unwrap();
+            assert_eq!(reference, expected);
+            assert_eq!(reference.to_string(), input);
+        }
+        assert!(Reference::schema("Foo").is_local());
+        assert_eq!(
+            Reference::new(ComponentKind::Links, "next").to_string(),
+            "#/components/links/next"
+        );
+
+        for input in [
+            "other.yaml",
+            "#/definitions/Foo",
+            "#/components/models/Foo",
+            "#/components/schemas/",
+            "#/components/schemas/Foo/properties/id",
+        ] {
+            assert!(input.parse::<Reference>().is_err(), "{}", input);
+        }
+    }
+}
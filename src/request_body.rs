@@ -23,5 +23,13 @@ pub struct RequestBody {
     pub required: bool,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
+}
+
+impl RequestBody {
+    /// Returns the content entry that applies to `media_type`, see
+    /// [content_for].
+    pub fn content_for(&self, media_type: &str) -> Option<&MediaType> {
+        content_for(&self.content, media_type).map(|(_, content)| content)
+    }
 }
@@ -0,0 +1,451 @@
+use crate::flat_operation::flatten;
+use crate::util::escape_pointer_token;
+use crate::validation::server_url;
+use crate::value_validation::{validate_value_at, Direction};
+use crate::*;
+use indexmap::IndexMap;
+use serde_json::Value;
+
+/// The operation serving a request, as returned by
+/// [OpenAPI::validate_request].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedOperation<'a> {
+    pub operation: FlatOperation<'a>,
+    /// The parameters of the request.
+    pub parameters: ParsedParameters,
+    /// The body of the request, if it has a JSON media type.
+    pub body: Option<Value>,
+}
+
+impl OpenAPI {
+    /// Finds the operation serving a request and checks the request against
+    /// it: its parameters are parsed with [Parameter::extract] and checked
+    /// against their schemas, and JSON bodies against the schema of their
+    /// media type. Bodies of other media types are only checked for being
+    /// accepted.
+    ///
+    /// `url` may be absolute or start at the path, and may include the base
+    /// path of one of the servers of the document. Violations point into the
+    /// request: `/query/{name}`, `/path/{name}`, `/header/{name}` and
+    /// `/cookie/{name}` for parameters, and `/body` for the body. Required
+    /// `readOnly` properties may be left out of the body.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "servers": [ { "url": "https://pets.example.com/v1" } ],
+    ///     "paths": { "/pets/{id}": { "put": {
+    ///         "parameters": [ { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } } ],
+    ///         "requestBody": { "required": true, "content": { "application/json": { "schema": {
+    ///             "type": "object", "required": ["name"], "properties": { "name": { "type": "string" } }
+    ///         } } } },
+    ///         "responses": {}
+    ///     } } }
+    /// }"#).unwrap();
+    /// let headers = [("Content-Type", "application/json")];
+    ///
+    /// let matched = api
+    ///     .validate_request("PUT", "/v1/pets/7", &headers, Some(br#"{ "name": "Rex" }"#))
+    ///     .unwrap();
+    /// assert_eq!(matched.operation.path, "/pets/{id}");
+    /// assert_eq!(matched.parameters.path["id"], 7);
+    ///
+    /// let violations = api
+    ///     .validate_request("PUT", "/v1/pets/x", &headers, Some(b"{}"))
+    ///     .unwrap_err();
+    /// let pointers: Vec<_> = violations.iter().map(|v| v.pointer.as_str()).collect();
+    /// assert_eq!(pointers, ["/path/id", "/body"]);
+    /// ```
+    pub fn validate_request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+    ) -> Result<MatchedOperation<'_>, Vec<Violation>> {
+        let violation = |kind, pointer: &str, message: String| Violation {
+            kind,
+            pointer: pointer.to_owned(),
+            message,
+        };
+        let url = path_and_query(url);
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let query = query.split('#').next().unwrap_or_default();
+        let path = path.split('#').next().unwrap_or_default();
+
+        let bases = self.servers.iter().filter_map(|server| {
+            let url = server_url(server)?;
+            let base = path_and_query(&url)
+                .split('?')
+                .next()?
+                .trim_end_matches('/');
+            let rest = path.strip_prefix(base)?;
+            (rest.starts_with('/') && !base.is_empty()).then(|| rest.to_owned())
+        });
+        let candidates: Vec<String> = bases.chain([path.to_owned()]).collect();
+        let found = candidates.iter().find_map(|path| {
+            let (key, item, template) = self.paths.find_template(path)?;
+            Some((path, key, item, template))
+        });
+        let no_operation = || {
+            vec![violation(
+                ViolationKind::NoMatchingOperation,
+                "",
+                format!("no operation serves {} {}", method, path),
+            )]
+        };
+        let (path, key, item, template) = found.ok_or_else(no_operation)?;
+        let item = match item {
+            ReferenceOr::Item(item) => item,
+            ReferenceOr::Reference { reference } => {
+                return Err(vec![violation(
+                    ViolationKind::UnresolvedReference,
+                    "",
+                    format!("path item `{}` is a reference to `{}`", key, reference),
+                )])
+            }
+        };
+        let (method, operation) = item
+            .iter()
+            .find(|(m, _)| m.eq_ignore_ascii_case(method))
+            .ok_or_else(no_operation)?;
+        let components = self.components.as_ref();
+        let operation = flatten(components, key, item, method, operation).map_err(|err| {
+            vec![violation(
+                ViolationKind::UnresolvedReference,
+                "",
+                err.to_string(),
+            )]
+        })?;
+
+        let cookie = header_values(headers, "cookie")
+            .collect::<Vec<_>>()
+            .join("; ");
+        let raw = RawParameters {
+            query,
+            path: template
+                .match_raw(path)
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            headers: headers.to_vec(),
+            cookie: &cookie,
+        };
+
+        let mut violations = Vec::new();
+        let mut parameters = ParsedParameters::default();
+        for parameter in &operation.parameters {
+            let data = parameter.parameter_data_ref();
+            let (location, values) = match parameter {
+                Parameter::Query { .. } => ("query", &mut parameters.query),
+                Parameter::Path { .. } => ("path", &mut parameters.path),
+                Parameter::Header { .. } => ("header", &mut parameters.header),
+                Parameter::Cookie { .. } => ("cookie", &mut parameters.cookie),
+            };
+            let pointer = format!("/{}/{}", location, escape_pointer_token(&data.name));
+            match parameter.extract(&raw, components) {
+                Ok(Some(value)) => {
+                    let schema = match &data.format {
+                        ParameterSchemaOrContent::Schema(schema) => Some(&**schema),
+                        ParameterSchemaOrContent::Content(content) => content
+                            .values()
+                            .next()
+                            .and_then(|media| media.schema.as_ref()),
+                    };
+                    if let Some(schema) = schema {
+                        violations.extend(validate_value_at(
+                            schema,
+                            &value,
+                            components,
+                            Direction::Request,
+                            &pointer,
+                        ));
+                    }
+                    values.insert(data.name.clone(), value);
+                }
+                Ok(None) if data.required || location == "path" => violations.push(violation(
+                    ViolationKind::MissingValue,
+                    &pointer,
+                    format!("missing required {} parameter `{}`", location, data.name),
+                )),
+                Ok(None) => {}
+                Err(err) => violations.push(violation(
+                    ViolationKind::UnparsableValue,
+                    &pointer,
+                    err.to_string(),
+                )),
+            }
+        }
+
+        let request_body = match (&operation.operation.request_body, components) {
+            (Some(ReferenceOr::Item(body)), _) => Some(body),
+            (Some(reference), Some(components)) => reference.resolve(components).ok(),
+            _ => None,
+        };
+        let mut json = None;
+        match (request_body, body.filter(|body| !body.is_empty())) {
+            (Some(request_body), None) if request_body.required => violations.push(violation(
+                ViolationKind::MissingValue,
+                "/body",
+                "missing required request body".to_owned(),
+            )),
+            (Some(request_body), Some(body)) => {
+                let content_type = header_values(headers, "content-type")
+                    .next()
+                    .unwrap_or_default();
+                json = check_body(
+                    &request_body.content,
+                    content_type,
+                    body,
+                    components,
+                    Direction::Request,
+                    &mut violations,
+                );
+            }
+            _ => {}
+        }
+
+        match violations.is_empty() {
+            true => Ok(MatchedOperation {
+                operation,
+                parameters,
+                body: json,
+            }),
+            false => Err(violations),
+        }
+    }
+}
+
+/// Checks a message body of media type `content_type` against `content`,
+/// pushing any violations onto `violations`. Returns the body if it is JSON.
+pub(crate) fn check_body(
+    content: &IndexMap<String, MediaType>,
+    content_type: &str,
+    body: &[u8],
+    components: Option<&Components>,
+    direction: Direction,
+    violations: &mut Vec<Violation>,
+) -> Option<Value> {
+    let mut violation = |kind, message| {
+        violations.push(Violation {
+            kind,
+            pointer: "/body".to_owned(),
+            message,
+        })
+    };
+    let media = match content_for(content, content_type) {
+        Some((_, media)) => media,
+        None => {
+            violation(
+                ViolationKind::UnsupportedMediaType,
+                format!("media type `{}` is not accepted", content_type),
+            );
+            return None;
+        }
+    };
+    if !is_json(content_type) {
+        return None;
+    }
+    match serde_json::from_slice::<Value>(body) {
+        Ok(value) => {
+            if let Some(schema) = &media.schema {
+                violations.extend(validate_value_at(
+                    schema, &value, components, direction, "/body",
+                ));
+            }
+            Some(value)
+        }
+        Err(err) => {
+            violation(
+                ViolationKind::UnparsableValue,
+                format!("invalid JSON: {}", err),
+            );
+            None
+        }
+    }
+}
+
+/// Strips the scheme and authority from an absolute `url`.
+pub(crate) fn path_and_query(url: &str) -> &str {
+    match url.split_once("://") {
+        Some((_, rest)) => match rest.find(['/', '?', '#']) {
+            Some(i) => &rest[i..],
+            None => "/",
+        },
+        None => url,
+    }
+}
+
+/// The values of the headers named `name`, compared case-insensitively.
+pub(crate) fn header_values<'h>(
+    headers: &'h [(&str, &'h str)],
+    name: &'h str,
+) -> impl Iterator<Item = &'h str> {
+    headers
+        .iter()
+        .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, v)| *v)
+}
+
+/// Whether `media_type` is JSON, i.e. `application/json` or a `+json`
+/// suffix.
+pub(crate) fn is_json(media_type: &str) -> bool {
+    media_type.parse::<MediaTypeKey>().is_ok_and(|key| {
+        key.typ == "application" && (key.subtype == "json" || key.suffix.as_deref() == Some("json"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn api() -> OpenAPI {
+        serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "parameters": [ { "$ref": "#/components/parameters/Limit" } ],
+                    "get": {
+                        "parameters": [
+                            { "name": "tags", "in": "query", "schema": { "type": "array", "items": { "type": "string", "enum": ["a", "b"] } } },
+                            { "name": "X-Trace", "in": "header", "required": true, "schema": { "type": "string" } },
+                            { "name": "session", "in": "cookie", "schema": { "type": "string" } }
+                        ],
+                        "responses": {}
+                    },
+                    "post": {
+                        "requestBody": { "$ref": "#/components/requestBodies/Pet" },
+                        "responses": {}
+                    }
+                },
+                "/pets/mine": { "get": { "responses": {} } }
+            },
+            "components": {
+                "parameters": {
+                    "Limit": { "name": "limit", "in": "query", "schema": { "type": "integer", "maximum": 100 } }
+                },
+                "requestBodies": {
+                    "Pet": { "content": {
+                        "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } },
+                        "text/plain": {}
+                    } }
+                },
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["id", "name"],
+                        "properties": {
+                            "id": { "type": "integer", "readOnly": true },
+                            "name": { "type": "string" }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn pointers(violations: Vec<Violation>) -> Vec<(ViolationKind, String)> {
+        violations
+            .into_iter()
+            .map(|violation| (violation.kind, violation.pointer))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_request_parameters() {
+        let api = api();
+        let headers = [
+            ("x-trace", "abc"),
+            ("Cookie", "theme=dark"),
+            ("cookie", "session=1"),
+        ];
+        let matched = api
+            .validate_request(
+                "get",
+                "https://pets.example.com/pets?limit=5&tags=a&tags=b#top",
+                &headers,
+                None,
+            )
+            .unwrap();
+        assert_eq!(matched.operation.method, "get");
+        assert_eq!(matched.parameters.query["limit"], json!(5));
+        assert_eq!(matched.parameters.query["tags"], json!(["a", "b"]));
+        assert_eq!(matched.parameters.header["X-Trace"], json!("abc"));
+        assert_eq!(matched.parameters.cookie["session"], json!("1"));
+
+        assert_eq!(
+            pointers(
+                api.validate_request("GET", "/pets?limit=500&tags=c", &[], None)
+                    .unwrap_err()
+            ),
+            [
+                (ViolationKind::SchemaMismatch, "/query/limit".to_owned()),
+                (ViolationKind::SchemaMismatch, "/query/tags/0".to_owned()),
+                (ViolationKind::MissingValue, "/header/X-Trace".to_owned()),
+            ]
+        );
+        assert_eq!(
+            pointers(
+                api.validate_request("GET", "/pets?limit=x", &headers, None)
+                    .unwrap_err()
+            ),
+            [(ViolationKind::UnparsableValue, "/query/limit".to_owned())]
+        );
+        assert_eq!(
+            pointers(
+                api.validate_request("DELETE", "/pets", &[], None)
+                    .unwrap_err()
+            ),
+            [(ViolationKind::NoMatchingOperation, String::new())]
+        );
+        assert!(api.validate_request("GET", "/pets/mine", &[], None).is_ok());
+        assert!(api.validate_request("GET", "/owners", &[], None).is_err());
+    }
+
+    #[test]
+    fn test_validate_request_body() {
+        let api = api();
+        let json = [("Content-Type", "application/json; charset=utf-8")];
+        let matched = api
+            .validate_request("POST", "/pets", &json, Some(br#"{ "name": "Rex" }"#))
+            .unwrap();
+        assert_eq!(matched.body, Some(json!({ "name": "Rex" })));
+        assert!(api.validate_request("POST", "/pets", &[], None).is_ok());
+        let text = [("content-type", "text/plain")];
+        assert!(api
+            .validate_request("POST", "/pets", &text, Some(b"Rex"))
+            .is_ok());
+
+        assert_eq!(
+            pointers(
+                api.validate_request("POST", "/pets", &json, Some(br#"{ "name": 1 }"#))
+                    .unwrap_err()
+            ),
+            [(ViolationKind::SchemaMismatch, "/body/name".to_owned())]
+        );
+        assert_eq!(
+            pointers(
+                api.validate_request("POST", "/pets", &json, Some(b"{"))
+                    .unwrap_err()
+            ),
+            [(ViolationKind::UnparsableValue, "/body".to_owned())]
+        );
+        assert_eq!(
+            pointers(
+                api.validate_request(
+                    "POST",
+                    "/pets",
+                    &[("content-type", "image/png")],
+                    Some(b"x")
+                )
+                .unwrap_err()
+            ),
+            [(ViolationKind::UnsupportedMediaType, "/body".to_owned())]
+        );
+    }
+}
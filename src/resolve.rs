@@ -0,0 +1,259 @@
+use std::fmt;
+
+use crate::util::{percent_decode, unescape_pointer_token};
+use crate::*;
+use indexmap::IndexMap;
+
+/// The error returned when a `$ref` cannot be resolved against [Components].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The reference points outside of the current document.
+    External { reference: String },
+    /// The reference is not of the form `#/components/<kind>/<name>` for the
+    /// expected kind of component.
+    Invalid {
+        reference: String,
+        expected: ComponentKind,
+    },
+    /// There is no component with the referenced name.
+    Missing { reference: String },
+    /// Following the chain of references leads back to an earlier one.
+    Cycle { reference: String },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::External { reference } => {
+                write!(f, "`{}` refers to an external document", reference)
+            }
+            ResolveError::Invalid {
+                reference,
+                expected,
+            } => write!(
+                f,
+                "`{}` is not a reference to #/components/{}",
+                reference, expected
+            ),
+            ResolveError::Missing { reference } => {
+                write!(f, "`{}` does not exist", reference)
+            }
+            ResolveError::Cycle { reference } => {
+                write!(f, "`{}` is part of a reference cycle", reference)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// Types that live in one of the sections of [Components], and can therefore
+/// be the target of a reference.
+pub trait Component: Sized {
+    /// The section of [Components] holding this type.
+    const KIND: ComponentKind;
+
+    fn section(components: &Components) -> &IndexMap<String, ReferenceOr<Self>>;
+
+    fn section_mut(components: &mut Components) -> &mut IndexMap<String, ReferenceOr<Self>>;
+}
+
+macro_rules! component {
+    ($ty:ty, $kind:ident, $field:ident) => {
+        impl Component for $ty {
+            const KIND: ComponentKind = ComponentKind::$kind;
+
+            fn section(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
+                &components.$field
+            }
+
+            fn section_mut(
+                components: &mut Components,
+            ) -> &mut IndexMap<String, ReferenceOr<Self>> {
+                &mut components.$field
+            }
+        }
+    };
+}
+
+component!(Schema, Schemas, schemas);
+component!(Response, Responses, responses);
+component!(Parameter, Parameters, parameters);
+component!(Example, Examples, examples);
+component!(RequestBody, RequestBodies, request_bodies);
+component!(Header, Headers, headers);
+component!(SecurityScheme, SecuritySchemes, security_schemes);
+component!(Link, Links, links);
+component!(Callback, Callbacks, callbacks);
+
+/// Extracts the component name from a local `#/components/<kind>/<name>`
+/// reference.
+pub(crate) fn component_name(reference: &str, kind: ComponentKind) -> Result<String, ResolveError> {
+    let fragment = reference
+        .strip_prefix('#')
+        .ok_or_else(|| ResolveError::External {
+            reference: reference.to_owned(),
+        })?;
+    let fragment = percent_decode(fragment);
+    let invalid = || ResolveError::Invalid {
+        reference: reference.to_owned(),
+        expected: kind,
+    };
+    let mut tokens = fragment.split('/');
+    match (tokens.next(), tokens.next(), tokens.next(), tokens.next()) {
+        (Some(""), Some("components"), Some(section), Some(name))
+            if section == kind.as_str() && tokens.next().is_none() =>
+        {
+            Ok(unescape_pointer_token(name))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Follows `reference`, and any references it leads to, to an item of type
+/// `T`.
+pub(crate) fn resolve_reference<'a, T: Component>(
+    components: &'a Components,
+    reference: &str,
+) -> Result<&'a T, ResolveError> {
+    let section = T::section(components);
+    let mut seen = Vec::new();
+    let mut current = reference.to_owned();
+    loop {
+        let name = component_name(&current, T::KIND)?;
+        if seen.contains(&name) {
+            return Err(ResolveError::Cycle { reference: current });
+        }
+        match section.get(&name) {
+            None => return Err(ResolveError::Missing { reference: current }),
+            Some(ReferenceOr::Item(item)) => return Ok(item),
+            Some(ReferenceOr::Reference { reference }) => {
+                seen.push(name);
+                current = reference.clone();
+            }
+        }
+    }
+}
+
+/// Returns the item itself, or the item that a reference resolves to.
+pub(crate) fn resolve_item<'a, T: Component>(
+    components: &'a Components,
+    item: &'a ReferenceOr<T>,
+) -> Result<&'a T, ResolveError> {
+    match item {
+        ReferenceOr::Item(item) => Ok(item),
+        ReferenceOr::Reference { reference } => resolve_reference(components, reference),
+    }
+}
+
+impl<T: Component> ReferenceOr<T> {
+    /// Returns the item, following the reference through `components` if
+    /// this is a reference.
+    ///
+    /// Chains of references are followed until an item is found. Only local
+    /// references of the form `#/components/<kind>/<name>` are supported.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let components: Components = serde_json::from_str(r##"{
+    ///     "schemas": {
+    ///         "Id": { "$ref": "#/components/schemas/Uuid" },
+    ///         "Uuid": { "type": "string", "format": "uuid" }
+    ///     }
+    /// }"##).unwrap();
+    /// let id = ReferenceOr::<Schema>::ref_("#/components/schemas/Id");
+    /// assert_eq!(id.resolve(&components).unwrap(), components.schemas["Uuid"].as_item().unwrap());
+    /// ```
+    pub fn resolve<'a>(&'a self, components: &'a Components) -> Result<&'a T, ResolveError> {
+        resolve_item(components, self)
+    }
+}
+
+impl ReferenceOr<Box<Schema>> {
+    /// Like [ReferenceOr::resolve], for boxed schemas such as the properties
+    /// of an object.
+    pub fn resolve<'a>(&'a self, components: &'a Components) -> Result<&'a Schema, ResolveError> {
+        match self {
+            ReferenceOr::Item(item) => Ok(item),
+            ReferenceOr::Reference { reference } => resolve_reference(components, reference),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let components: Components = serde_json::from_str(
+            r##"{
+                "schemas": {
+                    "Pet": { "type": "object", "properties": { "id": { "$ref": "#/components/schemas/Id" } } },
+                    "Id": { "$ref": "#/components/schemas/Uuid" },
+                    "Uuid": { "type": "string" },
+                    "A": { "$ref": "#/components/schemas/B" },
+                    "B": { "$ref": "#/components/schemas/A" }
+                },
+                "parameters": {
+                    "Limit": { "name": "limit", "in": "query", "schema": { "type": "integer" } }
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let pet = components.schemas["Pet"].resolve(&components).unwrap();
+        let id = match &pet.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => &object.properties["id"],
+            _ => panic!("not an object"),
+        };
+        assert_eq!(
+            id.resolve(&components).unwrap(),
+            components.schemas["Uuid"].as_item().unwrap()
+        );
+
+        let limit = ReferenceOr::<Parameter>::ref_("#/components/parameters/Limit");
+        assert_eq!(
+            limit
+                .resolve(&components)
+                .unwrap()
+                .parameter_data_ref()
+                .name,
+            "limit"
+        );
+
+        let cases = [
+            (
+                "#/components/schemas/Missing",
+                ResolveError::Missing {
+                    reference: "#/components/schemas/Missing".to_string(),
+                },
+            ),
+            (
+                "#/components/schemas/A",
+                ResolveError::Cycle {
+                    reference: "#/components/schemas/A".to_string(),
+                },
+            ),
+            (
+                "#/components/parameters/Limit",
+                ResolveError::Invalid {
+                    reference: "#/components/parameters/Limit".to_string(),
+                    expected: ComponentKind::Schemas,
+                },
+            ),
+            (
+                "other.json#/components/schemas/Pet",
+                ResolveError::External {
+                    reference: "other.json#/components/schemas/Pet".to_string(),
+                },
+            ),
+        ];
+        for (reference, error) in cases {
+            assert_eq!(
+                ReferenceOr::<Schema>::ref_(reference).resolve(&components),
+                Err(error)
+            );
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use crate::request_validation::{check_body, header_values};
+use crate::util::escape_pointer_token;
+use crate::value_validation::{validate_value_at, Direction};
+use crate::*;
+
+impl Operation {
+    /// Checks a response of this operation: the [Response] is picked with
+    /// [Responses::for_status], its declared headers are parsed and checked
+    /// against their schemas, and a JSON body against the schema of its media
+    /// type. Bodies of other media types are only checked for being declared.
+    ///
+    /// References are followed through `components`. Violations point into
+    /// the response: `/status`, `/header/{name}` and `/body`. Required
+    /// `writeOnly` properties may be left out of the body. An empty body is
+    /// not checked, since a response does not say whether it is required.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let operation: Operation = serde_json::from_str(r#"{ "responses": {
+    ///     "2XX": {
+    ///         "description": "",
+    ///         "headers": { "X-Rate-Limit": { "required": true, "schema": { "type": "integer" } } },
+    ///         "content": { "application/json": { "schema": { "type": "array" } } }
+    ///     },
+    ///     "default": { "description": "" }
+    /// } }"#).unwrap();
+    /// let headers = [("x-rate-limit", "10")];
+    ///
+    /// assert!(operation
+    ///     .validate_response(201, Some("application/json"), &headers, Some(b"[]"), None)
+    ///     .is_ok());
+    /// assert!(operation.validate_response(500, None, &[], None, None).is_ok());
+    ///
+    /// let violations = operation
+    ///     .validate_response(200, Some("application/json"), &[], Some(b"{}"), None)
+    ///     .unwrap_err();
+    /// let pointers: Vec<_> = violations.iter().map(|v| v.pointer.as_str()).collect();
+    /// assert_eq!(pointers, ["/header/X-Rate-Limit", "/body"]);
+    /// ```
+    pub fn validate_response(
+        &self,
+        status: u16,
+        content_type: Option<&str>,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+        components: Option<&Components>,
+    ) -> Result<(), Vec<Violation>> {
+        let violation = |kind, pointer: &str, message: String| Violation {
+            kind,
+            pointer: pointer.to_owned(),
+            message,
+        };
+        let response = match self.responses.for_status(status) {
+            Some(response) => response,
+            None => {
+                return Err(vec![violation(
+                    ViolationKind::UndeclaredStatus,
+                    "/status",
+                    format!("status {} is not declared", status),
+                )])
+            }
+        };
+        let response = match (response, components) {
+            (ReferenceOr::Item(response), _) => response,
+            (reference, Some(components)) => reference.resolve(components).map_err(|err| {
+                vec![violation(
+                    ViolationKind::UnresolvedReference,
+                    "/status",
+                    err.to_string(),
+                )]
+            })?,
+            (ReferenceOr::Reference { reference }, None) => {
+                return Err(vec![violation(
+                    ViolationKind::UnresolvedReference,
+                    "/status",
+                    format!("cannot resolve `{}` without components", reference),
+                )])
+            }
+        };
+
+        let mut violations = Vec::new();
+        let raw = RawParameters {
+            headers: headers.to_vec(),
+            ..Default::default()
+        };
+        let mut seen = Vec::new();
+        for (name, header) in &response.headers {
+            let key = name.to_ascii_lowercase();
+            if key == "content-type" || seen.contains(&key) {
+                continue;
+            }
+            seen.push(key);
+            let pointer = format!("/header/{}", escape_pointer_token(name));
+            let header = match (header, components) {
+                (ReferenceOr::Item(header), _) => header,
+                (reference, Some(components)) => match reference.resolve(components) {
+                    Ok(header) => header,
+                    Err(err) => {
+                        violations.push(violation(
+                            ViolationKind::UnresolvedReference,
+                            &pointer,
+                            err.to_string(),
+                        ));
+                        continue;
+                    }
+                },
+                (_, None) => continue,
+            };
+            let parameter = header_parameter(name, header);
+            match parameter.extract(&raw, components) {
+                Ok(Some(value)) => {
+                    let schema = match &header.format {
+                        ParameterSchemaOrContent::Schema(schema) => Some(&**schema),
+                        ParameterSchemaOrContent::Content(content) => content
+                            .values()
+                            .next()
+                            .and_then(|media| media.schema.as_ref()),
+                    };
+                    if let Some(schema) = schema {
+                        violations.extend(validate_value_at(
+                            schema,
+                            &value,
+                            components,
+                            Direction::Response,
+                            &pointer,
+                        ));
+                    }
+                }
+                Ok(None) if header.required => violations.push(violation(
+                    ViolationKind::MissingValue,
+                    &pointer,
+                    format!("missing required header `{}`", name),
+                )),
+                Ok(None) => {}
+                Err(err) => violations.push(violation(
+                    ViolationKind::UnparsableValue,
+                    &pointer,
+                    err.to_string(),
+                )),
+            }
+        }
+
+        if let Some(body) = body.filter(|body| !body.is_empty()) {
+            let content_type = content_type
+                .or_else(|| header_values(headers, "content-type").next())
+                .unwrap_or_default();
+            check_body(
+                &response.content,
+                content_type,
+                body,
+                components,
+                Direction::Response,
+                &mut violations,
+            );
+        }
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
+}
+
+/// The [Parameter] a response header is parsed as.
+fn header_parameter(name: &str, header: &Header) -> Parameter {
+    Parameter::Header {
+        parameter_data: ParameterData {
+            name: name.to_owned(),
+            description: None,
+            required: header.required,
+            deprecated: None,
+            format: header.format.clone(),
+            example: None,
+            examples: Default::default(),
+            explode: None,
+            extensions: Default::default(),
+        },
+        style: HeaderStyle::Simple,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_response() {
+        let components: Components = serde_json::from_value(json!({
+            "responses": {
+                "NotFound": {
+                    "description": "",
+                    "content": { "application/problem+json": { "schema": {
+                        "type": "object", "required": ["title"], "properties": { "title": { "type": "string" } }
+                    } } }
+                }
+            },
+            "headers": {
+                "Trace": { "schema": { "type": "string", "maxLength": 4 } }
+            },
+            "schemas": {
+                "User": {
+                    "type": "object",
+                    "required": ["name", "password"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "password": { "type": "string", "writeOnly": true }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let operation: Operation = serde_json::from_value(json!({ "responses": {
+            "200": {
+                "description": "",
+                "headers": {
+                    "X-Trace": { "$ref": "#/components/headers/Trace" },
+                    "X-Ids": { "schema": { "type": "array", "items": { "type": "integer" } } },
+                    "Content-Type": { "required": true, "schema": { "type": "string" } }
+                },
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/User" } },
+                    "text/*": {}
+                }
+            },
+            "404": { "$ref": "#/components/responses/NotFound" }
+        } }))
+        .unwrap();
+        let check = |status, content_type, headers: &[(&str, &str)], body: &[u8]| {
+            operation
+                .validate_response(status, content_type, headers, Some(body), Some(&components))
+                .map_err(|violations| {
+                    violations
+                        .into_iter()
+                        .map(|violation| (violation.kind, violation.pointer))
+                        .collect::<Vec<_>>()
+                })
+        };
+
+        let headers = [("x-trace", "abc"), ("X-IDS", "1,2")];
+        assert_eq!(
+            check(
+                200,
+                Some("application/json"),
+                &headers,
+                br#"{ "name": "a" }"#
+            ),
+            Ok(())
+        );
+        assert_eq!(check(200, Some("text/plain"), &[], b"hello"), Ok(()));
+        assert_eq!(
+            check(200, None, &[("Content-Type", "text/html")], b"<p>"),
+            Ok(())
+        );
+        assert_eq!(
+            check(404, Some("application/problem+json"), &[], b"{}"),
+            Err(vec![(ViolationKind::SchemaMismatch, "/body".to_owned())])
+        );
+        assert_eq!(
+            check(
+                200,
+                Some("application/json"),
+                &[("x-trace", "abcdef"), ("x-ids", "1,x")],
+                br#"{ "name": 1 }"#
+            ),
+            Err(vec![
+                (ViolationKind::UnparsableValue, "/header/X-Ids".to_owned()),
+                (ViolationKind::SchemaMismatch, "/header/X-Trace".to_owned()),
+                (ViolationKind::SchemaMismatch, "/body/name".to_owned()),
+            ])
+        );
+        assert_eq!(
+            check(200, Some("image/png"), &[], b"png"),
+            Err(vec![(
+                ViolationKind::UnsupportedMediaType,
+                "/body".to_owned()
+            )])
+        );
+        assert_eq!(
+            check(500, None, &[], b""),
+            Err(vec![(
+                ViolationKind::UndeclaredStatus,
+                "/status".to_owned()
+            )])
+        );
+    }
+}
@@ -30,8 +30,41 @@ pub struct Responses {
     #[serde(flatten, deserialize_with = "deserialize_responses")]
     pub responses: IndexMap<StatusCode, ReferenceOr<Response>>,
     /// Inline extensions to this object.
-    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    #[serde(flatten, deserialize_with = "deserialize_responses_extensions")]
+    pub extensions: Extensions,
+}
+
+impl Responses {
+    /// Returns the response documented for `status`: the response for that
+    /// exact code if there is one, otherwise the response for its range,
+    /// otherwise the default response.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let responses: Responses = serde_json::from_str(r#"{
+    ///     "200": { "description": "OK" },
+    ///     "2XX": { "description": "Success" },
+    ///     "default": { "description": "Error" }
+    /// }"#).unwrap();
+    /// let description = |status| match responses.for_status(status) {
+    ///     Some(ReferenceOr::Item(response)) => response.description.as_str(),
+    ///     _ => "",
+    /// };
+    /// assert_eq!(description(200), "OK");
+    /// assert_eq!(description(201), "Success");
+    /// assert_eq!(description(500), "Error");
+    /// ```
+    pub fn for_status(&self, status: u16) -> Option<&ReferenceOr<Response>> {
+        self.responses
+            .get(&StatusCode::Code(status))
+            .or_else(|| {
+                self.responses
+                    .iter()
+                    .find(|(code, _)| matches!(code, StatusCode::Range(_)) && code.matches(status))
+                    .map(|(_, response)| response)
+            })
+            .or(self.default.as_ref())
+    }
 }
 
 /// Describes a single response from an API Operation, including design-time,
@@ -65,7 +98,83 @@ pub struct Response {
 
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
+}
+
+impl Response {
+    /// Returns the content entry that applies to `media_type`, see
+    /// [content_for].
+    pub fn content_for(&self, media_type: &str) -> Option<&MediaType> {
+        content_for(&self.content, media_type).map(|(_, content)| content)
+    }
+
+    /// Returns the headers of this response with any `$ref`s followed through
+    /// `components`.
+    ///
+    /// Header names are matched case-insensitively as required by RFC7230. If
+    /// the same name is declared more than once, differing only in case, the
+    /// first declaration wins. A `Content-Type` header is left out, since the
+    /// specification says that it SHALL be ignored.
+    pub fn resolved_headers<'a>(
+        &'a self,
+        components: &'a Components,
+    ) -> Result<ResolvedHeaders<'a>, ResolveError> {
+        let mut headers = IndexMap::new();
+        for (name, header) in &self.headers {
+            let key = name.to_ascii_lowercase();
+            if key == "content-type" || headers.contains_key(&key) {
+                continue;
+            }
+            let header = crate::resolve::resolve_item(components, header)?;
+            headers.insert(key, (name.as_str(), header));
+        }
+        Ok(ResolvedHeaders { headers })
+    }
+}
+
+/// The headers of a [Response] with references resolved, as returned by
+/// [Response::resolved_headers]. Lookups are case-insensitive; iteration
+/// follows declaration order and yields names as declared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedHeaders<'a> {
+    headers: IndexMap<String, (&'a str, &'a Header)>,
+}
+
+impl<'a> ResolvedHeaders<'a> {
+    /// Looks up a header by name, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&'a Header> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(|(_, header)| *header)
+    }
+
+    /// Returns `true` if a header with this name is declared, ignoring case.
+    pub fn contains(&self, name: &str) -> bool {
+        self.headers.contains_key(&name.to_ascii_lowercase())
+    }
+
+    /// Iterates over the headers as `(declared name, header)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a Header)> + '_ {
+        self.headers.values().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.headers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+}
+
+fn deserialize_responses_extensions<'de, D>(deserializer: D) -> Result<Extensions, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    crate::extensions::deserialize_extensions_except(deserializer, |key| {
+        let key = serde::de::value::StrDeserializer::<serde::de::value::Error>::new(key);
+        StatusCode::deserialize(key).is_ok()
+    })
 }
 
 fn deserialize_responses<'de, D>(
@@ -83,7 +192,7 @@ where
 mod tests {
     use serde_json::json;
 
-    use crate::{ReferenceOr, Response, Responses, StatusCode};
+    use crate::{Components, ReferenceOr, ResolveError, Response, Responses, StatusCode};
 
     #[test]
     fn test_responses() {
@@ -107,4 +216,50 @@ mod tests {
         );
         assert_eq!(responses.extensions.get("x-foo"), Some(&json!("bar")));
     }
+
+    #[test]
+    fn test_resolved_headers() {
+        let components: Components = serde_json::from_value(json!({
+            "headers": {
+                "RateLimit": { "$ref": "#/components/headers/Limit" },
+                "Limit": { "description": "limit", "schema": { "type": "integer" } }
+            }
+        }))
+        .unwrap();
+        let response: Response = serde_json::from_str(
+            r##"{
+            "description": "ok",
+            "headers": {
+                "X-Rate-Limit": { "$ref": "#/components/headers/RateLimit" },
+                "x-rate-limit": { "description": "shadowed", "schema": {} },
+                "Content-Type": { "schema": {} },
+                "ETag": { "schema": { "type": "string" } }
+            }
+        }"##,
+        )
+        .unwrap();
+
+        let headers = response.resolved_headers(&components).unwrap();
+        assert_eq!(
+            headers.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!["X-Rate-Limit", "ETag"]
+        );
+        assert_eq!(
+            headers.get("x-RATE-limit").unwrap().description.as_deref(),
+            Some("limit")
+        );
+        assert!(!headers.contains("content-type"));
+
+        let broken: Response = serde_json::from_value(json!({
+            "description": "ok",
+            "headers": { "X": { "$ref": "#/components/headers/Nope" } }
+        }))
+        .unwrap();
+        assert_eq!(
+            broken.resolved_headers(&components),
+            Err(ResolveError::Missing {
+                reference: "#/components/headers/Nope".to_string()
+            })
+        );
+    }
 }
@@ -0,0 +1,152 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A runtime expression, such as `$request.body#/callbackUrl`, as used in
+/// the keys of a [Callback](crate::Callback) and the parameters of a
+/// [Link](crate::Link).
+///
+/// ```
+/// # use openapiv3::{ExpressionSource, RuntimeExpression};
+/// let expression: RuntimeExpression = "$request.body#/callbackUrl".parse().unwrap();
+/// assert_eq!(
+///     expression,
+///     RuntimeExpression::Request(ExpressionSource::Body(Some("/callbackUrl".to_owned())))
+/// );
+/// assert_eq!(expression.to_string(), "$request.body#/callbackUrl");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RuntimeExpression {
+    /// `$url`, the URL of the request.
+    Url,
+    /// `$method`, the HTTP method of the request.
+    Method,
+    /// `$statusCode`, the status code of the response.
+    StatusCode,
+    /// `$request.` followed by a part of the request.
+    Request(ExpressionSource),
+    /// `$response.` followed by a part of the response.
+    Response(ExpressionSource),
+}
+
+/// The part of a request or response a [RuntimeExpression] refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExpressionSource {
+    /// `header.{name}`
+    Header(String),
+    /// `query.{name}`
+    Query(String),
+    /// `path.{name}`
+    Path(String),
+    /// `body`, optionally followed by `#` and a JSON Pointer into it.
+    Body(Option<String>),
+}
+
+/// The error returned when a string is not a valid [RuntimeExpression].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeExpressionError {
+    input: String,
+    reason: &'static str,
+}
+
+impl fmt::Display for RuntimeExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid runtime expression `{}`: {}",
+            self.input, self.reason
+        )
+    }
+}
+
+impl std::error::Error for RuntimeExpressionError {}
+
+impl FromStr for RuntimeExpression {
+    type Err = RuntimeExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |reason| RuntimeExpressionError {
+            input: s.to_owned(),
+            reason,
+        };
+        let source = |source: &str| {
+            let named = |name: &str, reason| match name.is_empty() {
+                true => Err(error(reason)),
+                false => Ok(name.to_owned()),
+            };
+            if let Some(name) = source.strip_prefix("header.") {
+                if !name.chars().all(is_token_char) {
+                    return Err(error("header names may only contain token characters"));
+                }
+                Ok(ExpressionSource::Header(named(
+                    name,
+                    "missing header name",
+                )?))
+            } else if let Some(name) = source.strip_prefix("query.") {
+                Ok(ExpressionSource::Query(named(
+                    name,
+                    "missing query parameter name",
+                )?))
+            } else if let Some(name) = source.strip_prefix("path.") {
+                Ok(ExpressionSource::Path(named(
+                    name,
+                    "missing path parameter name",
+                )?))
+            } else if source == "body" {
+                Ok(ExpressionSource::Body(None))
+            } else if let Some(pointer) = source.strip_prefix("body#") {
+                if !pointer.is_empty() && !pointer.starts_with('/') {
+                    return Err(error("the body pointer must be empty or start with `/`"));
+                }
+                Ok(ExpressionSource::Body(Some(pointer.to_owned())))
+            } else {
+                Err(error("expected `header.`, `query.`, `path.` or `body`"))
+            }
+        };
+
+        match s {
+            "$url" => Ok(RuntimeExpression::Url),
+            "$method" => Ok(RuntimeExpression::Method),
+            "$statusCode" => Ok(RuntimeExpression::StatusCode),
+            _ => {
+                if let Some(rest) = s.strip_prefix("$request.") {
+                    Ok(RuntimeExpression::Request(source(rest)?))
+                } else if let Some(rest) = s.strip_prefix("$response.") {
+                    Ok(RuntimeExpression::Response(source(rest)?))
+                } else {
+                    Err(error(
+                        "expected `$url`, `$method`, `$statusCode`, `$request.` or `$response.`",
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for RuntimeExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeExpression::Url => f.write_str("$url"),
+            RuntimeExpression::Method => f.write_str("$method"),
+            RuntimeExpression::StatusCode => f.write_str("$statusCode"),
+            RuntimeExpression::Request(source) => write!(f, "$request.{}", source),
+            RuntimeExpression::Response(source) => write!(f, "$response.{}", source),
+        }
+    }
+}
+
+impl fmt::Display for ExpressionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpressionSource::Header(name) => write!(f, "header.{}", name),
+            ExpressionSource::Query(name) => write!(f, "query.{}", name),
+            ExpressionSource::Path(name) => write!(f, "path.{}", name),
+            ExpressionSource::Body(None) => f.write_str("body"),
+            ExpressionSource::Body(Some(pointer)) => write!(f, "body#{}", pointer),
+        }
+    }
+}
+
+/// Whether `c` may appear in an HTTP token, as defined by RFC 7230.
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
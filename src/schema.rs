@@ -28,8 +28,48 @@ pub struct SchemaData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<serde_json::Value>,
     /// Inline extensions to this object.
-    #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    #[serde(flatten, deserialize_with = "deserialize_schema_extensions")]
+    pub extensions: Extensions,
+}
+
+/// Skips the fields of [SchemaKind], which the extensions of [SchemaData]
+/// see as they are flattened side by side.
+fn deserialize_schema_extensions<'de, D>(deserializer: D) -> Result<Extensions, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    const SCHEMA_KIND_FIELDS: &[&str] = &[
+        "type",
+        "pattern",
+        "multipleOf",
+        "exclusiveMinimum",
+        "exclusiveMaximum",
+        "minimum",
+        "maximum",
+        "properties",
+        "required",
+        "additionalProperties",
+        "minProperties",
+        "maxProperties",
+        "items",
+        "minItems",
+        "maxItems",
+        "uniqueItems",
+        "contains",
+        "minContains",
+        "maxContains",
+        "enum",
+        "format",
+        "minLength",
+        "maxLength",
+        "oneOf",
+        "allOf",
+        "anyOf",
+        "not",
+    ];
+    crate::extensions::deserialize_extensions_except(deserializer, |key| {
+        SCHEMA_KIND_FIELDS.contains(&key)
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -40,6 +80,34 @@ pub struct Schema {
     pub schema_kind: SchemaKind,
 }
 
+impl Schema {
+    /// Returns the values of the `enum` keyword as JSON values, or `None` if
+    /// the schema does not have one. `null` entries of typed enumerations are
+    /// returned as [serde_json::Value::Null].
+    pub fn enum_values(&self) -> Option<Vec<serde_json::Value>> {
+        fn to_values<T: Serialize>(values: &[Option<T>]) -> Vec<serde_json::Value> {
+            values
+                .iter()
+                .map(|value| serde_json::to_value(value).unwrap_or(serde_json::Value::Null))
+                .collect()
+        }
+
+        let values = match &self.schema_kind {
+            SchemaKind::Type(Type::String(t)) => to_values(&t.enumeration),
+            SchemaKind::Type(Type::Number(t)) => to_values(&t.enumeration),
+            SchemaKind::Type(Type::Integer(t)) => to_values(&t.enumeration),
+            SchemaKind::Type(Type::Boolean(t)) => to_values(&t.enumeration),
+            SchemaKind::Any(any) => any.enumeration.clone(),
+            _ => return None,
+        };
+        if values.is_empty() {
+            None
+        } else {
+            Some(values)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum SchemaKind {
@@ -59,7 +127,7 @@ pub enum SchemaKind {
     Not {
         not: Box<ReferenceOr<Schema>>,
     },
-    Any(AnySchema),
+    Any(Box<AnySchema>),
 }
 
 // Custom Deserialize implementation that is similar to the logic for an
@@ -108,6 +176,12 @@ impl<'de> Deserialize<'de> for SchemaKind {
             max_items: Option<usize>,
             #[serde(default)]
             unique_items: Option<bool>,
+            #[serde(default)]
+            contains: Option<ReferenceOr<Box<Schema>>>,
+            #[serde(default)]
+            min_contains: Option<usize>,
+            #[serde(default)]
+            max_contains: Option<usize>,
             #[serde(rename = "enum", default)]
             enumeration: Option<Vec<serde_json::Value>>,
             #[serde(default)]
@@ -146,6 +220,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration,
                 format,
                 min_length,
@@ -186,6 +263,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration,
                 format,
                 min_length: None,
@@ -199,11 +279,11 @@ impl<'de> Deserialize<'de> for SchemaKind {
             {
                 Ok(Self::Type(Type::Number(NumberType {
                     format: format.into(),
-                    multiple_of: multiple_of.map(|v| v.as_f64().unwrap()),
+                    multiple_of: multiple_of.and_then(|v| v.as_f64()),
                     exclusive_minimum: exclusive_minimum.unwrap_or_default(),
                     exclusive_maximum: exclusive_maximum.unwrap_or_default(),
-                    minimum: minimum.map(|v| v.as_f64().unwrap()),
-                    maximum: maximum.map(|v| v.as_f64().unwrap()),
+                    minimum: minimum.and_then(|v| v.as_f64()),
+                    maximum: maximum.and_then(|v| v.as_f64()),
                     enumeration: enumerated_values_transform(
                         enumeration,
                         serde_json::Value::as_f64,
@@ -229,6 +309,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration,
                 format,
                 min_length: None,
@@ -238,22 +321,19 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 any_of: None,
                 not: None,
             } if typ == "integer"
-                && enumerated_values_valid(&enumeration, serde_json::Value::is_i64)
+                && enumerated_values_valid(&enumeration, |v| value_as_int(v).is_some())
                 && none_or_int(&multiple_of)
                 && none_or_int(&minimum)
                 && none_or_int(&maximum) =>
             {
                 Ok(Self::Type(Type::Integer(IntegerType {
                     format: format.into(),
-                    multiple_of: multiple_of.map(|v| v.as_i64().unwrap()),
+                    multiple_of: multiple_of.as_ref().and_then(as_int),
                     exclusive_minimum: exclusive_minimum.unwrap_or_default(),
                     exclusive_maximum: exclusive_maximum.unwrap_or_default(),
-                    minimum: minimum.map(|v| v.as_i64().unwrap()),
-                    maximum: maximum.map(|v| v.as_i64().unwrap()),
-                    enumeration: enumerated_values_transform(
-                        enumeration,
-                        serde_json::Value::as_i64,
-                    ),
+                    minimum: minimum.as_ref().and_then(as_int),
+                    maximum: maximum.as_ref().and_then(as_int),
+                    enumeration: enumerated_values_transform(enumeration, value_as_int),
                 })))
             }
 
@@ -275,6 +355,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration,
                 format: None,
                 min_length: None,
@@ -312,6 +395,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration: None,
                 format: None,
                 min_length: None,
@@ -346,6 +432,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items,
                 max_items,
                 unique_items,
+                contains,
+                min_contains,
+                max_contains,
                 enumeration: None,
                 format: None,
                 min_length: None,
@@ -359,6 +448,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items,
                 max_items,
                 unique_items: unique_items.unwrap_or_default(),
+                contains,
+                min_contains,
+                max_contains,
             }))),
 
             // OneOf
@@ -379,6 +471,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration: None,
                 format: None,
                 min_length: None,
@@ -407,6 +502,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration: None,
                 format: None,
                 min_length: None,
@@ -435,6 +533,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration: None,
                 format: None,
                 min_length: None,
@@ -463,6 +564,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items: None,
                 max_items: None,
                 unique_items: None,
+                contains: None,
+                min_contains: None,
+                max_contains: None,
                 enumeration: None,
                 format: None,
                 min_length: None,
@@ -491,6 +595,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items,
                 max_items,
                 unique_items,
+                contains,
+                min_contains,
+                max_contains,
                 enumeration,
                 format,
                 min_length,
@@ -499,14 +606,14 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 all_of,
                 any_of,
                 not,
-            } => Ok(Self::Any(AnySchema {
+            } => Ok(Self::Any(Box::new(AnySchema {
                 typ,
                 pattern,
-                multiple_of: multiple_of.map(|n| n.as_f64().unwrap()),
+                multiple_of: multiple_of.and_then(|n| n.as_f64()),
                 exclusive_minimum,
                 exclusive_maximum,
-                minimum: minimum.map(|n| n.as_f64().unwrap()),
-                maximum: maximum.map(|n| n.as_f64().unwrap()),
+                minimum: minimum.and_then(|n| n.as_f64()),
+                maximum: maximum.and_then(|n| n.as_f64()),
                 properties: properties.unwrap_or_default(),
                 required: required.unwrap_or_default(),
                 additional_properties,
@@ -516,6 +623,9 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 min_items,
                 max_items,
                 unique_items,
+                contains,
+                min_contains,
+                max_contains,
                 enumeration: enumeration.unwrap_or_default(),
                 format,
                 min_length,
@@ -524,7 +634,7 @@ impl<'de> Deserialize<'de> for SchemaKind {
                 all_of: all_of.unwrap_or_default(),
                 any_of: any_of.unwrap_or_default(),
                 not,
-            })),
+            }))),
         }
     }
 }
@@ -532,7 +642,26 @@ impl<'de> Deserialize<'de> for SchemaKind {
 fn none_or_int(value: &Option<serde_json::Number>) -> bool {
     match value {
         None => true,
-        Some(x) => x.is_i64(),
+        Some(x) => as_int(x).is_some(),
+    }
+}
+
+/// Converts `number` to an integer, accepting whole numbers written as
+/// floats such as `0.0` or `1e3`, which some generators emit for integer
+/// bounds.
+fn as_int(number: &serde_json::Number) -> Option<i64> {
+    number.as_i64().or_else(|| {
+        let float = number.as_f64()?;
+        // `i64::MAX as f64` rounds up to 2^63, which does not fit.
+        let in_range = float >= i64::MIN as f64 && float < i64::MAX as f64;
+        (float.fract() == 0.0 && in_range).then_some(float as i64)
+    })
+}
+
+fn value_as_int(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(number) => as_int(number),
+        _ => None,
     }
 }
 
@@ -546,13 +675,10 @@ where
     match enumeration {
         Some(values) => values
             .iter()
-            .map(|v| {
-                if v.is_null() {
-                    None
-                } else {
-                    Some(transform(v).unwrap())
-                }
-            })
+            // The values were checked by `enumerated_values_valid`; anything
+            // that still fails to convert is treated like `null` rather than
+            // panicking.
+            .map(|v| if v.is_null() { None } else { transform(v) })
             .collect::<Vec<_>>(),
         None => Default::default(),
     }
@@ -623,6 +749,12 @@ pub struct AnySchema {
     pub max_items: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub unique_items: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<ReferenceOr<Box<Schema>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_contains: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_contains: Option<usize>,
     #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
     pub enumeration: Vec<serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -709,6 +841,61 @@ pub struct ObjectType {
     pub max_properties: Option<usize>,
 }
 
+impl ObjectType {
+    /// Adds a property, or replaces the schema of an existing one, and marks
+    /// it as required or not. Returns the schema it replaced, if any.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut object = ObjectType::default();
+    /// object.add_property("id", ReferenceOr::ref_("#/components/schemas/Id"), true);
+    /// object.add_property("note", ReferenceOr::ref_("#/components/schemas/Note"), false);
+    /// assert_eq!(object.required, ["id"]);
+    ///
+    /// object.remove_property("id");
+    /// assert!(object.required.is_empty());
+    /// assert!(object.property("note").is_some());
+    /// ```
+    pub fn add_property(
+        &mut self,
+        name: impl Into<String>,
+        schema: ReferenceOr<Box<Schema>>,
+        required: bool,
+    ) -> Option<ReferenceOr<Box<Schema>>> {
+        let name = name.into();
+        match required {
+            true => self.require(name.clone()),
+            false => self.required.retain(|n| *n != name),
+        }
+        self.properties.insert(name, schema)
+    }
+
+    /// Removes a property, along with its name from `required`, keeping the
+    /// order of the remaining ones. Returns its schema, if it existed.
+    pub fn remove_property(&mut self, name: &str) -> Option<ReferenceOr<Box<Schema>>> {
+        self.required.retain(|n| n != name);
+        self.properties.shift_remove(name)
+    }
+
+    /// Marks a property as required, if it is not already.
+    pub fn require(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        if !self.required.contains(&name) {
+            self.required.push(name);
+        }
+    }
+
+    /// Returns the schema of a property.
+    pub fn property(&self, name: &str) -> Option<&ReferenceOr<Box<Schema>>> {
+        self.properties.get(name)
+    }
+
+    /// Returns `true` if a property is listed in `required`.
+    pub fn is_required(&self, name: &str) -> bool {
+        self.required.iter().any(|n| n == name)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ArrayType {
@@ -720,6 +907,17 @@ pub struct ArrayType {
     pub max_items: Option<usize>,
     #[serde(default, skip_serializing_if = "is_false")]
     pub unique_items: bool,
+    /// At least one item must match this schema. JSON Schema's `contains`
+    /// keyword, which OpenAPI 3.0 does not define but which specs authored
+    /// from JSON Schema commonly use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<ReferenceOr<Box<Schema>>>,
+    /// The minimum number of items matching `contains`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_contains: Option<usize>,
+    /// The maximum number of items matching `contains`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_contains: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -792,6 +990,263 @@ impl FromStr for StringFormat {
     }
 }
 
+/// Identifies a subschema relative to the schema containing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SubschemaKey<'a> {
+    Property(&'a str),
+    AdditionalProperties,
+    Items,
+    Contains,
+    OneOf(usize),
+    AllOf(usize),
+    AnyOf(usize),
+    Not,
+}
+
+impl SubschemaKey<'_> {
+    /// The JSON Pointer suffix leading from the parent schema to the
+    /// subschema, e.g. `/properties/name`.
+    pub(crate) fn pointer(&self) -> String {
+        match self {
+            SubschemaKey::Property(name) => {
+                format!("/properties/{}", crate::util::escape_pointer_token(name))
+            }
+            SubschemaKey::AdditionalProperties => "/additionalProperties".to_owned(),
+            SubschemaKey::Items => "/items".to_owned(),
+            SubschemaKey::Contains => "/contains".to_owned(),
+            SubschemaKey::OneOf(i) => format!("/oneOf/{}", i),
+            SubschemaKey::AllOf(i) => format!("/allOf/{}", i),
+            SubschemaKey::AnyOf(i) => format!("/anyOf/{}", i),
+            SubschemaKey::Not => "/not".to_owned(),
+        }
+    }
+}
+
+/// A mutable handle to a subschema, which may or may not be boxed.
+pub(crate) enum SchemaSlot<'a> {
+    Boxed(&'a mut ReferenceOr<Box<Schema>>),
+    Plain(&'a mut ReferenceOr<Schema>),
+}
+
+impl SchemaSlot<'_> {
+    pub(crate) fn as_item_mut(&mut self) -> Option<&mut Schema> {
+        match self {
+            SchemaSlot::Boxed(ReferenceOr::Item(schema)) => Some(schema),
+            SchemaSlot::Plain(ReferenceOr::Item(schema)) => Some(schema),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn reference(&self) -> Option<&str> {
+        match self {
+            SchemaSlot::Boxed(ReferenceOr::Reference { reference }) => Some(reference),
+            SchemaSlot::Plain(ReferenceOr::Reference { reference }) => Some(reference),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn replace_with_item(&mut self, schema: Schema) {
+        match self {
+            SchemaSlot::Boxed(slot) => **slot = ReferenceOr::boxed_item(schema),
+            SchemaSlot::Plain(slot) => **slot = ReferenceOr::Item(schema),
+        }
+    }
+
+    /// Replaces the slot with a reference, returning the inline schema it
+    /// held, if any.
+    pub(crate) fn replace_with_reference(&mut self, reference: String) -> Option<Schema> {
+        match self {
+            SchemaSlot::Boxed(slot) => {
+                match std::mem::replace(*slot, ReferenceOr::Reference { reference }) {
+                    ReferenceOr::Item(schema) => Some(*schema),
+                    ReferenceOr::Reference { .. } => None,
+                }
+            }
+            SchemaSlot::Plain(slot) => {
+                match std::mem::replace(*slot, ReferenceOr::Reference { reference }) {
+                    ReferenceOr::Item(schema) => Some(schema),
+                    ReferenceOr::Reference { .. } => None,
+                }
+            }
+        }
+    }
+}
+
+impl Schema {
+    /// Returns the immediate subschemas of this schema in document order.
+    pub(crate) fn subschemas(&self) -> Vec<(SubschemaKey<'_>, ReferenceOr<&Schema>)> {
+        fn plain(schema: &ReferenceOr<Schema>) -> ReferenceOr<&Schema> {
+            match schema {
+                ReferenceOr::Item(schema) => ReferenceOr::Item(schema),
+                ReferenceOr::Reference { reference } => ReferenceOr::Reference {
+                    reference: reference.clone(),
+                },
+            }
+        }
+        fn boxed(schema: &ReferenceOr<Box<Schema>>) -> ReferenceOr<&Schema> {
+            match schema {
+                ReferenceOr::Item(schema) => ReferenceOr::Item(schema),
+                ReferenceOr::Reference { reference } => ReferenceOr::Reference {
+                    reference: reference.clone(),
+                },
+            }
+        }
+
+        let (properties, additional_properties, items, contains) = match &self.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => (
+                Some(&object.properties),
+                object.additional_properties.as_ref(),
+                None,
+                None,
+            ),
+            SchemaKind::Type(Type::Array(array)) => {
+                (None, None, array.items.as_ref(), array.contains.as_ref())
+            }
+            SchemaKind::Any(any) => (
+                Some(&any.properties),
+                any.additional_properties.as_ref(),
+                any.items.as_ref(),
+                any.contains.as_ref(),
+            ),
+            _ => (None, None, None, None),
+        };
+        let (one_of, all_of, any_of, not) = match &self.schema_kind {
+            SchemaKind::OneOf { one_of } => (Some(one_of), None, None, None),
+            SchemaKind::AllOf { all_of } => (None, Some(all_of), None, None),
+            SchemaKind::AnyOf { any_of } => (None, None, Some(any_of), None),
+            SchemaKind::Not { not } => (None, None, None, Some(not)),
+            SchemaKind::Any(any) => (
+                Some(&any.one_of),
+                Some(&any.all_of),
+                Some(&any.any_of),
+                any.not.as_ref(),
+            ),
+            SchemaKind::Type(_) => (None, None, None, None),
+        };
+
+        let mut subschemas = Vec::new();
+        for (name, schema) in properties.into_iter().flatten() {
+            subschemas.push((SubschemaKey::Property(name), boxed(schema)));
+        }
+        if let Some(AdditionalProperties::Schema(schema)) = additional_properties {
+            subschemas.push((SubschemaKey::AdditionalProperties, plain(schema)));
+        }
+        if let Some(schema) = items {
+            subschemas.push((SubschemaKey::Items, boxed(schema)));
+        }
+        if let Some(schema) = contains {
+            subschemas.push((SubschemaKey::Contains, boxed(schema)));
+        }
+        for (i, schema) in one_of.into_iter().flatten().enumerate() {
+            subschemas.push((SubschemaKey::OneOf(i), plain(schema)));
+        }
+        for (i, schema) in all_of.into_iter().flatten().enumerate() {
+            subschemas.push((SubschemaKey::AllOf(i), plain(schema)));
+        }
+        for (i, schema) in any_of.into_iter().flatten().enumerate() {
+            subschemas.push((SubschemaKey::AnyOf(i), plain(schema)));
+        }
+        if let Some(schema) = not {
+            subschemas.push((SubschemaKey::Not, plain(schema)));
+        }
+        subschemas
+    }
+
+    /// Returns the immediate subschemas of this schema in document order.
+    pub(crate) fn subschemas_mut(&mut self) -> Vec<(SubschemaKey<'_>, SchemaSlot<'_>)> {
+        #[derive(Default)]
+        struct Parts<'a> {
+            properties: Option<&'a mut IndexMap<String, ReferenceOr<Box<Schema>>>>,
+            additional_properties: Option<&'a mut AdditionalProperties>,
+            items: Option<&'a mut ReferenceOr<Box<Schema>>>,
+            contains: Option<&'a mut ReferenceOr<Box<Schema>>>,
+            one_of: Option<&'a mut Vec<ReferenceOr<Schema>>>,
+            all_of: Option<&'a mut Vec<ReferenceOr<Schema>>>,
+            any_of: Option<&'a mut Vec<ReferenceOr<Schema>>>,
+            not: Option<&'a mut Box<ReferenceOr<Schema>>>,
+        }
+
+        let mut subschemas = Vec::new();
+        let Parts {
+            properties,
+            additional_properties,
+            items,
+            contains,
+            one_of,
+            all_of,
+            any_of,
+            not,
+        } = match &mut self.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => Parts {
+                properties: Some(&mut object.properties),
+                additional_properties: object.additional_properties.as_mut(),
+                ..Parts::default()
+            },
+            SchemaKind::Type(Type::Array(array)) => Parts {
+                items: array.items.as_mut(),
+                contains: array.contains.as_mut(),
+                ..Parts::default()
+            },
+            SchemaKind::Type(_) => Parts::default(),
+            SchemaKind::OneOf { one_of } => Parts {
+                one_of: Some(one_of),
+                ..Parts::default()
+            },
+            SchemaKind::AllOf { all_of } => Parts {
+                all_of: Some(all_of),
+                ..Parts::default()
+            },
+            SchemaKind::AnyOf { any_of } => Parts {
+                any_of: Some(any_of),
+                ..Parts::default()
+            },
+            SchemaKind::Not { not } => Parts {
+                not: Some(not),
+                ..Parts::default()
+            },
+            SchemaKind::Any(any) => Parts {
+                properties: Some(&mut any.properties),
+                additional_properties: any.additional_properties.as_mut(),
+                items: any.items.as_mut(),
+                contains: any.contains.as_mut(),
+                one_of: Some(&mut any.one_of),
+                all_of: Some(&mut any.all_of),
+                any_of: Some(&mut any.any_of),
+                not: any.not.as_mut(),
+            },
+        };
+
+        for (name, schema) in properties.into_iter().flatten() {
+            subschemas.push((SubschemaKey::Property(name), SchemaSlot::Boxed(schema)));
+        }
+        if let Some(AdditionalProperties::Schema(schema)) = additional_properties {
+            subschemas.push((
+                SubschemaKey::AdditionalProperties,
+                SchemaSlot::Plain(schema),
+            ));
+        }
+        if let Some(schema) = items {
+            subschemas.push((SubschemaKey::Items, SchemaSlot::Boxed(schema)));
+        }
+        if let Some(schema) = contains {
+            subschemas.push((SubschemaKey::Contains, SchemaSlot::Boxed(schema)));
+        }
+        for (i, schema) in one_of.into_iter().flatten().enumerate() {
+            subschemas.push((SubschemaKey::OneOf(i), SchemaSlot::Plain(schema)));
+        }
+        for (i, schema) in all_of.into_iter().flatten().enumerate() {
+            subschemas.push((SubschemaKey::AllOf(i), SchemaSlot::Plain(schema)));
+        }
+        for (i, schema) in any_of.into_iter().flatten().enumerate() {
+            subschemas.push((SubschemaKey::AnyOf(i), SchemaSlot::Plain(schema)));
+        }
+        if let Some(schema) = not {
+            subschemas.push((SubschemaKey::Not, SchemaSlot::Plain(schema)));
+        }
+        subschemas
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -850,7 +1305,7 @@ mod tests {
         ));
         assert!(matches!(
             &schema.schema_kind,
-            SchemaKind::Any(AnySchema { enumeration, .. }) if enumeration[0] == json!(null)));
+            SchemaKind::Any(any) if any.enumeration[0] == json!(null)));
     }
 
     #[test]
@@ -879,12 +1334,13 @@ mod tests {
         assert_eq!(schema.schema_data.extensions.get("x-foo").unwrap(), "bar");
 
         match schema.schema_kind {
-            SchemaKind::Any(AnySchema {
-                typ,
-                properties,
-                one_of,
-                ..
-            }) => {
+            SchemaKind::Any(any) => {
+                let AnySchema {
+                    typ,
+                    properties,
+                    one_of,
+                    ..
+                } = *any;
                 assert_eq!(typ.unwrap(), "object");
                 assert_eq!(properties.len(), 3);
                 assert_eq!(one_of.len(), 3);
@@ -919,4 +1375,78 @@ mod tests {
             _ => panic!("incorrect kind {:#?}", schema),
         }
     }
+
+    #[test]
+    fn test_enum_values() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "string",
+            "nullable": true,
+            "enum": ["a", null]
+        }))
+        .unwrap();
+        assert_eq!(schema.enum_values(), Some(vec![json!("a"), json!(null)]));
+
+        let schema: Schema = serde_json::from_value(json!({ "enum": [1, "a"] })).unwrap();
+        assert_eq!(schema.enum_values(), Some(vec![json!(1), json!("a")]));
+
+        let schema: Schema = serde_json::from_value(json!({ "type": "integer" })).unwrap();
+        assert_eq!(schema.enum_values(), None);
+    }
+
+    #[test]
+    fn test_integer_written_as_float() {
+        let schema: Schema = serde_json::from_value(json!({
+            "type": "integer",
+            "minimum": 0.0,
+            "maximum": 1e3,
+            "multipleOf": 5.0,
+            "enum": [5.0, 10, null]
+        }))
+        .unwrap();
+        match schema.schema_kind {
+            SchemaKind::Type(Type::Integer(integer)) => {
+                assert_eq!(integer.minimum, Some(0));
+                assert_eq!(integer.maximum, Some(1000));
+                assert_eq!(integer.multiple_of, Some(5));
+                assert_eq!(integer.enumeration, vec![Some(5), Some(10), None]);
+            }
+            _ => panic!("incorrect kind {:#?}", schema),
+        }
+
+        let schema: Schema =
+            serde_json::from_value(json!({ "type": "integer", "minimum": 0.5 })).unwrap();
+        assert!(matches!(schema.schema_kind, SchemaKind::Any(_)));
+        let schema: Schema =
+            serde_json::from_value(json!({ "type": "integer", "maximum": 1e19 })).unwrap();
+        assert!(matches!(schema.schema_kind, SchemaKind::Any(_)));
+    }
+
+    #[test]
+    fn test_contains() {
+        let value = json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "contains": { "type": "string", "enum": ["admin"] },
+            "minContains": 1,
+            "maxContains": 2
+        });
+        let schema: Schema = serde_json::from_value(value.clone()).unwrap();
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::Array(array)) => {
+                assert!(array.contains.is_some());
+                assert_eq!(array.min_contains, Some(1));
+                assert_eq!(array.max_contains, Some(2));
+            }
+            kind => panic!("unexpected {:?}", kind),
+        }
+        assert_eq!(serde_json::to_value(&schema).unwrap(), value);
+
+        let value = json!({ "contains": { "type": "integer" } });
+        let schema: Schema = serde_json::from_value(value.clone()).unwrap();
+        match &schema.schema_kind {
+            SchemaKind::Any(any) => assert!(any.contains.is_some()),
+            kind => panic!("unexpected {:?}", kind),
+        }
+        assert_eq!(serde_json::to_value(&schema).unwrap(), value);
+    }
 }
@@ -0,0 +1,189 @@
+use crate::resolve::component_name;
+use crate::*;
+use indexmap::IndexMap;
+
+impl Schema {
+    /// The names of the component schemas this schema refers to, directly or
+    /// through its inline subschemas, in order of first appearance.
+    pub fn schema_references(&self) -> Vec<String> {
+        fn collect(schema: &Schema, names: &mut Vec<String>) {
+            for (_, subschema) in schema.subschemas() {
+                match subschema {
+                    ReferenceOr::Reference { reference } => push_name(&reference, names),
+                    ReferenceOr::Item(schema) => collect(schema, names),
+                }
+            }
+        }
+
+        let mut names = Vec::new();
+        collect(self, &mut names);
+        names
+    }
+}
+
+impl Components {
+    /// Maps the name of every component schema to the component schemas it
+    /// refers to, see [Schema::schema_references]. References to missing or
+    /// external schemas are left out.
+    pub fn schema_graph(&self) -> IndexMap<&str, Vec<String>> {
+        let mut graph = IndexMap::new();
+        for (name, schema) in &self.schemas {
+            let mut references = match schema {
+                ReferenceOr::Item(schema) => schema.schema_references(),
+                ReferenceOr::Reference { reference } => {
+                    let mut names = Vec::new();
+                    push_name(reference, &mut names);
+                    names
+                }
+            };
+            references.retain(|reference| self.schemas.contains_key(reference));
+            graph.insert(name.as_str(), references);
+        }
+        graph
+    }
+
+    /// Finds the cycles among the component schemas, each as the chain of
+    /// its members: every member refers to the next one, and the last one
+    /// to the first.
+    ///
+    /// A depth-first search through the schemas in declaration order reports
+    /// one cycle per reference leading back to a schema being visited. Every
+    /// group of mutually recursive schemas yields at least one cycle, but not
+    /// every cycle, nor every recursive schema, is listed; see
+    /// [Components::recursive_schemas] for those.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let components: Components = serde_json::from_str(r##"{ "schemas": {
+    ///     "Node": { "type": "object", "properties": {
+    ///         "children": { "type": "array", "items": { "$ref": "#/components/schemas/Node" } }
+    ///     } },
+    ///     "Person": { "type": "object", "properties": {
+    ///         "employer": { "$ref": "#/components/schemas/Company" }
+    ///     } },
+    ///     "Company": { "type": "object", "properties": {
+    ///         "ceo": { "$ref": "#/components/schemas/Person" }
+    ///     } }
+    /// } }"##).unwrap();
+    /// assert_eq!(
+    ///     components.schema_cycles(),
+    ///     [vec!["Node".to_owned()], vec!["Person".to_owned(), "Company".to_owned()]]
+    /// );
+    /// ```
+    pub fn schema_cycles(&self) -> Vec<Vec<String>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            Visiting,
+            Done,
+        }
+
+        fn visit<'a>(
+            name: &'a str,
+            graph: &'a IndexMap<&str, Vec<String>>,
+            states: &mut IndexMap<&'a str, State>,
+            stack: &mut Vec<&'a str>,
+            cycles: &mut Vec<Vec<String>>,
+        ) {
+            states.insert(name, State::Visiting);
+            stack.push(name);
+            for next in &graph[name] {
+                match states.get(next.as_str()) {
+                    Some(State::Visiting) => {
+                        let start = stack.iter().position(|n| n == next).unwrap_or_default();
+                        cycles.push(stack[start..].iter().map(|n| (*n).to_owned()).collect());
+                    }
+                    Some(State::Done) => {}
+                    None => visit(next, graph, states, stack, cycles),
+                }
+            }
+            stack.pop();
+            states.insert(name, State::Done);
+        }
+
+        let graph = self.schema_graph();
+        let mut states = IndexMap::new();
+        let mut cycles = Vec::new();
+        for name in graph.keys() {
+            if !states.contains_key(name) {
+                visit(name, &graph, &mut states, &mut Vec::new(), &mut cycles);
+            }
+        }
+        cycles
+    }
+
+    /// The names of the component schemas that refer back to themselves,
+    /// directly or through other schemas, in declaration order.
+    pub fn recursive_schemas(&self) -> Vec<&str> {
+        let graph = self.schema_graph();
+        graph
+            .keys()
+            .copied()
+            .filter(|name| {
+                let mut seen: Vec<&str> = Vec::new();
+                let mut pending: Vec<&str> = graph[name].iter().map(String::as_str).collect();
+                while let Some(next) = pending.pop() {
+                    if next == *name {
+                        return true;
+                    }
+                    if !seen.contains(&next) {
+                        seen.push(next);
+                        pending.extend(graph[next].iter().map(String::as_str));
+                    }
+                }
+                false
+            })
+            .collect()
+    }
+}
+
+/// Adds the name of the local component schema `reference` points to, if it
+/// is one and is not already listed.
+fn push_name(reference: &str, names: &mut Vec<String>) {
+    if let Ok(name) = component_name(reference, ComponentKind::Schemas) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_cycles() {
+        let components: Components = serde_json::from_value(json!({ "schemas": {
+            "A": { "type": "object", "properties": {
+                "b": { "$ref": "#/components/schemas/B" },
+                "c": { "$ref": "#/components/schemas/C" }
+            } },
+            "B": { "allOf": [ { "$ref": "#/components/schemas/A" } ] },
+            "C": { "type": "array", "items": { "oneOf": [
+                { "$ref": "#/components/schemas/B" },
+                { "$ref": "#/components/schemas/Leaf" }
+            ] } },
+            "Leaf": { "type": "string" },
+            "Alias": { "$ref": "#/components/schemas/Alias2" },
+            "Alias2": { "$ref": "#/components/schemas/Alias" },
+            "User": { "type": "object", "properties": {
+                "leaf": { "$ref": "#/components/schemas/Leaf" },
+                "missing": { "$ref": "#/components/schemas/Missing" },
+                "external": { "$ref": "other.yaml#/components/schemas/User" }
+            } }
+        } }))
+        .unwrap();
+
+        let graph = components.schema_graph();
+        assert_eq!(graph["C"], ["B", "Leaf"]);
+        assert_eq!(graph["User"], ["Leaf"]);
+        assert_eq!(
+            components.schema_cycles(),
+            [vec!["A", "B"], vec!["Alias", "Alias2"]]
+        );
+        assert_eq!(
+            components.recursive_schemas(),
+            ["A", "Alias", "Alias2", "B", "C"]
+        );
+    }
+}
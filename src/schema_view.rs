@@ -0,0 +1,136 @@
+use crate::*;
+use indexmap::IndexMap;
+
+impl Schema {
+    /// Returns a copy of this schema as sent in requests: without its
+    /// `readOnly` properties, which are also removed from `required`.
+    ///
+    /// Inline subschemas are projected as well, while references are kept,
+    /// so component schemas that references lead to need views of their own.
+    /// Properties that are references are dropped if the schema they refer
+    /// to in `components` is `readOnly`.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let pet: Schema = serde_json::from_str(r#"{
+    ///     "type": "object",
+    ///     "required": ["id", "name", "password"],
+    ///     "properties": {
+    ///         "id": { "type": "integer", "readOnly": true },
+    ///         "name": { "type": "string" },
+    ///         "password": { "type": "string", "writeOnly": true }
+    ///     }
+    /// }"#).unwrap();
+    /// let components = Components::default();
+    ///
+    /// let request = serde_json::to_value(pet.request_view(&components)).unwrap();
+    /// assert_eq!(request["required"], serde_json::json!(["name", "password"]));
+    /// let response = serde_json::to_value(pet.response_view(&components)).unwrap();
+    /// assert_eq!(response["required"], serde_json::json!(["id", "name"]));
+    /// ```
+    pub fn request_view(&self, components: &Components) -> Schema {
+        let mut schema = self.clone();
+        project(&mut schema, components, |data| data.read_only);
+        schema
+    }
+
+    /// Returns a copy of this schema as received in responses: without its
+    /// `writeOnly` properties, see [Schema::request_view].
+    pub fn response_view(&self, components: &Components) -> Schema {
+        let mut schema = self.clone();
+        project(&mut schema, components, |data| data.write_only);
+        schema
+    }
+}
+
+/// Removes the properties for which `hidden` returns `true` from `schema`
+/// and its inline subschemas.
+fn project(schema: &mut Schema, components: &Components, hidden: fn(&SchemaData) -> bool) {
+    let object: Option<(&mut IndexMap<_, _>, &mut Vec<String>)> = match &mut schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => {
+            Some((&mut object.properties, &mut object.required))
+        }
+        SchemaKind::Any(any) => Some((&mut any.properties, &mut any.required)),
+        _ => None,
+    };
+    if let Some((properties, required)) = object {
+        properties.retain(|name, property: &mut ReferenceOr<Box<Schema>>| {
+            let keep = !property
+                .resolve(components)
+                .is_ok_and(|property| hidden(&property.schema_data));
+            if !keep {
+                required.retain(|n| n != name);
+            }
+            keep
+        });
+    }
+    for (_, mut slot) in schema.subschemas_mut() {
+        if let Some(subschema) = slot.as_item_mut() {
+            project(subschema, components, hidden);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_views() {
+        let components: Components = serde_json::from_value(json!({ "schemas": {
+            "Id": { "type": "string", "readOnly": true },
+            "Owner": { "type": "object", "properties": { "id": { "$ref": "#/components/schemas/Id" } } }
+        } }))
+        .unwrap();
+        let schema: Schema = serde_json::from_value(json!({
+            "allOf": [
+                { "$ref": "#/components/schemas/Owner" },
+                {
+                    "required": ["id", "secret", "pets"],
+                    "properties": {
+                        "id": { "$ref": "#/components/schemas/Id" },
+                        "secret": { "type": "string", "writeOnly": true },
+                        "pets": { "type": "array", "items": { "type": "object", "properties": {
+                            "created": { "type": "string", "readOnly": true },
+                            "name": { "type": "string" }
+                        } } },
+                        "missing": { "$ref": "#/components/schemas/Missing" }
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let request = serde_json::to_value(schema.request_view(&components)).unwrap();
+        let part = &request["allOf"][1];
+        assert_eq!(part["required"], json!(["secret", "pets"]));
+        assert_eq!(
+            part["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            ["missing", "pets", "secret"]
+        );
+        assert_eq!(
+            part["properties"]["pets"]["items"]["properties"],
+            json!({ "name": { "type": "string" } })
+        );
+        assert_eq!(
+            request["allOf"][0],
+            json!({ "$ref": "#/components/schemas/Owner" })
+        );
+
+        let response = serde_json::to_value(schema.response_view(&components)).unwrap();
+        let part = &response["allOf"][1];
+        assert_eq!(part["required"], json!(["id", "pets"]));
+        assert_eq!(
+            part["properties"]["pets"]["items"]["properties"]
+                .as_object()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
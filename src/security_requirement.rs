@@ -1,3 +1,4 @@
+use crate::*;
 use indexmap::IndexMap;
 
 /// Lists the required security schemes to execute this operation.
@@ -14,3 +15,283 @@ use indexmap::IndexMap;
 /// Security Requirement Objects in the list needs to be satisfied
 /// to authorize the request.
 pub type SecurityRequirement = IndexMap<String, Vec<String>>;
+
+/// The credentials held by a caller, mapping the name of each satisfied
+/// security scheme to the scopes granted for it.
+pub type Credentials = IndexMap<String, Vec<String>>;
+
+/// The outcome of checking [Credentials] against a list of
+/// [SecurityRequirement]s with [authorize].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Authorization<'a> {
+    /// The requirement at `index` is satisfied. An empty requirement object
+    /// (`{}`) is always satisfied, which makes security optional.
+    Granted {
+        index: usize,
+        requirement: &'a SecurityRequirement,
+    },
+    /// There are no requirements, so no security applies.
+    Unrestricted,
+    /// None of the alternatives is satisfied. Each entry describes what is
+    /// lacking for the alternative at that index.
+    Denied { unmet: Vec<UnmetRequirement<'a>> },
+}
+
+impl Authorization<'_> {
+    /// Returns `true` unless the request would be denied.
+    pub fn is_authorized(&self) -> bool {
+        !matches!(self, Authorization::Denied { .. })
+    }
+}
+
+/// What a caller lacks to satisfy a single [SecurityRequirement].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UnmetRequirement<'a> {
+    /// The position of the requirement in the list of alternatives.
+    pub index: usize,
+    /// Schemes for which the caller holds no credentials at all.
+    pub missing_schemes: Vec<&'a str>,
+    /// Schemes for which the caller holds credentials lacking some required
+    /// scopes, along with those scopes.
+    pub missing_scopes: IndexMap<&'a str, Vec<&'a str>>,
+}
+
+/// Decides whether `credentials` satisfy `requirements`, which should be the
+/// requirements in effect for an operation.
+///
+/// Only one of the alternatives needs to be satisfied, and an alternative is
+/// satisfied when the caller holds credentials for each of its schemes with
+/// all of the listed scopes. The first satisfied alternative is reported.
+///
+/// ```
+/// # use indexmap::IndexMap;
+/// # use openapiv3::{authorize, Authorization, Credentials, SecurityRequirement};
+/// let requirements: Vec<SecurityRequirement> = vec![
+///     IndexMap::from([("oauth".to_string(), vec!["pets:write".to_string()])]),
+///     IndexMap::from([("apiKey".to_string(), vec![])]),
+/// ];
+/// let credentials: Credentials = IndexMap::from([("apiKey".to_string(), vec![])]);
+/// assert!(matches!(
+///     authorize(&requirements, &credentials),
+///     Authorization::Granted { index: 1, .. }
+/// ));
+/// ```
+pub fn authorize<'a>(
+    requirements: &'a [SecurityRequirement],
+    credentials: &Credentials,
+) -> Authorization<'a> {
+    if requirements.is_empty() {
+        return Authorization::Unrestricted;
+    }
+
+    let mut unmet = Vec::new();
+    for (index, requirement) in requirements.iter().enumerate() {
+        let mut lacking = UnmetRequirement {
+            index,
+            ..Default::default()
+        };
+        for (scheme, scopes) in requirement {
+            match credentials.get(scheme) {
+                None => lacking.missing_schemes.push(scheme.as_str()),
+                Some(granted) => {
+                    let missing = scopes
+                        .iter()
+                        .filter(|scope| !granted.contains(scope))
+                        .map(String::as_str)
+                        .collect::<Vec<_>>();
+                    if !missing.is_empty() {
+                        lacking.missing_scopes.insert(scheme.as_str(), missing);
+                    }
+                }
+            }
+        }
+
+        if lacking.missing_schemes.is_empty() && lacking.missing_scopes.is_empty() {
+            return Authorization::Granted { index, requirement };
+        }
+        unmet.push(lacking);
+    }
+
+    Authorization::Denied { unmet }
+}
+
+/// A scheme named by a [SecurityRequirement], as returned by
+/// [OpenAPI::effective_security_schemes].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequiredScheme<'a> {
+    pub name: &'a str,
+    pub scopes: &'a [String],
+    /// The scheme defined in [Components::security_schemes], or `None` if
+    /// it is not defined or its reference cannot be resolved.
+    pub scheme: Option<&'a SecurityScheme>,
+}
+
+impl OpenAPI {
+    /// Returns the security requirements in effect for `operation`: those of
+    /// the operation if it declares any, even an empty list, which removes
+    /// security, and those of the document otherwise.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "security": [ { "apiKey": [] } ],
+    ///     "paths": {
+    ///         "/pets": { "get": { "responses": {} } },
+    ///         "/login": { "post": { "security": [], "responses": {} } }
+    ///     }
+    /// }"#).unwrap();
+    /// let security = api
+    ///     .operations()
+    ///     .map(|(path, _, operation)| (path, api.effective_security(operation).len()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(security, [("/pets", 1), ("/login", 0)]);
+    /// ```
+    pub fn effective_security<'a>(&'a self, operation: &'a Operation) -> &'a [SecurityRequirement] {
+        operation
+            .security
+            .as_deref()
+            .or(self.security.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns the [OpenAPI::effective_security] of `operation` with each
+    /// scheme looked up in the components. Schemes that are not defined are
+    /// kept with no [RequiredScheme::scheme], so that they can be reported.
+    pub fn effective_security_schemes<'a>(
+        &'a self,
+        operation: &'a Operation,
+    ) -> Vec<Vec<RequiredScheme<'a>>> {
+        let components = self.components.as_ref();
+        self.effective_security(operation)
+            .iter()
+            .map(|requirement| {
+                requirement
+                    .iter()
+                    .map(|(name, scopes)| RequiredScheme {
+                        name,
+                        scopes,
+                        scheme: components.and_then(|components| {
+                            components
+                                .security_schemes
+                                .get(name)?
+                                .resolve(components)
+                                .ok()
+                        }),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+
+    fn requirement(entries: &[(&str, &[&str])]) -> SecurityRequirement {
+        entries
+            .iter()
+            .map(|(name, scopes)| {
+                (
+                    name.to_string(),
+                    scopes.iter().map(|s| s.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_authorize() {
+        let requirements = vec![
+            requirement(&[("oauth", &["read", "write"]), ("mtls", &[])]),
+            requirement(&[("apiKey", &[])]),
+        ];
+
+        assert_eq!(
+            authorize(&[], &Credentials::new()),
+            Authorization::Unrestricted
+        );
+
+        let creds = requirement(&[("oauth", &["read", "write", "admin"]), ("mtls", &[])]);
+        assert!(matches!(
+            authorize(&requirements, &creds),
+            Authorization::Granted { index: 0, .. }
+        ));
+
+        let creds = requirement(&[("oauth", &["read"])]);
+        let decision = authorize(&requirements, &creds);
+        assert!(!decision.is_authorized());
+        assert_eq!(
+            decision,
+            Authorization::Denied {
+                unmet: vec![
+                    UnmetRequirement {
+                        index: 0,
+                        missing_schemes: vec!["mtls"],
+                        missing_scopes: IndexMap::from([("oauth", vec!["write"])]),
+                    },
+                    UnmetRequirement {
+                        index: 1,
+                        missing_schemes: vec!["apiKey"],
+                        missing_scopes: IndexMap::new(),
+                    },
+                ]
+            }
+        );
+
+        let optional = vec![requirement(&[("apiKey", &[])]), requirement(&[])];
+        assert!(matches!(
+            authorize(&optional, &Credentials::new()),
+            Authorization::Granted { index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_effective_security_schemes() {
+        let api: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "security": [ { "apiKey": [] } ],
+                "paths": {
+                    "/pets": {
+                        "get": { "responses": {} },
+                        "post": { "security": [ { "oauth": ["write"], "mtls": [] } ], "responses": {} }
+                    }
+                },
+                "components": { "securitySchemes": {
+                    "apiKey": { "type": "apiKey", "name": "key", "in": "header" },
+                    "oauth": { "$ref": "#/components/securitySchemes/OAuth" },
+                    "OAuth": { "type": "oauth2", "flows": {} }
+                } }
+            }"##,
+        )
+        .unwrap();
+        let item = api.paths.paths["/pets"].as_item().unwrap();
+
+        let get = api.effective_security_schemes(item.get.as_ref().unwrap());
+        assert_eq!(get.len(), 1);
+        assert_eq!(get[0][0].name, "apiKey");
+        assert!(matches!(
+            get[0][0].scheme,
+            Some(SecurityScheme::APIKey { .. })
+        ));
+
+        let post = api.effective_security_schemes(item.post.as_ref().unwrap());
+        let schemes = post[0]
+            .iter()
+            .map(|s| (s.name, s.scopes, s.scheme.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            schemes,
+            [
+                ("oauth", &["write".to_owned()][..], true),
+                ("mtls", &[][..], false)
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::Extensions;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
@@ -5,7 +6,6 @@ use serde::{Deserialize, Serialize};
 /// Supported schemes are HTTP authentication, an API key (either as a
 /// header or as a query parameter), OAuth2's common flows (implicit, password,
 /// application and access code) as defined in RFC6749, and OpenID Connect Discovery.
-#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
 pub enum SecurityScheme {
@@ -24,7 +24,7 @@ pub enum SecurityScheme {
 
         /// Inline extensions to this object.
         #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-        extensions: IndexMap<String, serde_json::Value>,
+        extensions: Extensions,
     },
     #[serde(rename = "http")]
     HTTP {
@@ -41,13 +41,13 @@ pub enum SecurityScheme {
 
         /// Inline extensions to this object.
         #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-        extensions: IndexMap<String, serde_json::Value>,
+        extensions: Extensions,
     },
     #[serde(rename = "oauth2")]
     OAuth2 {
         /// An object containing configuration information for the flow types
         /// supported.
-        flows: OAuth2Flows,
+        flows: Box<OAuth2Flows>,
         /// A short description for security scheme. CommonMark syntax MAY be
         /// used for rich text representation.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,7 +55,7 @@ pub enum SecurityScheme {
 
         /// Inline extensions to this object.
         #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-        extensions: IndexMap<String, serde_json::Value>,
+        extensions: Extensions,
     },
     #[serde(rename = "openIdConnect")]
     OpenIDConnect {
@@ -70,7 +70,7 @@ pub enum SecurityScheme {
 
         /// Inline extensions to this object.
         #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-        extensions: IndexMap<String, serde_json::Value>,
+        extensions: Extensions,
     },
 }
 
@@ -100,25 +100,43 @@ pub struct OAuth2Flows {
 
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
+impl OAuth2Flows {
+    /// The scopes of all flows, with their descriptions, in order of first
+    /// appearance.
+    pub fn scopes(&self) -> IndexMap<&str, &str> {
+        let flows = [
+            self.implicit.as_ref().map(|flow| &flow.scopes),
+            self.password.as_ref().map(|flow| &flow.scopes),
+            self.client_credentials.as_ref().map(|flow| &flow.scopes),
+            self.authorization_code.as_ref().map(|flow| &flow.scopes),
+        ];
+        let mut scopes = IndexMap::new();
+        for (scope, description) in flows.into_iter().flatten().flatten() {
+            scopes.entry(scope.as_str()).or_insert(description.as_str());
+        }
+        scopes
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ImplicitOAuth2Flow {
     /// The authorization URL to be used for this flow. This MUST be in the
     /// form of a URL.
-    authorization_url: String,
+    pub(crate) authorization_url: String,
     /// The URL to be used for obtaining refresh tokens. This MUST be in the
     /// form of a URL.
     #[serde(skip_serializing_if = "Option::is_none")]
-    refresh_url: Option<String>,
+    pub(crate) refresh_url: Option<String>,
     /// The available scopes for the OAuth2 security scheme. A map between the
     /// scope name and a short description for it. The map MAY be empty.
     scopes: IndexMap<String, String>,
 
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -127,17 +145,17 @@ pub struct PasswordOAuth2Flow {
     /// The URL to be used for obtaining refresh tokens. This MUST be in the
     /// form of a URL.
     #[serde(skip_serializing_if = "Option::is_none")]
-    refresh_url: Option<String>,
+    pub(crate) refresh_url: Option<String>,
     /// The token URL to be used for this flow. This MUST be in the form of a
     /// URL.
-    token_url: String,
+    pub(crate) token_url: String,
     /// The available scopes for the OAuth2 security scheme. A map between the
     /// scope name and a short description for it. The map MAY be empty.
     scopes: IndexMap<String, String>,
 
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -145,17 +163,17 @@ pub struct ClientCredentialsOAuth2Flow {
     /// The URL to be used for obtaining refresh tokens. This MUST be in the
     /// form of a URL.
     #[serde(skip_serializing_if = "Option::is_none")]
-    refresh_url: Option<String>,
+    pub(crate) refresh_url: Option<String>,
     /// The token URL to be used for this flow. This MUST be in the form of a
     /// URL.
-    token_url: String,
+    pub(crate) token_url: String,
     /// The available scopes for the OAuth2 security scheme. A map between the
     /// scope name and a short description for it. The map MAY be empty.
     scopes: IndexMap<String, String>,
 
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -163,21 +181,21 @@ pub struct ClientCredentialsOAuth2Flow {
 pub struct AuthorizationCodeOAuth2Flow {
     /// The authorization URL to be used for this flow. This MUST be in the
     /// form of a URL.
-    authorization_url: String,
+    pub(crate) authorization_url: String,
     /// The token URL to be used for this flow. This MUST be in the form of a
     /// URL.
-    token_url: String,
+    pub(crate) token_url: String,
     /// The URL to be used for obtaining refresh tokens. This MUST be in the
     /// form of a URL.
     #[serde(skip_serializing_if = "Option::is_none")]
-    refresh_url: Option<String>,
+    pub(crate) refresh_url: Option<String>,
     /// The available scopes for the OAuth2 security scheme. A map between the
     /// scope name and a short description for it. The map MAY be empty.
     scopes: IndexMap<String, String>,
 
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
 
 #[cfg(test)]
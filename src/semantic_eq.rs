@@ -0,0 +1,282 @@
+use crate::util::write_canonical;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Fields holding arrays whose order carries no meaning.
+const UNORDERED: [&str; 3] = ["security", "tags", "required"];
+
+/// Optional fields that mean the same when absent and when `false` or
+/// empty.
+const DEFAULTED: [&str; 5] = [
+    "deprecated",
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "uniqueItems",
+    "variables",
+];
+
+/// Fields holding maps from names chosen by the author to objects of the
+/// model.
+const MAPS: [&str; 14] = [
+    "paths",
+    "properties",
+    "schemas",
+    "responses",
+    "parameters",
+    "examples",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "content",
+    "encoding",
+    "variables",
+    "mapping",
+];
+
+/// Fields holding values supplied by the author, which are compared as they
+/// are.
+const VALUES: [&str; 4] = ["example", "default", "enum", "value"];
+
+/// Equality that ignores differences which do not change the meaning of a
+/// document, implemented for every part of it.
+pub trait SemanticEq {
+    /// Whether `self` and `other` are equal, ignoring the order of object
+    /// keys, extensions and maps, the order of security requirements, tags
+    /// and required properties, and optional fields such as `deprecated`
+    /// that are absent on one side and `false` on the other.
+    ///
+    /// An empty `security` array is not ignored, as it overrides the
+    /// security requirements of the document. Examples, defaults, enums and
+    /// extensions are compared as they are.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let a: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": {},
+    ///     "tags": [ { "name": "pets" }, { "name": "admin" } ]
+    /// }"#).unwrap();
+    /// let mut b = a.clone();
+    /// b.tags.reverse();
+    /// assert_ne!(a, b);
+    /// assert!(a.semantically_equal(&b));
+    /// ```
+    fn semantically_equal(&self, other: &Self) -> bool;
+}
+
+impl<T: Serialize> SemanticEq for T {
+    fn semantically_equal(&self, other: &Self) -> bool {
+        match (serde_json::to_value(self), serde_json::to_value(other)) {
+            (Ok(left), Ok(right)) => normalize(left) == normalize(right),
+            _ => false,
+        }
+    }
+}
+
+/// Normalizes an object of the model, or an array of them.
+fn normalize(value: Value) -> Value {
+    let mut object = match value {
+        Value::Object(object) => object,
+        Value::Array(items) => return Value::Array(items.into_iter().map(normalize).collect()),
+        scalar => return scalar,
+    };
+    // Links hold the parameters and request body to call an operation with.
+    let link = !object.contains_key("responses")
+        && (object.contains_key("operationId") || object.contains_key("operationRef"));
+    let keys: Vec<String> = object.keys().cloned().collect();
+    for key in keys {
+        let Some(child) = object.remove(&key) else {
+            continue;
+        };
+        let child = match (key.as_str(), child) {
+            (key, child) if key.starts_with("x-") || VALUES.contains(&key) => child,
+            ("parameters" | "requestBody", child) if link => child,
+            ("security", Value::Array(mut items)) => {
+                items.sort_by_cached_key(canonical);
+                Value::Array(items)
+            }
+            (key, Value::Array(items)) if UNORDERED.contains(&key) => {
+                let mut items: Vec<Value> = items.into_iter().map(normalize).collect();
+                items.sort_by_cached_key(canonical);
+                Value::Array(items)
+            }
+            ("callbacks", Value::Object(callbacks)) => Value::Object(
+                callbacks
+                    .into_iter()
+                    .map(|(name, callback)| (name, normalize_map(callback, false)))
+                    .collect(),
+            ),
+            ("paths" | "responses", child @ Value::Object(_)) => normalize_map(child, true),
+            (key, child @ Value::Object(_)) if MAPS.contains(&key) => normalize_map(child, false),
+            (_, child) => normalize(child),
+        };
+        if DEFAULTED.contains(&key.as_str()) && is_default(&child) {
+            continue;
+        }
+        object.insert(key, child);
+    }
+    Value::Object(object)
+}
+
+/// Normalizes the objects of the model held by a map, which holds
+/// extensions as well if `extensions` is set.
+fn normalize_map(value: Value, extensions: bool) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, child)| match extensions && key.starts_with("x-") {
+                    true => (key, child),
+                    false => (key, normalize(child)),
+                })
+                .collect(),
+        ),
+        value => normalize(value),
+    }
+}
+
+fn canonical(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn is_default(value: &Value) -> bool {
+    match value {
+        Value::Bool(false) => true,
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_semantically_equal() {
+        let a: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1", "x-a": 1, "x-b": 2 },
+            "security": [ { "key": [] }, { "oauth": ["read"] } ],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "security": [],
+                        "responses": { "200": {
+                            "description": "",
+                            "content": { "application/json": { "schema": {
+                                "type": "object",
+                                "required": ["id", "name"],
+                                "properties": { "id": { "type": "integer" }, "name": { "type": "string" } }
+                            } } }
+                        } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let b: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "x-b": 2, "x-a": 1, "version": "1", "title": "Pets" },
+            "security": [ { "oauth": ["read"] }, { "key": [] } ],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "deprecated": false,
+                        "security": [],
+                        "responses": { "200": {
+                            "description": "",
+                            "headers": {},
+                            "content": { "application/json": { "schema": {
+                                "type": "object",
+                                "required": ["name", "id"],
+                                "properties": { "name": { "type": "string" }, "id": { "type": "integer" } }
+                            } } }
+                        } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        assert_ne!(a, b);
+        assert!(a.semantically_equal(&b));
+
+        let mut c = b.clone();
+        if let ReferenceOr::Item(item) = &mut c.paths.paths["/pets"] {
+            item.get.as_mut().unwrap().security = None;
+        }
+        assert!(!a.semantically_equal(&c));
+        let mut d = b;
+        d.security.as_mut().unwrap()[0]
+            .get_mut("oauth")
+            .unwrap()
+            .push("write".to_owned());
+        assert!(!a.semantically_equal(&d));
+
+        let schema = |value| serde_json::from_value::<Schema>(value).unwrap();
+        assert!(schema(json!({ "type": "string", "nullable": false }))
+            .semantically_equal(&schema(json!({ "type": "string" }))));
+        assert!(!schema(json!({ "type": "string", "enum": ["a", "b"] }))
+            .semantically_equal(&schema(json!({ "type": "string", "enum": ["b", "a"] }))));
+    }
+
+    #[test]
+    fn test_semantically_equal_positions() {
+        let schema = |value| serde_json::from_value::<Schema>(value).unwrap();
+        let differ = |a, b| !schema(a).semantically_equal(&schema(b));
+        assert!(differ(
+            json!({ "type": "object", "additionalProperties": false }),
+            json!({ "type": "object" })
+        ));
+        assert!(differ(
+            json!({ "type": "boolean", "default": false }),
+            json!({ "type": "boolean" })
+        ));
+        assert!(differ(
+            json!({ "type": "object", "example": { "tags": ["a", "b"], "required": false } }),
+            json!({ "type": "object", "example": { "tags": ["b", "a"] } })
+        ));
+        assert!(differ(
+            json!({ "type": "object", "x-config": { "security": [], "enabled": false } }),
+            json!({ "type": "object", "x-config": { "security": [] } })
+        ));
+        assert!(differ(
+            json!({ "type": "object", "properties": { "variables": {} } }),
+            json!({ "type": "object" })
+        ));
+        assert!(!differ(
+            json!({ "type": "object", "properties": { "tags": { "type": "object", "required": ["b", "a"] } } }),
+            json!({ "type": "object", "properties": { "tags": { "type": "object", "required": ["a", "b"] } } })
+        ));
+        assert!(!differ(
+            json!({ "type": "array", "uniqueItems": false }),
+            json!({ "type": "array" })
+        ));
+
+        let parameter = |value| serde_json::from_value::<Parameter>(value).unwrap();
+        assert!(parameter(
+            json!({ "name": "id", "in": "query", "schema": {}, "deprecated": false })
+        )
+        .semantically_equal(&parameter(
+            json!({ "name": "id", "in": "query", "schema": {} })
+        )));
+        assert!(
+            !parameter(json!({ "name": "id", "in": "query", "schema": {}, "explode": false }))
+                .semantically_equal(&parameter(
+                    json!({ "name": "id", "in": "query", "schema": {} })
+                ))
+        );
+
+        let link = |value| serde_json::from_value::<Link>(value).unwrap();
+        assert!(
+            !link(json!({ "operationId": "getPet", "parameters": { "all": false } }))
+                .semantically_equal(&link(
+                    json!({ "operationId": "getPet", "parameters": { "all": {} } })
+                ))
+        );
+    }
+}
@@ -23,5 +23,300 @@ pub struct Server {
     pub variables: Option<IndexMap<String, ServerVariable>>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
+}
+
+impl Server {
+    /// Parses [Server::url] into a [ServerUrlTemplate].
+    pub fn url_template(&self) -> Result<ServerUrlTemplate, ServerUrlTemplateError> {
+        self.url.parse()
+    }
+
+    /// Returns the names of placeholders in [Server::url] that have no
+    /// corresponding entry in [Server::variables].
+    pub fn undefined_variables(&self) -> Result<Vec<String>, ServerUrlTemplateError> {
+        let template = self.url_template()?;
+        Ok(template
+            .variables()
+            .filter(|name| {
+                !self
+                    .variables
+                    .as_ref()
+                    .is_some_and(|vars| vars.contains_key(*name))
+            })
+            .map(String::from)
+            .collect())
+    }
+
+    /// Renders [Server::url], substituting each placeholder with the value in
+    /// `values` or else the default of the corresponding [ServerVariable].
+    ///
+    /// Values are checked against the variable's enumeration, if it has one.
+    ///
+    /// ```
+    /// # use indexmap::IndexMap;
+    /// # use openapiv3::{Server, ServerVariable};
+    /// let server = Server {
+    ///     url: "https://{region}.example.com/v1".to_string(),
+    ///     variables: Some(IndexMap::from([(
+    ///         "region".to_string(),
+    ///         ServerVariable {
+    ///             default: "eu".to_string(),
+    ///             ..Default::default()
+    ///         },
+    ///     )])),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     server.render_url(&IndexMap::new()).unwrap(),
+    ///     "https://eu.example.com/v1"
+    /// );
+    /// ```
+    pub fn render_url(
+        &self,
+        values: &IndexMap<String, String>,
+    ) -> Result<String, ServerUrlTemplateError> {
+        let template = self.url_template()?;
+        let mut resolved = IndexMap::new();
+        for name in template.variables() {
+            let variable = self.variables.as_ref().and_then(|vars| vars.get(name));
+            let value = match (values.get(name), variable) {
+                (Some(value), Some(variable))
+                    if !variable.enumeration.is_empty()
+                        && !variable.enumeration.contains(value) =>
+                {
+                    return Err(ServerUrlTemplateError::ValueNotAllowed {
+                        name: name.to_owned(),
+                        value: value.clone(),
+                    })
+                }
+                (Some(value), _) => value.clone(),
+                (None, Some(variable)) => variable.default.clone(),
+                (None, None) => {
+                    return Err(ServerUrlTemplateError::MissingValue {
+                        name: name.to_owned(),
+                    })
+                }
+            };
+            resolved.insert(name.to_owned(), value);
+        }
+        template.render(&resolved)
+    }
+}
+
+/// A parsed server URL template, e.g. `https://{region}.example.com/{base}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerUrlTemplate {
+    segments: Vec<ServerUrlSegment>,
+}
+
+/// A piece of a [ServerUrlTemplate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerUrlSegment {
+    /// Text that is copied verbatim.
+    Literal(String),
+    /// A `{name}` placeholder for a [ServerVariable].
+    Variable(String),
+}
+
+/// Errors from parsing or rendering a [ServerUrlTemplate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerUrlTemplateError {
+    /// A `{` at the given byte offset has no matching `}`.
+    UnclosedBrace { position: usize },
+    /// A `}` at the given byte offset has no matching `{`.
+    UnexpectedClosingBrace { position: usize },
+    /// A placeholder at the given byte offset has no name, or contains `{`.
+    InvalidVariable { position: usize },
+    /// No value was available for the named variable.
+    MissingValue { name: String },
+    /// The value is not part of the variable's enumeration.
+    ValueNotAllowed { name: String, value: String },
+}
+
+impl std::fmt::Display for ServerUrlTemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnclosedBrace { position } => write!(f, "unclosed `{{` at offset {}", position),
+            Self::UnexpectedClosingBrace { position } => {
+                write!(f, "unexpected `}}` at offset {}", position)
+            }
+            Self::InvalidVariable { position } => {
+                write!(f, "invalid variable name at offset {}", position)
+            }
+            Self::MissingValue { name } => write!(f, "no value for server variable `{}`", name),
+            Self::ValueNotAllowed { name, value } => write!(
+                f,
+                "`{}` is not an allowed value for server variable `{}`",
+                value, name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ServerUrlTemplateError {}
+
+impl ServerUrlTemplate {
+    /// The literal and variable pieces of this template, in order.
+    pub fn segments(&self) -> &[ServerUrlSegment] {
+        &self.segments
+    }
+
+    /// Iterates over the distinct variable names in order of first
+    /// appearance.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        let mut seen = Vec::new();
+        self.segments
+            .iter()
+            .filter_map(move |segment| match segment {
+                ServerUrlSegment::Variable(name) if !seen.contains(&name) => {
+                    seen.push(name);
+                    Some(name.as_str())
+                }
+                _ => None,
+            })
+    }
+
+    /// Substitutes every placeholder with its entry in `values`.
+    pub fn render(
+        &self,
+        values: &IndexMap<String, String>,
+    ) -> Result<String, ServerUrlTemplateError> {
+        let mut url = String::new();
+        for segment in &self.segments {
+            match segment {
+                ServerUrlSegment::Literal(text) => url.push_str(text),
+                ServerUrlSegment::Variable(name) => match values.get(name) {
+                    Some(value) => url.push_str(value),
+                    None => {
+                        return Err(ServerUrlTemplateError::MissingValue { name: name.clone() })
+                    }
+                },
+            }
+        }
+        Ok(url)
+    }
+}
+
+impl std::str::FromStr for ServerUrlTemplate {
+    type Err = ServerUrlTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut rest = s;
+        let mut offset = 0;
+        while !rest.is_empty() {
+            let open = rest.find('{');
+            let close = rest.find('}');
+            match (open, close) {
+                (_, Some(c)) if open.is_none_or(|o| c < o) => {
+                    return Err(ServerUrlTemplateError::UnexpectedClosingBrace {
+                        position: offset + c,
+                    })
+                }
+                (Some(o), close) => {
+                    let c = close.ok_or(ServerUrlTemplateError::UnclosedBrace {
+                        position: offset + o,
+                    })?;
+                    let name = &rest[o + 1..c];
+                    if name.is_empty() || name.contains('{') {
+                        return Err(ServerUrlTemplateError::InvalidVariable {
+                            position: offset + o,
+                        });
+                    }
+                    if o > 0 {
+                        segments.push(ServerUrlSegment::Literal(rest[..o].to_owned()));
+                    }
+                    segments.push(ServerUrlSegment::Variable(name.to_owned()));
+                    offset += c + 1;
+                    rest = &rest[c + 1..];
+                }
+                (None, _) => {
+                    segments.push(ServerUrlSegment::Literal(rest.to_owned()));
+                    break;
+                }
+            }
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl std::fmt::Display for ServerUrlTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                ServerUrlSegment::Literal(text) => f.write_str(text)?,
+                ServerUrlSegment::Variable(name) => write!(f, "{{{}}}", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_template() {
+        let template: ServerUrlTemplate = "https://{env}.example.com:{port}/{env}".parse().unwrap();
+        assert_eq!(
+            template.variables().collect::<Vec<_>>(),
+            vec!["env", "port"]
+        );
+        assert_eq!(template.segments().len(), 6);
+        assert_eq!(
+            template.to_string(),
+            "https://{env}.example.com:{port}/{env}"
+        );
+
+        assert_eq!(
+            "https://{env".parse::<ServerUrlTemplate>(),
+            Err(ServerUrlTemplateError::UnclosedBrace { position: 8 })
+        );
+        assert_eq!(
+            "https://env}".parse::<ServerUrlTemplate>(),
+            Err(ServerUrlTemplateError::UnexpectedClosingBrace { position: 11 })
+        );
+        assert_eq!(
+            "/{}".parse::<ServerUrlTemplate>(),
+            Err(ServerUrlTemplateError::InvalidVariable { position: 1 })
+        );
+    }
+
+    #[test]
+    fn test_server_variables() {
+        let server = Server {
+            url: "https://{env}.example.com:{port}".to_string(),
+            variables: Some(IndexMap::from([(
+                "env".to_string(),
+                ServerVariable {
+                    enumeration: vec!["prod".to_string(), "staging".to_string()],
+                    default: "prod".to_string(),
+                    ..Default::default()
+                },
+            )])),
+            ..Default::default()
+        };
+        assert_eq!(server.undefined_variables().unwrap(), vec!["port"]);
+        assert_eq!(
+            server.render_url(&IndexMap::new()),
+            Err(ServerUrlTemplateError::MissingValue {
+                name: "port".to_string()
+            })
+        );
+
+        let mut values = IndexMap::from([("port".to_string(), "8443".to_string())]);
+        assert_eq!(
+            server.render_url(&values).unwrap(),
+            "https://prod.example.com:8443"
+        );
+        values.insert("env".to_string(), "dev".to_string());
+        assert!(matches!(
+            server.render_url(&values),
+            Err(ServerUrlTemplateError::ValueNotAllowed { .. })
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-use indexmap::IndexMap;
+use crate::Extensions;
 use serde::{Deserialize, Serialize};
 
 /// An object representing a Server Variable
@@ -17,8 +17,9 @@ pub struct ServerVariable {
     /// An optional description for the server
     /// variable. CommonMark syntax MAY be used
     /// for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
@@ -0,0 +1,235 @@
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// Where a value is in the input it was parsed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// The byte offset of the first byte of the value.
+    pub start: usize,
+    /// The byte offset just past the last byte of the value.
+    pub end: usize,
+    /// The 1-based line at which the value starts.
+    pub line: usize,
+    /// The 1-based column, in characters, at which the value starts.
+    pub column: usize,
+}
+
+/// The [Span] of every value of a JSON document, keyed by JSON Pointer.
+///
+/// ```
+/// # use openapiv3::*;
+/// let input = r#"{
+///     "openapi": "3.0.3",
+///     "info": { "title": "Pets", "version": "1" },
+///     "paths": { "/pets": { "get": { "responses": {} } } }
+/// }"#;
+/// let (api, spans) = OpenAPI::from_str_with_spans(input).unwrap();
+/// assert_eq!(api.info.title, "Pets");
+/// let span = spans.get("/paths/~1pets/get").unwrap();
+/// assert_eq!((span.line, span.column), (4, 34));
+/// assert_eq!(&input[span.start..span.end], r#"{ "responses": {} }"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    spans: IndexMap<String, Span>,
+}
+
+impl SourceMap {
+    /// Records the spans of the values of a JSON document.
+    pub fn from_json(input: &str) -> Result<SourceMap, Error> {
+        // Validate first, so that the scanner below can rely on the input
+        // being well-formed and not too deeply nested.
+        serde_json::from_str::<serde::de::IgnoredAny>(input)?;
+        let mut scanner = Scanner {
+            input,
+            position: 0,
+            line: 1,
+            line_start: 0,
+            spans: IndexMap::new(),
+        };
+        scanner.value(String::new());
+        Ok(SourceMap {
+            spans: scanner.spans,
+        })
+    }
+
+    /// The span of the value at `pointer`, where the empty pointer is the
+    /// whole document.
+    pub fn get(&self, pointer: &str) -> Option<&Span> {
+        self.spans.get(pointer)
+    }
+
+    /// The span of the value at `pointer`, or of its closest ancestor that
+    /// has one, such as when `pointer` refers to a default value that is
+    /// missing from the input.
+    pub fn closest(&self, pointer: &str) -> Option<&Span> {
+        let mut pointer = pointer;
+        loop {
+            if let Some(span) = self.spans.get(pointer) {
+                return Some(span);
+            }
+            pointer = &pointer[..pointer.rfind('/')?];
+        }
+    }
+
+    /// Iterates over the pointers and spans of all values, in the order in
+    /// which they start.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Span)> {
+        self.spans
+            .iter()
+            .map(|(pointer, span)| (pointer.as_str(), span))
+    }
+}
+
+impl OpenAPI {
+    /// Parses a JSON document like [from_str], along with the [SourceMap] of
+    /// its values, so that problems found in the document, such as
+    /// [Violation]s, can be traced back to the input.
+    pub fn from_str_with_spans(input: &str) -> Result<(OpenAPI, SourceMap), Error> {
+        let document = from_str(input)?;
+        Ok((document, SourceMap::from_json(input)?))
+    }
+}
+
+struct Scanner<'a> {
+    input: &'a str,
+    position: usize,
+    line: usize,
+    line_start: usize,
+    spans: IndexMap<String, Span>,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> u8 {
+        self.input.as_bytes()[self.position]
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.input.as_bytes().get(self.position) {
+            match byte {
+                b'\n' => {
+                    self.line += 1;
+                    self.line_start = self.position + 1;
+                }
+                b' ' | b'\t' | b'\r' => {}
+                _ => return,
+            }
+            self.position += 1;
+        }
+    }
+
+    fn value(&mut self, pointer: String) {
+        self.skip_whitespace();
+        let start = self.position;
+        let line = self.line;
+        let column = self.input[self.line_start..start].chars().count() + 1;
+        // A repeated key replaces the span of the earlier value, as serde_json
+        // keeps the last one.
+        let (index, _) = self.spans.insert_full(
+            pointer.clone(),
+            Span {
+                start,
+                end: start,
+                line,
+                column,
+            },
+        );
+
+        match self.peek() {
+            b'{' => {
+                self.position += 1;
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == b'}' {
+                        break;
+                    }
+                    let key = self.string();
+                    let key: String = serde_json::from_str(&self.input[key..self.position])
+                        .expect("the input is valid JSON");
+                    self.skip_whitespace();
+                    self.position += 1; // ':'
+                    self.value(format!("{}/{}", pointer, escape_pointer_token(&key)));
+                    self.skip_whitespace();
+                    if self.peek() == b',' {
+                        self.position += 1;
+                    }
+                }
+                self.position += 1;
+            }
+            b'[' => {
+                self.position += 1;
+                let mut i = 0;
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == b']' {
+                        break;
+                    }
+                    self.value(format!("{}/{}", pointer, i));
+                    i += 1;
+                    self.skip_whitespace();
+                    if self.peek() == b',' {
+                        self.position += 1;
+                    }
+                }
+                self.position += 1;
+            }
+            b'"' => {
+                self.string();
+            }
+            _ => {
+                while let Some(byte) = self.input.as_bytes().get(self.position) {
+                    if matches!(byte, b',' | b'}' | b']') || byte.is_ascii_whitespace() {
+                        break;
+                    }
+                    self.position += 1;
+                }
+            }
+        }
+
+        self.spans[index].end = self.position;
+    }
+
+    /// Skips over a string, returning the offset of its opening quote.
+    fn string(&mut self) -> usize {
+        let start = self.position;
+        self.position += 1;
+        loop {
+            match self.peek() {
+                b'\\' => self.position += 2,
+                b'"' => break,
+                _ => self.position += 1,
+            }
+        }
+        self.position += 1;
+        start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_map() {
+        let input = "{\"a\": [1, true, \"x\\\"y\"],\n \"b/\\u00e9\": {\"\\u007e\": null},\r\n\t\"é\": -1.5e3 }";
+        let spans = SourceMap::from_json(input).unwrap();
+        let text = |pointer: &str| {
+            let span = spans.get(pointer).unwrap();
+            (&input[span.start..span.end], span.line, span.column)
+        };
+        assert_eq!(text(""), (input, 1, 1));
+        assert_eq!(text("/a"), ("[1, true, \"x\\\"y\"]", 1, 7));
+        assert_eq!(text("/a/1"), ("true", 1, 11));
+        assert_eq!(text("/a/2"), ("\"x\\\"y\"", 1, 17));
+        assert_eq!(text("/b~1é/~0"), ("null", 2, 25));
+        assert_eq!(text("/é"), ("-1.5e3", 3, 7));
+        assert_eq!(spans.iter().count(), 8);
+        assert_eq!(spans.closest("/a/1/x/y"), spans.get("/a/1"));
+        assert_eq!(spans.get("/c"), None);
+
+        assert!(SourceMap::from_json("{\"a\": }").is_err());
+        let spans = SourceMap::from_json(r#"{ "a": 1, "b": 2, "a": [3] }"#).unwrap();
+        assert_eq!(spans.get("/a").map(|span| span.column), Some(24));
+    }
+}
@@ -7,6 +7,66 @@ pub enum StatusCode {
     Range(u16),
 }
 
+impl StatusCode {
+    /// The class of the status code, i.e. its first digit, e.g. `2` for both
+    /// `204` and `2XX`.
+    pub fn class(&self) -> u16 {
+        match self {
+            StatusCode::Code(n) => n / 100,
+            StatusCode::Range(n) => *n,
+        }
+    }
+
+    /// Returns `true` if `status` is this code, or falls within this range.
+    ///
+    /// ```
+    /// # use openapiv3::StatusCode;
+    /// assert!(StatusCode::Range(2).matches(204));
+    /// assert!(!StatusCode::Range(2).matches(304));
+    /// assert!(StatusCode::Code(404).matches(404));
+    /// ```
+    pub fn matches(&self, status: u16) -> bool {
+        match self {
+            StatusCode::Code(n) => *n == status,
+            StatusCode::Range(n) => (100..1000).contains(&status) && status / 100 == *n,
+        }
+    }
+
+    /// Returns `true` if this is a status code between 100 and 599, or one of
+    /// the ranges `1XX` to `5XX`, as allowed by the specification.
+    ///
+    /// Deserialization rejects anything else, but the variants can still be
+    /// constructed directly.
+    pub fn is_valid(&self) -> bool {
+        (1..=5).contains(&self.class())
+    }
+
+    /// `1XX` codes.
+    pub fn is_informational(&self) -> bool {
+        self.class() == 1
+    }
+
+    /// `2XX` codes.
+    pub fn is_success(&self) -> bool {
+        self.class() == 2
+    }
+
+    /// `3XX` codes.
+    pub fn is_redirection(&self) -> bool {
+        self.class() == 3
+    }
+
+    /// `4XX` codes.
+    pub fn is_client_error(&self) -> bool {
+        self.class() == 4
+    }
+
+    /// `5XX` codes.
+    pub fn is_server_error(&self) -> bool {
+        self.class() == 5
+    }
+}
+
 impl fmt::Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -29,14 +89,14 @@ impl<'de> Deserialize<'de> for StatusCode {
             type Value = StatusCode;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("number between 100 and 999 (as string or integer) or a string that matches `\\dXX`")
+                formatter.write_str("number between 100 and 599 (as string or integer) or a string that matches `[1-5]XX`")
             }
 
             fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                if (100..1000).contains(&value) {
+                if (100..600).contains(&value) {
                     Ok(StatusCode::Code(value as u16))
                 } else {
                     Err(E::invalid_value(Unexpected::Signed(value), &self))
@@ -47,7 +107,7 @@ impl<'de> Deserialize<'de> for StatusCode {
             where
                 E: de::Error,
             {
-                if (100..1000).contains(&value) {
+                if (100..600).contains(&value) {
                     Ok(StatusCode::Code(value as u16))
                 } else {
                     Err(E::invalid_value(Unexpected::Unsigned(value), &self))
@@ -69,17 +129,18 @@ impl<'de> Deserialize<'de> for StatusCode {
                 if !value.is_ascii() {
                     return Err(E::invalid_value(
                         Unexpected::Str(value),
-                        &"ascii, format `\\dXX`",
+                        &"ascii, format `[1-5]XX`",
                     ));
                 }
 
                 let v = value.as_bytes().to_ascii_uppercase();
 
                 match [v[0], v[1], v[2]] {
-                    [n, b'X', b'X'] if n.is_ascii_digit() => {
-                        Ok(StatusCode::Range(u16::from(n - b'0')))
-                    }
-                    _ => Err(E::invalid_value(Unexpected::Str(value), &"format `\\dXX`")),
+                    [n @ b'1'..=b'5', b'X', b'X'] => Ok(StatusCode::Range(u16::from(n - b'0'))),
+                    _ => Err(E::invalid_value(
+                        Unexpected::Str(value),
+                        &"format `[1-5]XX`",
+                    )),
                 }
             }
         }
@@ -97,6 +158,51 @@ impl Serialize for StatusCode {
     }
 }
 
+#[cfg(feature = "http")]
+impl From<http::StatusCode> for StatusCode {
+    fn from(status: http::StatusCode) -> Self {
+        StatusCode::Code(status.as_u16())
+    }
+}
+
+/// The error returned when converting a [StatusCode] that is a range, or
+/// otherwise not a single HTTP status code, into an [http::StatusCode].
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpStatusCodeError {
+    status: StatusCode,
+}
+
+#[cfg(feature = "http")]
+impl fmt::Display for HttpStatusCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a single HTTP status code", self.status)
+    }
+}
+
+#[cfg(feature = "http")]
+impl std::error::Error for HttpStatusCodeError {}
+
+/// ```
+/// # use openapiv3::StatusCode;
+/// let status: http::StatusCode = StatusCode::Code(404).try_into().unwrap();
+/// assert_eq!(status, http::StatusCode::NOT_FOUND);
+/// assert!(http::StatusCode::try_from(StatusCode::Range(4)).is_err());
+/// ```
+#[cfg(feature = "http")]
+impl TryFrom<StatusCode> for http::StatusCode {
+    type Error = HttpStatusCodeError;
+
+    fn try_from(status: StatusCode) -> Result<Self, Self::Error> {
+        match status {
+            StatusCode::Code(n) => {
+                http::StatusCode::from_u16(n).map_err(|_| HttpStatusCodeError { status })
+            }
+            StatusCode::Range(_) => Err(HttpStatusCodeError { status }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::StatusCode;
@@ -120,9 +226,46 @@ mod tests {
         assert_eq!(StatusCode::Range(4), from_str("'4xx'").unwrap(),);
     }
 
+    #[test]
+    fn classify() {
+        assert!(StatusCode::Code(201).is_success());
+        assert!(StatusCode::Range(4).is_client_error());
+        assert!(StatusCode::Code(503).is_server_error());
+        assert!(StatusCode::Code(599).is_valid());
+        assert!(!StatusCode::Code(666).is_valid());
+        assert!(!StatusCode::Range(0).is_valid());
+        assert!(!StatusCode::Range(2).matches(2000));
+    }
+
     #[test]
     #[should_panic = "invalid value"]
     fn deserialize_invalid_range() {
         let _: StatusCode = from_str("2XY").unwrap();
     }
+
+    #[test]
+    fn deserialize_out_of_range() {
+        for input in [
+            "99", "600", "'600'", "999", "'099'", "0XX", "6XX", "'9xx'", "-200",
+        ] {
+            assert!(from_str::<StatusCode>(input).is_err(), "{}", input);
+        }
+        assert_eq!(StatusCode::Code(100), from_str("100").unwrap());
+        assert_eq!(StatusCode::Code(599), from_str("'599'").unwrap());
+        assert_eq!(StatusCode::Range(1), from_str("1XX").unwrap());
+        assert_eq!(StatusCode::Range(5), from_str("5XX").unwrap());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn http_conversions() {
+        let status = StatusCode::from(http::StatusCode::CREATED);
+        assert_eq!(status, StatusCode::Code(201));
+        assert_eq!(
+            http::StatusCode::try_from(status).unwrap(),
+            http::StatusCode::CREATED
+        );
+        assert!(http::StatusCode::try_from(StatusCode::Range(2)).is_err());
+        assert!(http::StatusCode::try_from(StatusCode::Code(42)).is_err());
+    }
 }
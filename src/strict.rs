@@ -0,0 +1,173 @@
+use std::fmt;
+
+use crate::unknown_fields::unknown_fields;
+use crate::*;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A key of a raw document that is ignored when it is deserialized, as
+/// reported by [OpenAPI::from_value_reporting].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoredKey {
+    /// The JSON Pointer of the object holding the key.
+    pub pointer: String,
+    pub key: String,
+}
+
+impl fmt::Display for IgnoredKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: `{}` is ignored", self.pointer, self.key)
+    }
+}
+
+/// The error returned by [OpenAPI::from_value_strict].
+#[derive(Debug)]
+pub enum StrictError {
+    /// The document could not be deserialized at all.
+    Invalid(serde_json::Error),
+    /// The document contains keys that would be ignored.
+    Ignored(Vec<IgnoredKey>),
+}
+
+impl fmt::Display for StrictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrictError::Invalid(err) => err.fmt(f),
+            StrictError::Ignored(keys) => {
+                write!(f, "{} keys are ignored", keys.len())?;
+                for key in keys {
+                    write!(f, "\n{}", key)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for StrictError {}
+
+impl From<serde_json::Error> for StrictError {
+    fn from(err: serde_json::Error) -> Self {
+        StrictError::Invalid(err)
+    }
+}
+
+impl OpenAPI {
+    /// Deserializes a document, also reporting the keys that are silently
+    /// ignored, such as keys of [Paths] that do not start with `/`, keys of
+    /// [Responses] that are not status codes, fields the specification does
+    /// not define and fields next to a `$ref`.
+    ///
+    /// Keys whose values are `null`, `false` or empty are not reported, as
+    /// they cannot be told apart from defaults that are not serialized.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let raw = serde_json::json!({
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "pets": { "summary": "Pets" }, "/pets": { "get": { "responses": { "OK": { "description": "" } } } } }
+    /// });
+    /// let (_, ignored) = OpenAPI::from_value_reporting(raw).unwrap();
+    /// let ignored = ignored.iter().map(ToString::to_string).collect::<Vec<_>>();
+    /// assert_eq!(ignored, [
+    ///     "/paths: `pets` is ignored",
+    ///     "/paths/~1pets/get/responses: `OK` is ignored",
+    /// ]);
+    /// ```
+    pub fn from_value_reporting(
+        value: Value,
+    ) -> Result<(OpenAPI, Vec<IgnoredKey>), serde_json::Error> {
+        let document = OpenAPI::deserialize(&value)?;
+        let ignored = unknown_fields(&value, &document)?
+            .into_iter()
+            .flat_map(|(pointer, fields)| {
+                fields
+                    .into_iter()
+                    .filter(|(_, value)| !is_empty(value))
+                    .map(move |(key, _)| IgnoredKey {
+                        pointer: pointer.clone(),
+                        key,
+                    })
+            })
+            .collect();
+        Ok((document, ignored))
+    }
+
+    /// Deserializes a document, failing if any key would be ignored, see
+    /// [OpenAPI::from_value_reporting].
+    pub fn from_value_strict(value: Value) -> Result<OpenAPI, StrictError> {
+        let (document, ignored) = OpenAPI::from_value_reporting(value)?;
+        if ignored.is_empty() {
+            Ok(document)
+        } else {
+            Err(StrictError::Ignored(ignored))
+        }
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(array) => array.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_strict() {
+        let raw = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            { "name": "limit", "in": "query", "allowEmptyValue": false, "schema": { "type": "integer", "nullable": false } },
+                            { "$ref": "#/components/parameters/Offset", "x-position": 2 }
+                        ],
+                        "responses": { "200": { "description": "", "name: count": 3 } }
+                    }
+                }
+            }
+        });
+        let err = OpenAPI::from_value_strict(raw.clone()).unwrap_err();
+        let StrictError::Ignored(ignored) = err else {
+            panic!("unexpected error {}", err);
+        };
+        assert_eq!(
+            ignored,
+            [
+                IgnoredKey {
+                    pointer: "/paths/~1pets/get/parameters/1".to_owned(),
+                    key: "x-position".to_owned(),
+                },
+                IgnoredKey {
+                    pointer: "/paths/~1pets/get/responses/200".to_owned(),
+                    key: "name: count".to_owned(),
+                },
+            ]
+        );
+
+        let mut valid = raw;
+        valid["paths"]["/pets"]["get"]["parameters"][1]
+            .as_object_mut()
+            .unwrap()
+            .remove("x-position");
+        valid["paths"]["/pets"]["get"]["responses"]["200"]
+            .as_object_mut()
+            .unwrap()
+            .remove("name: count");
+        assert!(OpenAPI::from_value_strict(valid).is_ok());
+        assert!(matches!(
+            OpenAPI::from_value_strict(json!({ "openapi": "3.0.3" })),
+            Err(StrictError::Invalid(_))
+        ));
+    }
+}
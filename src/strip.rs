@@ -0,0 +1,449 @@
+use crate::*;
+use indexmap::IndexMap;
+
+/// What [OpenAPI::strip] removes. Nothing is removed by default.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StripOptions {
+    descriptions: bool,
+    summaries: bool,
+    examples: bool,
+    external_docs: bool,
+    extensions: bool,
+}
+
+impl StripOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes everything that can be removed.
+    pub fn all() -> Self {
+        StripOptions {
+            descriptions: true,
+            summaries: true,
+            examples: true,
+            external_docs: true,
+            extensions: true,
+        }
+    }
+
+    /// Sets whether descriptions are removed. The required description of
+    /// responses is emptied instead.
+    pub fn descriptions(mut self, descriptions: bool) -> Self {
+        self.descriptions = descriptions;
+        self
+    }
+
+    /// Sets whether the summaries of path items, operations and examples
+    /// are removed.
+    pub fn summaries(mut self, summaries: bool) -> Self {
+        self.summaries = summaries;
+        self
+    }
+
+    /// Sets whether `example` and `examples` are removed from schemas,
+    /// parameters, headers and media types, along with the examples in
+    /// [Components].
+    pub fn examples(mut self, examples: bool) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    /// Sets whether external documentation is removed from the document,
+    /// operations, schemas and tags.
+    pub fn external_docs(mut self, external_docs: bool) -> Self {
+        self.external_docs = external_docs;
+        self
+    }
+
+    /// Sets whether specification extensions are removed from every object.
+    pub fn extensions(mut self, extensions: bool) -> Self {
+        self.extensions = extensions;
+        self
+    }
+}
+
+impl OpenAPI {
+    /// Removes documentation from this document, as set in `options`, to
+    /// make it as small as possible. Everything needed to describe the API
+    /// itself, such as schemas, parameters and `title`s, is kept.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1", "description": "All about pets" },
+    ///     "paths": { "/pets": { "get": {
+    ///         "summary": "List pets",
+    ///         "parameters": [ { "name": "limit", "in": "query", "example": 10, "schema": {
+    ///             "type": "integer", "description": "At most this many", "x-go-type": "int32"
+    ///         } } ],
+    ///         "responses": { "200": { "description": "The pets" } }
+    ///     } } }
+    /// }"#).unwrap();
+    /// api.strip(&StripOptions::all());
+    ///
+    /// assert_eq!(api.info.description, None);
+    /// let operation = api.operations().next().unwrap().2;
+    /// assert_eq!(operation.summary, None);
+    /// let data = operation.parameters[0].as_item().unwrap().parameter_data_ref();
+    /// assert_eq!(data.example, None);
+    /// let ParameterSchemaOrContent::Schema(schema) = &data.format else { panic!() };
+    /// let schema = schema.as_item().unwrap();
+    /// assert_eq!(schema.schema_data.description, None);
+    /// assert!(schema.schema_data.extensions.is_empty());
+    /// ```
+    pub fn strip(&mut self, options: &StripOptions) {
+        if options.extensions {
+            struct ClearExtensions;
+            impl VisitorMut for ClearExtensions {
+                fn visit_extensions_mut(&mut self, _: &str, extensions: &mut Extensions) {
+                    extensions.clear();
+                }
+            }
+            self.transform(&mut ClearExtensions);
+        }
+
+        let s = Stripper { options };
+        s.description(&mut self.info.description);
+        for server in &mut self.servers {
+            s.server(server);
+        }
+        for item in self.paths.paths.values_mut() {
+            if let ReferenceOr::Item(item) = item {
+                s.path_item(item);
+            }
+        }
+        if let Some(components) = &mut self.components {
+            s.components(components);
+        }
+        for tag in &mut self.tags {
+            s.description(&mut tag.description);
+            s.external_docs(&mut tag.external_docs);
+        }
+        s.external_docs(&mut self.external_docs);
+    }
+}
+
+struct Stripper<'a> {
+    options: &'a StripOptions,
+}
+
+impl Stripper<'_> {
+    fn description(&self, description: &mut Option<String>) {
+        if self.options.descriptions {
+            *description = None;
+        }
+    }
+
+    fn summary(&self, summary: &mut Option<String>) {
+        if self.options.summaries {
+            *summary = None;
+        }
+    }
+
+    fn examples(
+        &self,
+        example: &mut Option<serde_json::Value>,
+        examples: &mut IndexMap<String, ReferenceOr<Example>>,
+    ) {
+        if self.options.examples {
+            *example = None;
+            examples.clear();
+        }
+        for example in examples.values_mut() {
+            if let ReferenceOr::Item(example) = example {
+                self.example(example);
+            }
+        }
+    }
+
+    fn example(&self, example: &mut Example) {
+        self.summary(&mut example.summary);
+        self.description(&mut example.description);
+    }
+
+    fn external_docs(&self, docs: &mut Option<ExternalDocumentation>) {
+        if self.options.external_docs {
+            *docs = None;
+        }
+        if let Some(docs) = docs {
+            self.description(&mut docs.description);
+        }
+    }
+
+    fn server(&self, server: &mut Server) {
+        self.description(&mut server.description);
+        for variable in server.variables.iter_mut().flat_map(IndexMap::values_mut) {
+            self.description(&mut variable.description);
+        }
+    }
+
+    fn components(&self, components: &mut Components) {
+        for schema in components.schemas.values_mut() {
+            if let ReferenceOr::Item(schema) = schema {
+                self.schema(schema);
+            }
+        }
+        for response in components.responses.values_mut() {
+            if let ReferenceOr::Item(response) = response {
+                self.response(response);
+            }
+        }
+        for parameter in components.parameters.values_mut() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                self.parameter(parameter);
+            }
+        }
+        let mut no_example = None;
+        self.examples(&mut no_example, &mut components.examples);
+        for body in components.request_bodies.values_mut() {
+            if let ReferenceOr::Item(body) = body {
+                self.request_body(body);
+            }
+        }
+        for header in components.headers.values_mut() {
+            if let ReferenceOr::Item(header) = header {
+                self.header(header);
+            }
+        }
+        for scheme in components.security_schemes.values_mut() {
+            if let ReferenceOr::Item(scheme) = scheme {
+                self.description(match scheme {
+                    SecurityScheme::APIKey { description, .. }
+                    | SecurityScheme::HTTP { description, .. }
+                    | SecurityScheme::OAuth2 { description, .. }
+                    | SecurityScheme::OpenIDConnect { description, .. } => description,
+                });
+            }
+        }
+        for link in components.links.values_mut() {
+            if let ReferenceOr::Item(link) = link {
+                self.link(link);
+            }
+        }
+        for callback in components.callbacks.values_mut() {
+            if let ReferenceOr::Item(callback) = callback {
+                self.callback(callback);
+            }
+        }
+    }
+
+    fn path_item(&self, item: &mut PathItem) {
+        self.summary(&mut item.summary);
+        self.description(&mut item.description);
+        for server in &mut item.servers {
+            self.server(server);
+        }
+        self.parameters(&mut item.parameters);
+        for (_, operation) in item.iter_mut() {
+            self.operation(operation);
+        }
+    }
+
+    fn operation(&self, operation: &mut Operation) {
+        self.summary(&mut operation.summary);
+        self.description(&mut operation.description);
+        self.external_docs(&mut operation.external_docs);
+        self.parameters(&mut operation.parameters);
+        if let Some(ReferenceOr::Item(body)) = &mut operation.request_body {
+            self.request_body(body);
+        }
+        let responses = &mut operation.responses;
+        for response in responses
+            .default
+            .iter_mut()
+            .chain(responses.responses.values_mut())
+        {
+            if let ReferenceOr::Item(response) = response {
+                self.response(response);
+            }
+        }
+        for callback in operation.callbacks.values_mut() {
+            self.callback(callback);
+        }
+        for server in &mut operation.servers {
+            self.server(server);
+        }
+    }
+
+    fn callback(&self, callback: &mut Callback) {
+        for item in callback.values_mut() {
+            self.path_item(item);
+        }
+    }
+
+    fn parameters(&self, parameters: &mut [ReferenceOr<Parameter>]) {
+        for parameter in parameters {
+            if let ReferenceOr::Item(parameter) = parameter {
+                self.parameter(parameter);
+            }
+        }
+    }
+
+    fn parameter(&self, parameter: &mut Parameter) {
+        let data = parameter.parameter_data_mut();
+        self.description(&mut data.description);
+        self.examples(&mut data.example, &mut data.examples);
+        self.schema_or_content(&mut data.format);
+    }
+
+    fn header(&self, header: &mut Header) {
+        self.description(&mut header.description);
+        self.examples(&mut header.example, &mut header.examples);
+        self.schema_or_content(&mut header.format);
+    }
+
+    fn headers(&self, headers: &mut IndexMap<String, ReferenceOr<Header>>) {
+        for header in headers.values_mut() {
+            if let ReferenceOr::Item(header) = header {
+                self.header(header);
+            }
+        }
+    }
+
+    fn request_body(&self, body: &mut RequestBody) {
+        self.description(&mut body.description);
+        self.content(&mut body.content);
+    }
+
+    fn response(&self, response: &mut Response) {
+        if self.options.descriptions {
+            response.description.clear();
+        }
+        self.headers(&mut response.headers);
+        self.content(&mut response.content);
+        for link in response.links.values_mut() {
+            if let ReferenceOr::Item(link) = link {
+                self.link(link);
+            }
+        }
+    }
+
+    fn link(&self, link: &mut Link) {
+        self.description(&mut link.description);
+        if let Some(server) = &mut link.server {
+            self.server(server);
+        }
+    }
+
+    fn schema_or_content(&self, format: &mut ParameterSchemaOrContent) {
+        match format {
+            ParameterSchemaOrContent::Schema(schema) => {
+                if let ReferenceOr::Item(schema) = &mut **schema {
+                    self.schema(schema);
+                }
+            }
+            ParameterSchemaOrContent::Content(content) => self.content(content),
+        }
+    }
+
+    fn content(&self, content: &mut IndexMap<String, MediaType>) {
+        for media_type in content.values_mut() {
+            if let Some(ReferenceOr::Item(schema)) = &mut media_type.schema {
+                self.schema(schema);
+            }
+            self.examples(&mut media_type.example, &mut media_type.examples);
+            for encoding in media_type.encoding.values_mut() {
+                self.headers(&mut encoding.headers);
+            }
+        }
+    }
+
+    fn schema(&self, schema: &mut Schema) {
+        let data = &mut schema.schema_data;
+        self.description(&mut data.description);
+        self.external_docs(&mut data.external_docs);
+        if self.options.examples {
+            data.example = None;
+        }
+        for (_, mut slot) in schema.subschemas_mut() {
+            if let Some(subschema) = slot.as_item_mut() {
+                self.schema(subschema);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_strip() {
+        let input = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1", "description": "d", "x-logo": "l" },
+            "servers": [ { "url": "/{v}", "description": "d", "variables": { "v": { "default": "1", "description": "d" } } } ],
+            "paths": { "/pets": {
+                "summary": "s",
+                "post": {
+                    "description": "d",
+                    "externalDocs": { "url": "/docs", "description": "d" },
+                    "requestBody": { "content": { "application/json": {
+                        "schema": { "type": "object", "example": {}, "properties": {
+                            "description": { "type": "string", "description": "d", "example": "e" }
+                        } },
+                        "examples": { "a": { "summary": "s", "value": 1 } }
+                    } } },
+                    "responses": {},
+                    "callbacks": { "c": { "/hook": { "post": { "summary": "s", "responses": {
+                        "default": { "description": "d", "headers": { "X": { "description": "d", "example": 1, "style": "simple", "schema": {} } } }
+                    } } } } }
+                }
+            } },
+            "components": {
+                "examples": { "E": { "value": 1 } },
+                "securitySchemes": { "key": { "type": "apiKey", "name": "k", "in": "header", "description": "d" } }
+            },
+            "tags": [ { "name": "pets", "description": "d", "externalDocs": { "url": "/tags" } } ]
+        });
+        let api: OpenAPI = serde_json::from_value(input.clone()).unwrap();
+
+        let mut unchanged = api.clone();
+        unchanged.strip(&StripOptions::new());
+        assert_eq!(unchanged, api);
+
+        let mut stripped = api.clone();
+        stripped.strip(&StripOptions::all());
+        let value = serde_json::to_value(&stripped).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "servers": [ { "url": "/{v}", "variables": { "v": { "default": "1" } } } ],
+                "paths": { "/pets": {
+                    "post": {
+                        "requestBody": { "content": { "application/json": {
+                            "schema": { "type": "object", "properties": {
+                                "description": { "type": "string" }
+                            } }
+                        } } },
+                        "responses": {},
+                        "callbacks": { "c": { "/hook": { "post": { "responses": {
+                            "default": { "description": "", "headers": { "X": { "style": "simple", "schema": {} } } }
+                        } } } } }
+                    }
+                } },
+                "components": {
+                    "securitySchemes": { "key": { "type": "apiKey", "name": "k", "in": "header" } }
+                },
+                "tags": [ { "name": "pets" } ]
+            })
+        );
+
+        let mut summaries = api.clone();
+        summaries.strip(&StripOptions::new().summaries(true).external_docs(true));
+        let value = serde_json::to_value(&summaries).unwrap();
+        let post = &value["paths"]["/pets"]["post"];
+        assert_eq!(post["description"], "d");
+        assert_eq!(post["externalDocs"], json!(null));
+        assert_eq!(
+            post["requestBody"]["content"]["application/json"]["examples"]["a"],
+            json!({ "value": 1 })
+        );
+        assert_eq!(value["info"]["x-logo"], "l");
+    }
+}
@@ -1,5 +1,4 @@
 use crate::*;
-use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 /// Adds metadata to a single tag that is used by the
@@ -18,5 +17,5 @@ pub struct Tag {
     pub external_docs: Option<ExternalDocumentation>,
     /// Inline extensions to this object.
     #[serde(flatten, deserialize_with = "crate::util::deserialize_extensions")]
-    pub extensions: IndexMap<String, serde_json::Value>,
+    pub extensions: Extensions,
 }
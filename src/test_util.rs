@@ -0,0 +1,189 @@
+//! Helpers for regression-testing the parsing of a corpus of documents.
+//!
+//! ```no_run
+//! use openapiv3::test_util::{assert_round_trip, assert_snapshot, canonical_snapshot, load_corpus};
+//!
+//! for fixture in load_corpus("fixtures").unwrap() {
+//!     let openapi = fixture.parse().unwrap();
+//!     assert_round_trip(&openapi);
+//!     assert_snapshot(
+//!         &canonical_snapshot(&openapi),
+//!         format!("snapshots/{}.json", fixture.name),
+//!     );
+//! }
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::*;
+
+/// The serialization format of a [Fixture].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureFormat {
+    Json,
+    Yaml,
+}
+
+/// A document loaded by [load_corpus].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    /// The file name, e.g. `petstore.yaml`.
+    pub name: String,
+    pub path: PathBuf,
+    pub format: FixtureFormat,
+    pub contents: String,
+}
+
+impl Fixture {
+    /// Parses the fixture as an [OpenAPI] document.
+    pub fn parse(&self) -> Result<OpenAPI, String> {
+        match self.format {
+            FixtureFormat::Json => serde_json::from_str(&self.contents).map_err(|e| e.to_string()),
+            FixtureFormat::Yaml => from_yaml_str(&self.contents).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Loads every `.json`, `.yaml` and `.yml` file directly inside `dir`,
+/// sorted by name.
+pub fn load_corpus(dir: impl AsRef<Path>) -> io::Result<Vec<Fixture>> {
+    let mut fixtures = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => FixtureFormat::Json,
+            Some("yaml") | Some("yml") => FixtureFormat::Yaml,
+            _ => continue,
+        };
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let contents = fs::read_to_string(&path)?;
+        fixtures.push(Fixture {
+            name,
+            path,
+            format,
+            contents,
+        });
+    }
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(fixtures)
+}
+
+/// Asserts that `openapi` survives serializing to JSON and YAML and parsing
+/// the result back.
+pub fn assert_round_trip(openapi: &OpenAPI) {
+    let json = serde_json::to_string(openapi).expect("failed to serialize JSON");
+    let from_json: OpenAPI = serde_json::from_str(&json).expect("failed to reparse JSON");
+    assert!(
+        from_json == *openapi,
+        "document changed after a JSON round trip"
+    );
+
+    let yaml = to_yaml_string(openapi).expect("failed to serialize YAML");
+    let from_yaml: OpenAPI = from_yaml_str(&yaml).expect("failed to reparse YAML");
+    assert!(
+        from_yaml == *openapi,
+        "document changed after a YAML round trip"
+    );
+}
+
+/// Renders `openapi` as pretty-printed JSON with object keys sorted, so that
+/// snapshots do not change with key order.
+pub fn canonical_snapshot(openapi: &OpenAPI) -> String {
+    fn sorted(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(object) => {
+                let mut entries = object.into_iter().collect::<Vec<_>>();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                serde_json::Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key, sorted(value)))
+                        .collect(),
+                )
+            }
+            serde_json::Value::Array(array) => {
+                serde_json::Value::Array(array.into_iter().map(sorted).collect())
+            }
+            scalar => scalar,
+        }
+    }
+
+    let value = serde_json::to_value(openapi).expect("failed to serialize JSON");
+    let mut snapshot =
+        serde_json::to_string_pretty(&sorted(value)).expect("failed to serialize JSON");
+    snapshot.push('\n');
+    snapshot
+}
+
+/// Asserts that `actual` matches the snapshot stored at `path`.
+///
+/// The snapshot is written instead if it does not exist yet or if the
+/// `UPDATE_SNAPSHOTS` environment variable is set.
+pub fn assert_snapshot(actual: &str, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("failed to create snapshot directory");
+        }
+        fs::write(path, actual).expect("failed to write snapshot");
+        return;
+    }
+
+    let expected = fs::read_to_string(path).expect("failed to read snapshot");
+    if expected != actual {
+        let line = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e != a)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "snapshot {} differs starting at line {}; set UPDATE_SNAPSHOTS=1 to accept the new output",
+            path.display(),
+            line + 1
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_corpus() {
+        let fixtures = load_corpus(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures")).unwrap();
+        assert!(fixtures.iter().any(|f| f.name == "petstore.yaml"));
+        for fixture in fixtures {
+            let openapi = fixture
+                .parse()
+                .unwrap_or_else(|e| panic!("{}: {}", fixture.name, e));
+            assert_round_trip(&openapi);
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let openapi: OpenAPI = serde_json::from_str(
+            r#"{"paths": {}, "openapi": "3.0.3", "info": {"version": "1", "title": "A"}}"#,
+        )
+        .unwrap();
+        let snapshot = canonical_snapshot(&openapi);
+        assert_eq!(
+            snapshot,
+            "{\n  \"info\": {\n    \"title\": \"A\",\n    \"version\": \"1\"\n  },\n  \"openapi\": \"3.0.3\",\n  \"paths\": {}\n}\n"
+        );
+
+        let path =
+            std::env::temp_dir().join(format!("openapiv3-snapshot-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_snapshot(&snapshot, &path);
+        assert_snapshot(&snapshot, &path);
+        let result = std::panic::catch_unwind(|| assert_snapshot("{}\n", &path));
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
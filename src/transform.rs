@@ -0,0 +1,608 @@
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// Rewrites a document in place through [OpenAPI::transform].
+///
+/// Every method receives the JSON Pointer of the value as it was when the
+/// traversal reached it. All methods do nothing by default.
+pub trait VisitorMut {
+    /// Called for every inline schema, before its subschemas, so that
+    /// subschemas added by the visitor are visited as well.
+    fn visit_schema_mut(&mut self, pointer: &str, schema: &mut Schema) {
+        let _ = (pointer, schema);
+    }
+
+    /// Called for every `$ref`, of schemas and of any other object.
+    /// `pointer` is that of the reference object itself.
+    fn visit_reference_mut(&mut self, pointer: &str, reference: &mut String) {
+        let _ = (pointer, reference);
+    }
+
+    /// Called for every `description` that is present. `pointer` ends in
+    /// `/description`.
+    fn visit_description_mut(&mut self, pointer: &str, description: &mut String) {
+        let _ = (pointer, description);
+    }
+
+    /// Called with the specification extensions of every object that can
+    /// have them, after the object's description and before its children.
+    fn visit_extensions_mut(&mut self, pointer: &str, extensions: &mut Extensions) {
+        let _ = (pointer, extensions);
+    }
+}
+
+impl OpenAPI {
+    /// Passes every schema, reference, description and set of extensions in
+    /// this document to `visitor`, in document order.
+    ///
+    /// Referenced objects are not followed; their `$ref` is passed to
+    /// [VisitorMut::visit_reference_mut] instead.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1", "x-internal": true },
+    ///     "paths": {},
+    ///     "components": { "schemas": {
+    ///         "Pets": { "type": "array", "items": { "$ref": "#/definitions/Pet" } }
+    ///     } }
+    /// }"##).unwrap();
+    ///
+    /// struct Cleanup;
+    /// impl VisitorMut for Cleanup {
+    ///     fn visit_reference_mut(&mut self, _: &str, reference: &mut String) {
+    ///         if let Some(name) = reference.strip_prefix("#/definitions/") {
+    ///             *reference = format!("#/components/schemas/{}", name);
+    ///         }
+    ///     }
+    ///     fn visit_extensions_mut(&mut self, _: &str, extensions: &mut Extensions) {
+    ///         extensions.clear();
+    ///     }
+    /// }
+    /// api.transform(&mut Cleanup);
+    /// assert!(api.info.extensions.is_empty());
+    /// assert_eq!(
+    ///     serde_json::to_value(&api.components.unwrap().schemas["Pets"]).unwrap()["items"]["$ref"],
+    ///     "#/components/schemas/Pet"
+    /// );
+    /// ```
+    pub fn transform<V: VisitorMut>(&mut self, visitor: &mut V) {
+        let mut t = Transformer { visitor };
+
+        let info = &mut self.info;
+        t.description(&mut info.description, "/info");
+        t.extensions(&mut info.extensions, "/info");
+        if let Some(contact) = &mut info.contact {
+            t.extensions(&mut contact.extensions, "/info/contact");
+        }
+        if let Some(license) = &mut info.license {
+            t.extensions(&mut license.extensions, "/info/license");
+        }
+
+        for (i, server) in self.servers.iter_mut().enumerate() {
+            t.server(server, &format!("/servers/{}", i));
+        }
+
+        t.extensions(&mut self.paths.extensions, "/paths");
+        for (path, item) in self.paths.paths.iter_mut() {
+            let pointer = format!("/paths/{}", escape_pointer_token(path));
+            if let Some(item) = t.reference_or(item, &pointer) {
+                t.path_item(item, &pointer);
+            }
+        }
+
+        if let Some(components) = &mut self.components {
+            t.components(components);
+        }
+
+        for (i, tag) in self.tags.iter_mut().enumerate() {
+            let pointer = format!("/tags/{}", i);
+            t.description(&mut tag.description, &pointer);
+            t.extensions(&mut tag.extensions, &pointer);
+            t.external_docs(&mut tag.external_docs, &pointer);
+        }
+        t.external_docs(&mut self.external_docs, "");
+        t.extensions(&mut self.extensions, "");
+    }
+
+    /// Replaces every `$ref` in this document for which `map` returns a new
+    /// reference, in one traversal with [OpenAPI::transform].
+    ///
+    /// Discriminator mappings are not `$ref`s and are left alone.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let mut api: OpenAPI = serde_json::from_str(r##"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "get": { "responses": {
+    ///         "200": { "$ref": "#/components/responses/Pets" },
+    ///         "default": { "$ref": "#/definitions/Error" }
+    ///     } } } }
+    /// }"##).unwrap();
+    /// api.remap_refs(|reference| {
+    ///     let name = reference.strip_prefix("#/definitions/")?;
+    ///     Some(format!("common.yaml#/components/schemas/{}", name))
+    /// });
+    ///
+    /// let value = serde_json::to_value(&api).unwrap();
+    /// let responses = &value["paths"]["/pets"]["get"]["responses"];
+    /// assert_eq!(responses["200"]["$ref"], "#/components/responses/Pets");
+    /// assert_eq!(responses["default"]["$ref"], "common.yaml#/components/schemas/Error");
+    /// ```
+    pub fn remap_refs<F: FnMut(&str) -> Option<String>>(&mut self, map: F) {
+        struct Remap<F>(F);
+
+        impl<F: FnMut(&str) -> Option<String>> VisitorMut for Remap<F> {
+            fn visit_reference_mut(&mut self, _: &str, reference: &mut String) {
+                if let Some(remapped) = (self.0)(reference) {
+                    *reference = remapped;
+                }
+            }
+        }
+
+        self.transform(&mut Remap(map));
+    }
+}
+
+struct Transformer<'v, V> {
+    visitor: &'v mut V,
+}
+
+impl<V: VisitorMut> Transformer<'_, V> {
+    /// Visits the reference if `value` is one, otherwise returns the item.
+    fn reference_or<'a, T>(
+        &mut self,
+        value: &'a mut ReferenceOr<T>,
+        pointer: &str,
+    ) -> Option<&'a mut T> {
+        match value {
+            ReferenceOr::Item(item) => Some(item),
+            ReferenceOr::Reference { reference } => {
+                self.visitor.visit_reference_mut(pointer, reference);
+                None
+            }
+        }
+    }
+
+    fn description(&mut self, description: &mut Option<String>, pointer: &str) {
+        if let Some(description) = description {
+            let pointer = format!("{}/description", pointer);
+            self.visitor.visit_description_mut(&pointer, description);
+        }
+    }
+
+    fn extensions(&mut self, extensions: &mut Extensions, pointer: &str) {
+        self.visitor.visit_extensions_mut(pointer, extensions);
+    }
+
+    fn external_docs(&mut self, docs: &mut Option<ExternalDocumentation>, pointer: &str) {
+        if let Some(docs) = docs {
+            let pointer = format!("{}/externalDocs", pointer);
+            self.description(&mut docs.description, &pointer);
+            self.extensions(&mut docs.extensions, &pointer);
+        }
+    }
+
+    fn server(&mut self, server: &mut Server, pointer: &str) {
+        self.description(&mut server.description, pointer);
+        self.extensions(&mut server.extensions, pointer);
+        for (name, variable) in server.variables.iter_mut().flatten() {
+            let pointer = format!("{}/variables/{}", pointer, escape_pointer_token(name));
+            self.description(&mut variable.description, &pointer);
+            self.extensions(&mut variable.extensions, &pointer);
+        }
+    }
+
+    fn components(&mut self, components: &mut Components) {
+        let pointer =
+            |kind: &str, name: &str| format!("/components/{}/{}", kind, escape_pointer_token(name));
+        for (name, schema) in components.schemas.iter_mut() {
+            let pointer = pointer("schemas", name);
+            if let Some(schema) = self.reference_or(schema, &pointer) {
+                self.schema(schema, &pointer);
+            }
+        }
+        for (name, response) in components.responses.iter_mut() {
+            let pointer = pointer("responses", name);
+            if let Some(response) = self.reference_or(response, &pointer) {
+                self.response(response, &pointer);
+            }
+        }
+        for (name, parameter) in components.parameters.iter_mut() {
+            let pointer = pointer("parameters", name);
+            if let Some(parameter) = self.reference_or(parameter, &pointer) {
+                self.parameter(parameter, &pointer);
+            }
+        }
+        for (name, example) in components.examples.iter_mut() {
+            let pointer = pointer("examples", name);
+            if let Some(example) = self.reference_or(example, &pointer) {
+                self.example(example, &pointer);
+            }
+        }
+        for (name, body) in components.request_bodies.iter_mut() {
+            let pointer = pointer("requestBodies", name);
+            if let Some(body) = self.reference_or(body, &pointer) {
+                self.request_body(body, &pointer);
+            }
+        }
+        for (name, header) in components.headers.iter_mut() {
+            let pointer = pointer("headers", name);
+            if let Some(header) = self.reference_or(header, &pointer) {
+                self.header(header, &pointer);
+            }
+        }
+        for (name, scheme) in components.security_schemes.iter_mut() {
+            let pointer = pointer("securitySchemes", name);
+            if let Some(scheme) = self.reference_or(scheme, &pointer) {
+                self.security_scheme(scheme, &pointer);
+            }
+        }
+        for (name, link) in components.links.iter_mut() {
+            let pointer = pointer("links", name);
+            if let Some(link) = self.reference_or(link, &pointer) {
+                self.link(link, &pointer);
+            }
+        }
+        for (name, callback) in components.callbacks.iter_mut() {
+            let pointer = pointer("callbacks", name);
+            if let Some(callback) = self.reference_or(callback, &pointer) {
+                self.callback(callback, &pointer);
+            }
+        }
+        self.extensions(&mut components.extensions, "/components");
+    }
+
+    fn path_item(&mut self, item: &mut PathItem, pointer: &str) {
+        self.description(&mut item.description, pointer);
+        self.extensions(&mut item.extensions, pointer);
+        for (i, server) in item.servers.iter_mut().enumerate() {
+            self.server(server, &format!("{}/servers/{}", pointer, i));
+        }
+        self.parameters(&mut item.parameters, pointer);
+        for (method, operation) in item.iter_mut() {
+            self.operation(operation, &format!("{}/{}", pointer, method));
+        }
+    }
+
+    fn parameters(&mut self, parameters: &mut [ReferenceOr<Parameter>], pointer: &str) {
+        for (i, parameter) in parameters.iter_mut().enumerate() {
+            let pointer = format!("{}/parameters/{}", pointer, i);
+            if let Some(parameter) = self.reference_or(parameter, &pointer) {
+                self.parameter(parameter, &pointer);
+            }
+        }
+    }
+
+    fn operation(&mut self, operation: &mut Operation, pointer: &str) {
+        self.description(&mut operation.description, pointer);
+        self.extensions(&mut operation.extensions, pointer);
+        self.external_docs(&mut operation.external_docs, pointer);
+        self.parameters(&mut operation.parameters, pointer);
+        if let Some(body) = &mut operation.request_body {
+            let pointer = format!("{}/requestBody", pointer);
+            if let Some(body) = self.reference_or(body, &pointer) {
+                self.request_body(body, &pointer);
+            }
+        }
+
+        let responses = &mut operation.responses;
+        let responses_pointer = format!("{}/responses", pointer);
+        self.extensions(&mut responses.extensions, &responses_pointer);
+        let statuses = responses
+            .default
+            .iter_mut()
+            .map(|response| ("default".to_owned(), response))
+            .chain(
+                responses
+                    .responses
+                    .iter_mut()
+                    .map(|(status, response)| (status.to_string(), response)),
+            );
+        for (status, response) in statuses {
+            let pointer = format!("{}/{}", responses_pointer, status);
+            if let Some(response) = self.reference_or(response, &pointer) {
+                self.response(response, &pointer);
+            }
+        }
+
+        for (name, callback) in operation.callbacks.iter_mut() {
+            let pointer = format!("{}/callbacks/{}", pointer, escape_pointer_token(name));
+            self.callback(callback, &pointer);
+        }
+        for (i, server) in operation.servers.iter_mut().enumerate() {
+            self.server(server, &format!("{}/servers/{}", pointer, i));
+        }
+    }
+
+    fn callback(&mut self, callback: &mut Callback, pointer: &str) {
+        for (expression, item) in callback.iter_mut() {
+            let pointer = format!("{}/{}", pointer, escape_pointer_token(expression));
+            self.path_item(item, &pointer);
+        }
+    }
+
+    fn parameter(&mut self, parameter: &mut Parameter, pointer: &str) {
+        let data = parameter.parameter_data_mut();
+        self.description(&mut data.description, pointer);
+        self.extensions(&mut data.extensions, pointer);
+        self.schema_or_content(&mut data.format, pointer);
+        self.examples(&mut data.examples, pointer);
+    }
+
+    fn header(&mut self, header: &mut Header, pointer: &str) {
+        self.description(&mut header.description, pointer);
+        self.extensions(&mut header.extensions, pointer);
+        self.schema_or_content(&mut header.format, pointer);
+        self.examples(&mut header.examples, pointer);
+    }
+
+    fn headers(&mut self, headers: &mut IndexMap<String, ReferenceOr<Header>>, pointer: &str) {
+        for (name, header) in headers.iter_mut() {
+            let pointer = format!("{}/headers/{}", pointer, escape_pointer_token(name));
+            if let Some(header) = self.reference_or(header, &pointer) {
+                self.header(header, &pointer);
+            }
+        }
+    }
+
+    fn request_body(&mut self, body: &mut RequestBody, pointer: &str) {
+        self.description(&mut body.description, pointer);
+        self.extensions(&mut body.extensions, pointer);
+        self.content(&mut body.content, pointer);
+    }
+
+    fn response(&mut self, response: &mut Response, pointer: &str) {
+        self.visitor.visit_description_mut(
+            &format!("{}/description", pointer),
+            &mut response.description,
+        );
+        self.extensions(&mut response.extensions, pointer);
+        self.headers(&mut response.headers, pointer);
+        self.content(&mut response.content, pointer);
+        for (name, link) in response.links.iter_mut() {
+            let pointer = format!("{}/links/{}", pointer, escape_pointer_token(name));
+            if let Some(link) = self.reference_or(link, &pointer) {
+                self.link(link, &pointer);
+            }
+        }
+    }
+
+    fn link(&mut self, link: &mut Link, pointer: &str) {
+        self.description(&mut link.description, pointer);
+        self.extensions(&mut link.extensions, pointer);
+        if let Some(server) = &mut link.server {
+            self.server(server, &format!("{}/server", pointer));
+        }
+    }
+
+    fn example(&mut self, example: &mut Example, pointer: &str) {
+        self.description(&mut example.description, pointer);
+        self.extensions(&mut example.extensions, pointer);
+    }
+
+    fn examples(&mut self, examples: &mut IndexMap<String, ReferenceOr<Example>>, pointer: &str) {
+        for (name, example) in examples.iter_mut() {
+            let pointer = format!("{}/examples/{}", pointer, escape_pointer_token(name));
+            if let Some(example) = self.reference_or(example, &pointer) {
+                self.example(example, &pointer);
+            }
+        }
+    }
+
+    fn security_scheme(&mut self, scheme: &mut SecurityScheme, pointer: &str) {
+        let (description, extensions) = match scheme {
+            SecurityScheme::APIKey {
+                description,
+                extensions,
+                ..
+            }
+            | SecurityScheme::HTTP {
+                description,
+                extensions,
+                ..
+            }
+            | SecurityScheme::OAuth2 {
+                description,
+                extensions,
+                ..
+            }
+            | SecurityScheme::OpenIDConnect {
+                description,
+                extensions,
+                ..
+            } => (description, extensions),
+        };
+        self.description(description, pointer);
+        self.extensions(extensions, pointer);
+
+        if let SecurityScheme::OAuth2 { flows, .. } = scheme {
+            let pointer = format!("{}/flows", pointer);
+            self.extensions(&mut flows.extensions, &pointer);
+            if let Some(flow) = &mut flows.implicit {
+                self.extensions(&mut flow.extensions, &format!("{}/implicit", pointer));
+            }
+            if let Some(flow) = &mut flows.password {
+                self.extensions(&mut flow.extensions, &format!("{}/password", pointer));
+            }
+            if let Some(flow) = &mut flows.client_credentials {
+                let pointer = format!("{}/clientCredentials", pointer);
+                self.extensions(&mut flow.extensions, &pointer);
+            }
+            if let Some(flow) = &mut flows.authorization_code {
+                let pointer = format!("{}/authorizationCode", pointer);
+                self.extensions(&mut flow.extensions, &pointer);
+            }
+        }
+    }
+
+    fn schema_or_content(&mut self, format: &mut ParameterSchemaOrContent, pointer: &str) {
+        match format {
+            ParameterSchemaOrContent::Schema(schema) => {
+                let pointer = format!("{}/schema", pointer);
+                if let Some(schema) = self.reference_or(schema, &pointer) {
+                    self.schema(schema, &pointer);
+                }
+            }
+            ParameterSchemaOrContent::Content(content) => self.content(content, pointer),
+        }
+    }
+
+    fn content(&mut self, content: &mut IndexMap<String, MediaType>, pointer: &str) {
+        for (media_type, value) in content.iter_mut() {
+            let pointer = format!("{}/content/{}", pointer, escape_pointer_token(media_type));
+            self.extensions(&mut value.extensions, &pointer);
+            if let Some(schema) = &mut value.schema {
+                let pointer = format!("{}/schema", pointer);
+                if let Some(schema) = self.reference_or(schema, &pointer) {
+                    self.schema(schema, &pointer);
+                }
+            }
+            self.examples(&mut value.examples, &pointer);
+            for (property, encoding) in value.encoding.iter_mut() {
+                let pointer = format!("{}/encoding/{}", pointer, escape_pointer_token(property));
+                self.extensions(&mut encoding.extensions, &pointer);
+                self.headers(&mut encoding.headers, &pointer);
+            }
+        }
+    }
+
+    fn schema(&mut self, schema: &mut Schema, pointer: &str) {
+        self.visitor.visit_schema_mut(pointer, schema);
+
+        let data = &mut schema.schema_data;
+        self.description(&mut data.description, pointer);
+        self.extensions(&mut data.extensions, pointer);
+        self.external_docs(&mut data.external_docs, pointer);
+        if let Some(discriminator) = &mut data.discriminator {
+            let pointer = format!("{}/discriminator", pointer);
+            self.extensions(&mut discriminator.extensions, &pointer);
+        }
+
+        for (key, slot) in schema.subschemas_mut() {
+            let pointer = format!("{}{}", pointer, key.pointer());
+            let subschema = match slot {
+                SchemaSlot::Boxed(slot) => self.reference_or(slot, &pointer).map(|s| &mut **s),
+                SchemaSlot::Plain(slot) => self.reference_or(slot, &pointer),
+            };
+            if let Some(subschema) = subschema {
+                self.schema(subschema, &pointer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Default)]
+    struct Rewrite {
+        schemas: Vec<String>,
+        descriptions: Vec<String>,
+    }
+
+    impl VisitorMut for Rewrite {
+        fn visit_schema_mut(&mut self, pointer: &str, schema: &mut Schema) {
+            self.schemas.push(pointer.to_owned());
+            schema.schema_data.nullable = true;
+        }
+
+        fn visit_reference_mut(&mut self, _: &str, reference: &mut String) {
+            *reference = reference.replace("#/components/", "common.yaml#/components/");
+        }
+
+        fn visit_description_mut(&mut self, pointer: &str, description: &mut String) {
+            self.descriptions.push(pointer.to_owned());
+            description.make_ascii_uppercase();
+        }
+
+        fn visit_extensions_mut(&mut self, _: &str, extensions: &mut Extensions) {
+            extensions.retain(|key, _| !key.starts_with("x-internal"));
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        let mut api: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1", "x-internal-id": 7, "x-public": 1 },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "description": "list pets",
+                        "parameters": [ { "$ref": "#/components/parameters/Limit" } ],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "x-internal-note": "",
+                                "content": { "application/json": { "schema": {
+                                    "type": "array",
+                                    "items": { "$ref": "#/components/schemas/Pet" }
+                                } } }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "description": "a pet",
+                        "properties": { "name": { "type": "string", "x-internal": true } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut rewrite = Rewrite::default();
+        api.transform(&mut rewrite);
+
+        assert_eq!(
+            rewrite.schemas,
+            [
+                "/paths/~1pets/get/responses/200/content/application~1json/schema",
+                "/components/schemas/Pet",
+                "/components/schemas/Pet/properties/name",
+            ]
+        );
+        assert_eq!(
+            rewrite.descriptions,
+            [
+                "/paths/~1pets/get/description",
+                "/paths/~1pets/get/responses/200/description",
+                "/components/schemas/Pet/description",
+            ]
+        );
+
+        let value = serde_json::to_value(&api).unwrap();
+        assert_eq!(
+            value["info"],
+            json!({ "title": "Pets", "version": "1", "x-public": 1 })
+        );
+        let get = &value["paths"]["/pets"]["get"];
+        assert_eq!(
+            get["parameters"][0]["$ref"],
+            "common.yaml#/components/parameters/Limit"
+        );
+        assert_eq!(get["description"], "LIST PETS");
+        let response = &get["responses"]["200"];
+        assert!(response.get("x-internal-note").is_none());
+        let schema = &response["content"]["application/json"]["schema"];
+        assert_eq!(schema["nullable"], true);
+        assert_eq!(
+            schema["items"]["$ref"],
+            "common.yaml#/components/schemas/Pet"
+        );
+        assert_eq!(
+            value["components"]["schemas"]["Pet"]["properties"]["name"],
+            json!({ "type": "string", "nullable": true })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// The fields of a raw document that are lost when it is deserialized,
+/// keyed by the JSON Pointer of the object that held them.
+///
+/// These are fields the specification does not define, fields that are
+/// ignored such as those next to a `$ref`, and values the typed model cannot
+/// represent exactly.
+pub type UnknownFields = IndexMap<String, IndexMap<String, Value>>;
+
+impl OpenAPI {
+    /// Deserializes a document, also returning every field that would not
+    /// survive serializing it again, so that they can be restored with
+    /// [OpenAPI::to_value_preserving].
+    ///
+    /// Fields are found by comparing `value` with the serialized document,
+    /// so this costs an extra serialization.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let raw = serde_json::json!({
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1", "audience": "internal" },
+    ///     "paths": {}
+    /// });
+    /// let (api, unknown) = OpenAPI::from_value_preserving(raw.clone()).unwrap();
+    /// assert_eq!(unknown["/info"]["audience"], "internal");
+    /// assert_eq!(api.to_value_preserving(&unknown).unwrap(), raw);
+    /// ```
+    pub fn from_value_preserving(
+        value: Value,
+    ) -> Result<(OpenAPI, UnknownFields), serde_json::Error> {
+        let document = OpenAPI::deserialize(&value)?;
+        let unknown = unknown_fields(&value, &document)?;
+        Ok((document, unknown))
+    }
+
+    /// Serializes the document, adding back the `unknown` fields returned
+    /// by [OpenAPI::from_value_preserving].
+    ///
+    /// Fields are only added to objects that still exist at their pointer,
+    /// and never replace a field the document serializes itself, so they
+    /// may be lost or misplaced if the document was restructured in between.
+    pub fn to_value_preserving(&self, unknown: &UnknownFields) -> Result<Value, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        for (pointer, fields) in unknown {
+            if let Some(Value::Object(object)) = value.pointer_mut(pointer) {
+                for (key, field) in fields {
+                    if !object.contains_key(key) {
+                        object.insert(key.clone(), field.clone());
+                    }
+                }
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Returns the fields of `raw` that are lost in `document`, which was
+/// deserialized from it.
+pub(crate) fn unknown_fields(
+    raw: &Value,
+    document: &OpenAPI,
+) -> Result<UnknownFields, serde_json::Error> {
+    let serialized = serde_json::to_value(document)?;
+    let mut unknown = UnknownFields::new();
+    collect(raw, &serialized, &mut String::new(), &mut unknown);
+    Ok(unknown)
+}
+
+/// Records the fields of `raw` that are missing in `serialized`.
+fn collect(raw: &Value, serialized: &Value, pointer: &mut String, unknown: &mut UnknownFields) {
+    match (raw, serialized) {
+        (Value::Object(raw), Value::Object(serialized)) => {
+            let missing = missing_fields(raw, serialized);
+            if !missing.is_empty() {
+                unknown.insert(pointer.clone(), missing);
+            }
+            for (key, child) in raw {
+                if let Some(serialized) = serialized.get(key) {
+                    let length = pointer.len();
+                    pointer.push('/');
+                    pointer.push_str(&escape_pointer_token(key));
+                    collect(child, serialized, pointer, unknown);
+                    pointer.truncate(length);
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(serialized)) if raw.len() == serialized.len() => {
+            for (i, (child, serialized)) in raw.iter().zip(serialized).enumerate() {
+                let length = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&i.to_string());
+                collect(child, serialized, pointer, unknown);
+                pointer.truncate(length);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn missing_fields(
+    raw: &Map<String, Value>,
+    serialized: &Map<String, Value>,
+) -> IndexMap<String, Value> {
+    raw.iter()
+        .filter(|(key, _)| !serialized.contains_key(*key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip() {
+        let raw = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            { "$ref": "#/components/parameters/Limit", "description": "Page size" }
+                        ],
+                        "responses": { "200": { "description": "Pets", "name: count": 3 } }
+                    }
+                }
+            },
+            "components": {
+                "schemas": { "Pet": { "type": "object", "additionalItems": false } }
+            }
+        });
+
+        let (mut api, unknown) = OpenAPI::from_value_preserving(raw.clone()).unwrap();
+        assert_eq!(
+            unknown.keys().collect::<Vec<_>>(),
+            [
+                "/components/schemas/Pet",
+                "/paths/~1pets/get/parameters/0",
+                "/paths/~1pets/get/responses/200",
+            ]
+        );
+        assert_ne!(serde_json::to_value(&api).unwrap(), raw);
+        assert_eq!(api.to_value_preserving(&unknown).unwrap(), raw);
+
+        api.components = None;
+        let value = api.to_value_preserving(&unknown).unwrap();
+        assert!(value.get("components").is_none());
+        assert_eq!(
+            value["paths"]["/pets"]["get"]["responses"]["200"]["name: count"],
+            3
+        );
+    }
+}
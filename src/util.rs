@@ -1,28 +1,30 @@
 use std::hash::Hash;
 use std::marker::PhantomData;
 
+use crate::Extensions;
 use indexmap::IndexMap;
 use serde::{
     de::{IgnoredAny, Visitor},
     Deserialize, Deserializer,
 };
 
+/// The fields of a path item holding operations, in the order of the
+/// specification.
+pub(crate) const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
 #[allow(clippy::trivially_copy_pass_by_ref)] // needs to match signature for use in serde attribute
 #[inline]
 pub const fn is_false(v: &bool) -> bool {
     !(*v)
 }
 
-pub(crate) fn deserialize_extensions<'de, D>(
-    deserializer: D,
-) -> Result<IndexMap<String, serde_json::Value>, D::Error>
+pub(crate) fn deserialize_extensions<'de, D>(deserializer: D) -> Result<Extensions, D::Error>
 where
     D: Deserializer<'de>,
 {
-    deserializer.deserialize_map(PredicateVisitor(
-        |key: &String| key.starts_with("x-"),
-        PhantomData,
-    ))
+    Extensions::deserialize(deserializer)
 }
 
 /// Used to deserialize IndexMap<K, V> that are flattened within other structs.
@@ -63,3 +65,119 @@ where
         Ok(ret)
     }
 }
+
+/// Decodes `%XX` escapes. Malformed escapes are kept verbatim, as is the whole
+/// input if the decoded bytes are not valid UTF-8.
+pub(crate) fn percent_decode(input: &str) -> String {
+    if !input.contains('%') {
+        return input.to_owned();
+    }
+
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            decoded.push((hex_value(bytes[i + 1]) << 4) | hex_value(bytes[i + 2]));
+            i += 3;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8(decoded).unwrap_or_else(|_| input.to_owned())
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+/// Escapes a single JSON Pointer reference token as described in RFC 6901.
+pub(crate) fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Reverses [escape_pointer_token].
+pub(crate) fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters, and also
+/// reserved characters if `allow_reserved` is set.
+pub(crate) fn percent_encode(input: &str, allow_reserved: bool) -> String {
+    const RESERVED: &[u8] = b":/?#[]@!$&'()*+,;=";
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        if byte.is_ascii_alphanumeric()
+            || b"-._~".contains(&byte)
+            || (allow_reserved && RESERVED.contains(&byte))
+        {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Writes `value` as JSON with object keys in sorted order and no whitespace.
+pub(crate) fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, child)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(child, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(array) => {
+            out.push('[');
+            for (i, child) in array.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(child, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::percent_decode;
+
+    #[test]
+    fn percent_decode_escapes() {
+        assert_eq!(percent_decode("a%20b%2Fc%2f"), "a b/c/");
+        assert_eq!(percent_decode("%E2%82%AC"), "\u{20ac}");
+    }
+
+    #[test]
+    fn percent_decode_keeps_malformed_escapes() {
+        // A sign is not a hex digit, even though from_str_radix accepts it.
+        assert_eq!(percent_decode("%+1"), "%+1");
+        assert_eq!(percent_decode("%-1"), "%-1");
+        assert_eq!(percent_decode("%zz"), "%zz");
+        assert_eq!(percent_decode("a%1"), "a%1");
+        assert_eq!(percent_decode("a%"), "a%");
+        assert_eq!(percent_decode("%FF"), "%FF");
+    }
+}
@@ -0,0 +1,1164 @@
+use std::fmt;
+
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// The rule broken by a [Violation].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViolationKind {
+    /// A required field is empty.
+    MissingField,
+    /// A key of [Paths] is not a valid path template.
+    InvalidPath,
+    /// A parameter of the path template is not declared by an operation.
+    UndeclaredPathParameter,
+    /// A path parameter does not appear in the path template.
+    UnusedPathParameter,
+    /// A path parameter is not marked as required.
+    OptionalPathParameter,
+    /// An operation has no responses.
+    NoResponses,
+    /// The name of a component does not match `^[a-zA-Z0-9.\-_]+$`.
+    InvalidComponentName,
+    /// Two operations share an `operationId`.
+    DuplicateOperationId,
+    /// An operation has no `operationId`, as reported by
+    /// [OpenAPI::validate_operation_ids].
+    MissingOperationId,
+    /// An `operationId` does not follow the naming convention checked by
+    /// [OpenAPI::validate_operation_ids].
+    InvalidOperationId,
+    /// A security requirement names a scheme that is not defined in
+    /// [Components::security_schemes].
+    UndefinedSecurityScheme,
+    /// A security requirement asks for an OAuth2 scope that none of the
+    /// flows of the scheme defines.
+    UndefinedScope,
+    /// A security requirement lists scopes for a scheme other than OAuth2 or
+    /// OpenID Connect, for which the list must be empty.
+    UnexpectedScopes,
+    /// Two fields that exclude each other are both set, such as `value` and
+    /// `externalValue` of an [Example].
+    MutuallyExclusiveFields,
+    /// A server URL is not a valid template, or uses a variable that the
+    /// server does not define.
+    InvalidServerUrl,
+    /// The default of a server variable is not one of its enumerated values.
+    InvalidServerVariable,
+    /// A key of a [Callback] is not a valid [CallbackUrl].
+    InvalidCallbackExpression,
+    /// A key of a `content` map, or the `contentType` of an [Encoding], is
+    /// not a valid media type, or looks like a misspelling of a common one.
+    InvalidMediaType,
+    /// A response is declared for a status code outside 100 to 599, or for
+    /// a range other than `1XX` to `5XX`.
+    InvalidStatusCode,
+    /// A URL is not syntactically valid, as reported by
+    /// [OpenAPI::validate_urls].
+    InvalidUrl,
+    /// A value does not match its schema, as reported by
+    /// [Schema::validate_value].
+    SchemaMismatch,
+    /// No operation serves a request, as reported by
+    /// [OpenAPI::validate_request].
+    NoMatchingOperation,
+    /// A reference needed to validate a message cannot be resolved.
+    UnresolvedReference,
+    /// A required parameter, header or body is absent from a message.
+    MissingValue,
+    /// A parameter, header or body cannot be parsed.
+    UnparsableValue,
+    /// A body has a media type that its operation does not accept.
+    UnsupportedMediaType,
+    /// A response has a status code that its operation does not declare, as
+    /// reported by [Operation::validate_response].
+    UndeclaredStatus,
+}
+
+/// A way in which a document does not conform to the specification, as
+/// returned by [OpenAPI::validate], or in which a value or message does not
+/// conform to a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    /// The JSON Pointer of the offending value, within the document or
+    /// the value being checked.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.pointer, self.message)
+    }
+}
+
+impl OpenAPI {
+    /// Checks the rules of the specification that deserialization does not
+    /// enforce, returning every violation found in document order.
+    ///
+    /// Path items that are references are not checked, and parameters that
+    /// cannot be resolved are ignored.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets/{id}": { "get": {
+    ///         "responses": { "200": { "description": "A pet" } }
+    ///     } } }
+    /// }"#).unwrap();
+    /// let violations = api.validate();
+    /// assert_eq!(violations[0].kind, ViolationKind::UndeclaredPathParameter);
+    /// assert_eq!(violations[0].pointer, "/paths/~1pets~1{id}/get");
+    /// ```
+    pub fn validate(&self) -> Vec<Violation> {
+        self.validate_with(&ValidateOptions::default())
+    }
+
+    /// Like [OpenAPI::validate], checking only the optional rules enabled in
+    /// `options`.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "get": {
+    ///         "responses": { "200": { "description": "Pets", "content": { "application/jsoon": {} } } }
+    ///     } } }
+    /// }"#).unwrap();
+    /// assert!(api.validate().is_empty());
+    ///
+    /// let options = ValidateOptions::new().media_type_suggestions(true);
+    /// let violations = api.validate_with(&options);
+    /// assert_eq!(violations[0].kind, ViolationKind::InvalidMediaType);
+    /// assert_eq!(
+    ///     violations[0].message,
+    ///     "unknown media type `application/jsoon`, did you mean `application/json`?"
+    /// );
+    /// ```
+    pub fn validate_with(&self, options: &ValidateOptions) -> Vec<Violation> {
+        let mut validator = Validator {
+            document: self,
+            options,
+            violations: Vec::new(),
+            operation_ids: IndexMap::new(),
+        };
+        validator.document();
+        validator.violations
+    }
+}
+
+/// The optional rules checked by [OpenAPI::validate_with]. By default, media
+/// types are checked to be valid, without suggestions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidateOptions {
+    media_types: bool,
+    media_type_suggestions: bool,
+}
+
+impl Default for ValidateOptions {
+    fn default() -> Self {
+        ValidateOptions {
+            media_types: true,
+            media_type_suggestions: false,
+        }
+    }
+}
+
+impl ValidateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the keys of `content` maps and the `contentType`s of
+    /// encodings are checked to be valid media types or ranges.
+    pub fn media_types(mut self, media_types: bool) -> Self {
+        self.media_types = media_types;
+        self
+    }
+
+    /// Sets whether valid media types one letter away from a common media
+    /// type, such as `application/jsoon`, are reported as likely typos.
+    /// Registered types such as `text/css` and vendor or `x-` types are not
+    /// reported.
+    pub fn media_type_suggestions(mut self, media_type_suggestions: bool) -> Self {
+        self.media_type_suggestions = media_type_suggestions;
+        self
+    }
+}
+
+impl OpenAPI {
+    /// Checks that the URLs of the document are syntactically valid URI
+    /// references: server URLs, with their variables replaced by their
+    /// defaults, external documentation, contact, license and terms of
+    /// service URLs, and the URLs of security schemes.
+    ///
+    /// This is not part of [OpenAPI::validate], as documents often carry
+    /// placeholder URLs that are valid for their purpose.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1", "license": { "name": "MIT", "url": "https://example.com/MIT license" } },
+    ///     "servers": [ { "url": "{scheme}://pets.example.com", "variables": { "scheme": { "default": "https" } } } ],
+    ///     "paths": {}
+    /// }"#).unwrap();
+    /// let violations = api.validate_urls();
+    /// assert_eq!(violations.len(), 1);
+    /// assert_eq!(violations[0].pointer, "/info/license/url");
+    /// ```
+    pub fn validate_urls(&self) -> Vec<Violation> {
+        let mut urls: Vec<(String, String)> = Vec::new();
+        let mut servers = |servers: &[Server], pointer: &str| {
+            for (i, server) in servers.iter().enumerate() {
+                urls.extend(
+                    server_url(server).map(|url| (format!("{}/servers/{}/url", pointer, i), url)),
+                );
+            }
+        };
+        servers(&self.servers, "");
+        for (path, item) in self.paths.iter() {
+            if let ReferenceOr::Item(item) = item {
+                let pointer = format!("/paths/{}", escape_pointer_token(path));
+                servers(&item.servers, &pointer);
+                for (method, operation) in item.iter() {
+                    servers(&operation.servers, &format!("{}/{}", pointer, method));
+                }
+            }
+        }
+
+        let info = &self.info;
+        let mut optional = |pointer: String, url: Option<&String>| {
+            urls.extend(url.map(|url| (pointer, url.clone())));
+        };
+        optional(
+            "/info/termsOfService".to_owned(),
+            info.terms_of_service.as_ref(),
+        );
+        optional(
+            "/info/contact/url".to_owned(),
+            info.contact.as_ref().and_then(|c| c.url.as_ref()),
+        );
+        optional(
+            "/info/license/url".to_owned(),
+            info.license.as_ref().and_then(|l| l.url.as_ref()),
+        );
+        optional(
+            "/externalDocs/url".to_owned(),
+            self.external_docs.as_ref().map(|d| &d.url),
+        );
+        for (i, tag) in self.tags.iter().enumerate() {
+            let url = tag.external_docs.as_ref().map(|d| &d.url);
+            optional(format!("/tags/{}/externalDocs/url", i), url);
+        }
+        for (path, method, operation) in self.operations() {
+            let pointer = format!(
+                "/paths/{}/{}/externalDocs/url",
+                escape_pointer_token(path),
+                method
+            );
+            optional(pointer, operation.external_docs.as_ref().map(|d| &d.url));
+        }
+        for (pointer, schema) in self.all_schemas() {
+            let url = schema.schema_data.external_docs.as_ref().map(|d| &d.url);
+            optional(format!("{}/externalDocs/url", pointer), url);
+        }
+
+        for (name, scheme) in self.components.iter().flat_map(|c| &c.security_schemes) {
+            let pointer = format!("/components/securitySchemes/{}", escape_pointer_token(name));
+            match scheme {
+                ReferenceOr::Item(SecurityScheme::OpenIDConnect {
+                    open_id_connect_url,
+                    ..
+                }) => {
+                    optional(
+                        format!("{}/openIdConnectUrl", pointer),
+                        Some(open_id_connect_url),
+                    );
+                }
+                ReferenceOr::Item(SecurityScheme::OAuth2 { flows, .. }) => {
+                    let mut flow = |flow: &str,
+                                    authorization: Option<&String>,
+                                    token: Option<&String>,
+                                    refresh: Option<&String>| {
+                        let pointer = format!("{}/flows/{}", pointer, flow);
+                        optional(format!("{}/authorizationUrl", pointer), authorization);
+                        optional(format!("{}/tokenUrl", pointer), token);
+                        optional(format!("{}/refreshUrl", pointer), refresh);
+                    };
+                    if let Some(f) = &flows.implicit {
+                        flow(
+                            "implicit",
+                            Some(&f.authorization_url),
+                            None,
+                            f.refresh_url.as_ref(),
+                        );
+                    }
+                    if let Some(f) = &flows.password {
+                        flow("password", None, Some(&f.token_url), f.refresh_url.as_ref());
+                    }
+                    if let Some(f) = &flows.client_credentials {
+                        flow(
+                            "clientCredentials",
+                            None,
+                            Some(&f.token_url),
+                            f.refresh_url.as_ref(),
+                        );
+                    }
+                    if let Some(f) = &flows.authorization_code {
+                        flow(
+                            "authorizationCode",
+                            Some(&f.authorization_url),
+                            Some(&f.token_url),
+                            f.refresh_url.as_ref(),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        urls.into_iter()
+            .filter_map(|(pointer, url)| {
+                let reason = check_url(&url).err()?;
+                Some(Violation {
+                    kind: ViolationKind::InvalidUrl,
+                    pointer,
+                    message: format!("invalid URL `{}`: {}", url, reason),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Renders the URL of `server` with each variable replaced by its default,
+/// or `None` if it is not a valid template, which [OpenAPI::validate]
+/// reports.
+pub(crate) fn server_url(server: &Server) -> Option<String> {
+    let template = server.url_template().ok()?;
+    let values = template
+        .variables()
+        .map(|name| {
+            let variable = server.variables.as_ref().and_then(|vars| vars.get(name));
+            let value = variable.map_or("x", |variable| variable.default.as_str());
+            (name.to_owned(), value.to_owned())
+        })
+        .collect();
+    template.render(&values).ok()
+}
+
+/// Checks that `url` is a URI reference as defined by RFC 3986, and that
+/// `http` and `https` URLs have a host.
+fn check_url(url: &str) -> Result<(), &'static str> {
+    if url.is_empty() {
+        return Err("URLs must not be empty");
+    }
+    let bytes = url.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'%' => {
+                let escape = bytes.get(i + 1..i + 3);
+                if !escape.is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+                    return Err("`%` must be followed by two hexadecimal digits");
+                }
+            }
+            b if b.is_ascii_alphanumeric() || b"-._~:/?#[]@!$&'()*+,;=".contains(&b) => {}
+            _ => return Err("characters such as spaces must be percent-encoded"),
+        }
+    }
+
+    if let Some(i) = url.find([':', '/', '?', '#']).filter(|&i| bytes[i] == b':') {
+        let scheme = &url[..i];
+        let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        if !valid {
+            return Err("invalid scheme");
+        }
+        if scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https") {
+            let host = url[i + 1..]
+                .strip_prefix("//")
+                .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+            if host.is_none_or(|host| host.rsplit('@').next().unwrap_or_default().is_empty()) {
+                return Err("HTTP URLs must have a host");
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Validator<'a> {
+    document: &'a OpenAPI,
+    options: &'a ValidateOptions,
+    violations: Vec<Violation>,
+    /// The pointer of the first operation using each `operationId`.
+    operation_ids: IndexMap<&'a str, String>,
+}
+
+impl<'a> Validator<'a> {
+    fn report(&mut self, kind: ViolationKind, pointer: String, message: String) {
+        self.violations.push(Violation {
+            kind,
+            pointer,
+            message,
+        });
+    }
+
+    fn document(&mut self) {
+        let document = self.document;
+        for (pointer, value) in [
+            ("/openapi", &document.openapi),
+            ("/info/title", &document.info.title),
+            ("/info/version", &document.info.version),
+        ] {
+            if value.trim().is_empty() {
+                self.report(
+                    ViolationKind::MissingField,
+                    pointer.to_owned(),
+                    format!("`{}` must not be empty", &pointer[1..]),
+                );
+            }
+        }
+
+        self.servers(&document.servers, "");
+        for (i, requirement) in document.security.iter().flatten().enumerate() {
+            self.security_requirement(requirement, format!("/security/{}", i));
+        }
+
+        for (path, item) in document.paths.iter() {
+            if let ReferenceOr::Item(item) = item {
+                self.path_item(
+                    path,
+                    item,
+                    &format!("/paths/{}", escape_pointer_token(path)),
+                );
+            }
+        }
+
+        if let Some(components) = &document.components {
+            for kind in ComponentKind::ALL {
+                for name in component_names(components, kind) {
+                    let valid = !name.is_empty()
+                        && name
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+                    if !valid {
+                        self.report(
+                            ViolationKind::InvalidComponentName,
+                            format!("/components/{}/{}", kind, escape_pointer_token(name)),
+                            format!(
+                                "component name `{}` may only contain letters, digits, `.`, `-` and `_`",
+                                name
+                            ),
+                        );
+                    }
+                }
+            }
+            self.components(components);
+        }
+    }
+
+    fn path_item(&mut self, path: &str, item: &'a PathItem, pointer: &str) {
+        let template = match path.parse::<PathTemplate>() {
+            Ok(template) => template,
+            Err(err) => {
+                self.report(
+                    ViolationKind::InvalidPath,
+                    pointer.to_owned(),
+                    format!("invalid path template `{}`: {}", path, err),
+                );
+                return;
+            }
+        };
+        let template_parameters = template.parameters().collect::<Vec<_>>();
+        self.servers(&item.servers, pointer);
+
+        let mut shared = Vec::new();
+        for (i, parameter) in item.parameters.iter().enumerate() {
+            let pointer = format!("{}/parameters/{}", pointer, i);
+            if let ReferenceOr::Item(parameter) = parameter {
+                self.parameter(parameter, &pointer);
+            }
+            if let Some(parameter) = self.path_parameter(parameter, &template_parameters, &pointer)
+            {
+                shared.push(parameter);
+            }
+        }
+
+        for (method, operation) in item.iter() {
+            let pointer = format!("{}/{}", pointer, method);
+            let mut declared = shared.clone();
+            for (i, parameter) in operation.parameters.iter().enumerate() {
+                let pointer = format!("{}/parameters/{}", pointer, i);
+                if let ReferenceOr::Item(parameter) = parameter {
+                    self.parameter(parameter, &pointer);
+                }
+                declared.extend(self.path_parameter(parameter, &template_parameters, &pointer));
+            }
+            for name in &template_parameters {
+                if !declared.contains(name) {
+                    self.report(
+                        ViolationKind::UndeclaredPathParameter,
+                        pointer.clone(),
+                        format!("path parameter `{}` is not declared", name),
+                    );
+                }
+            }
+            self.operation(operation, pointer);
+        }
+    }
+
+    /// Checks a parameter, returning its name if it is a path parameter.
+    fn path_parameter(
+        &mut self,
+        parameter: &'a ReferenceOr<Parameter>,
+        template_parameters: &[&str],
+        pointer: &str,
+    ) -> Option<&'a str> {
+        let parameter = match (parameter, &self.document.components) {
+            (ReferenceOr::Item(parameter), _) => parameter,
+            (ReferenceOr::Reference { .. }, Some(components)) => {
+                parameter.resolve(components).ok()?
+            }
+            (ReferenceOr::Reference { .. }, None) => return None,
+        };
+        let data = match parameter {
+            Parameter::Path { parameter_data, .. } => parameter_data,
+            _ => return None,
+        };
+        if !template_parameters.contains(&data.name.as_str()) {
+            self.report(
+                ViolationKind::UnusedPathParameter,
+                pointer.to_owned(),
+                format!("path parameter `{}` does not appear in the path", data.name),
+            );
+        }
+        if !data.required {
+            self.report(
+                ViolationKind::OptionalPathParameter,
+                pointer.to_owned(),
+                format!("path parameter `{}` must be required", data.name),
+            );
+        }
+        Some(&data.name)
+    }
+
+    fn operation(&mut self, operation: &'a Operation, pointer: String) {
+        if operation.responses.default.is_none() && operation.responses.responses.is_empty() {
+            self.report(
+                ViolationKind::NoResponses,
+                format!("{}/responses", pointer),
+                "operations must declare at least one response".to_owned(),
+            );
+        }
+        self.servers(&operation.servers, &pointer);
+        if let Some(ReferenceOr::Item(body)) = &operation.request_body {
+            self.content(&body.content, &format!("{}/requestBody", pointer));
+        }
+        let responses = &operation.responses;
+        for status in responses
+            .responses
+            .keys()
+            .filter(|status| !status.is_valid())
+        {
+            self.report(
+                ViolationKind::InvalidStatusCode,
+                format!("{}/responses/{}", pointer, status),
+                format!("`{}` is not a valid status code", status),
+            );
+        }
+        let statuses = responses.responses.iter().map(|(s, r)| (s.to_string(), r));
+        for (status, response) in responses
+            .default
+            .iter()
+            .map(|r| ("default".to_owned(), r))
+            .chain(statuses)
+        {
+            if let ReferenceOr::Item(response) = response {
+                let pointer = format!("{}/responses/{}", pointer, status);
+                self.response(response, &pointer);
+            }
+        }
+        for (i, requirement) in operation.security.iter().flatten().enumerate() {
+            self.security_requirement(requirement, format!("{}/security/{}", pointer, i));
+        }
+        for (name, callback) in &operation.callbacks {
+            for (expression, item) in callback {
+                let pointer = format!(
+                    "{}/callbacks/{}/{}",
+                    pointer,
+                    escape_pointer_token(name),
+                    escape_pointer_token(expression)
+                );
+                if let Err(err) = expression.parse::<CallbackUrl>() {
+                    self.report(
+                        ViolationKind::InvalidCallbackExpression,
+                        pointer.clone(),
+                        format!("invalid callback expression `{}`: {}", expression, err),
+                    );
+                }
+                for (method, operation) in item.iter() {
+                    self.operation(operation, format!("{}/{}", pointer, method));
+                }
+            }
+        }
+
+        if let Some(id) = &operation.operation_id {
+            match self.operation_ids.get(id.as_str()) {
+                Some(first) => {
+                    let message = format!("operationId `{}` is already used by {}", id, first);
+                    self.report(
+                        ViolationKind::DuplicateOperationId,
+                        format!("{}/operationId", pointer),
+                        message,
+                    );
+                }
+                None => {
+                    self.operation_ids.insert(id, pointer);
+                }
+            }
+        }
+    }
+
+    fn servers(&mut self, servers: &[Server], pointer: &str) {
+        for (i, server) in servers.iter().enumerate() {
+            let pointer = format!("{}/servers/{}", pointer, i);
+            match server.undefined_variables() {
+                Ok(undefined) => {
+                    for name in undefined {
+                        self.report(
+                            ViolationKind::InvalidServerUrl,
+                            format!("{}/url", pointer),
+                            format!("server variable `{}` is not defined", name),
+                        );
+                    }
+                }
+                Err(err) => self.report(
+                    ViolationKind::InvalidServerUrl,
+                    format!("{}/url", pointer),
+                    format!("invalid server URL `{}`: {}", server.url, err),
+                ),
+            }
+            for (name, variable) in server.variables.iter().flatten() {
+                if !variable.enumeration.is_empty()
+                    && !variable.enumeration.contains(&variable.default)
+                {
+                    self.report(
+                        ViolationKind::InvalidServerVariable,
+                        format!(
+                            "{}/variables/{}/default",
+                            pointer,
+                            escape_pointer_token(name)
+                        ),
+                        format!(
+                            "default `{}` of server variable `{}` is not one of its values",
+                            variable.default, name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn components(&mut self, components: &Components) {
+        self.examples(&components.examples, "/components");
+        for (name, parameter) in &components.parameters {
+            if let ReferenceOr::Item(parameter) = parameter {
+                let pointer = format!("/components/parameters/{}", escape_pointer_token(name));
+                self.parameter(parameter, &pointer);
+            }
+        }
+        for (name, header) in &components.headers {
+            if let ReferenceOr::Item(header) = header {
+                let pointer = format!("/components/headers/{}", escape_pointer_token(name));
+                self.examples(&header.examples, &pointer);
+                self.format(&header.format, &pointer);
+            }
+        }
+        for (name, body) in &components.request_bodies {
+            if let ReferenceOr::Item(body) = body {
+                let pointer = format!("/components/requestBodies/{}", escape_pointer_token(name));
+                self.content(&body.content, &pointer);
+            }
+        }
+        for (name, response) in &components.responses {
+            if let ReferenceOr::Item(response) = response {
+                let pointer = format!("/components/responses/{}", escape_pointer_token(name));
+                self.response(response, &pointer);
+            }
+        }
+    }
+
+    fn parameter(&mut self, parameter: &Parameter, pointer: &str) {
+        let data = parameter.parameter_data_ref();
+        self.examples(&data.examples, pointer);
+        self.format(&data.format, pointer);
+    }
+
+    fn response(&mut self, response: &Response, pointer: &str) {
+        for (name, header) in &response.headers {
+            if let ReferenceOr::Item(header) = header {
+                let pointer = format!("{}/headers/{}", pointer, escape_pointer_token(name));
+                self.examples(&header.examples, &pointer);
+                self.format(&header.format, &pointer);
+            }
+        }
+        self.content(&response.content, pointer);
+    }
+
+    fn format(&mut self, format: &ParameterSchemaOrContent, pointer: &str) {
+        if let ParameterSchemaOrContent::Content(content) = format {
+            self.content(content, pointer);
+        }
+    }
+
+    fn content(&mut self, content: &Content, pointer: &str) {
+        for (name, media_type) in content {
+            let pointer = format!("{}/content/{}", pointer, escape_pointer_token(name));
+            self.media_type(name, pointer.clone());
+            for (property, encoding) in &media_type.encoding {
+                for content_type in encoding.content_type.iter().flat_map(|c| c.split(',')) {
+                    let pointer = format!(
+                        "{}/encoding/{}/contentType",
+                        pointer,
+                        escape_pointer_token(property)
+                    );
+                    self.media_type(content_type.trim(), pointer);
+                }
+            }
+            self.examples(&media_type.examples, &pointer);
+        }
+    }
+
+    fn media_type(&mut self, media_type: &str, pointer: String) {
+        if !self.options.media_types {
+            return;
+        }
+        let key = match media_type.parse::<MediaTypeKey>() {
+            Ok(key) => key,
+            Err(err) => {
+                return self.report(ViolationKind::InvalidMediaType, pointer, err.to_string())
+            }
+        };
+        let essence = key.essence();
+        if !self.options.media_type_suggestions
+            || COMMON_MEDIA_TYPES.contains(&essence.as_str())
+            || REGISTERED_MEDIA_TYPES.contains(&essence.as_str())
+        {
+            return;
+        }
+        let subtype = essence.split('/').nth(1).unwrap_or_default();
+        if ["vnd.", "prs.", "x-"]
+            .iter()
+            .any(|tree| subtype.starts_with(tree))
+        {
+            return;
+        }
+        if let Some(common) = COMMON_MEDIA_TYPES
+            .iter()
+            .find(|common| is_one_edit_away(&essence, common))
+        {
+            self.report(
+                ViolationKind::InvalidMediaType,
+                pointer,
+                format!(
+                    "unknown media type `{}`, did you mean `{}`?",
+                    essence, common
+                ),
+            );
+        }
+    }
+
+    fn examples(&mut self, examples: &IndexMap<String, ReferenceOr<Example>>, pointer: &str) {
+        for (name, example) in examples {
+            if let ReferenceOr::Item(example) = example {
+                if example.value.is_some() && example.external_value.is_some() {
+                    self.report(
+                        ViolationKind::MutuallyExclusiveFields,
+                        format!("{}/examples/{}", pointer, escape_pointer_token(name)),
+                        "`value` and `externalValue` are mutually exclusive".to_owned(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn security_requirement(&mut self, requirement: &SecurityRequirement, pointer: String) {
+        let schemes = self
+            .document
+            .components
+            .as_ref()
+            .map(|components| &components.security_schemes);
+        for (name, scopes) in requirement {
+            let pointer = format!("{}/{}", pointer, escape_pointer_token(name));
+            let scheme = match schemes.and_then(|schemes| schemes.get(name)) {
+                Some(scheme) => scheme,
+                None => {
+                    self.report(
+                        ViolationKind::UndefinedSecurityScheme,
+                        pointer,
+                        format!("security scheme `{}` is not defined", name),
+                    );
+                    continue;
+                }
+            };
+            match scheme {
+                ReferenceOr::Item(SecurityScheme::OAuth2 { flows, .. }) => {
+                    let defined = flows.scopes();
+                    for (i, scope) in scopes.iter().enumerate() {
+                        if !defined.contains_key(scope.as_str()) {
+                            self.report(
+                                ViolationKind::UndefinedScope,
+                                format!("{}/{}", pointer, i),
+                                format!("scope `{}` is not defined by `{}`", scope, name),
+                            );
+                        }
+                    }
+                }
+                ReferenceOr::Item(SecurityScheme::APIKey { .. } | SecurityScheme::HTTP { .. })
+                    if !scopes.is_empty() =>
+                {
+                    self.report(
+                        ViolationKind::UnexpectedScopes,
+                        pointer,
+                        format!("security scheme `{}` does not take scopes", name),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Media types whose misspellings are reported by
+/// [ValidateOptions::media_type_suggestions].
+const COMMON_MEDIA_TYPES: &[&str] = &[
+    "application/json",
+    "application/problem+json",
+    "application/xml",
+    "application/x-www-form-urlencoded",
+    "application/octet-stream",
+    "application/pdf",
+    "application/zip",
+    "multipart/form-data",
+    "multipart/mixed",
+    "text/plain",
+    "text/html",
+    "text/csv",
+    "text/event-stream",
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/svg+xml",
+];
+
+/// Registered media types one letter away from one of [COMMON_MEDIA_TYPES],
+/// which are not reported as typos.
+const REGISTERED_MEDIA_TYPES: &[&str] = &["application/gzip", "image/apng", "text/css"];
+
+/// Returns `true` if `a` becomes `b` by inserting, removing or replacing a
+/// single character.
+fn is_one_edit_away(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_rest, b_rest) = (a.len() - prefix - suffix, b.len() - prefix - suffix);
+    a_rest.max(b_rest) == 1
+}
+
+fn component_names(components: &Components, kind: ComponentKind) -> Vec<&String> {
+    match kind {
+        ComponentKind::Schemas => components.schemas.keys().collect(),
+        ComponentKind::Responses => components.responses.keys().collect(),
+        ComponentKind::Parameters => components.parameters.keys().collect(),
+        ComponentKind::Examples => components.examples.keys().collect(),
+        ComponentKind::RequestBodies => components.request_bodies.keys().collect(),
+        ComponentKind::Headers => components.headers.keys().collect(),
+        ComponentKind::SecuritySchemes => components.security_schemes.keys().collect(),
+        ComponentKind::Links => components.links.keys().collect(),
+        ComponentKind::Callbacks => components.callbacks.keys().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_url() {
+        for url in [
+            "https://example.com",
+            "https://user@example.com:8080/a%20b?q=1#top",
+            "/v1",
+            "//cdn.example.com/docs",
+            "mailto:support@example.com",
+            "urn:isbn:0451450523",
+        ] {
+            assert_eq!(check_url(url), Ok(()), "{}", url);
+        }
+        for url in [
+            "",
+            "https://example.com/a b",
+            "https://example.com/%zz",
+            "https:/example.com",
+            "http://",
+            "1http://example.com",
+            "https://exämple.com",
+        ] {
+            assert!(check_url(url).is_err(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut api: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "", "version": "1" },
+                "servers": [
+                    { "url": "https://{region}.example.com/{version}", "variables": {
+                        "region": { "default": "us", "enum": ["eu", "ap"] }
+                    } }
+                ],
+                "security": [ { "oauth": [] }, { "apiKey": [] } ],
+                "paths": {
+                    "/pets/{id}": {
+                        "parameters": [ { "$ref": "#/components/parameters/Id" } ],
+                        "get": { "operationId": "getPet", "responses": {} },
+                        "put": {
+                            "operationId": "getPet",
+                            "parameters": [
+                                { "name": "name", "in": "path", "schema": { "type": "string" } }
+                            ],
+                            "responses": { "200": { "description": "" } }
+                        }
+                    },
+                    "/pets": {
+                        "post": {
+                            "requestBody": { "content": { "application/json": { "examples": {
+                                "cat": { "value": {}, "externalValue": "https://example.com/cat.json" }
+                            } } } },
+                            "responses": { "default": { "description": "" } },
+                            "callbacks": { "onAdopt": { "{$request.cookie.id}": {} } }
+                        }
+                    },
+                    "/pets/{petId": {},
+                    "/owners": { "servers": [ { "url": "https://{host" } ] },
+                    "/owners/{ownerId}": {
+                        "get": {
+                            "security": [ { "basic": [] } ],
+                            "responses": { "200": { "description": "" } }
+                        }
+                    }
+                },
+                "components": {
+                    "parameters": {
+                        "Id": { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+                    },
+                    "schemas": { "Pet Type": { "type": "string" } },
+                    "examples": {
+                        "Dog": { "value": "dog" },
+                        "Cat": { "value": "cat", "externalValue": "cat.json" }
+                    },
+                    "securitySchemes": { "apiKey": { "type": "apiKey", "name": "key", "in": "header" } }
+                }
+            }"##,
+        )
+        .unwrap();
+        // Deserialization rejects status codes outside 100 to 599.
+        if let ReferenceOr::Item(item) = &mut api.paths.paths["/pets"] {
+            item.post.as_mut().unwrap().responses.responses.insert(
+                StatusCode::Code(666),
+                ReferenceOr::Item(Response::default()),
+            );
+        }
+
+        let violations = api
+            .validate()
+            .into_iter()
+            .map(|v| (v.kind, v.pointer))
+            .collect::<Vec<_>>();
+        let expected = [
+            (ViolationKind::MissingField, "/info/title"),
+            (ViolationKind::InvalidServerUrl, "/servers/0/url"),
+            (
+                ViolationKind::InvalidServerVariable,
+                "/servers/0/variables/region/default",
+            ),
+            (ViolationKind::UndefinedSecurityScheme, "/security/0/oauth"),
+            (
+                ViolationKind::NoResponses,
+                "/paths/~1pets~1{id}/get/responses",
+            ),
+            (
+                ViolationKind::UnusedPathParameter,
+                "/paths/~1pets~1{id}/put/parameters/0",
+            ),
+            (
+                ViolationKind::OptionalPathParameter,
+                "/paths/~1pets~1{id}/put/parameters/0",
+            ),
+            (
+                ViolationKind::DuplicateOperationId,
+                "/paths/~1pets~1{id}/put/operationId",
+            ),
+            (
+                ViolationKind::MutuallyExclusiveFields,
+                "/paths/~1pets/post/requestBody/content/application~1json/examples/cat",
+            ),
+            (
+                ViolationKind::InvalidStatusCode,
+                "/paths/~1pets/post/responses/666",
+            ),
+            (
+                ViolationKind::InvalidCallbackExpression,
+                "/paths/~1pets/post/callbacks/onAdopt/{$request.cookie.id}",
+            ),
+            (ViolationKind::InvalidPath, "/paths/~1pets~1{petId"),
+            (
+                ViolationKind::InvalidServerUrl,
+                "/paths/~1owners/servers/0/url",
+            ),
+            (
+                ViolationKind::UndeclaredPathParameter,
+                "/paths/~1owners~1{ownerId}/get",
+            ),
+            (
+                ViolationKind::UndefinedSecurityScheme,
+                "/paths/~1owners~1{ownerId}/get/security/0/basic",
+            ),
+            (
+                ViolationKind::InvalidComponentName,
+                "/components/schemas/Pet Type",
+            ),
+            (
+                ViolationKind::MutuallyExclusiveFields,
+                "/components/examples/Cat",
+            ),
+        ]
+        .map(|(kind, pointer)| (kind, pointer.to_string()));
+        assert_eq!(violations, expected);
+
+        assert!(api.validate_urls().is_empty());
+
+        let duplicate = api
+            .validate()
+            .into_iter()
+            .find(|v| v.kind == ViolationKind::DuplicateOperationId)
+            .unwrap();
+        assert_eq!(
+            duplicate.message,
+            "operationId `getPet` is already used by /paths/~1pets~1{id}/get"
+        );
+    }
+
+    #[test]
+    fn test_validate_scopes() {
+        let api: OpenAPI = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "security": [ { "oauth": ["read", "admin"], "key": ["read"] } ],
+            "paths": { "/pets": { "get": {
+                "security": [ { "oidc": ["openid"], "basic": [], "missing": ["read"] } ],
+                "responses": { "200": { "description": "" } }
+            } } },
+            "components": { "securitySchemes": {
+                "oauth": { "type": "oauth2", "flows": {
+                    "implicit": { "authorizationUrl": "https://example.com/auth", "scopes": { "read": "" } },
+                    "clientCredentials": { "tokenUrl": "https://example.com/token", "scopes": { "write": "" } }
+                } },
+                "key": { "type": "apiKey", "name": "key", "in": "header" },
+                "basic": { "type": "http", "scheme": "basic" },
+                "oidc": { "type": "openIdConnect", "openIdConnectUrl": "https://example.com/.well-known" }
+            } }
+        }))
+        .unwrap();
+
+        let violations = api
+            .validate()
+            .into_iter()
+            .map(|v| (v.kind, v.pointer))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            violations,
+            [
+                (
+                    ViolationKind::UnexpectedScopes,
+                    "/security/0/key".to_owned()
+                ),
+                (
+                    ViolationKind::UndefinedScope,
+                    "/security/0/oauth/1".to_owned()
+                ),
+                (
+                    ViolationKind::UndefinedSecurityScheme,
+                    "/paths/~1pets/get/security/0/missing".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_media_types() {
+        let api: OpenAPI = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Pets", "version": "1" },
+            "paths": { "/pets": { "post": {
+                "requestBody": { "content": {
+                    "multipart/form-data": { "encoding": {
+                        "photo": { "contentType": "image/png, image/jpg" },
+                        "tags": { "contentType": "text/*" }
+                    } },
+                    "application/vnd.pets+json; version=2": {},
+                    "json": {}
+                } },
+                "responses": { "200": { "description": "", "content": {
+                    "*/*": {},
+                    "text/xml": {},
+                    "text/css": {},
+                    "application/gzip": {},
+                    "application/x-zipp": {}
+                } } }
+            } } }
+        }))
+        .unwrap();
+
+        let body = "/paths/~1pets/post/requestBody/content";
+        let violations = |options: &ValidateOptions| {
+            api.validate_with(options)
+                .into_iter()
+                .map(|v| (v.kind, v.pointer))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            violations(&ValidateOptions::new()),
+            [(ViolationKind::InvalidMediaType, format!("{}/json", body))]
+        );
+        assert_eq!(
+            violations(&ValidateOptions::new().media_type_suggestions(true)),
+            [
+                (ViolationKind::InvalidMediaType, format!("{}/json", body)),
+                (
+                    ViolationKind::InvalidMediaType,
+                    format!("{}/multipart~1form-data/encoding/photo/contentType", body)
+                ),
+            ]
+        );
+
+        assert!(!is_one_edit_away("text/plian", "text/plain"));
+        assert!(is_one_edit_away("text/pain", "text/plain"));
+        assert!(is_one_edit_away("text/plainn", "text/plain"));
+        assert!(!is_one_edit_away("text/plain", "text/plain"));
+    }
+}
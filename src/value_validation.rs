@@ -0,0 +1,869 @@
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+use serde_json::Value;
+
+/// Where a value being validated is sent, which decides whether required
+/// `readOnly` and `writeOnly` properties may be left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Request,
+    Response,
+}
+
+impl Schema {
+    /// Checks `value` against this schema, returning a violation of kind
+    /// [ViolationKind::SchemaMismatch] for each failing keyword, with the
+    /// JSON Pointer of the offending part of `value`.
+    ///
+    /// References are followed through `components`; those that cannot be
+    /// resolved accept any value, as do `pattern` and unknown formats. The
+    /// `date`, `date-time` and `byte` formats are checked.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let schema: Schema = serde_json::from_str(r#"{
+    ///     "type": "object",
+    ///     "required": ["name"],
+    ///     "properties": { "tags": { "type": "array", "items": { "type": "string" } } }
+    /// }"#).unwrap();
+    /// let violations = schema.validate_value(&serde_json::json!({ "tags": ["a", 1] }), None);
+    /// assert_eq!(violations.len(), 2);
+    /// assert_eq!(violations[0].pointer, "");
+    /// assert_eq!(violations[1].pointer, "/tags/1");
+    /// ```
+    pub fn validate_value(&self, value: &Value, components: Option<&Components>) -> Vec<Violation> {
+        let mut validator = ValueValidator::new(components, None);
+        validator.schema(self, value, "");
+        validator.violations
+    }
+}
+
+/// Checks `value` against `schema` as sent in `direction`, prefixing every
+/// pointer with `pointer`.
+pub(crate) fn validate_value_at(
+    schema: &ReferenceOr<Schema>,
+    value: &Value,
+    components: Option<&Components>,
+    direction: Direction,
+    pointer: &str,
+) -> Vec<Violation> {
+    let mut validator = ValueValidator::new(components, Some(direction));
+    validator.reference(schema, value, pointer);
+    validator.violations
+}
+
+struct ValueValidator<'a> {
+    components: Option<&'a Components>,
+    direction: Option<Direction>,
+    violations: Vec<Violation>,
+}
+
+impl<'a> ValueValidator<'a> {
+    fn new(components: Option<&'a Components>, direction: Option<Direction>) -> Self {
+        ValueValidator {
+            components,
+            direction,
+            violations: Vec::new(),
+        }
+    }
+
+    fn report(&mut self, pointer: &str, message: String) {
+        self.violations.push(Violation {
+            kind: ViolationKind::SchemaMismatch,
+            pointer: pointer.to_owned(),
+            message,
+        });
+    }
+
+    fn resolve<'s>(&self, schema: &'s ReferenceOr<Schema>) -> Option<&'s Schema>
+    where
+        'a: 's,
+    {
+        match (schema, self.components) {
+            (ReferenceOr::Item(schema), _) => Some(schema),
+            (reference, Some(components)) => reference.resolve(components).ok(),
+            (_, None) => None,
+        }
+    }
+
+    fn resolve_boxed<'s>(&self, schema: &'s ReferenceOr<Box<Schema>>) -> Option<&'s Schema>
+    where
+        'a: 's,
+    {
+        match (schema, self.components) {
+            (ReferenceOr::Item(schema), _) => Some(schema),
+            (reference, Some(components)) => reference.resolve(components).ok(),
+            (_, None) => None,
+        }
+    }
+
+    fn reference(&mut self, schema: &ReferenceOr<Schema>, value: &Value, pointer: &str) {
+        if let Some(schema) = self.resolve(schema) {
+            self.schema(schema, value, pointer);
+        }
+    }
+
+    fn boxed(&mut self, schema: &ReferenceOr<Box<Schema>>, value: &Value, pointer: &str) {
+        if let Some(schema) = self.resolve_boxed(schema) {
+            self.schema(schema, value, pointer);
+        }
+    }
+
+    /// Whether `value` matches `schema`, without reporting anything.
+    fn matches(&self, schema: &Schema, value: &Value) -> bool {
+        let mut validator = ValueValidator::new(self.components, self.direction);
+        validator.schema(schema, value, "");
+        validator.violations.is_empty()
+    }
+
+    fn schema(&mut self, schema: &Schema, value: &Value, pointer: &str) {
+        let typed = match &schema.schema_kind {
+            SchemaKind::Type(_) => true,
+            SchemaKind::Any(any) => any.typ.is_some(),
+            _ => false,
+        };
+        if value.is_null() && typed {
+            if !schema.schema_data.nullable {
+                self.report(pointer, "null is not allowed".to_owned());
+            }
+            return;
+        }
+
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::String(string)) => {
+                let s = match value.as_str() {
+                    Some(s) => s,
+                    None => return self.report(pointer, "expected a string".to_owned()),
+                };
+                let format = match &string.format {
+                    VariantOrUnknownOrEmpty::Item(StringFormat::Date) => Some("date"),
+                    VariantOrUnknownOrEmpty::Item(StringFormat::DateTime) => Some("date-time"),
+                    VariantOrUnknownOrEmpty::Item(StringFormat::Byte) => Some("byte"),
+                    VariantOrUnknownOrEmpty::Unknown(format) => Some(format.as_str()),
+                    _ => None,
+                };
+                self.string(s, string.min_length, string.max_length, format, pointer);
+                if !string.enumeration.is_empty()
+                    && !string.enumeration.iter().flatten().any(|e| e == s)
+                {
+                    self.not_enumerated(value, pointer);
+                }
+            }
+            SchemaKind::Type(Type::Number(number)) => {
+                let n = match value.as_f64() {
+                    Some(n) => n,
+                    None => return self.report(pointer, "expected a number".to_owned()),
+                };
+                self.number(
+                    n,
+                    number.multiple_of,
+                    number.minimum.map(|m| (m, number.exclusive_minimum)),
+                    number.maximum.map(|m| (m, number.exclusive_maximum)),
+                    pointer,
+                );
+                if !number.enumeration.is_empty()
+                    && !number.enumeration.iter().flatten().any(|e| *e == n)
+                {
+                    self.not_enumerated(value, pointer);
+                }
+            }
+            SchemaKind::Type(Type::Integer(integer)) => {
+                let n = match value.as_f64().filter(|n| n.fract() == 0.0) {
+                    Some(n) => n,
+                    None => return self.report(pointer, "expected an integer".to_owned()),
+                };
+                if integer.format == VariantOrUnknownOrEmpty::Item(IntegerFormat::Int32)
+                    && (n < i32::MIN as f64 || n > i32::MAX as f64)
+                {
+                    self.report(pointer, format!("{} does not fit in an int32", value));
+                }
+                self.number(
+                    n,
+                    integer.multiple_of.map(|m| m as f64),
+                    integer
+                        .minimum
+                        .map(|m| (m as f64, integer.exclusive_minimum)),
+                    integer
+                        .maximum
+                        .map(|m| (m as f64, integer.exclusive_maximum)),
+                    pointer,
+                );
+                if !integer.enumeration.is_empty()
+                    && !integer.enumeration.iter().flatten().any(|e| *e as f64 == n)
+                {
+                    self.not_enumerated(value, pointer);
+                }
+            }
+            SchemaKind::Type(Type::Boolean(boolean)) => {
+                let b = match value.as_bool() {
+                    Some(b) => b,
+                    None => return self.report(pointer, "expected a boolean".to_owned()),
+                };
+                if !boolean.enumeration.is_empty()
+                    && !boolean.enumeration.iter().flatten().any(|e| *e == b)
+                {
+                    self.not_enumerated(value, pointer);
+                }
+            }
+            SchemaKind::Type(Type::Object(object)) => match value.as_object() {
+                Some(map) => self.object(
+                    map,
+                    &object.properties,
+                    &object.required,
+                    object.additional_properties.as_ref(),
+                    (object.min_properties, object.max_properties),
+                    pointer,
+                ),
+                None => self.report(pointer, "expected an object".to_owned()),
+            },
+            SchemaKind::Type(Type::Array(array)) => match value.as_array() {
+                Some(items) => self.array(
+                    items,
+                    array.items.as_ref(),
+                    (array.min_items, array.max_items),
+                    array.unique_items,
+                    array.contains.as_ref(),
+                    (array.min_contains, array.max_contains),
+                    pointer,
+                ),
+                None => self.report(pointer, "expected an array".to_owned()),
+            },
+            SchemaKind::OneOf { one_of } => {
+                self.one_of(schema, one_of, value, pointer);
+            }
+            SchemaKind::AllOf { all_of } => {
+                for schema in all_of {
+                    self.reference(schema, value, pointer);
+                }
+            }
+            SchemaKind::AnyOf { any_of } => self.any_of(any_of, value, pointer),
+            SchemaKind::Not { not } => self.not(not, value, pointer),
+            SchemaKind::Any(any) => self.any(schema, any, value, pointer),
+        }
+    }
+
+    fn any(&mut self, schema: &Schema, any: &AnySchema, value: &Value, pointer: &str) {
+        if let Some(typ) = &any.typ {
+            let matches = match typ.as_str() {
+                "string" => value.is_string(),
+                "number" => value.is_number(),
+                "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+                "boolean" => value.is_boolean(),
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                _ => true,
+            };
+            if !matches {
+                let article = if typ.starts_with(['a', 'i', 'o']) {
+                    "an"
+                } else {
+                    "a"
+                };
+                return self.report(pointer, format!("expected {} {}", article, typ));
+            }
+        }
+
+        match value {
+            Value::String(s) => self.string(
+                s,
+                any.min_length,
+                any.max_length,
+                any.format.as_deref(),
+                pointer,
+            ),
+            Value::Number(n) => self.number(
+                n.as_f64().unwrap_or_default(),
+                any.multiple_of,
+                any.minimum
+                    .map(|m| (m, any.exclusive_minimum.unwrap_or_default())),
+                any.maximum
+                    .map(|m| (m, any.exclusive_maximum.unwrap_or_default())),
+                pointer,
+            ),
+            Value::Object(map) => self.object(
+                map,
+                &any.properties,
+                &any.required,
+                any.additional_properties.as_ref(),
+                (any.min_properties, any.max_properties),
+                pointer,
+            ),
+            Value::Array(items) => self.array(
+                items,
+                any.items.as_ref(),
+                (any.min_items, any.max_items),
+                any.unique_items.unwrap_or_default(),
+                any.contains.as_ref(),
+                (any.min_contains, any.max_contains),
+                pointer,
+            ),
+            _ => {}
+        }
+        if !any.enumeration.is_empty() && !any.enumeration.contains(value) {
+            self.not_enumerated(value, pointer);
+        }
+
+        for schema in &any.all_of {
+            self.reference(schema, value, pointer);
+        }
+        if !any.any_of.is_empty() {
+            self.any_of(&any.any_of, value, pointer);
+        }
+        if !any.one_of.is_empty() {
+            self.one_of(schema, &any.one_of, value, pointer);
+        }
+        if let Some(not) = &any.not {
+            self.not(not, value, pointer);
+        }
+    }
+
+    fn not_enumerated(&mut self, value: &Value, pointer: &str) {
+        self.report(
+            pointer,
+            format!("{} is not one of the enumerated values", value),
+        );
+    }
+
+    fn string(
+        &mut self,
+        s: &str,
+        min_length: Option<usize>,
+        max_length: Option<usize>,
+        format: Option<&str>,
+        pointer: &str,
+    ) {
+        let length = s.chars().count();
+        if min_length.is_some_and(|min| length < min) {
+            self.report(
+                pointer,
+                format!(
+                    "must be at least {} characters long",
+                    min_length.unwrap_or_default()
+                ),
+            );
+        }
+        if max_length.is_some_and(|max| length > max) {
+            self.report(
+                pointer,
+                format!(
+                    "must be at most {} characters long",
+                    max_length.unwrap_or_default()
+                ),
+            );
+        }
+        let valid = match format {
+            Some("date") => is_date(s),
+            Some("date-time") => is_date_time(s),
+            Some("byte") => is_base64(s),
+            _ => true,
+        };
+        if !valid {
+            self.report(
+                pointer,
+                format!("`{}` is not a valid {}", s, format.unwrap_or_default()),
+            );
+        }
+    }
+
+    fn number(
+        &mut self,
+        n: f64,
+        multiple_of: Option<f64>,
+        minimum: Option<(f64, bool)>,
+        maximum: Option<(f64, bool)>,
+        pointer: &str,
+    ) {
+        if let Some(m) = multiple_of.filter(|m| *m > 0.0) {
+            let quotient = n / m;
+            if (quotient - quotient.round()).abs() > 1e-9 {
+                self.report(pointer, format!("{} is not a multiple of {}", n, m));
+            }
+        }
+        match minimum {
+            Some((min, true)) if n <= min => {
+                self.report(pointer, format!("{} must be greater than {}", n, min))
+            }
+            Some((min, false)) if n < min => {
+                self.report(pointer, format!("{} must be at least {}", n, min))
+            }
+            _ => {}
+        }
+        match maximum {
+            Some((max, true)) if n >= max => {
+                self.report(pointer, format!("{} must be less than {}", n, max))
+            }
+            Some((max, false)) if n > max => {
+                self.report(pointer, format!("{} must be at most {}", n, max))
+            }
+            _ => {}
+        }
+    }
+
+    fn object(
+        &mut self,
+        map: &serde_json::Map<String, Value>,
+        properties: &IndexMap<String, ReferenceOr<Box<Schema>>>,
+        required: &[String],
+        additional_properties: Option<&AdditionalProperties>,
+        (min_properties, max_properties): (Option<usize>, Option<usize>),
+        pointer: &str,
+    ) {
+        for name in required {
+            if map.contains_key(name) {
+                continue;
+            }
+            let property = properties
+                .get(name)
+                .and_then(|property| self.resolve_boxed(property));
+            let exempt = match (self.direction, property) {
+                (Some(Direction::Request), Some(property)) => property.schema_data.read_only,
+                (Some(Direction::Response), Some(property)) => property.schema_data.write_only,
+                _ => false,
+            };
+            if !exempt {
+                self.report(pointer, format!("missing required property `{}`", name));
+            }
+        }
+
+        for (key, value) in map {
+            let pointer = format!("{}/{}", pointer, escape_pointer_token(key));
+            match (properties.get(key), additional_properties) {
+                (Some(property), _) => self.boxed(property, value, &pointer),
+                (None, Some(AdditionalProperties::Any(false))) => {
+                    self.report(&pointer, format!("unexpected property `{}`", key))
+                }
+                (None, Some(AdditionalProperties::Schema(schema))) => {
+                    self.reference(schema, value, &pointer)
+                }
+                (None, _) => {}
+            }
+        }
+
+        if min_properties.is_some_and(|min| map.len() < min) {
+            self.report(
+                pointer,
+                format!(
+                    "must have at least {} properties",
+                    min_properties.unwrap_or_default()
+                ),
+            );
+        }
+        if max_properties.is_some_and(|max| map.len() > max) {
+            self.report(
+                pointer,
+                format!(
+                    "must have at most {} properties",
+                    max_properties.unwrap_or_default()
+                ),
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn array(
+        &mut self,
+        items: &[Value],
+        item_schema: Option<&ReferenceOr<Box<Schema>>>,
+        (min_items, max_items): (Option<usize>, Option<usize>),
+        unique_items: bool,
+        contains: Option<&ReferenceOr<Box<Schema>>>,
+        (min_contains, max_contains): (Option<usize>, Option<usize>),
+        pointer: &str,
+    ) {
+        if let Some(schema) = item_schema {
+            for (i, item) in items.iter().enumerate() {
+                self.boxed(schema, item, &format!("{}/{}", pointer, i));
+            }
+        }
+        if min_items.is_some_and(|min| items.len() < min) {
+            self.report(
+                pointer,
+                format!("must have at least {} items", min_items.unwrap_or_default()),
+            );
+        }
+        if max_items.is_some_and(|max| items.len() > max) {
+            self.report(
+                pointer,
+                format!("must have at most {} items", max_items.unwrap_or_default()),
+            );
+        }
+        if unique_items
+            && items
+                .iter()
+                .enumerate()
+                .any(|(i, item)| items[..i].contains(item))
+        {
+            self.report(pointer, "items must be unique".to_owned());
+        }
+
+        if let Some(contains) = contains.and_then(|schema| self.resolve_boxed(schema)) {
+            let count = items
+                .iter()
+                .filter(|item| self.matches(contains, item))
+                .count();
+            let min = min_contains.unwrap_or(1);
+            if count < min {
+                self.report(
+                    pointer,
+                    format!("must contain at least {} matching items", min),
+                );
+            }
+            if max_contains.is_some_and(|max| count > max) {
+                self.report(
+                    pointer,
+                    format!(
+                        "must contain at most {} matching items",
+                        max_contains.unwrap_or_default()
+                    ),
+                );
+            }
+        }
+    }
+
+    fn one_of(
+        &mut self,
+        schema: &Schema,
+        one_of: &[ReferenceOr<Schema>],
+        value: &Value,
+        pointer: &str,
+    ) {
+        if let (Some(_), Some(components)) = (&schema.schema_data.discriminator, self.components) {
+            return match schema.select_variant(value, components) {
+                Ok((_, variant)) => self.schema(variant, value, pointer),
+                Err(err) => self.report(pointer, err.to_string()),
+            };
+        }
+        let count = one_of
+            .iter()
+            .filter_map(|schema| self.resolve(schema))
+            .filter(|schema| self.matches(schema, value))
+            .count();
+        if count != 1 {
+            self.report(
+                pointer,
+                format!(
+                    "must match exactly one schema in oneOf, but matches {}",
+                    count
+                ),
+            );
+        }
+    }
+
+    fn any_of(&mut self, any_of: &[ReferenceOr<Schema>], value: &Value, pointer: &str) {
+        let matches = any_of.iter().any(|schema| match self.resolve(schema) {
+            Some(schema) => self.matches(schema, value),
+            None => true,
+        });
+        if !matches {
+            self.report(pointer, "must match a schema in anyOf".to_owned());
+        }
+    }
+
+    fn not(&mut self, not: &ReferenceOr<Schema>, value: &Value, pointer: &str) {
+        if self
+            .resolve(not)
+            .is_some_and(|schema| self.matches(schema, value))
+        {
+            self.report(pointer, "must not match the schema in not".to_owned());
+        }
+    }
+}
+
+/// Whether `s` is a full-date of RFC 3339, e.g. `2024-02-29`.
+#[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
+fn is_date(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let digits = |range: std::ops::Range<usize>| {
+        bytes[range.clone()].iter().all(u8::is_ascii_digit) && !range.is_empty()
+    };
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    if !(digits(0..4) && digits(5..7) && digits(8..10)) {
+        return false;
+    }
+    let year: u32 = s[0..4].parse().unwrap_or_default();
+    let month: u32 = s[5..7].parse().unwrap_or_default();
+    let day: u32 = s[8..10].parse().unwrap_or_default();
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+/// Whether `s` is a date-time of RFC 3339, e.g. `2024-02-29T12:00:00.5Z`.
+fn is_date_time(s: &str) -> bool {
+    let (date, time) = match s.split_once(['T', 't']) {
+        Some(parts) => parts,
+        None => return false,
+    };
+    let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+        Some(i) => time.split_at(i),
+        None => return false,
+    };
+    let two_digits = |s: &str, max: u32| {
+        s.len() == 2
+            && s.bytes().all(|b| b.is_ascii_digit())
+            && s.parse::<u32>().is_ok_and(|n| n <= max)
+    };
+    let (time, fraction) = time.split_once('.').unwrap_or((time, "0"));
+    let mut hms = time.split(':');
+    let valid_time = matches!(
+        (hms.next(), hms.next(), hms.next(), hms.next()),
+        (Some(h), Some(m), Some(s), None) if two_digits(h, 23) && two_digits(m, 59) && two_digits(s, 60)
+    ) && !fraction.is_empty()
+        && fraction.bytes().all(|b| b.is_ascii_digit());
+    let valid_offset = offset.eq_ignore_ascii_case("z")
+        || offset[1..]
+            .split_once(':')
+            .is_some_and(|(h, m)| two_digits(h, 23) && two_digits(m, 59));
+    is_date(date) && valid_time && valid_offset
+}
+
+/// Whether `s` is base64 encoded, as required by the `byte` format.
+#[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
+fn is_base64(s: &str) -> bool {
+    let data = s.trim_end_matches('=');
+    s.len() % 4 == 0
+        && s.len() - data.len() <= 2
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn violations(schema: Value, value: Value) -> Vec<(String, String)> {
+        let components: Components = serde_json::from_value(json!({
+            "schemas": {
+                "Pet": {
+                    "type": "object",
+                    "required": ["id", "name"],
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true },
+                        "name": { "type": "string", "minLength": 1 }
+                    }
+                },
+                "Cat": { "type": "object", "properties": { "lives": { "type": "integer", "maximum": 9 } } },
+                "Dog": { "type": "object", "properties": { "bark": { "type": "boolean" } } }
+            }
+        }))
+        .unwrap();
+        let schema: Schema = serde_json::from_value(schema).unwrap();
+        schema
+            .validate_value(&value, Some(&components))
+            .into_iter()
+            .map(|violation| {
+                assert_eq!(violation.kind, ViolationKind::SchemaMismatch);
+                (violation.pointer, violation.message)
+            })
+            .collect()
+    }
+
+    fn messages(schema: Value, value: Value) -> Vec<String> {
+        violations(schema, value)
+            .into_iter()
+            .map(|(_, m)| m)
+            .collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert!(messages(
+            json!({ "type": "string", "format": "date" }),
+            json!("2024-02-29")
+        )
+        .is_empty());
+        assert_eq!(
+            messages(
+                json!({ "type": "string", "format": "date" }),
+                json!("2023-02-29")
+            ),
+            ["`2023-02-29` is not a valid date"]
+        );
+        assert_eq!(
+            messages(
+                json!({ "type": "string", "maxLength": 2, "enum": ["abc"] }),
+                json!("abc")
+            ),
+            ["must be at most 2 characters long"]
+        );
+        assert_eq!(
+            messages(json!({ "type": "string" }), json!(1)),
+            ["expected a string"]
+        );
+        assert_eq!(
+            messages(json!({ "type": "string" }), json!(null)),
+            ["null is not allowed"]
+        );
+        assert!(messages(json!({ "type": "string", "nullable": true }), json!(null)).is_empty());
+        assert_eq!(
+            messages(
+                json!({ "type": "integer", "minimum": 1, "exclusiveMinimum": true }),
+                json!(1)
+            ),
+            ["1 must be greater than 1"]
+        );
+        assert_eq!(
+            messages(json!({ "type": "integer", "format": "int32" }), json!(1.5)),
+            ["expected an integer"]
+        );
+        assert_eq!(
+            messages(json!({ "type": "number", "multipleOf": 0.5 }), json!(1.25)),
+            ["1.25 is not a multiple of 0.5"]
+        );
+        assert_eq!(
+            messages(json!({ "type": "boolean", "enum": [true] }), json!(false)),
+            ["false is not one of the enumerated values"]
+        );
+        for valid in ["2024-01-01T00:00:00Z", "2024-01-01t23:59:60.123+05:30"] {
+            assert!(messages(
+                json!({ "type": "string", "format": "date-time" }),
+                json!(valid)
+            )
+            .is_empty());
+        }
+        for invalid in ["2024-01-01", "2024-01-01T24:00:00Z", "2024-01-01T00:00:00"] {
+            assert_eq!(
+                messages(
+                    json!({ "type": "string", "format": "date-time" }),
+                    json!(invalid)
+                )
+                .len(),
+                1
+            );
+        }
+        assert!(messages(json!({ "type": "string", "format": "byte" }), json!("aGk=")).is_empty());
+        assert_eq!(
+            messages(json!({ "type": "string", "format": "byte" }), json!("aGk")).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_objects_and_arrays() {
+        let pet = json!({ "$ref": "#/components/schemas/Pet" });
+        let schema = json!({
+            "type": "array",
+            "items": pet,
+            "maxItems": 2,
+            "uniqueItems": true
+        });
+        assert_eq!(
+            violations(
+                schema.clone(),
+                json!([{ "id": 1, "name": "" }, { "name": "Rex" }])
+            ),
+            [
+                (
+                    "/0/name".to_owned(),
+                    "must be at least 1 characters long".to_owned()
+                ),
+                ("/1".to_owned(), "missing required property `id`".to_owned()),
+            ]
+        );
+        assert_eq!(
+            messages(
+                schema,
+                json!([{ "id": 1, "name": "a" }, { "id": 1, "name": "a" }, {}])
+            ),
+            [
+                "missing required property `id`",
+                "missing required property `name`",
+                "must have at most 2 items",
+                "items must be unique"
+            ]
+        );
+        let request = validate_value_at(
+            &serde_json::from_value(pet).unwrap(),
+            &json!({ "name": "Rex" }),
+            None,
+            Direction::Request,
+            "/body",
+        );
+        assert!(request.is_empty());
+
+        assert_eq!(
+            violations(
+                json!({ "type": "object", "additionalProperties": false, "properties": { "a~b": {} } }),
+                json!({ "a~b": 1, "c": 2 })
+            ),
+            [("/c".to_owned(), "unexpected property `c`".to_owned())]
+        );
+        assert_eq!(
+            violations(
+                json!({ "type": "object", "additionalProperties": { "type": "integer" } }),
+                json!({ "a/b": "x" })
+            ),
+            [("/a~1b".to_owned(), "expected an integer".to_owned())]
+        );
+        assert_eq!(
+            messages(
+                json!({ "type": "array", "contains": { "type": "string" }, "maxContains": 1 }),
+                json!(["a", "b", 1])
+            ),
+            ["must contain at most 1 matching items"]
+        );
+    }
+
+    #[test]
+    fn test_compositions() {
+        let one_of = json!({
+            "oneOf": [ { "$ref": "#/components/schemas/Cat" }, { "$ref": "#/components/schemas/Dog" } ]
+        });
+        assert_eq!(
+            messages(one_of.clone(), json!({ "lives": 3 })),
+            ["must match exactly one schema in oneOf, but matches 2"]
+        );
+        assert_eq!(
+            messages(one_of, json!(1)),
+            ["must match exactly one schema in oneOf, but matches 0"]
+        );
+        let discriminated = json!({
+            "oneOf": [ { "$ref": "#/components/schemas/Cat" }, { "$ref": "#/components/schemas/Dog" } ],
+            "discriminator": { "propertyName": "kind" }
+        });
+        assert_eq!(
+            violations(discriminated.clone(), json!({ "kind": "Cat", "lives": 10 })),
+            [("/lives".to_owned(), "10 must be at most 9".to_owned())]
+        );
+        assert_eq!(
+            messages(discriminated, json!({ "kind": "Cow" })),
+            ["`Cow` does not select any variant"]
+        );
+
+        assert!(messages(
+            json!({ "anyOf": [ { "type": "string" }, { "type": "integer" } ] }),
+            json!(1)
+        )
+        .is_empty());
+        assert_eq!(
+            messages(json!({ "not": { "type": "string" } }), json!("a")),
+            ["must not match the schema in not"]
+        );
+        assert_eq!(
+            messages(
+                json!({ "allOf": [ { "$ref": "#/components/schemas/Pet" } ], "properties": {} }),
+                json!({ "id": 1 })
+            ),
+            ["missing required property `name`"]
+        );
+        assert_eq!(
+            messages(
+                json!({ "type": "object", "minProperties": 1, "oneOf": [] }),
+                json!([])
+            ),
+            ["expected an object"]
+        );
+        assert!(messages(json!({ "$ref": "#/components/schemas/Missing" }), json!(1)).is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// Collects every schema that is not itself nested in another schema, along
+/// with its JSON Pointer, in document order.
+pub(crate) fn schema_slots_mut(openapi: &mut OpenAPI) -> Vec<(String, SchemaSlot<'_>)> {
+    let mut slots = Slots(Vec::new());
+
+    for (path, item) in openapi.paths.paths.iter_mut() {
+        if let ReferenceOr::Item(item) = item {
+            let pointer = format!("/paths/{}", escape_pointer_token(path));
+            slots.path_item(item, &pointer);
+        }
+    }
+
+    if let Some(components) = &mut openapi.components {
+        for (name, schema) in components.schemas.iter_mut() {
+            let pointer = format!("/components/schemas/{}", escape_pointer_token(name));
+            slots.0.push((pointer, SchemaSlot::Plain(schema)));
+        }
+        for (name, response) in components.responses.iter_mut() {
+            if let ReferenceOr::Item(response) = response {
+                let pointer = format!("/components/responses/{}", escape_pointer_token(name));
+                slots.response(response, &pointer);
+            }
+        }
+        for (name, parameter) in components.parameters.iter_mut() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                let pointer = format!("/components/parameters/{}", escape_pointer_token(name));
+                slots.schema_or_content(&mut parameter.parameter_data_mut().format, &pointer);
+            }
+        }
+        for (name, body) in components.request_bodies.iter_mut() {
+            if let ReferenceOr::Item(body) = body {
+                let pointer = format!("/components/requestBodies/{}", escape_pointer_token(name));
+                slots.content(&mut body.content, &pointer);
+            }
+        }
+        for (name, header) in components.headers.iter_mut() {
+            if let ReferenceOr::Item(header) = header {
+                let pointer = format!("/components/headers/{}", escape_pointer_token(name));
+                slots.schema_or_content(&mut header.format, &pointer);
+            }
+        }
+        for (name, callback) in components.callbacks.iter_mut() {
+            if let ReferenceOr::Item(callback) = callback {
+                let pointer = format!("/components/callbacks/{}", escape_pointer_token(name));
+                slots.callback(callback, &pointer);
+            }
+        }
+    }
+
+    slots.0
+}
+
+struct Slots<'a>(Vec<(String, SchemaSlot<'a>)>);
+
+impl<'a> Slots<'a> {
+    fn path_item(&mut self, item: &'a mut PathItem, pointer: &str) {
+        let PathItem {
+            parameters,
+            get,
+            put,
+            post,
+            delete,
+            options,
+            head,
+            patch,
+            trace,
+            ..
+        } = item;
+        for (i, parameter) in parameters.iter_mut().enumerate() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                let pointer = format!("{}/parameters/{}", pointer, i);
+                self.schema_or_content(&mut parameter.parameter_data_mut().format, &pointer);
+            }
+        }
+        let operations = [
+            ("get", get),
+            ("put", put),
+            ("post", post),
+            ("delete", delete),
+            ("options", options),
+            ("head", head),
+            ("patch", patch),
+            ("trace", trace),
+        ];
+        for (method, operation) in operations {
+            if let Some(operation) = operation {
+                self.operation(operation, &format!("{}/{}", pointer, method));
+            }
+        }
+    }
+
+    fn operation(&mut self, operation: &'a mut Operation, pointer: &str) {
+        for (i, parameter) in operation.parameters.iter_mut().enumerate() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                let pointer = format!("{}/parameters/{}", pointer, i);
+                self.schema_or_content(&mut parameter.parameter_data_mut().format, &pointer);
+            }
+        }
+        if let Some(ReferenceOr::Item(body)) = &mut operation.request_body {
+            self.content(&mut body.content, &format!("{}/requestBody", pointer));
+        }
+        if let Some(ReferenceOr::Item(response)) = &mut operation.responses.default {
+            self.response(response, &format!("{}/responses/default", pointer));
+        }
+        for (status, response) in operation.responses.responses.iter_mut() {
+            if let ReferenceOr::Item(response) = response {
+                self.response(response, &format!("{}/responses/{}", pointer, status));
+            }
+        }
+        for (name, callback) in operation.callbacks.iter_mut() {
+            let pointer = format!("{}/callbacks/{}", pointer, escape_pointer_token(name));
+            self.callback(callback, &pointer);
+        }
+    }
+
+    fn callback(&mut self, callback: &'a mut Callback, pointer: &str) {
+        for (expression, item) in callback.iter_mut() {
+            let pointer = format!("{}/{}", pointer, escape_pointer_token(expression));
+            self.path_item(item, &pointer);
+        }
+    }
+
+    fn response(&mut self, response: &'a mut Response, pointer: &str) {
+        for (name, header) in response.headers.iter_mut() {
+            if let ReferenceOr::Item(header) = header {
+                let pointer = format!("{}/headers/{}", pointer, escape_pointer_token(name));
+                self.schema_or_content(&mut header.format, &pointer);
+            }
+        }
+        self.content(&mut response.content, pointer);
+    }
+
+    fn schema_or_content(&mut self, format: &'a mut ParameterSchemaOrContent, pointer: &str) {
+        match format {
+            ParameterSchemaOrContent::Schema(schema) => self
+                .0
+                .push((format!("{}/schema", pointer), SchemaSlot::Plain(schema))),
+            ParameterSchemaOrContent::Content(content) => self.content(content, pointer),
+        }
+    }
+
+    fn content(&mut self, content: &'a mut IndexMap<String, MediaType>, pointer: &str) {
+        for (media_type_name, media_type) in content.iter_mut() {
+            if let Some(schema) = &mut media_type.schema {
+                let pointer = format!(
+                    "{}/content/{}/schema",
+                    pointer,
+                    escape_pointer_token(media_type_name)
+                );
+                self.0.push((pointer, SchemaSlot::Plain(schema)));
+            }
+        }
+    }
+}
@@ -0,0 +1,702 @@
+use std::fmt;
+
+use crate::util::escape_pointer_token;
+use crate::*;
+use indexmap::IndexMap;
+
+/// Where in a document a schema was found, as passed to a [Visitor].
+///
+/// The [Display](fmt::Display) implementation describes the location in
+/// terms of the API, e.g. `request body of POST /pets, application/json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// The JSON Pointer of the schema.
+    pub pointer: String,
+    /// The operation, path item or component the schema belongs to.
+    pub owner: LocationOwner,
+    /// The role of the top-level schema within its owner.
+    pub context: SchemaContext,
+    /// The JSON Pointer of the schema relative to the top-level schema, e.g.
+    /// `/properties/name`. Empty for the top-level schema itself.
+    pub subschema: String,
+}
+
+/// The object a [Location] belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocationOwner {
+    /// An operation. For operations of callbacks, `path` is the callback's
+    /// runtime expression.
+    Operation { method: String, path: String },
+    /// A path item, for parameters shared by its operations.
+    PathItem { path: String },
+    /// An entry of [Components].
+    Component { kind: ComponentKind, name: String },
+}
+
+/// The role of a top-level schema within its [LocationOwner].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaContext {
+    /// The schema is a component of [Components::schemas] itself.
+    Definition,
+    Parameter {
+        name: String,
+        location: String,
+    },
+    RequestBody {
+        media_type: String,
+    },
+    /// The content of a response. `status` is `None` for responses defined
+    /// in [Components].
+    Response {
+        status: Option<String>,
+        media_type: String,
+    },
+    /// A header, either of a response or defined in [Components].
+    Header {
+        status: Option<String>,
+        name: String,
+        media_type: Option<String>,
+    },
+}
+
+impl fmt::Display for LocationOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocationOwner::Operation { method, path } => {
+                write!(f, "{} {}", method.to_ascii_uppercase(), path)
+            }
+            LocationOwner::PathItem { path } => write!(f, "path {}", path),
+            LocationOwner::Component { kind, name } => {
+                let kind = match kind {
+                    ComponentKind::Schemas => "schema",
+                    ComponentKind::Responses => "response",
+                    ComponentKind::Parameters => "parameter",
+                    ComponentKind::Examples => "example",
+                    ComponentKind::RequestBodies => "request body",
+                    ComponentKind::Headers => "header",
+                    ComponentKind::SecuritySchemes => "security scheme",
+                    ComponentKind::Links => "link",
+                    ComponentKind::Callbacks => "callback",
+                };
+                write!(f, "{} `{}`", kind, name)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let media_type = match &self.context {
+            SchemaContext::Definition => {
+                write!(f, "{}", self.owner)?;
+                None
+            }
+            SchemaContext::Parameter { name, location } => {
+                write!(f, "{} parameter `{}` of {}", location, name, self.owner)?;
+                None
+            }
+            SchemaContext::RequestBody { media_type } => {
+                write!(f, "request body of {}", self.owner)?;
+                Some(media_type)
+            }
+            SchemaContext::Response { status, media_type } => {
+                match status {
+                    Some(status) => write!(f, "response {} of {}", status, self.owner)?,
+                    None => write!(f, "{}", self.owner)?,
+                }
+                Some(media_type)
+            }
+            SchemaContext::Header {
+                status,
+                name,
+                media_type,
+            } => {
+                match status {
+                    Some(status) => write!(
+                        f,
+                        "header `{}` of response {} of {}",
+                        name, status, self.owner
+                    )?,
+                    None if matches!(
+                        self.owner,
+                        LocationOwner::Component {
+                            kind: ComponentKind::Headers,
+                            ..
+                        }
+                    ) =>
+                    {
+                        write!(f, "{}", self.owner)?
+                    }
+                    None => write!(f, "header `{}` of {}", name, self.owner)?,
+                }
+                media_type.as_ref()
+            }
+        };
+        if let Some(media_type) = media_type {
+            write!(f, ", {}", media_type)?;
+        }
+        if !self.subschema.is_empty() {
+            write!(f, ", at {}", self.subschema)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the schemas of a document from [OpenAPI::walk].
+///
+/// All methods do nothing by default.
+pub trait Visitor {
+    /// Called for every inline schema, before its subschemas.
+    fn visit_schema(&mut self, location: &Location, schema: &Schema) {
+        let _ = (location, schema);
+    }
+
+    /// Called for every schema that is a `$ref`.
+    fn visit_schema_reference(&mut self, location: &Location, reference: &str) {
+        let _ = (location, reference);
+    }
+}
+
+impl OpenAPI {
+    /// Visits every schema in this document, including subschemas, in
+    /// document order.
+    ///
+    /// Path items that are references are skipped.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": { "/pets": { "post": {
+    ///         "requestBody": { "content": { "application/json": { "schema": { "type": "object" } } } },
+    ///         "responses": {}
+    ///     } } }
+    /// }"#).unwrap();
+    ///
+    /// struct Locations(Vec<String>);
+    /// impl Visitor for Locations {
+    ///     fn visit_schema(&mut self, location: &Location, _: &Schema) {
+    ///         self.0.push(location.to_string());
+    ///     }
+    /// }
+    /// let mut locations = Locations(Vec::new());
+    /// api.walk(&mut locations);
+    /// assert_eq!(locations.0, ["request body of POST /pets, application/json"]);
+    /// ```
+    pub fn walk<V: Visitor>(&self, visitor: &mut V) {
+        walk(self, &mut ForVisitor(visitor));
+    }
+
+    /// Returns every inline schema in this document along with its JSON
+    /// Pointer: component schemas, the schemas of parameters, headers and
+    /// media types, and all of their subschemas, in the order of
+    /// [OpenAPI::walk].
+    ///
+    /// References are not followed.
+    ///
+    /// ```
+    /// # use openapiv3::*;
+    /// let api: OpenAPI = serde_json::from_str(r#"{
+    ///     "openapi": "3.0.3",
+    ///     "info": { "title": "Pets", "version": "1" },
+    ///     "paths": {},
+    ///     "components": { "schemas": {
+    ///         "Pet": { "type": "object", "properties": { "tags": { "type": "array", "items": { "type": "string" } } } }
+    ///     } }
+    /// }"#).unwrap();
+    /// let pointers = api.all_schemas().map(|(pointer, _)| pointer).collect::<Vec<_>>();
+    /// assert_eq!(pointers, [
+    ///     "/components/schemas/Pet",
+    ///     "/components/schemas/Pet/properties/tags",
+    ///     "/components/schemas/Pet/properties/tags/items",
+    /// ]);
+    /// ```
+    pub fn all_schemas(&self) -> impl Iterator<Item = (String, &Schema)> {
+        struct Collect<'a>(Vec<(String, &'a Schema)>);
+
+        impl<'a> Sink<'a> for Collect<'a> {
+            fn schema(&mut self, location: &Location, schema: &'a Schema) {
+                self.0.push((location.pointer.clone(), schema));
+            }
+
+            fn reference(&mut self, _: &Location, _: &str) {}
+        }
+
+        let mut collect = Collect(Vec::new());
+        walk(self, &mut collect);
+        collect.0.into_iter()
+    }
+
+    /// Calls `f` with every inline schema in this document and its JSON
+    /// Pointer, like [OpenAPI::all_schemas].
+    ///
+    /// This takes a closure rather than returning an iterator because a
+    /// schema cannot be borrowed mutably at the same time as its subschemas.
+    /// Each schema is passed before its subschemas, so subschemas added by
+    /// `f` are visited as well.
+    pub fn all_schemas_mut<F: FnMut(&str, &mut Schema)>(&mut self, f: F) {
+        struct Schemas<F>(F);
+
+        impl<F: FnMut(&str, &mut Schema)> VisitorMut for Schemas<F> {
+            fn visit_schema_mut(&mut self, pointer: &str, schema: &mut Schema) {
+                (self.0)(pointer, schema);
+            }
+        }
+
+        self.transform(&mut Schemas(f));
+    }
+}
+
+/// Receives the schemas found by [walk], borrowed for as long as the
+/// document.
+trait Sink<'a> {
+    fn schema(&mut self, location: &Location, schema: &'a Schema);
+    fn reference(&mut self, location: &Location, reference: &str);
+}
+
+struct ForVisitor<'v, V>(&'v mut V);
+
+impl<V: Visitor> Sink<'_> for ForVisitor<'_, V> {
+    fn schema(&mut self, location: &Location, schema: &Schema) {
+        self.0.visit_schema(location, schema);
+    }
+
+    fn reference(&mut self, location: &Location, reference: &str) {
+        self.0.visit_schema_reference(location, reference);
+    }
+}
+
+fn walk<'a, S: Sink<'a>>(document: &'a OpenAPI, sink: &mut S) {
+    let mut walker = Walker { sink };
+
+    for (path, item) in document.paths.iter() {
+        if let ReferenceOr::Item(item) = item {
+            let pointer = format!("/paths/{}", escape_pointer_token(path));
+            walker.path_item(item, path, &pointer);
+        }
+    }
+
+    let components = match &document.components {
+        Some(components) => components,
+        None => return,
+    };
+    let owner = |kind, name: &str| LocationOwner::Component {
+        kind,
+        name: name.to_owned(),
+    };
+    for (name, schema) in &components.schemas {
+        let location = Location {
+            pointer: format!("/components/schemas/{}", escape_pointer_token(name)),
+            owner: owner(ComponentKind::Schemas, name),
+            context: SchemaContext::Definition,
+            subschema: String::new(),
+        };
+        walker.schema(location, schema.as_ref_item());
+    }
+    for (name, response) in &components.responses {
+        if let ReferenceOr::Item(response) = response {
+            let pointer = format!("/components/responses/{}", escape_pointer_token(name));
+            walker.response(
+                response,
+                &owner(ComponentKind::Responses, name),
+                None,
+                &pointer,
+            );
+        }
+    }
+    for (name, parameter) in &components.parameters {
+        if let ReferenceOr::Item(parameter) = parameter {
+            let pointer = format!("/components/parameters/{}", escape_pointer_token(name));
+            walker.parameter(parameter, &owner(ComponentKind::Parameters, name), &pointer);
+        }
+    }
+    for (name, body) in &components.request_bodies {
+        if let ReferenceOr::Item(body) = body {
+            let pointer = format!("/components/requestBodies/{}", escape_pointer_token(name));
+            walker.content(&body.content, &pointer, |media_type| {
+                (
+                    owner(ComponentKind::RequestBodies, name),
+                    SchemaContext::RequestBody { media_type },
+                )
+            });
+        }
+    }
+    for (name, header) in &components.headers {
+        if let ReferenceOr::Item(header) = header {
+            let pointer = format!("/components/headers/{}", escape_pointer_token(name));
+            walker.header(
+                &header.format,
+                &owner(ComponentKind::Headers, name),
+                None,
+                name,
+                &pointer,
+            );
+        }
+    }
+    for (name, callback) in &components.callbacks {
+        if let ReferenceOr::Item(callback) = callback {
+            let pointer = format!("/components/callbacks/{}", escape_pointer_token(name));
+            walker.callback(callback, &pointer);
+        }
+    }
+}
+
+impl<T> ReferenceOr<T> {
+    fn as_ref_item(&self) -> ReferenceOr<&T> {
+        match self {
+            ReferenceOr::Item(item) => ReferenceOr::Item(item),
+            ReferenceOr::Reference { reference } => ReferenceOr::Reference {
+                reference: reference.clone(),
+            },
+        }
+    }
+}
+
+struct Walker<'s, S> {
+    sink: &'s mut S,
+}
+
+impl<'a, S: Sink<'a>> Walker<'_, S> {
+    fn path_item(&mut self, item: &'a PathItem, path: &str, pointer: &str) {
+        let owner = LocationOwner::PathItem {
+            path: path.to_owned(),
+        };
+        for (i, parameter) in item.parameters.iter().enumerate() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                self.parameter(parameter, &owner, &format!("{}/parameters/{}", pointer, i));
+            }
+        }
+        for (method, operation) in item.iter() {
+            let owner = LocationOwner::Operation {
+                method: method.to_owned(),
+                path: path.to_owned(),
+            };
+            self.operation(operation, &owner, &format!("{}/{}", pointer, method));
+        }
+    }
+
+    fn operation(&mut self, operation: &'a Operation, owner: &LocationOwner, pointer: &str) {
+        for (i, parameter) in operation.parameters.iter().enumerate() {
+            if let ReferenceOr::Item(parameter) = parameter {
+                self.parameter(parameter, owner, &format!("{}/parameters/{}", pointer, i));
+            }
+        }
+        if let Some(ReferenceOr::Item(body)) = &operation.request_body {
+            let pointer = format!("{}/requestBody", pointer);
+            self.content(&body.content, &pointer, |media_type| {
+                (owner.clone(), SchemaContext::RequestBody { media_type })
+            });
+        }
+        let responses = operation
+            .responses
+            .default
+            .iter()
+            .map(|response| ("default".to_owned(), response))
+            .chain(
+                operation
+                    .responses
+                    .responses
+                    .iter()
+                    .map(|(status, response)| (status.to_string(), response)),
+            );
+        for (status, response) in responses {
+            if let ReferenceOr::Item(response) = response {
+                let pointer = format!("{}/responses/{}", pointer, status);
+                self.response(response, owner, Some(status), &pointer);
+            }
+        }
+        for (name, callback) in &operation.callbacks {
+            let pointer = format!("{}/callbacks/{}", pointer, escape_pointer_token(name));
+            self.callback(callback, &pointer);
+        }
+    }
+
+    fn callback(&mut self, callback: &'a Callback, pointer: &str) {
+        for (expression, item) in callback {
+            let pointer = format!("{}/{}", pointer, escape_pointer_token(expression));
+            self.path_item(item, expression, &pointer);
+        }
+    }
+
+    fn response(
+        &mut self,
+        response: &'a Response,
+        owner: &LocationOwner,
+        status: Option<String>,
+        pointer: &str,
+    ) {
+        for (name, header) in &response.headers {
+            if let ReferenceOr::Item(header) = header {
+                let pointer = format!("{}/headers/{}", pointer, escape_pointer_token(name));
+                self.header(&header.format, owner, status.clone(), name, &pointer);
+            }
+        }
+        self.content(&response.content, pointer, |media_type| {
+            (
+                owner.clone(),
+                SchemaContext::Response {
+                    status: status.clone(),
+                    media_type,
+                },
+            )
+        });
+    }
+
+    fn parameter(&mut self, parameter: &'a Parameter, owner: &LocationOwner, pointer: &str) {
+        let location = match parameter {
+            Parameter::Query { .. } => "query",
+            Parameter::Header { .. } => "header",
+            Parameter::Path { .. } => "path",
+            Parameter::Cookie { .. } => "cookie",
+        };
+        let data = parameter.parameter_data_ref();
+        let context = SchemaContext::Parameter {
+            name: data.name.clone(),
+            location: location.to_owned(),
+        };
+        match &data.format {
+            ParameterSchemaOrContent::Schema(schema) => {
+                let location = Location {
+                    pointer: format!("{}/schema", pointer),
+                    owner: owner.clone(),
+                    context,
+                    subschema: String::new(),
+                };
+                self.schema(location, schema.as_ref_item());
+            }
+            ParameterSchemaOrContent::Content(content) => {
+                self.content(content, pointer, |_| (owner.clone(), context.clone()));
+            }
+        }
+    }
+
+    fn header(
+        &mut self,
+        format: &'a ParameterSchemaOrContent,
+        owner: &LocationOwner,
+        status: Option<String>,
+        name: &str,
+        pointer: &str,
+    ) {
+        let context = |media_type| SchemaContext::Header {
+            status: status.clone(),
+            name: name.to_owned(),
+            media_type,
+        };
+        match format {
+            ParameterSchemaOrContent::Schema(schema) => {
+                let location = Location {
+                    pointer: format!("{}/schema", pointer),
+                    owner: owner.clone(),
+                    context: context(None),
+                    subschema: String::new(),
+                };
+                self.schema(location, schema.as_ref_item());
+            }
+            ParameterSchemaOrContent::Content(content) => {
+                self.content(content, pointer, |media_type| {
+                    (owner.clone(), context(Some(media_type)))
+                });
+            }
+        }
+    }
+
+    fn content<F>(&mut self, content: &'a IndexMap<String, MediaType>, pointer: &str, context: F)
+    where
+        F: Fn(String) -> (LocationOwner, SchemaContext),
+    {
+        for (media_type, value) in content {
+            let pointer = format!("{}/content/{}", pointer, escape_pointer_token(media_type));
+            if let Some(schema) = &value.schema {
+                let (owner, context) = context(media_type.clone());
+                let location = Location {
+                    pointer: format!("{}/schema", pointer),
+                    owner,
+                    context,
+                    subschema: String::new(),
+                };
+                self.schema(location, schema.as_ref_item());
+            }
+            for (property, encoding) in &value.encoding {
+                for (name, header) in &encoding.headers {
+                    if let ReferenceOr::Item(header) = header {
+                        let (owner, context) = context(media_type.clone());
+                        let status = match context {
+                            SchemaContext::Response { status, .. } => status,
+                            _ => None,
+                        };
+                        let pointer = format!(
+                            "{}/encoding/{}/headers/{}",
+                            pointer,
+                            escape_pointer_token(property),
+                            escape_pointer_token(name)
+                        );
+                        self.header(&header.format, &owner, status, name, &pointer);
+                    }
+                }
+            }
+        }
+    }
+
+    fn schema(&mut self, location: Location, schema: ReferenceOr<&'a Schema>) {
+        let schema = match schema {
+            ReferenceOr::Item(schema) => schema,
+            ReferenceOr::Reference { reference } => {
+                self.sink.reference(&location, &reference);
+                return;
+            }
+        };
+        self.sink.schema(&location, schema);
+        for (key, subschema) in schema.subschemas() {
+            let suffix = key.pointer();
+            let child = Location {
+                pointer: format!("{}{}", location.pointer, suffix),
+                owner: location.owner.clone(),
+                context: location.context.clone(),
+                subschema: format!("{}{}", location.subschema, suffix),
+            };
+            self.schema(child, subschema);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Collect(Vec<(String, String)>);
+
+    impl Visitor for Collect {
+        fn visit_schema(&mut self, location: &Location, _: &Schema) {
+            self.0
+                .push((location.pointer.clone(), location.to_string()));
+        }
+
+        fn visit_schema_reference(&mut self, location: &Location, reference: &str) {
+            self.0.push((
+                location.pointer.clone(),
+                format!("{} -> {}", location, reference),
+            ));
+        }
+    }
+
+    #[test]
+    fn test_walk() {
+        let api: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {
+                    "/pets/{id}": {
+                        "parameters": [ { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } } ],
+                        "get": {
+                            "responses": {
+                                "200": {
+                                    "description": "",
+                                    "headers": { "X-Rate": { "schema": { "type": "integer" } } },
+                                    "content": { "application/json": { "schema": {
+                                        "type": "array", "items": { "$ref": "#/components/schemas/Pet" }
+                                    } } }
+                                }
+                            }
+                        }
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "Pet": { "type": "object", "properties": { "name": { "type": "string" } } }
+                    },
+                    "responses": {
+                        "NotFound": { "description": "", "content": { "text/plain": { "schema": {} } } }
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let mut collect = Collect::default();
+        api.walk(&mut collect);
+        assert_eq!(
+            collect.0,
+            [
+                (
+                    "/paths/~1pets~1{id}/parameters/0/schema",
+                    "path parameter `id` of path /pets/{id}"
+                ),
+                (
+                    "/paths/~1pets~1{id}/get/responses/200/headers/X-Rate/schema",
+                    "header `X-Rate` of response 200 of GET /pets/{id}"
+                ),
+                (
+                    "/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema",
+                    "response 200 of GET /pets/{id}, application/json"
+                ),
+                (
+                    "/paths/~1pets~1{id}/get/responses/200/content/application~1json/schema/items",
+                    "response 200 of GET /pets/{id}, application/json, at /items -> #/components/schemas/Pet"
+                ),
+                ("/components/schemas/Pet", "schema `Pet`"),
+                (
+                    "/components/schemas/Pet/properties/name",
+                    "schema `Pet`, at /properties/name"
+                ),
+                (
+                    "/components/responses/NotFound/content/text~1plain/schema",
+                    "response `NotFound`, text/plain"
+                ),
+            ]
+            .map(|(pointer, description)| (pointer.to_string(), description.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_all_schemas() {
+        let mut api: OpenAPI = serde_json::from_str(
+            r##"{
+                "openapi": "3.0.3",
+                "info": { "title": "Pets", "version": "1" },
+                "paths": {
+                    "/pets": {
+                        "post": {
+                            "parameters": [ { "name": "q", "in": "query", "schema": { "type": "string" } } ],
+                            "requestBody": { "content": { "multipart/form-data": {
+                                "schema": { "allOf": [ { "$ref": "#/components/schemas/Pet" }, { "type": "object" } ] },
+                                "encoding": { "photo": { "headers": { "X-Size": { "schema": { "type": "integer" } } } } }
+                            } } },
+                            "responses": {}
+                        }
+                    }
+                },
+                "components": {
+                    "schemas": { "Pet": { "type": "object" } }
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let expected = [
+            "/paths/~1pets/post/parameters/0/schema",
+            "/paths/~1pets/post/requestBody/content/multipart~1form-data/schema",
+            "/paths/~1pets/post/requestBody/content/multipart~1form-data/schema/allOf/1",
+            "/paths/~1pets/post/requestBody/content/multipart~1form-data/encoding/photo/headers/X-Size/schema",
+            "/components/schemas/Pet",
+        ];
+        let pointers = api
+            .all_schemas()
+            .map(|(pointer, _)| pointer)
+            .collect::<Vec<_>>();
+        assert_eq!(pointers, expected);
+
+        let mut pointers = Vec::new();
+        api.all_schemas_mut(|pointer, schema| {
+            pointers.push(pointer.to_owned());
+            schema.schema_data.read_only = true;
+        });
+        assert_eq!(pointers, expected);
+        assert!(api
+            .all_schemas()
+            .all(|(_, schema)| schema.schema_data.read_only));
+    }
+}
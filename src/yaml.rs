@@ -0,0 +1,93 @@
+use std::fmt;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The error returned by [from_yaml_str] and [to_yaml_string].
+///
+/// The error does not expose the YAML library in use, so that the backend
+/// can be swapped without affecting callers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlError {
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl YamlError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The 1-based line of the input at which the error occurred, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The 1-based column of the input at which the error occurred, if
+    /// known.
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl fmt::Display for YamlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for YamlError {}
+
+/// Deserializes a document, or any part of one, from YAML.
+///
+/// ```
+/// # use openapiv3::{from_yaml_str, OpenAPI};
+/// let api: OpenAPI = from_yaml_str("openapi: 3.0.3\ninfo:\n  title: A\n  version: '1'\npaths: {}\n").unwrap();
+/// assert_eq!(api.info.title, "A");
+/// ```
+pub fn from_yaml_str<T: DeserializeOwned>(input: &str) -> Result<T, YamlError> {
+    backend::from_str(input)
+}
+
+/// Serializes a document, or any part of one, to YAML.
+pub fn to_yaml_string<T: Serialize>(value: &T) -> Result<String, YamlError> {
+    backend::to_string(value)
+}
+
+mod backend {
+    use super::YamlError;
+    use serde::{de::DeserializeOwned, Serialize};
+
+    fn convert(err: serde_yaml::Error) -> YamlError {
+        let location = err.location();
+        YamlError {
+            message: err.to_string(),
+            line: location.as_ref().map(|l| l.line()),
+            column: location.as_ref().map(|l| l.column()),
+        }
+    }
+
+    pub(super) fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, YamlError> {
+        serde_yaml::from_str(input).map_err(convert)
+    }
+
+    pub(super) fn to_string<T: Serialize>(value: &T) -> Result<String, YamlError> {
+        serde_yaml::to_string(value).map_err(convert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_round_trip() {
+        let yaml = "openapi: 3.0.3\ninfo:\n  title: A\n  version: '1'\npaths: {}\n";
+        let api: OpenAPI = from_yaml_str(yaml).unwrap();
+        assert_eq!(to_yaml_string(&api).unwrap(), yaml);
+
+        let err = from_yaml_str::<OpenAPI>("openapi: 3.0.3\ninfo: [\n").unwrap_err();
+        assert!(err.line().is_some());
+    }
+}
@@ -1,4 +1,4 @@
-#![allow(clippy::expect_fun_call)]
+#![allow(clippy::expect_fun_call, clippy::upper_case_acronyms)]
 
 use indexmap::IndexMap;
 use newline_converter::dos2unix;
@@ -151,7 +151,7 @@ fn petstore_discriminated() {
             }),
             version: "1.0.0".to_owned(),
             extensions: {
-                let mut ext = IndexMap::new();
+                let mut ext = Extensions::new();
                 ext.insert("x-hash".to_string(), serde_json::json!("abc123"));
                 ext
             },
@@ -333,3 +333,72 @@ fn global_security_removed_with_override() {
         panic!("Path not found")
     }
 }
+
+#[test]
+fn effective_security_follows_overrides() {
+    let openapi: OpenAPI = serde_yaml::from_str(include_str!("../fixtures/adobe_aem.yaml"))
+        .expect("Could not deserialize adobe_aem.yaml");
+    let path_with_security_override = "/libs/granite/core/content/login.html";
+    let path_no_security_override = "/libs/granite/security/truststore.json";
+
+    let effective = |path: &str| {
+        let operation = openapi.paths.paths[path].as_item().unwrap().get.as_ref();
+        openapi.effective_security(operation.unwrap())
+    };
+    assert!(effective(path_with_security_override).is_empty());
+    assert_eq!(
+        effective(path_no_security_override),
+        openapi.security.as_deref().unwrap()
+    );
+}
+
+/// Components, like every other map in the model, keep the order in which
+/// they are declared rather than being sorted by name.
+#[test]
+fn component_order_survives_round_trip() {
+    let yaml = "\
+openapi: 3.0.3
+info:
+  title: Order
+  version: '1'
+paths: {}
+components:
+  schemas:
+    Zebra:
+      type: string
+    Apple:
+      type: string
+  responses:
+    NotFound:
+      description: ''
+    BadRequest:
+      description: ''
+  parameters:
+    offset:
+      name: offset
+      in: query
+      schema:
+        type: integer
+    limit:
+      name: limit
+      in: query
+      schema:
+        type: integer
+";
+    let openapi: OpenAPI = serde_yaml::from_str(yaml).unwrap();
+    let serialized = serde_json::to_string(&openapi).unwrap();
+    let openapi: OpenAPI = serde_json::from_str(&serialized).unwrap();
+    let components = openapi.components.as_ref().unwrap();
+    assert_eq!(
+        components.schemas.keys().collect::<Vec<_>>(),
+        ["Zebra", "Apple"]
+    );
+    assert_eq!(
+        components.responses.keys().collect::<Vec<_>>(),
+        ["NotFound", "BadRequest"]
+    );
+    assert_eq!(
+        components.parameters.keys().collect::<Vec<_>>(),
+        ["offset", "limit"]
+    );
+}